@@ -95,7 +95,7 @@ class _BrainScreenState extends State<BrainScreen> {
     } catch (e) {
       if (!mounted) return;
       ScaffoldMessenger.of(context).showSnackBar(
-        SnackBar(content: Text('${tr(context, 'errorSearchingMemories')}: $e')),
+        SnackBar(content: Text('${tr(context, 'errorSearchingMemories')}: ${trError(context, e)}')),
       );
     } finally {
       if (mounted) {
@@ -126,7 +126,7 @@ class _BrainScreenState extends State<BrainScreen> {
     } catch (e) {
       if (!mounted) return;
       ScaffoldMessenger.of(context).showSnackBar(
-        SnackBar(content: Text('${tr(context, 'errorLoadingProfileSections')}: $e')),
+        SnackBar(content: Text('${tr(context, 'errorLoadingProfileSections')}: ${trError(context, e)}')),
       );
     } finally {
       if (mounted) {
@@ -207,22 +207,16 @@ class _BrainScreenState extends State<BrainScreen> {
 
     try {
       final animaService = context.read<AnimaService>();
-      final cleared = await animaService.clearProfile();
+      await animaService.clearProfile();
       if (!mounted) return;
 
-      if (cleared) {
-        ScaffoldMessenger.of(context).showSnackBar(
-          SnackBar(content: Text(tr(context, 'cognitiveDeleted'))),
-        );
-      } else {
-        ScaffoldMessenger.of(context).showSnackBar(
-          SnackBar(content: Text(tr(context, 'cognitiveDeleteFailed'))),
-        );
-      }
+      ScaffoldMessenger.of(context).showSnackBar(
+        SnackBar(content: Text(tr(context, 'cognitiveDeleted'))),
+      );
     } catch (e) {
       if (!mounted) return;
       ScaffoldMessenger.of(context).showSnackBar(
-        SnackBar(content: Text('${tr(context, 'errorDeletingProfile')}: $e')),
+        SnackBar(content: Text('${tr(context, 'errorDeletingProfile')}: ${trError(context, e)}')),
       );
     }
   }
@@ -284,7 +278,7 @@ class _BrainScreenState extends State<BrainScreen> {
       _sleepProgressTimer?.cancel();
       if (mounted) {
         ScaffoldMessenger.of(context).showSnackBar(
-          SnackBar(content: Text('${tr(context, 'errorSleepCycle')}: $e')),
+          SnackBar(content: Text('${tr(context, 'errorSleepCycle')}: ${trError(context, e)}')),
         );
       }
     } finally {
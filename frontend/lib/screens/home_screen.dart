@@ -3,8 +3,10 @@
 import 'package:flutter/material.dart';
 import 'package:provider/provider.dart';
 import 'dart:async';
+import '../services/anima_error.dart';
 import '../services/anima_service.dart';
 import '../services/translation_service.dart';
+import '../src/rust/api/simple.dart';
 import '../src/rust/db.dart';
 import '../widgets/chat_bubble.dart';
 import '../widgets/main_drawer.dart';
@@ -27,6 +29,7 @@ class _HomeScreenState extends State<HomeScreen> {
 
   final ScrollController _scrollController = ScrollController();
   StreamSubscription<void>? _factoryResetSubscription;
+  Future<bool> Function()? _cancelActiveReply;
   List<ChatMessage> _historyMessages = [];
   List<ChatMessage> _sessionMessages = [];
   bool _isHistoryExpanded = false;
@@ -103,7 +106,7 @@ class _HomeScreenState extends State<HomeScreen> {
       ScaffoldMessenger.of(
         context,
       ).showSnackBar(
-        SnackBar(content: Text('${tr(context, 'failedLoadHistory')}: $e')),
+        SnackBar(content: Text('${tr(context, 'failedLoadHistory')}: ${trError(context, e)}')),
       );
     } finally {
       if (mounted) {
@@ -148,10 +151,7 @@ class _HomeScreenState extends State<HomeScreen> {
           await animaService.initialize();
           break;
         } catch (e) {
-          final errorText = e.toString().toLowerCase();
-          final isRuntimeNotReady =
-              errorText.contains('runtime is not initialized') ||
-              errorText.contains('motor ia no cargado');
+          final isRuntimeNotReady = animaErrorCode(e) == 'model_not_loaded';
 
           if (isRuntimeNotReady && attempt < maxInitAttempts) {
             await Future.delayed(const Duration(milliseconds: 900));
@@ -184,10 +184,7 @@ class _HomeScreenState extends State<HomeScreen> {
       _cacheHomeState();
       _scrollToBottom();
     } catch (e) {
-      final errorText = e.toString().toLowerCase();
-      final isRuntimeNotReady =
-          errorText.contains('runtime is not initialized') ||
-          errorText.contains('motor ia no cargado');
+      final isRuntimeNotReady = animaErrorCode(e) == 'model_not_loaded';
 
       if (!mounted) return;
       setState(() {
@@ -201,7 +198,7 @@ class _HomeScreenState extends State<HomeScreen> {
       }
 
       ScaffoldMessenger.of(context).showSnackBar(
-        SnackBar(content: Text('${tr(context, 'failedGenerateGreeting')}: $e')),
+        SnackBar(content: Text('${tr(context, 'failedGenerateGreeting')}: ${trError(context, e)}')),
       );
     }
   }
@@ -236,8 +233,6 @@ class _HomeScreenState extends State<HomeScreen> {
   Future<void> _sendMessage(String content) async {
     if (content.trim().isEmpty) return;
 
-    final recentMessagesForRust = _recentMessagesForRustContext();
-
     final userMessageId = DateTime.now().millisecondsSinceEpoch;
     final assistantMessageId = userMessageId + 1;
     final nowIso = DateTime.now().toUtc().toIso8601String();
@@ -263,32 +258,49 @@ class _HomeScreenState extends State<HomeScreen> {
     _cacheHomeState();
     _scrollToBottom();
 
+    String? streamErrorCode;
     try {
       final animaService = context.read<AnimaService>();
       final uiLanguage = context.read<TranslationService>().language;
-      await for (final chunk in animaService.streamMessage(
+      await for (final event in animaService.streamMessage(
         content,
         appLanguage: uiLanguage,
-        recentMessages: recentMessagesForRust,
+        onRequestId: (requestId) =>
+            _cancelActiveReply = () => animaService.cancelMessage(requestId),
       )) {
         if (!mounted) return;
-        final normalizedChunk = _normalizeModelText(chunk);
-        setState(() {
-          _sessionMessages = _sessionMessages
-              .map(
-                (message) => message.id == assistantMessageId
-                    ? ChatMessage(
-                        id: message.id,
-                        role: message.role,
-                        content: '${message.content}$normalizedChunk',
-                        timestamp: message.timestamp,
-                      )
-                    : message,
-              )
-              .toList();
-        });
-              _cacheHomeState();
-        _scrollToBottom();
+        switch (event) {
+          case ChatEvent_Token(:final field0):
+            final normalizedChunk = _normalizeModelText(field0);
+            setState(() {
+              _sessionMessages = _sessionMessages
+                  .map(
+                    (message) => message.id == assistantMessageId
+                        ? ChatMessage(
+                            id: message.id,
+                            role: message.role,
+                            content: '${message.content}$normalizedChunk',
+                            timestamp: message.timestamp,
+                          )
+                        : message,
+                  )
+                  .toList();
+            });
+            _cacheHomeState();
+            _scrollToBottom();
+          case ChatEvent_Error(:final code, :final detail):
+            debugPrint('[chat] generation failed code=$code detail=$detail');
+            streamErrorCode = code;
+          case ChatEvent_Done(:final stopReason, :final tokensPerSec):
+            debugPrint('[chat] done stopReason=$stopReason tokensPerSec=$tokensPerSec');
+          case ChatEvent_ContextRetrieved():
+            break;
+        }
+      }
+      _cancelActiveReply = null;
+
+      if (streamErrorCode != null) {
+        throw StateError('Chat stream failed: $streamErrorCode');
       }
 
       final finalAssistantMessage = _sessionMessages
@@ -307,7 +319,7 @@ class _HomeScreenState extends State<HomeScreen> {
                     ? ChatMessage(
                         id: message.id,
                         role: message.role,
-                        content: '[Error del Sistema: Falló la inferencia del modelo]',
+                        content: tr(context, 'systemFriendlyInferenceError'),
                         timestamp: message.timestamp,
                       )
                     : message,
@@ -323,7 +335,9 @@ class _HomeScreenState extends State<HomeScreen> {
       });
       _cacheHomeState();
     } catch (e) {
+      _cancelActiveReply = null;
       if (!mounted) return;
+      final errorMessage = trErrorCode(context, streamErrorCode ?? animaErrorCode(e));
       setState(() {
         _sessionMessages = _sessionMessages
             .map(
@@ -331,9 +345,7 @@ class _HomeScreenState extends State<HomeScreen> {
                   ? ChatMessage(
                       id: message.id,
                       role: message.role,
-                      content: message.content.isEmpty
-                          ? tr(context, 'systemFriendlyInferenceError')
-                          : message.content,
+                      content: message.content.isEmpty ? '[$errorMessage]' : message.content,
                       timestamp: message.timestamp,
                     )
                   : message,
@@ -345,31 +357,19 @@ class _HomeScreenState extends State<HomeScreen> {
       ScaffoldMessenger.of(
         context,
       ).showSnackBar(
-        SnackBar(content: Text(tr(context, 'systemFriendlyInferenceError'))),
+        SnackBar(content: Text(errorMessage)),
       );
     }
 
     _scrollToBottom();
   }
 
-  List<ChatMessage> _recentMessagesForRustContext() {
-    final merged = <ChatMessage>[
-      ..._historyMessages,
-      ..._sessionMessages,
-    ].where((message) {
-      final role = message.role.toLowerCase();
-      return (role == 'user' || role == 'assistant') && message.content.trim().isNotEmpty;
-    }).toList();
-
-    if (merged.length <= 4) {
-      return merged;
-    }
-
-    return merged.sublist(merged.length - 4);
-  }
-
   @override
   void dispose() {
+    final cancelActiveReply = _cancelActiveReply;
+    if (cancelActiveReply != null) {
+      unawaited(cancelActiveReply());
+    }
     _factoryResetSubscription?.cancel();
     _scrollController.dispose();
     super.dispose();
@@ -39,7 +39,7 @@ class _MemoryBrowserScreenState extends State<MemoryBrowserScreen> {
       if (!mounted) return;
       ScaffoldMessenger.of(
         context,
-      ).showSnackBar(SnackBar(content: Text('${tr(context, 'errorLoadingMemories')}: $e')));
+      ).showSnackBar(SnackBar(content: Text('${tr(context, 'errorLoadingMemories')}: ${trError(context, e)}')));
     } finally {
       if (mounted) {
         setState(() {
@@ -52,16 +52,9 @@ class _MemoryBrowserScreenState extends State<MemoryBrowserScreen> {
   Future<void> _deleteMemory(MemoryItem memory) async {
     try {
       final animaService = context.read<AnimaService>();
-      final deleted = await animaService.deleteMemory(memory.id);
+      await animaService.deleteMemory(memory.id);
 
       if (!mounted) return;
-      if (!deleted) {
-        ScaffoldMessenger.of(
-          context,
-        ).showSnackBar(SnackBar(content: Text(tr(context, 'deleteMemoryFailed'))));
-        return;
-      }
-
       setState(() {
         _memories = _memories.where((item) => item.id != memory.id).toList();
       });
@@ -73,7 +66,7 @@ class _MemoryBrowserScreenState extends State<MemoryBrowserScreen> {
       if (!mounted) return;
       ScaffoldMessenger.of(
         context,
-      ).showSnackBar(SnackBar(content: Text('${tr(context, 'errorDeletingMemory')}: $e')));
+      ).showSnackBar(SnackBar(content: Text('${tr(context, 'errorDeletingMemory')}: ${trError(context, e)}')));
     }
   }
 
@@ -227,9 +227,10 @@ class _OnboardingScreenState extends State<OnboardingScreen> {
       }
 
       if (!mounted) return;
-      final nameSaved = await animaService.setUserName(name);
-
-      if (!nameSaved) {
+      try {
+        await animaService.setUserName(name);
+      } catch (e) {
+        debugPrint('[onboarding] Failed to save name: $e');
         if (!mounted) return;
         ScaffoldMessenger.of(context).showSnackBar(
           SnackBar(content: Text(tr(context, 'couldNotSaveName'))),
@@ -256,7 +257,7 @@ class _OnboardingScreenState extends State<OnboardingScreen> {
     } catch (e) {
       if (!mounted) return;
       ScaffoldMessenger.of(context).showSnackBar(
-        SnackBar(content: Text('${tr(context, 'onboardingError')}: $e')),
+        SnackBar(content: Text('${tr(context, 'onboardingError')}: ${trError(context, e)}')),
       );
       setState(() {
         _isSubmitting = false;
@@ -78,7 +78,7 @@ class _SettingsScreenState extends State<SettingsScreen> {
       if (!mounted) return;
       ScaffoldMessenger.of(
         context,
-      ).showSnackBar(SnackBar(content: Text('${tr(context, 'errorLoadingSettings')}: $e')));
+      ).showSnackBar(SnackBar(content: Text('${tr(context, 'errorLoadingSettings')}: ${trError(context, e)}')));
     } finally {
       if (mounted) {
         setState(() {
@@ -124,16 +124,9 @@ class _SettingsScreenState extends State<SettingsScreen> {
 
     try {
       final animaService = context.read<AnimaService>();
-      final saved = await animaService.setUserName(newName);
+      await animaService.setUserName(newName);
       if (!mounted) return;
 
-      if (!saved) {
-        ScaffoldMessenger.of(
-          context,
-        ).showSnackBar(SnackBar(content: Text(tr(context, 'nameSaveFailed'))));
-        return;
-      }
-
       setState(() {
         _userName = newName;
       });
@@ -144,7 +137,7 @@ class _SettingsScreenState extends State<SettingsScreen> {
       if (!mounted) return;
       ScaffoldMessenger.of(
         context,
-      ).showSnackBar(SnackBar(content: Text('${tr(context, 'errorSavingName')}: $e')));
+      ).showSnackBar(SnackBar(content: Text('${tr(context, 'errorSavingName')}: ${trError(context, e)}')));
     }
   }
 
@@ -173,23 +166,16 @@ class _SettingsScreenState extends State<SettingsScreen> {
       if (!mounted) return;
       ScaffoldMessenger.of(
         context,
-      ).showSnackBar(SnackBar(content: Text('${tr(context, 'languageChangeFailed')}: $e')));
+      ).showSnackBar(SnackBar(content: Text('${tr(context, 'languageChangeFailed')}: ${trError(context, e)}')));
     }
   }
 
   Future<void> _saveTemperature(double value) async {
     try {
       final animaService = context.read<AnimaService>();
-      final saved = await animaService.setTemperature(value);
+      await animaService.setTemperature(value);
       if (!mounted) return;
 
-      if (!saved) {
-        ScaffoldMessenger.of(context).showSnackBar(
-          SnackBar(content: Text(tr(context, 'creativitySaveFailed'))),
-        );
-        return;
-      }
-
       ScaffoldMessenger.of(
         context,
       ).showSnackBar(SnackBar(content: Text(tr(context, 'creativityUpdated'))));
@@ -197,7 +183,7 @@ class _SettingsScreenState extends State<SettingsScreen> {
       if (!mounted) return;
       ScaffoldMessenger.of(
         context,
-      ).showSnackBar(SnackBar(content: Text('${tr(context, 'errorSavingCreativity')}: $e')));
+      ).showSnackBar(SnackBar(content: Text('${tr(context, 'errorSavingCreativity')}: ${trError(context, e)}')));
     }
   }
 
@@ -264,7 +250,7 @@ class _SettingsScreenState extends State<SettingsScreen> {
     } catch (e) {
       if (!mounted) return;
       ScaffoldMessenger.of(context).showSnackBar(
-        SnackBar(content: Text('${tr(context, 'errorExportingBrain')}: $e')),
+        SnackBar(content: Text('${tr(context, 'errorExportingBrain')}: ${trError(context, e)}')),
       );
     } finally {
       if (mounted) {
@@ -332,7 +318,7 @@ class _SettingsScreenState extends State<SettingsScreen> {
     } catch (e) {
       if (!mounted) return;
       ScaffoldMessenger.of(context).showSnackBar(
-        SnackBar(content: Text('${tr(context, 'errorImportingBrain')}: $e')),
+        SnackBar(content: Text('${tr(context, 'errorImportingBrain')}: ${trError(context, e)}')),
       );
     } finally {
       if (mounted) {
@@ -835,7 +821,7 @@ class _SettingsScreenState extends State<SettingsScreen> {
       if (!mounted) return;
       ScaffoldMessenger.of(
         context,
-      ).showSnackBar(SnackBar(content: Text('${t('factoryResetError')}: $e')));
+      ).showSnackBar(SnackBar(content: Text('${t('factoryResetError')}: ${trError(context, e)}')));
     } finally {
       flowFinished = true;
       hardStopTimer.cancel();
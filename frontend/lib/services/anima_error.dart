@@ -0,0 +1,56 @@
+// Maps Rust errors to stable codes and translation keys
+
+import '../src/rust/error.dart';
+
+extension AnimaErrorCode on AnimaError {
+  /// Same string as `AnimaError::code()` on the Rust side and as
+  /// `ChatEvent.error.code`.
+  String get code => switch (this) {
+        AnimaError_ModelNotLoaded() => 'model_not_loaded',
+        AnimaError_ModelLoadFailed() => 'model_load_failed',
+        AnimaError_InvalidModelFile() => 'invalid_model_file',
+        AnimaError_ModelTruncated() => 'model_truncated',
+        AnimaError_ModelChecksumMismatch() => 'model_checksum_mismatch',
+        AnimaError_WrongModelKind() => 'wrong_model_kind',
+        AnimaError_InsufficientMemory() => 'insufficient_memory',
+        AnimaError_ContextOverflow() => 'context_overflow',
+        AnimaError_BackendUnavailable() => 'backend_unavailable',
+        AnimaError_EmptyOutput() => 'empty_output',
+        AnimaError_GenerationFailed() => 'generation_failed',
+        AnimaError_EmbeddingFailed() => 'embedding_failed',
+        AnimaError_Cancelled() => 'cancelled',
+        AnimaError_Preempted() => 'preempted',
+        AnimaError_DbLocked() => 'db_locked',
+        AnimaError_DbIo() => 'db_io',
+        AnimaError_InvalidInput() => 'invalid_input',
+        AnimaError_Io() => 'io',
+        AnimaError_Internal() => 'internal',
+      };
+
+  /// Technical detail for logs; not meant for the user.
+  String get detail => field0;
+}
+
+/// Code for any error thrown by the bridge; anything that is not an
+/// [AnimaError] counts as `internal`.
+String animaErrorCode(Object error) => error is AnimaError ? error.code : 'internal';
+
+/// Translation key with the user-facing message for an error [code].
+String errorTranslationKey(String code) => switch (code) {
+      'model_not_loaded' => 'errorModelNotLoaded',
+      'model_load_failed' ||
+      'invalid_model_file' ||
+      'model_truncated' ||
+      'model_checksum_mismatch' ||
+      'wrong_model_kind' =>
+        'errorModelFile',
+      'insufficient_memory' => 'errorInsufficientMemory',
+      'context_overflow' => 'errorContextOverflow',
+      'backend_unavailable' => 'errorBackendUnavailable',
+      'empty_output' || 'generation_failed' || 'embedding_failed' => 'errorGenerationFailed',
+      'cancelled' || 'preempted' => 'errorCancelled',
+      'db_locked' => 'errorDbLocked',
+      'db_io' || 'io' => 'errorStorage',
+      'invalid_input' => 'errorInvalidInput',
+      _ => 'errorInternal',
+    };
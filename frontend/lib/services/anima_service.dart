@@ -4,14 +4,20 @@ import 'dart:async';
 import 'dart:io';
 
 import 'package:logger/logger.dart';
+import 'package:uuid/uuid.dart';
 import '../api.dart' as rust_api;
 import '../src/rust/db.dart';
+import '../src/rust/error.dart';
 import '../src/rust/api/simple.dart' as rust_simple;
+import 'anima_error.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
 
 class AnimaService {
-  static const int _maxRustHistoryMessages = 4;
   final Logger _logger = Logger();
+  static const Uuid _uuid = Uuid();
+
+  /// Negative temperatures make Rust keep the one saved in the settings.
+  static const double _persistedTemperature = -1;
   bool _initialized = false;
   Future<void>? _initializingFuture;
   final StreamController<void> _factoryResetController = StreamController<void>.broadcast();
@@ -67,28 +73,15 @@ class AnimaService {
     return File(absolutePath).absolute.path;
   }
 
-  void _printDetailedRustError(Object error) {
-    final errorText = error.toString();
-    var technical = errorText;
-
-    final bracketMatch = RegExp(r'\[Error del Sistema:\s*(.*?)\]').firstMatch(errorText);
-    if (bracketMatch != null) {
-      technical = bracketMatch.group(1) ?? errorText;
-    } else {
-      final rustMatch = RegExp(r'Error al generar respuesta LLM:\s*(.*)$').firstMatch(errorText);
-      if (rustMatch != null) {
-        technical = rustMatch.group(1) ?? errorText;
-      }
+  void _logRustError(String operation, Object error) {
+    if (error is AnimaError) {
+      _logger.w('$operation failed code=${error.code} detail=${error.detail}');
     }
-
-    // ignore: avoid_print
-    print('Error detallado de Rust: $technical');
   }
 
   Future<String> processMessage(
     String text, {
     String? appLanguage,
-    List<ChatMessage>? recentMessages,
   }) async {
     final stopwatch = Stopwatch()..start();
     _logger.i('processMessage start');
@@ -99,17 +92,14 @@ class AnimaService {
         await setAppLanguage(appLanguage);
       }
       final response = await rust_simple.sendMessage(
-        message: _buildRustPayload(text, recentMessages),
-        temperature: 0.7,
+        message: text.trim(),
+        temperature: _persistedTemperature,
         maxTokens: 512,
+        requestId: _uuid.v4(),
       );
 
       if (response.trim().isEmpty) {
-        throw Exception('[Error del Sistema: Falló la inferencia del modelo]');
-      }
-
-      if (response.startsWith('Error:') || response.startsWith('[Error del Sistema:')) {
-        throw Exception(response);
+        throw const AnimaError.emptyOutput('sendMessage returned an empty reply');
       }
 
       stopwatch.stop();
@@ -118,7 +108,7 @@ class AnimaService {
       return response;
     } catch (e, st) {
       stopwatch.stop();
-      _printDetailedRustError(e);
+      _logRustError('processMessage', e);
       _logger.e(
         'processMessage failed after ${stopwatch.elapsedMilliseconds}ms',
         error: e,
@@ -128,24 +118,30 @@ class AnimaService {
     }
   }
 
-  Stream<String> streamMessage(
+  /// Streams the reply to [text] as [rust_simple.ChatEvent]s.
+  ///
+  /// The request id is handed to [onRequestId] before generation starts so the
+  /// caller can stop it with [cancelMessage].
+  Stream<rust_simple.ChatEvent> streamMessage(
     String text, {
     String? appLanguage,
-    List<ChatMessage>? recentMessages,
+    void Function(String requestId)? onRequestId,
   }) {
     _logger.i('streamMessage start');
     _logger.d('streamMessage payload length=${text.length}');
+    final requestId = _uuid.v4();
+    onRequestId?.call(requestId);
     return _streamMessageInternal(
       text,
+      requestId: requestId,
       appLanguage: appLanguage,
-      recentMessages: recentMessages,
     );
   }
 
-  Stream<String> _streamMessageInternal(
+  Stream<rust_simple.ChatEvent> _streamMessageInternal(
     String text, {
+    required String requestId,
     String? appLanguage,
-    List<ChatMessage>? recentMessages,
   }) async* {
     try {
       await initialize();
@@ -153,41 +149,37 @@ class AnimaService {
         await setAppLanguage(appLanguage);
       }
       yield* rust_simple.sendMessageStream(
-        message: _buildRustPayload(text, recentMessages),
-        temperature: 0.7,
+        message: text.trim(),
+        temperature: _persistedTemperature,
         maxTokens: 512,
+        requestId: requestId,
       );
     } catch (e, st) {
-      _printDetailedRustError(e);
+      _logRustError('streamMessage', e);
       _logger.e('streamMessage failed', error: e, stackTrace: st);
       rethrow;
     }
   }
 
-  String _buildRustPayload(String text, List<ChatMessage>? recentMessages) {
-    if (recentMessages == null || recentMessages.isEmpty) {
-      return text;
-    }
-
-    final boundedHistory = recentMessages
-        .where((message) => message.content.trim().isNotEmpty)
-        .take(_maxRustHistoryMessages)
-        .map((message) => '${message.role}: ${message.content.trim()}')
-        .join('\n');
-
-    if (boundedHistory.isEmpty) {
-      return text;
+  /// Stops the generation started under [requestId]. Returns false when it
+  /// had already finished.
+  Future<bool> cancelMessage(String requestId) async {
+    _logger.i('cancelMessage start requestId=$requestId');
+    try {
+      final cancelled = await rust_simple.cancelGeneration(requestId: requestId);
+      _logger.i('cancelMessage result=$cancelled');
+      return cancelled;
+    } catch (e, st) {
+      _logger.e('cancelMessage failed', error: e, stackTrace: st);
+      rethrow;
     }
-
-    return '<ANIMA_HISTORY>\n$boundedHistory\n</ANIMA_HISTORY>\n<ANIMA_USER>\n${text.trim()}\n</ANIMA_USER>';
   }
 
-  Future<bool> saveAssistantMessage(String text) async {
+  Future<void> saveAssistantMessage(String text) async {
     _logger.i('saveAssistantMessage start length=${text.length}');
     try {
-      final saved = await rust_simple.saveAssistantMessage(message: text);
-      _logger.i('saveAssistantMessage result=$saved');
-      return saved;
+      await rust_simple.saveAssistantMessage(message: text);
+      _logger.i('saveAssistantMessage success');
     } catch (e, st) {
       _logger.e('saveAssistantMessage failed', error: e, stackTrace: st);
       rethrow;
@@ -206,13 +198,13 @@ class AnimaService {
       );
 
       if (greeting.trim().isEmpty) {
-        throw Exception('[Error del Sistema: Falló la inferencia del modelo]');
+        throw const AnimaError.emptyOutput('generateProactiveGreeting returned an empty reply');
       }
 
       _logger.i('generateProactiveGreeting success length=${greeting.length}');
       return greeting;
     } catch (e, st) {
-      _printDetailedRustError(e);
+      _logRustError('generateProactiveGreeting', e);
       _logger.e('generateProactiveGreeting failed', error: e, stackTrace: st);
       rethrow;
     }
@@ -254,12 +246,11 @@ class AnimaService {
     }
   }
 
-  Future<bool> deleteMemory(PlatformInt64 memoryId) async {
+  Future<void> deleteMemory(PlatformInt64 memoryId) async {
     _logger.i('deleteMemory start id=$memoryId');
     try {
-      final deleted = await rust_simple.deleteMemory(id: memoryId);
-      _logger.i('deleteMemory result id=$memoryId deleted=$deleted');
-      return deleted;
+      await rust_simple.deleteMemory(id: memoryId);
+      _logger.i('deleteMemory success id=$memoryId');
     } catch (e, st) {
       _logger.e('deleteMemory failed id=$memoryId', error: e, stackTrace: st);
       rethrow;
@@ -278,12 +269,11 @@ class AnimaService {
     }
   }
 
-  Future<bool> setCorePrompt(String prompt) async {
+  Future<void> setCorePrompt(String prompt) async {
     _logger.i('setCorePrompt start length=${prompt.length}');
     try {
-      final saved = await rust_simple.setCorePrompt(prompt: prompt);
-      _logger.i('setCorePrompt result saved=$saved');
-      return saved;
+      await rust_simple.setCorePrompt(prompt: prompt);
+      _logger.i('setCorePrompt success');
     } catch (e, st) {
       _logger.e('setCorePrompt failed', error: e, stackTrace: st);
       rethrow;
@@ -329,12 +319,11 @@ class AnimaService {
     }
   }
 
-  Future<bool> clearProfile() async {
+  Future<void> clearProfile() async {
     _logger.i('clearProfile start');
     try {
-      final cleared = await rust_simple.clearProfile();
-      _logger.i('clearProfile result=$cleared');
-      return cleared;
+      await rust_simple.clearProfile();
+      _logger.i('clearProfile success');
     } catch (e, st) {
       _logger.e('clearProfile failed', error: e, stackTrace: st);
       rethrow;
@@ -353,12 +342,11 @@ class AnimaService {
     }
   }
 
-  Future<bool> setUserName(String name) async {
+  Future<void> setUserName(String name) async {
     _logger.i('setUserName start length=${name.length}');
     try {
-      final saved = await rust_simple.setUserName(name: name);
-      _logger.i('setUserName result=$saved');
-      return saved;
+      await rust_simple.setUserName(name: name);
+      _logger.i('setUserName success');
     } catch (e, st) {
       _logger.e('setUserName failed', error: e, stackTrace: st);
       rethrow;
@@ -377,12 +365,11 @@ class AnimaService {
     }
   }
 
-  Future<bool> setAppLanguage(String lang) async {
+  Future<void> setAppLanguage(String lang) async {
     _logger.i('setAppLanguage start value=$lang');
     try {
-      final saved = await rust_simple.setAppLanguage(lang: lang);
-      _logger.i('setAppLanguage result=$saved');
-      return saved;
+      await rust_simple.setAppLanguage(lang: lang);
+      _logger.i('setAppLanguage success value=$lang');
     } catch (e, st) {
       _logger.e('setAppLanguage failed', error: e, stackTrace: st);
       rethrow;
@@ -401,12 +388,11 @@ class AnimaService {
     }
   }
 
-  Future<bool> setTemperature(double temperature) async {
+  Future<void> setTemperature(double temperature) async {
     _logger.i('setTemperature start value=$temperature');
     try {
-      final saved = await rust_simple.setTemperature(temperature: temperature);
-      _logger.i('setTemperature result=$saved');
-      return saved;
+      await rust_simple.setTemperature(temperature: temperature);
+      _logger.i('setTemperature success value=$temperature');
     } catch (e, st) {
       _logger.e('setTemperature failed', error: e, stackTrace: st);
       rethrow;
@@ -425,15 +411,14 @@ class AnimaService {
     }
   }
 
-  Future<bool> addProfileTrait(String category, String content) async {
+  Future<void> addProfileTrait(String category, String content) async {
     _logger.i('addProfileTrait start category=$category');
     try {
-      final saved = await rust_simple.addProfileTrait(
+      await rust_simple.addProfileTrait(
         category: category,
         content: content,
       );
-      _logger.i('addProfileTrait result=$saved');
-      return saved;
+      _logger.i('addProfileTrait success');
     } catch (e, st) {
       _logger.e('addProfileTrait failed', error: e, stackTrace: st);
       rethrow;
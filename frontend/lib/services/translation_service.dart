@@ -1,6 +1,7 @@
 import 'package:flutter/material.dart';
 import 'package:provider/provider.dart';
 
+import 'anima_error.dart';
 import 'anima_service.dart';
 
 class AppTranslations {
@@ -1040,6 +1041,105 @@ class AppTranslations {
       'ZH': '引导流程出错',
       'AR': 'خطأ أثناء الإعداد',
     },
+    'errorModelNotLoaded': {
+      'EN': 'The AI model is still loading. Please try again in a moment.',
+      'ES': 'El modelo de IA todavía se está cargando. Inténtalo de nuevo en un momento.',
+      'DE': 'Das KI-Modell wird noch geladen. Bitte versuche es gleich noch einmal.',
+      'RU': 'Модель ИИ ещё загружается. Попробуйте снова через мгновение.',
+      'JP': 'AIモデルを読み込み中です。少し待ってから再度お試しください。',
+      'ZH': 'AI 模型仍在加载中，请稍后再试。',
+      'AR': 'لا يزال نموذج الذكاء الاصطناعي قيد التحميل. يرجى المحاولة بعد قليل.',
+    },
+    'errorModelFile': {
+      'EN': 'The model file could not be loaded. Check the selected model in Settings.',
+      'ES': 'No se pudo cargar el archivo del modelo. Revisa el modelo seleccionado en Ajustes.',
+      'DE': 'Die Modelldatei konnte nicht geladen werden. Prüfe das ausgewählte Modell in den Einstellungen.',
+      'RU': 'Не удалось загрузить файл модели. Проверьте выбранную модель в настройках.',
+      'JP': 'モデルファイルを読み込めませんでした。設定で選択中のモデルを確認してください。',
+      'ZH': '无法加载模型文件。请在设置中检查所选模型。',
+      'AR': 'تعذّر تحميل ملف النموذج. تحقق من النموذج المحدد في الإعدادات.',
+    },
+    'errorInsufficientMemory': {
+      'EN': 'Not enough free memory for the model. Close other apps or choose a smaller model.',
+      'ES': 'No hay suficiente memoria libre para el modelo. Cierra otras aplicaciones o elige un modelo más pequeño.',
+      'DE': 'Nicht genug freier Speicher für das Modell. Schließe andere Apps oder wähle ein kleineres Modell.',
+      'RU': 'Недостаточно свободной памяти для модели. Закройте другие приложения или выберите модель поменьше.',
+      'JP': 'モデルに必要な空きメモリが不足しています。他のアプリを閉じるか、より小さいモデルを選んでください。',
+      'ZH': '内存不足，无法加载模型。请关闭其他应用或选择更小的模型。',
+      'AR': 'لا توجد ذاكرة كافية للنموذج. أغلق التطبيقات الأخرى أو اختر نموذجًا أصغر.',
+    },
+    'errorContextOverflow': {
+      'EN': 'The conversation is too long for the model. Try a shorter message.',
+      'ES': 'La conversación es demasiado larga para el modelo. Prueba con un mensaje más corto.',
+      'DE': 'Die Unterhaltung ist zu lang für das Modell. Versuche eine kürzere Nachricht.',
+      'RU': 'Разговор слишком длинный для модели. Попробуйте сообщение покороче.',
+      'JP': '会話がモデルには長すぎます。短いメッセージでお試しください。',
+      'ZH': '对话内容超出模型上限，请尝试更短的消息。',
+      'AR': 'المحادثة طويلة جدًا بالنسبة للنموذج. جرّب رسالة أقصر.',
+    },
+    'errorBackendUnavailable': {
+      'EN': 'The inference server is not responding. Check the backend in Settings.',
+      'ES': 'El servidor de inferencia no responde. Revisa el backend en Ajustes.',
+      'DE': 'Der Inferenzserver antwortet nicht. Prüfe das Backend in den Einstellungen.',
+      'RU': 'Сервер инференса не отвечает. Проверьте бэкенд в настройках.',
+      'JP': '推論サーバーが応答していません。設定でバックエンドを確認してください。',
+      'ZH': '推理服务器无响应，请在设置中检查后端。',
+      'AR': 'خادم الاستدلال لا يستجيب. تحقق من الواجهة الخلفية في الإعدادات.',
+    },
+    'errorGenerationFailed': {
+      'EN': 'I could not answer right now. Please try again.',
+      'ES': 'No pude responder ahora mismo. Inténtalo de nuevo.',
+      'DE': 'Ich konnte gerade nicht antworten. Bitte versuche es erneut.',
+      'RU': 'Сейчас не удалось ответить. Попробуйте ещё раз.',
+      'JP': '現在応答できませんでした。もう一度お試しください。',
+      'ZH': '当前无法回答，请稍后重试。',
+      'AR': 'تعذّر الرد الآن. يرجى المحاولة مرة أخرى.',
+    },
+    'errorCancelled': {
+      'EN': 'The request was cancelled.',
+      'ES': 'La solicitud se canceló.',
+      'DE': 'Die Anfrage wurde abgebrochen.',
+      'RU': 'Запрос был отменён.',
+      'JP': 'リクエストはキャンセルされました。',
+      'ZH': '请求已取消。',
+      'AR': 'تم إلغاء الطلب.',
+    },
+    'errorDbLocked': {
+      'EN': 'The database is busy. Please try again in a moment.',
+      'ES': 'La base de datos está ocupada. Inténtalo de nuevo en un momento.',
+      'DE': 'Die Datenbank ist beschäftigt. Bitte versuche es gleich noch einmal.',
+      'RU': 'База данных занята. Попробуйте снова через мгновение.',
+      'JP': 'データベースが使用中です。少し待ってから再度お試しください。',
+      'ZH': '数据库繁忙，请稍后再试。',
+      'AR': 'قاعدة البيانات مشغولة. يرجى المحاولة بعد قليل.',
+    },
+    'errorStorage': {
+      'EN': 'Could not read or write local data.',
+      'ES': 'No se pudieron leer o escribir los datos locales.',
+      'DE': 'Lokale Daten konnten nicht gelesen oder geschrieben werden.',
+      'RU': 'Не удалось прочитать или записать локальные данные.',
+      'JP': 'ローカルデータの読み書きができませんでした。',
+      'ZH': '无法读取或写入本地数据。',
+      'AR': 'تعذّرت قراءة البيانات المحلية أو كتابتها.',
+    },
+    'errorInvalidInput': {
+      'EN': 'That value is not valid.',
+      'ES': 'Ese valor no es válido.',
+      'DE': 'Dieser Wert ist ungültig.',
+      'RU': 'Это значение недопустимо.',
+      'JP': 'その値は無効です。',
+      'ZH': '该值无效。',
+      'AR': 'هذه القيمة غير صالحة.',
+    },
+    'errorInternal': {
+      'EN': 'Something went wrong. Please try again.',
+      'ES': 'Algo salió mal. Inténtalo de nuevo.',
+      'DE': 'Etwas ist schiefgelaufen. Bitte versuche es erneut.',
+      'RU': 'Что-то пошло не так. Попробуйте ещё раз.',
+      'JP': '問題が発生しました。もう一度お試しください。',
+      'ZH': '出现问题，请重试。',
+      'AR': 'حدث خطأ ما. يرجى المحاولة مرة أخرى.',
+    },
   };
 }
 
@@ -1070,7 +1170,8 @@ class TranslationService extends ChangeNotifier {
     }
 
     try {
-      return await _animaService.setAppLanguage(normalized);
+      await _animaService.setAppLanguage(normalized);
+      return true;
     } catch (_) {
       return false;
     }
@@ -1133,3 +1234,13 @@ class TranslationService extends ChangeNotifier {
 String tr(BuildContext context, String key) {
   return context.watch<TranslationService>().tr(key);
 }
+
+/// User-facing message for an error thrown by the Rust bridge.
+String trError(BuildContext context, Object error) {
+  return trErrorCode(context, animaErrorCode(error));
+}
+
+/// User-facing message for an error code, e.g. from `ChatEvent.error`.
+String trErrorCode(BuildContext context, String code) {
+  return context.read<TranslationService>().tr(errorTranslationKey(code));
+}
@@ -3,96 +3,371 @@
 
 // ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
 
+import '../ai.dart';
+import '../backend.dart';
 import '../db.dart';
+import '../error.dart';
 import '../frb_generated.dart';
+import '../job_queue.dart';
+import '../load_strategy.dart';
+import '../model_manager.dart';
+import '../model_registry.dart';
+import '../reembed.dart';
+import '../runtime_config.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+import 'package:freezed_annotation/freezed_annotation.dart' hide protected;
+part 'simple.freezed.dart';
 
-// These functions are ignored because they are not marked as `pub`: `current_init_error`, `prepare_message_context`, `resolve_model_path`, `set_init_error`
+            // These functions are ignored because they are not marked as `pub`: `apply_rerank_scores`, `embed_message`, `format_memory_snippets`, `history_turns`, `insert_message_with_timeout`, `load_stored_history`, `panic_payload_to_string`, `persisted_sampling_params`, `prepare_message_context`, `push_stream_error`, `request_options_sampling`, `request_sampling_params`, `resolve_model_path`, `retrieve_memories`, `saturating_u32`
+// These function are ignored because they are on traits that is not defined in current crate (put an empty `#[frb]` on it to unignore): `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `clone`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`, `fmt`
 
-String greet({required String name}) =>
-    RustLib.instance.api.crateApiSimpleGreet(name: name);
 
-Future<String> sendMessage(
-        {required String message,
-        required double temperature,
-        required int maxTokens}) =>
-    RustLib.instance.api.crateApiSimpleSendMessage(
-        message: message, temperature: temperature, maxTokens: maxTokens);
+            String  greet({required String name }) => RustLib.instance.api.crateApiSimpleGreet(name: name);
 
-Stream<String> sendMessageStream(
-        {required String message,
-        required double temperature,
-        required int maxTokens}) =>
-    RustLib.instance.api.crateApiSimpleSendMessageStream(
-        message: message, temperature: temperature, maxTokens: maxTokens);
+/// `request_id`, when given, is the id `cancel_generation` stops this reply by.
+Future<String>  sendMessage({required String message , required double temperature , required int maxTokens , String? requestId }) => RustLib.instance.api.crateApiSimpleSendMessage(message: message, temperature: temperature, maxTokens: maxTokens, requestId: requestId);
 
-Future<double> getTemperature() =>
-    RustLib.instance.api.crateApiSimpleGetTemperature();
+Stream<ChatEvent>  sendMessageStream({required String message , required double temperature , required int maxTokens , String? requestId }) => RustLib.instance.api.crateApiSimpleSendMessageStream(message: message, temperature: temperature, maxTokens: maxTokens, requestId: requestId);
 
-Future<bool> setTemperature({required double temperature}) =>
-    RustLib.instance.api.crateApiSimpleSetTemperature(temperature: temperature);
+Future<ChatCompletion>  sendChat({required ChatRequest request }) => RustLib.instance.api.crateApiSimpleSendChat(request: request);
 
-Future<String> exportBrain() =>
-    RustLib.instance.api.crateApiSimpleExportBrain();
+Stream<ChatEvent>  sendChatStream({required ChatRequest request }) => RustLib.instance.api.crateApiSimpleSendChatStream(request: request);
 
-Future<bool> saveAssistantMessage({required String message}) =>
-    RustLib.instance.api.crateApiSimpleSaveAssistantMessage(message: message);
+/// Dry run of `send_message`: retrieves memories and assembles the prompt
+/// against the stored history, but stores nothing and decodes nothing.
+Future<PromptPreview>  previewPrompt({required String message }) => RustLib.instance.api.crateApiSimplePreviewPrompt(message: message);
 
-Future<String> generateProactiveGreeting({required String timeOfDay}) =>
-    RustLib.instance.api
-        .crateApiSimpleGenerateProactiveGreeting(timeOfDay: timeOfDay);
+Future<bool>  cancelGeneration({required String requestId }) => RustLib.instance.api.crateApiSimpleCancelGeneration(requestId: requestId);
 
-Future<List<ChatMessage>> getChatHistory() =>
-    RustLib.instance.api.crateApiSimpleGetChatHistory();
+Future<double>  getTemperature() => RustLib.instance.api.crateApiSimpleGetTemperature();
 
-Future<List<MemoryItem>> getAllMemories() =>
-    RustLib.instance.api.crateApiSimpleGetAllMemories();
+Future<void>  setTemperature({required double temperature }) => RustLib.instance.api.crateApiSimpleSetTemperature(temperature: temperature);
 
-Future<List<MemoryItem>> searchMemories({required String query}) =>
-    RustLib.instance.api.crateApiSimpleSearchMemories(query: query);
+Future<SamplingParams>  getSamplingParams() => RustLib.instance.api.crateApiSimpleGetSamplingParams();
 
-Future<bool> deleteMemory({required PlatformInt64 id}) =>
-    RustLib.instance.api.crateApiSimpleDeleteMemory(id: id);
+Future<void>  setSamplingParams({required SamplingParams params }) => RustLib.instance.api.crateApiSimpleSetSamplingParams(params: params);
 
-Future<String> getCorePrompt() =>
-    RustLib.instance.api.crateApiSimpleGetCorePrompt();
+Future<bool>  getPrefixCacheEnabled() => RustLib.instance.api.crateApiSimpleGetPrefixCacheEnabled();
 
-Future<bool> setCorePrompt({required String prompt}) =>
-    RustLib.instance.api.crateApiSimpleSetCorePrompt(prompt: prompt);
+/// Turning this off restores a full KV cache clear before and after every turn.
+Future<void>  setPrefixCacheEnabled({required bool enabled }) => RustLib.instance.api.crateApiSimpleSetPrefixCacheEnabled(enabled: enabled);
 
-Future<String> getUserName() =>
-    RustLib.instance.api.crateApiSimpleGetUserName();
+Future<double>  getLexicalWeight() => RustLib.instance.api.crateApiSimpleGetLexicalWeight();
 
-Future<bool> setUserName({required String name}) =>
-    RustLib.instance.api.crateApiSimpleSetUserName(name: name);
+/// Share of memory retrieval given to full-text ranking: 0 ranks by meaning
+/// only, 1 by shared words only. Clamped to that range.
+Future<void>  setLexicalWeight({required double weight }) => RustLib.instance.api.crateApiSimpleSetLexicalWeight(weight: weight);
 
-Future<String> getAppLanguage() =>
-    RustLib.instance.api.crateApiSimpleGetAppLanguage();
+Future<double>  getMmrLambda() => RustLib.instance.api.crateApiSimpleGetMmrLambda();
 
-Future<bool> setAppLanguage({required String lang}) =>
-    RustLib.instance.api.crateApiSimpleSetAppLanguage(lang: lang);
+/// 1 injects the best-scored memories as they are; lower values trade score
+/// for memories unlike the ones already picked. Clamped to 0..=1.
+Future<void>  setMmrLambda({required double lambda }) => RustLib.instance.api.crateApiSimpleSetMmrLambda(lambda: lambda);
 
-Future<bool> addProfileTrait(
-        {required String category, required String content}) =>
-    RustLib.instance.api
-        .crateApiSimpleAddProfileTrait(category: category, content: content);
+Future<MemoryScoringWeights>  getMemoryScoringWeights() => RustLib.instance.api.crateApiSimpleGetMemoryScoringWeights();
 
-Future<bool> exportDatabase({required String destPath}) =>
-    RustLib.instance.api.crateApiSimpleExportDatabase(destPath: destPath);
+/// Weights are clamped to 0..=10 and the half-life to at least 0.1 days.
+Future<void>  setMemoryScoringWeights({required MemoryScoringWeights weights }) => RustLib.instance.api.crateApiSimpleSetMemoryScoringWeights(weights: weights);
 
-Future<bool> factoryReset() =>
-    RustLib.instance.api.crateApiSimpleFactoryReset();
+Future<String>  exportBrain() => RustLib.instance.api.crateApiSimpleExportBrain();
 
-Future<bool> runSleepCycle() =>
-    RustLib.instance.api.crateApiSimpleRunSleepCycle();
+Future<void>  saveAssistantMessage({required String message }) => RustLib.instance.api.crateApiSimpleSaveAssistantMessage(message: message);
 
-Future<List<ProfileTrait>> getProfileTraits() =>
-    RustLib.instance.api.crateApiSimpleGetProfileTraits();
+Future<String>  generateProactiveGreeting({required String timeOfDay }) => RustLib.instance.api.crateApiSimpleGenerateProactiveGreeting(timeOfDay: timeOfDay);
 
-Future<bool> clearProfile() =>
-    RustLib.instance.api.crateApiSimpleClearProfile();
+Future<List<ChatMessage>>  getChatHistory() => RustLib.instance.api.crateApiSimpleGetChatHistory();
 
-Future<void> initApp(
-        {required String chatModelPath, required String embeddingModelPath}) =>
-    RustLib.instance.api.crateApiSimpleInitApp(
-        chatModelPath: chatModelPath, embeddingModelPath: embeddingModelPath);
+Future<List<MemoryItem>>  getAllMemories() => RustLib.instance.api.crateApiSimpleGetAllMemories();
+
+Future<List<MemoryItem>>  searchMemories({required String query }) => RustLib.instance.api.crateApiSimpleSearchMemories(query: query);
+
+Future<void>  deleteMemory({required PlatformInt64 id }) => RustLib.instance.api.crateApiSimpleDeleteMemory(id: id);
+
+Future<String>  getCorePrompt() => RustLib.instance.api.crateApiSimpleGetCorePrompt();
+
+Future<void>  setCorePrompt({required String prompt }) => RustLib.instance.api.crateApiSimpleSetCorePrompt(prompt: prompt);
+
+Future<String>  getUserName() => RustLib.instance.api.crateApiSimpleGetUserName();
+
+Future<void>  setUserName({required String name }) => RustLib.instance.api.crateApiSimpleSetUserName(name: name);
+
+Future<String>  getAppLanguage() => RustLib.instance.api.crateApiSimpleGetAppLanguage();
+
+Future<void>  setAppLanguage({required String lang }) => RustLib.instance.api.crateApiSimpleSetAppLanguage(lang: lang);
+
+Future<void>  addProfileTrait({required String category , required String content }) => RustLib.instance.api.crateApiSimpleAddProfileTrait(category: category, content: content);
+
+Future<bool>  exportDatabase({required String destPath }) => RustLib.instance.api.crateApiSimpleExportDatabase(destPath: destPath);
+
+Future<bool>  factoryReset() => RustLib.instance.api.crateApiSimpleFactoryReset();
+
+Future<bool>  runSleepCycle() => RustLib.instance.api.crateApiSimpleRunSleepCycle();
+
+Future<List<ProfileTrait>>  getProfileTraits() => RustLib.instance.api.crateApiSimpleGetProfileTraits();
+
+Future<void>  clearProfile() => RustLib.instance.api.crateApiSimpleClearProfile();
+
+Future<void>  initApp({required String chatModelPath , required String embeddingModelPath }) => RustLib.instance.api.crateApiSimpleInitApp(chatModelPath: chatModelPath, embeddingModelPath: embeddingModelPath);
+
+Future<ModelStatus>  getModelStatus() => RustLib.instance.api.crateApiSimpleGetModelStatus();
+
+/// Running and waiting inference jobs per worker; position 0 is running.
+Future<InferenceQueueStatus>  getInferenceQueue() => RustLib.instance.api.crateApiSimpleGetInferenceQueue();
+
+/// Stops a running sleep cycle turn so the user's request starts right away.
+/// Queuing a chat turn already does this; the preempted turn is queued again
+/// behind it.
+Future<bool>  preemptBackgroundJobs() => RustLib.instance.api.crateApiSimplePreemptBackgroundJobs();
+
+/// Loads a model into its slot, replacing the current one. Relative paths are
+/// resolved like in `init_app`.
+Future<void>  loadModel({required ModelKind kind , required String path }) => RustLib.instance.api.crateApiSimpleLoadModel(kind: kind, path: path);
+
+Future<void>  reloadModel({required ModelKind kind }) => RustLib.instance.api.crateApiSimpleReloadModel(kind: kind);
+
+/// Cancels running generations on that model and frees it.
+Future<void>  unloadModel({required ModelKind kind }) => RustLib.instance.api.crateApiSimpleUnloadModel(kind: kind);
+
+/// Applies to the next load or reload.
+Future<LoadStrategy>  getLoadStrategy() => RustLib.instance.api.crateApiSimpleGetLoadStrategy();
+
+Future<void>  setLoadStrategy({required LoadStrategy strategy }) => RustLib.instance.api.crateApiSimpleSetLoadStrategy(strategy: strategy);
+
+/// Context size, batch and thread settings. They apply when the chat context is
+/// next created, i.e. on the next load or `reload_model`.
+Future<RuntimeSettings>  getRuntimeSettings() => RustLib.instance.api.crateApiSimpleGetRuntimeSettings();
+
+Future<void>  setRuntimeSettings({required RuntimeSettings settings }) => RustLib.instance.api.crateApiSimpleSetRuntimeSettings(settings: settings);
+
+/// What the loaded chat context was created with, after auto-tuning.
+Future<ContextConfig>  getChatContextConfig() => RustLib.instance.api.crateApiSimpleGetChatContextConfig();
+
+/// GGUF files in the models folder, described from their headers.
+Future<List<ModelInfo>>  listModels() => RustLib.instance.api.crateApiSimpleListModels();
+
+/// Loads the model with that registry id as the chat model and keeps using it
+/// on later starts.
+Future<ModelInfo>  selectChatModel({required String id }) => RustLib.instance.api.crateApiSimpleSelectChatModel(id: id);
+
+Future<ModelInfo>  selectEmbeddingModel({required String id }) => RustLib.instance.api.crateApiSimpleSelectEmbeddingModel(id: id);
+
+/// Loads a cross-encoder reranking model (rank pooling) that reorders the
+/// memories retrieved for each message.
+Future<ModelInfo>  selectRerankerModel({required String id }) => RustLib.instance.api.crateApiSimpleSelectRerankerModel(id: id);
+
+/// Unloads the reranker and forgets the selection, so retrieval runs without
+/// one from now on.
+Future<void>  clearRerankerModel() => RustLib.instance.api.crateApiSimpleClearRerankerModel();
+
+Future<String>  getModelsDir() => RustLib.instance.api.crateApiSimpleGetModelsDir();
+
+Future<void>  setModelsDir({required String path }) => RustLib.instance.api.crateApiSimpleSetModelsDir(path: path);
+
+/// Whether memories need `reembed_memories` after an embedding model change.
+Future<EmbeddingStatus>  getEmbeddingStatus() => RustLib.instance.api.crateApiSimpleGetEmbeddingStatus();
+
+/// Re-embeds memories stored by another embedding model with the loaded one.
+/// Safe to stop at any point; calling it again continues with what is left.
+Stream<ReembedEvent>  reembedMemories() => RustLib.instance.api.crateApiSimpleReembedMemories();
+
+Future<bool>  cancelReembedding() => RustLib.instance.api.crateApiSimpleCancelReembedding();
+
+Future<BackendSettings>  getBackendSettings() => RustLib.instance.api.crateApiSimpleGetBackendSettings();
+
+/// Saves the backend choice. A slot moved to the server or the mock has its
+/// local model unloaded so the same weights are not held twice; moving back
+/// needs a `load_model`.
+Future<void>  setBackendSettings({required BackendSettings settings }) => RustLib.instance.api.crateApiSimpleSetBackendSettings(settings: settings);
+
+            /// Assistant reply; when `stop_reason` is `Cancelled` the text is partial and
+/// has not been stored, so the caller decides whether to keep it.
+class ChatCompletion  {
+                final String text;
+final StopReason stopReason;
+
+                const ChatCompletion({required this.text ,required this.stopReason ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => text.hashCode^stopReason.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is ChatCompletion &&
+                runtimeType == other.runtimeType
+                && text == other.text&& stopReason == other.stopReason;
+        
+            }
+
+@freezed
+                sealed class ChatEvent with _$ChatEvent  {
+                    const ChatEvent._();
+
+                     const factory ChatEvent.contextRetrieved({   required List<MemoryMatch> memories , }) = ChatEvent_ContextRetrieved;
+ const factory ChatEvent.token(  String field0,) = ChatEvent_Token;
+ const factory ChatEvent.done({   required int promptTokens ,  required int completionTokens ,  required double tokensPerSec ,  required StopReason stopReason , }) = ChatEvent_Done;
+ /// `code` is `AnimaError::code()`, so the UI can localize it.
+const factory ChatEvent.error({   required String code ,  required String detail , }) = ChatEvent_Error;
+
+                    
+
+                    
+                }
+
+class ChatOptions  {
+                /// Overrides the persisted sampling settings for this request only.
+final SamplingParams? sampling;
+final int maxTokens;
+
+                const ChatOptions({this.sampling ,required this.maxTokens ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => sampling.hashCode^maxTokens.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is ChatOptions &&
+                runtimeType == other.runtimeType
+                && sampling == other.sampling&& maxTokens == other.maxTokens;
+        
+            }
+
+class ChatRequest  {
+                /// Id accepted by `cancel_generation`; requests without one cannot be cancelled.
+final String? requestId;
+final List<Turn> history;
+final String message;
+final ChatOptions options;
+
+                const ChatRequest({this.requestId ,required this.history ,required this.message ,required this.options ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => requestId.hashCode^history.hashCode^message.hashCode^options.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is ChatRequest &&
+                runtimeType == other.runtimeType
+                && requestId == other.requestId&& history == other.history&& message == other.message&& options == other.options;
+        
+            }
+
+class PromptPreview  {
+                final List<PromptSectionPreview> sections;
+final List<MemoryMatch> memories;
+/// The full prompt after the chat template is applied.
+final String renderedPrompt;
+final int totalTokens;
+final int contextWindow;
+final int reservedForGeneration;
+
+                const PromptPreview({required this.sections ,required this.memories ,required this.renderedPrompt ,required this.totalTokens ,required this.contextWindow ,required this.reservedForGeneration ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => sections.hashCode^memories.hashCode^renderedPrompt.hashCode^totalTokens.hashCode^contextWindow.hashCode^reservedForGeneration.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is PromptPreview &&
+                runtimeType == other.runtimeType
+                && sections == other.sections&& memories == other.memories&& renderedPrompt == other.renderedPrompt&& totalTokens == other.totalTokens&& contextWindow == other.contextWindow&& reservedForGeneration == other.reservedForGeneration;
+        
+            }
+
+/// One prompt section after budgeting; `text` is exactly what the model sees.
+class PromptSectionPreview  {
+                final String name;
+final String text;
+final int tokens;
+final int requestedTokens;
+final int itemsKept;
+final int itemsTotal;
+final bool truncated;
+
+                const PromptSectionPreview({required this.name ,required this.text ,required this.tokens ,required this.requestedTokens ,required this.itemsKept ,required this.itemsTotal ,required this.truncated ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => name.hashCode^text.hashCode^tokens.hashCode^requestedTokens.hashCode^itemsKept.hashCode^itemsTotal.hashCode^truncated.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is PromptSectionPreview &&
+                runtimeType == other.runtimeType
+                && name == other.name&& text == other.text&& tokens == other.tokens&& requestedTokens == other.requestedTokens&& itemsKept == other.itemsKept&& itemsTotal == other.itemsTotal&& truncated == other.truncated;
+        
+            }
+
+@freezed
+                sealed class ReembedEvent with _$ReembedEvent  {
+                    const ReembedEvent._();
+
+                     const factory ReembedEvent.progress({   required int done ,  required int total , }) = ReembedEvent_Progress;
+ const factory ReembedEvent.done({   required String modelId ,  required int reembedded , }) = ReembedEvent_Done;
+ /// `code` is `AnimaError::code()`; `cancelled` when stopped with
+/// `cancel_reembedding`. Memories done so far keep their new vectors.
+const factory ReembedEvent.error({   required String code ,  required String detail , }) = ReembedEvent_Error;
+
+                    
+
+                    
+                }
+
+/// One prior dialogue turn; `role` is `"user"` or `"assistant"`.
+class Turn  {
+                final String role;
+final String content;
+
+                const Turn({required this.role ,required this.content ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => role.hashCode^content.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is Turn &&
+                runtimeType == other.runtimeType
+                && role == other.role&& content == other.content;
+        
+            }
+            
\ No newline at end of file
@@ -0,0 +1,62 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import 'frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+
+
+            
+
+            
+
+            enum BackendKind {
+                    /// llama.cpp in this process, using the models from `load_model`.
+llama,
+/// An OpenAI-compatible server on this machine, e.g. llama-server or Ollama.
+openAiServer,
+/// Scripted replies and hashed embeddings from `MockBackend`, for running
+/// the app and its integration tests without model files.
+mock,
+                    ;
+                    
+                }
+
+/// Persisted backend choice. Chat and embeddings are chosen separately, so a
+/// large served chat model can be combined with the small local embedder.
+class BackendSettings  {
+                final BackendKind chatBackend;
+final BackendKind embeddingBackend;
+/// Base URL of the server, e.g. `http://127.0.0.1:8080` for llama-server
+/// or `http://127.0.0.1:11434` for Ollama. Must be on localhost.
+final String serverUrl;
+/// `model` sent with chat requests; llama-server ignores it.
+final String serverChatModel;
+/// `model` sent with embedding requests. Required when embeddings come
+/// from the server: it is recorded with every memory, so a different
+/// embedder behind the same URL is noticed as a model change.
+final String serverEmbeddingModel;
+/// The server's context size, which it does not report in a standard way.
+final int serverContextWindow;
+
+                const BackendSettings({required this.chatBackend ,required this.embeddingBackend ,required this.serverUrl ,required this.serverChatModel ,required this.serverEmbeddingModel ,required this.serverContextWindow ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => chatBackend.hashCode^embeddingBackend.hashCode^serverUrl.hashCode^serverChatModel.hashCode^serverEmbeddingModel.hashCode^serverContextWindow.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is BackendSettings &&
+                runtimeType == other.runtimeType
+                && chatBackend == other.chatBackend&& embeddingBackend == other.embeddingBackend&& serverUrl == other.serverUrl&& serverChatModel == other.serverChatModel&& serverEmbeddingModel == other.serverEmbeddingModel&& serverContextWindow == other.serverContextWindow;
+        
+            }
+            
\ No newline at end of file
@@ -6,75 +6,219 @@
 import 'frb_generated.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
 
-class ChatMessage {
-  final PlatformInt64 id;
-  final String role;
-  final String content;
-  final String timestamp;
-
-  const ChatMessage({
-    required this.id,
-    required this.role,
-    required this.content,
-    required this.timestamp,
-  });
-
-  @override
-  int get hashCode =>
-      id.hashCode ^ role.hashCode ^ content.hashCode ^ timestamp.hashCode;
-
-  @override
-  bool operator ==(Object other) =>
-      identical(this, other) ||
-      other is ChatMessage &&
-          runtimeType == other.runtimeType &&
-          id == other.id &&
-          role == other.role &&
-          content == other.content &&
-          timestamp == other.timestamp;
-}
-
-class MemoryItem {
-  final PlatformInt64 id;
-  final String content;
-  final String createdAt;
-
-  const MemoryItem({
-    required this.id,
-    required this.content,
-    required this.createdAt,
-  });
-
-  @override
-  int get hashCode => id.hashCode ^ content.hashCode ^ createdAt.hashCode;
-
-  @override
-  bool operator ==(Object other) =>
-      identical(this, other) ||
-      other is MemoryItem &&
-          runtimeType == other.runtimeType &&
-          id == other.id &&
-          content == other.content &&
-          createdAt == other.createdAt;
-}
-
-class ProfileTrait {
-  final String category;
-  final String content;
-
-  const ProfileTrait({
-    required this.category,
-    required this.content,
-  });
-
-  @override
-  int get hashCode => category.hashCode ^ content.hashCode;
-
-  @override
-  bool operator ==(Object other) =>
-      identical(this, other) ||
-      other is ProfileTrait &&
-          runtimeType == other.runtimeType &&
-          category == other.category &&
-          content == other.content;
-}
+
+            
+
+            
+
+            class ChatMessage  {
+                final PlatformInt64 id;
+final String role;
+final String content;
+final String timestamp;
+
+                const ChatMessage({required this.id ,required this.role ,required this.content ,required this.timestamp ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => id.hashCode^role.hashCode^content.hashCode^timestamp.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is ChatMessage &&
+                runtimeType == other.runtimeType
+                && id == other.id&& role == other.role&& content == other.content&& timestamp == other.timestamp;
+        
+            }
+
+class MemoryItem  {
+                final PlatformInt64 id;
+final String content;
+final String createdAt;
+
+                const MemoryItem({required this.id ,required this.content ,required this.createdAt ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => id.hashCode^content.hashCode^createdAt.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is MemoryItem &&
+                runtimeType == other.runtimeType
+                && id == other.id&& content == other.content&& createdAt == other.createdAt;
+        
+            }
+
+class MemoryMatch  {
+                final PlatformInt64 messageId;
+final String role;
+final String content;
+/// Cosine similarity to the query; 0 for lexical-only hits from another
+/// embedding model.
+final double similarity;
+/// Fused rank score of the similarity and full-text rankings; only
+/// comparable within one query.
+final double relevance;
+final double importance;
+final int accessCount;
+/// What the results are ordered by.
+final MemoryScore score;
+/// Cross-encoder score when a reranker model reordered the results; it
+/// then takes precedence over `score`.
+final double? rerankScore;
+final String timestamp;
+final String memoryType;
+final PlatformInt64 memoryUnixTimestamp;
+
+                const MemoryMatch({required this.messageId ,required this.role ,required this.content ,required this.similarity ,required this.relevance ,required this.importance ,required this.accessCount ,required this.score ,this.rerankScore ,required this.timestamp ,required this.memoryType ,required this.memoryUnixTimestamp ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => messageId.hashCode^role.hashCode^content.hashCode^similarity.hashCode^relevance.hashCode^importance.hashCode^accessCount.hashCode^score.hashCode^rerankScore.hashCode^timestamp.hashCode^memoryType.hashCode^memoryUnixTimestamp.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is MemoryMatch &&
+                runtimeType == other.runtimeType
+                && messageId == other.messageId&& role == other.role&& content == other.content&& similarity == other.similarity&& relevance == other.relevance&& importance == other.importance&& accessCount == other.accessCount&& score == other.score&& rerankScore == other.rerankScore&& timestamp == other.timestamp&& memoryType == other.memoryType&& memoryUnixTimestamp == other.memoryUnixTimestamp;
+        
+            }
+
+/// Per-signal contributions to a retrieved memory's score, each already
+/// multiplied by its weight, so they add up to `total`.
+class MemoryScore  {
+                final double relevance;
+final double recency;
+final double importance;
+final double access;
+final double total;
+
+                const MemoryScore({required this.relevance ,required this.recency ,required this.importance ,required this.access ,required this.total ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => relevance.hashCode^recency.hashCode^importance.hashCode^access.hashCode^total.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is MemoryScore &&
+                runtimeType == other.runtimeType
+                && relevance == other.relevance&& recency == other.recency&& importance == other.importance&& access == other.access&& total == other.total;
+        
+            }
+
+/// How retrieval trades relevance to the query against the age, importance
+/// and retrieval count of a memory. A weight of 0 ignores that signal.
+class MemoryScoringWeights  {
+                final double relevance;
+final double recency;
+final double importance;
+final double access;
+/// Age at which the recency signal has halved.
+final double recencyHalfLifeDays;
+
+                const MemoryScoringWeights({required this.relevance ,required this.recency ,required this.importance ,required this.access ,required this.recencyHalfLifeDays ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => relevance.hashCode^recency.hashCode^importance.hashCode^access.hashCode^recencyHalfLifeDays.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is MemoryScoringWeights &&
+                runtimeType == other.runtimeType
+                && relevance == other.relevance&& recency == other.recency&& importance == other.importance&& access == other.access&& recencyHalfLifeDays == other.recencyHalfLifeDays;
+        
+            }
+
+class ProfileTrait  {
+                final String category;
+final String content;
+
+                const ProfileTrait({required this.category ,required this.content ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => category.hashCode^content.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is ProfileTrait &&
+                runtimeType == other.runtimeType
+                && category == other.category&& content == other.content;
+        
+            }
+
+/// Sampler configuration used for a single generation.
+///
+/// `temperature <= 0` selects greedy decoding, `mirostat` is 0 (off), 1 or 2,
+/// and a `seed` of `None` draws a fresh seed per request.
+class SamplingParams  {
+                final double temperature;
+final int topK;
+final double topP;
+final double minP;
+final double repeatPenalty;
+final int repeatLastN;
+final int? seed;
+final int mirostat;
+final double mirostatTau;
+final double mirostatEta;
+
+                const SamplingParams({required this.temperature ,required this.topK ,required this.topP ,required this.minP ,required this.repeatPenalty ,required this.repeatLastN ,this.seed ,required this.mirostat ,required this.mirostatTau ,required this.mirostatEta ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => temperature.hashCode^topK.hashCode^topP.hashCode^minP.hashCode^repeatPenalty.hashCode^repeatLastN.hashCode^seed.hashCode^mirostat.hashCode^mirostatTau.hashCode^mirostatEta.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is SamplingParams &&
+                runtimeType == other.runtimeType
+                && temperature == other.temperature&& topK == other.topK&& topP == other.topP&& minP == other.minP&& repeatPenalty == other.repeatPenalty&& repeatLastN == other.repeatLastN&& seed == other.seed&& mirostat == other.mirostat&& mirostatTau == other.mirostatTau&& mirostatEta == other.mirostatEta;
+        
+            }
+            
\ No newline at end of file
@@ -0,0 +1,50 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import 'frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+import 'package:freezed_annotation/freezed_annotation.dart' hide protected;
+part 'error.freezed.dart';
+
+            
+
+            
+
+            @freezed
+                sealed class AnimaError with _$AnimaError implements FrbException {
+                    const AnimaError._();
+
+                     const factory AnimaError.modelNotLoaded(  String field0,) = AnimaError_ModelNotLoaded;
+ const factory AnimaError.modelLoadFailed(  String field0,) = AnimaError_ModelLoadFailed;
+ /// The file is not a readable GGUF model.
+const factory AnimaError.invalidModelFile(  String field0,) = AnimaError_InvalidModelFile;
+ /// The file is shorter than its header declares, e.g. an interrupted download.
+const factory AnimaError.modelTruncated(  String field0,) = AnimaError_ModelTruncated;
+ /// The file does not match the SHA-256 in its `.sha256` sidecar.
+const factory AnimaError.modelChecksumMismatch(  String field0,) = AnimaError_ModelChecksumMismatch;
+ /// An embedding model was given to the chat slot or the other way round.
+const factory AnimaError.wrongModelKind(  String field0,) = AnimaError_WrongModelKind;
+ /// Not enough free RAM for the model with the chosen load strategy.
+const factory AnimaError.insufficientMemory(  String field0,) = AnimaError_InsufficientMemory;
+ const factory AnimaError.contextOverflow(  String field0,) = AnimaError_ContextOverflow;
+ /// The configured local inference server did not answer or answered with an error.
+const factory AnimaError.backendUnavailable(  String field0,) = AnimaError_BackendUnavailable;
+ const factory AnimaError.emptyOutput(  String field0,) = AnimaError_EmptyOutput;
+ const factory AnimaError.generationFailed(  String field0,) = AnimaError_GenerationFailed;
+ const factory AnimaError.embeddingFailed(  String field0,) = AnimaError_EmbeddingFailed;
+ const factory AnimaError.cancelled(  String field0,) = AnimaError_Cancelled;
+ /// A background job gave way to more urgent work before finishing.
+const factory AnimaError.preempted(  String field0,) = AnimaError_Preempted;
+ const factory AnimaError.dbLocked(  String field0,) = AnimaError_DbLocked;
+ const factory AnimaError.dbIo(  String field0,) = AnimaError_DbIo;
+ const factory AnimaError.invalidInput(  String field0,) = AnimaError_InvalidInput;
+ const factory AnimaError.io(  String field0,) = AnimaError_Io;
+ const factory AnimaError.internal(  String field0,) = AnimaError_Internal;
+
+                    
+
+                    
+                }
+            
\ No newline at end of file
@@ -3,1156 +3,2929 @@
 
 // ignore_for_file: unused_import, unused_element, unnecessary_import, duplicate_ignore, invalid_use_of_internal_member, annotate_overrides, non_constant_identifier_names, curly_braces_in_flow_control_structures, prefer_const_literals_to_create_immutables, unused_field
 
+import 'ai.dart';
 import 'api/simple.dart';
+import 'backend.dart';
 import 'dart:async';
 import 'dart:convert';
 import 'db.dart';
+import 'error.dart';
 import 'frb_generated.dart';
-import 'frb_generated.io.dart'
-    if (dart.library.js_interop) 'frb_generated.web.dart';
+import 'frb_generated.io.dart' if (dart.library.js_interop) 'frb_generated.web.dart';
+import 'job_queue.dart';
+import 'load_strategy.dart';
+import 'model_manager.dart';
+import 'model_registry.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+import 'reembed.dart';
+import 'runtime_config.dart';
 
-/// Main entrypoint of the Rust API
-class RustLib extends BaseEntrypoint<RustLibApi, RustLibApiImpl, RustLibWire> {
-  @internal
-  static final instance = RustLib._();
 
-  RustLib._();
+                /// Main entrypoint of the Rust API
+                class RustLib extends BaseEntrypoint<RustLibApi, RustLibApiImpl, RustLibWire> {
+                  @internal
+                  static final instance = RustLib._();
 
-  /// Initialize flutter_rust_bridge
-  static Future<void> init({
-    RustLibApi? api,
-    BaseHandler? handler,
-    ExternalLibrary? externalLibrary,
-    bool forceSameCodegenVersion = true,
-  }) async {
-    await instance.initImpl(
-      api: api,
-      handler: handler,
-      externalLibrary: externalLibrary,
-      forceSameCodegenVersion: forceSameCodegenVersion,
-    );
-  }
+                  RustLib._();
 
-  /// Initialize flutter_rust_bridge in mock mode.
-  /// No libraries for FFI are loaded.
-  static void initMock({
-    required RustLibApi api,
-  }) {
-    instance.initMockImpl(
-      api: api,
-    );
-  }
+                  /// Initialize flutter_rust_bridge
+                  static Future<void> init({
+                    RustLibApi? api,
+                    BaseHandler? handler,
+                    ExternalLibrary? externalLibrary,
+                    bool forceSameCodegenVersion = true,
+                  }) async {
+                    await instance.initImpl(
+                      api: api,
+                      handler: handler,
+                      externalLibrary: externalLibrary,
+                      forceSameCodegenVersion: forceSameCodegenVersion,
+                    );
+                  }
 
-  /// Dispose flutter_rust_bridge
-  ///
-  /// The call to this function is optional, since flutter_rust_bridge (and everything else)
-  /// is automatically disposed when the app stops.
-  static void dispose() => instance.disposeImpl();
+                  /// Initialize flutter_rust_bridge in mock mode.
+                  /// No libraries for FFI are loaded.
+                  static void initMock({
+                    required RustLibApi api,
+                  }) {
+                    instance.initMockImpl(
+                      api: api,
+                    );
+                  }
 
-  @override
-  ApiImplConstructor<RustLibApiImpl, RustLibWire> get apiImplConstructor =>
-      RustLibApiImpl.new;
+                  /// Dispose flutter_rust_bridge
+                  ///
+                  /// The call to this function is optional, since flutter_rust_bridge (and everything else)
+                  /// is automatically disposed when the app stops.
+                  static void dispose() => instance.disposeImpl();
 
-  @override
-  WireConstructor<RustLibWire> get wireConstructor =>
-      RustLibWire.fromExternalLibrary;
+                  @override
+                  ApiImplConstructor<RustLibApiImpl, RustLibWire> get apiImplConstructor => RustLibApiImpl.new;
 
-  @override
-  Future<void> executeRustInitializers() async {}
+                  @override
+                  WireConstructor<RustLibWire> get wireConstructor => RustLibWire.fromExternalLibrary;
 
-  @override
-  ExternalLibraryLoaderConfig get defaultExternalLibraryLoaderConfig =>
-      kDefaultExternalLibraryLoaderConfig;
+                  @override
+                  Future<void> executeRustInitializers() async {
+                    
+                  }
 
-  @override
-  String get codegenVersion => '2.11.1';
+                  @override
+                  ExternalLibraryLoaderConfig get defaultExternalLibraryLoaderConfig => kDefaultExternalLibraryLoaderConfig;
 
-  @override
-  int get rustContentHash => -1117369052;
+                  @override
+                  String get codegenVersion => '2.11.1';
 
-  static const kDefaultExternalLibraryLoaderConfig =
-      ExternalLibraryLoaderConfig(
-    stem: 'rust_lib_anima',
-    ioDirectory: 'rust/target/release/',
-    webPrefix: 'pkg/',
-  );
-}
-
-abstract class RustLibApi extends BaseApi {
-  Future<bool> crateApiSimpleAddProfileTrait(
-      {required String category, required String content});
-
-  Future<bool> crateApiSimpleClearProfile();
-
-  Future<bool> crateApiSimpleDeleteMemory({required PlatformInt64 id});
-
-  Future<String> crateApiSimpleExportBrain();
-
-  Future<bool> crateApiSimpleExportDatabase({required String destPath});
-
-  Future<bool> crateApiSimpleFactoryReset();
-
-  Future<String> crateApiSimpleGenerateProactiveGreeting(
-      {required String timeOfDay});
-
-  Future<List<MemoryItem>> crateApiSimpleGetAllMemories();
-
-  Future<String> crateApiSimpleGetAppLanguage();
-
-  Future<List<ChatMessage>> crateApiSimpleGetChatHistory();
-
-  Future<String> crateApiSimpleGetCorePrompt();
-
-  Future<List<ProfileTrait>> crateApiSimpleGetProfileTraits();
-
-  Future<double> crateApiSimpleGetTemperature();
-
-  Future<String> crateApiSimpleGetUserName();
-
-  String crateApiSimpleGreet({required String name});
-
-  Future<void> crateApiSimpleInitApp(
-      {required String chatModelPath, required String embeddingModelPath});
-
-  Future<bool> crateApiSimpleRunSleepCycle();
-
-  Future<bool> crateApiSimpleSaveAssistantMessage({required String message});
-
-  Future<List<MemoryItem>> crateApiSimpleSearchMemories(
-      {required String query});
-
-  Future<String> crateApiSimpleSendMessage(
-      {required String message,
-      required double temperature,
-      required int maxTokens});
-
-  Stream<String> crateApiSimpleSendMessageStream(
-      {required String message,
-      required double temperature,
-      required int maxTokens});
-
-  Future<bool> crateApiSimpleSetAppLanguage({required String lang});
-
-  Future<bool> crateApiSimpleSetCorePrompt({required String prompt});
-
-  Future<bool> crateApiSimpleSetTemperature({required double temperature});
-
-  Future<bool> crateApiSimpleSetUserName({required String name});
-}
-
-class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
-  RustLibApiImpl({
-    required super.handler,
-    required super.wire,
-    required super.generalizedFrbRustBinding,
-    required super.portManager,
-  });
-
-  @override
-  Future<bool> crateApiSimpleAddProfileTrait(
-      {required String category, required String content}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(category, serializer);
-        sse_encode_String(content, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 1, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_bool,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleAddProfileTraitConstMeta,
-      argValues: [category, content],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleAddProfileTraitConstMeta =>
-      const TaskConstMeta(
-        debugName: "add_profile_trait",
-        argNames: ["category", "content"],
-      );
-
-  @override
-  Future<bool> crateApiSimpleClearProfile() {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 2, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_bool,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleClearProfileConstMeta,
-      argValues: [],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleClearProfileConstMeta => const TaskConstMeta(
-        debugName: "clear_profile",
-        argNames: [],
-      );
-
-  @override
-  Future<bool> crateApiSimpleDeleteMemory({required PlatformInt64 id}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_i_64(id, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 3, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_bool,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleDeleteMemoryConstMeta,
-      argValues: [id],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleDeleteMemoryConstMeta => const TaskConstMeta(
-        debugName: "delete_memory",
-        argNames: ["id"],
-      );
-
-  @override
-  Future<String> crateApiSimpleExportBrain() {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 4, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_String,
-        decodeErrorData: sse_decode_String,
-      ),
-      constMeta: kCrateApiSimpleExportBrainConstMeta,
-      argValues: [],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleExportBrainConstMeta => const TaskConstMeta(
-        debugName: "export_brain",
-        argNames: [],
-      );
-
-  @override
-  Future<bool> crateApiSimpleExportDatabase({required String destPath}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(destPath, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 5, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_bool,
-        decodeErrorData: sse_decode_String,
-      ),
-      constMeta: kCrateApiSimpleExportDatabaseConstMeta,
-      argValues: [destPath],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleExportDatabaseConstMeta =>
-      const TaskConstMeta(
-        debugName: "export_database",
-        argNames: ["destPath"],
-      );
-
-  @override
-  Future<bool> crateApiSimpleFactoryReset() {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 6, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_bool,
-        decodeErrorData: sse_decode_String,
-      ),
-      constMeta: kCrateApiSimpleFactoryResetConstMeta,
-      argValues: [],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleFactoryResetConstMeta => const TaskConstMeta(
-        debugName: "factory_reset",
-        argNames: [],
-      );
-
-  @override
-  Future<String> crateApiSimpleGenerateProactiveGreeting(
-      {required String timeOfDay}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(timeOfDay, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 7, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_String,
-        decodeErrorData: sse_decode_String,
-      ),
-      constMeta: kCrateApiSimpleGenerateProactiveGreetingConstMeta,
-      argValues: [timeOfDay],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleGenerateProactiveGreetingConstMeta =>
-      const TaskConstMeta(
-        debugName: "generate_proactive_greeting",
-        argNames: ["timeOfDay"],
-      );
-
-  @override
-  Future<List<MemoryItem>> crateApiSimpleGetAllMemories() {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 8, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_list_memory_item,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleGetAllMemoriesConstMeta,
-      argValues: [],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleGetAllMemoriesConstMeta =>
-      const TaskConstMeta(
-        debugName: "get_all_memories",
-        argNames: [],
-      );
-
-  @override
-  Future<String> crateApiSimpleGetAppLanguage() {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 9, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_String,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleGetAppLanguageConstMeta,
-      argValues: [],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleGetAppLanguageConstMeta =>
-      const TaskConstMeta(
-        debugName: "get_app_language",
-        argNames: [],
-      );
-
-  @override
-  Future<List<ChatMessage>> crateApiSimpleGetChatHistory() {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 10, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_list_chat_message,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleGetChatHistoryConstMeta,
-      argValues: [],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleGetChatHistoryConstMeta =>
-      const TaskConstMeta(
-        debugName: "get_chat_history",
-        argNames: [],
-      );
-
-  @override
-  Future<String> crateApiSimpleGetCorePrompt() {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 11, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_String,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleGetCorePromptConstMeta,
-      argValues: [],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleGetCorePromptConstMeta =>
-      const TaskConstMeta(
-        debugName: "get_core_prompt",
-        argNames: [],
-      );
-
-  @override
-  Future<List<ProfileTrait>> crateApiSimpleGetProfileTraits() {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 12, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_list_profile_trait,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleGetProfileTraitsConstMeta,
-      argValues: [],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleGetProfileTraitsConstMeta =>
-      const TaskConstMeta(
-        debugName: "get_profile_traits",
-        argNames: [],
-      );
-
-  @override
-  Future<double> crateApiSimpleGetTemperature() {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 13, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_f_32,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleGetTemperatureConstMeta,
-      argValues: [],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleGetTemperatureConstMeta =>
-      const TaskConstMeta(
-        debugName: "get_temperature",
-        argNames: [],
-      );
-
-  @override
-  Future<String> crateApiSimpleGetUserName() {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 14, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_String,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleGetUserNameConstMeta,
-      argValues: [],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleGetUserNameConstMeta => const TaskConstMeta(
-        debugName: "get_user_name",
-        argNames: [],
-      );
-
-  @override
-  String crateApiSimpleGreet({required String name}) {
-    return handler.executeSync(SyncTask(
-      callFfi: () {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(name, serializer);
-        return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 15)!;
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_String,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleGreetConstMeta,
-      argValues: [name],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleGreetConstMeta => const TaskConstMeta(
-        debugName: "greet",
-        argNames: ["name"],
-      );
-
-  @override
-  Future<void> crateApiSimpleInitApp(
-      {required String chatModelPath, required String embeddingModelPath}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(chatModelPath, serializer);
-        sse_encode_String(embeddingModelPath, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 16, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_unit,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleInitAppConstMeta,
-      argValues: [chatModelPath, embeddingModelPath],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleInitAppConstMeta => const TaskConstMeta(
-        debugName: "init_app",
-        argNames: ["chatModelPath", "embeddingModelPath"],
-      );
-
-  @override
-  Future<bool> crateApiSimpleRunSleepCycle() {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 17, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_bool,
-        decodeErrorData: sse_decode_String,
-      ),
-      constMeta: kCrateApiSimpleRunSleepCycleConstMeta,
-      argValues: [],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleRunSleepCycleConstMeta =>
-      const TaskConstMeta(
-        debugName: "run_sleep_cycle",
-        argNames: [],
-      );
-
-  @override
-  Future<bool> crateApiSimpleSaveAssistantMessage({required String message}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(message, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 18, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_bool,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleSaveAssistantMessageConstMeta,
-      argValues: [message],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleSaveAssistantMessageConstMeta =>
-      const TaskConstMeta(
-        debugName: "save_assistant_message",
-        argNames: ["message"],
-      );
-
-  @override
-  Future<List<MemoryItem>> crateApiSimpleSearchMemories(
-      {required String query}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(query, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 19, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_list_memory_item,
-        decodeErrorData: sse_decode_String,
-      ),
-      constMeta: kCrateApiSimpleSearchMemoriesConstMeta,
-      argValues: [query],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleSearchMemoriesConstMeta =>
-      const TaskConstMeta(
-        debugName: "search_memories",
-        argNames: ["query"],
-      );
-
-  @override
-  Future<String> crateApiSimpleSendMessage(
-      {required String message,
-      required double temperature,
-      required int maxTokens}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(message, serializer);
-        sse_encode_f_32(temperature, serializer);
-        sse_encode_u_32(maxTokens, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 20, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_String,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleSendMessageConstMeta,
-      argValues: [message, temperature, maxTokens],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleSendMessageConstMeta => const TaskConstMeta(
-        debugName: "send_message",
-        argNames: ["message", "temperature", "maxTokens"],
-      );
-
-  @override
-  Stream<String> crateApiSimpleSendMessageStream(
-      {required String message,
-      required double temperature,
-      required int maxTokens}) {
-    final sink = RustStreamSink<String>();
-    unawaited(handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(message, serializer);
-        sse_encode_f_32(temperature, serializer);
-        sse_encode_u_32(maxTokens, serializer);
-        sse_encode_StreamSink_String_Sse(sink, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 21, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_unit,
-        decodeErrorData: sse_decode_String,
-      ),
-      constMeta: kCrateApiSimpleSendMessageStreamConstMeta,
-      argValues: [message, temperature, maxTokens, sink],
-      apiImpl: this,
-    )));
-    return sink.stream;
-  }
-
-  TaskConstMeta get kCrateApiSimpleSendMessageStreamConstMeta =>
-      const TaskConstMeta(
-        debugName: "send_message_stream",
-        argNames: ["message", "temperature", "maxTokens", "sink"],
-      );
-
-  @override
-  Future<bool> crateApiSimpleSetAppLanguage({required String lang}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(lang, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 22, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_bool,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleSetAppLanguageConstMeta,
-      argValues: [lang],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleSetAppLanguageConstMeta =>
-      const TaskConstMeta(
-        debugName: "set_app_language",
-        argNames: ["lang"],
-      );
-
-  @override
-  Future<bool> crateApiSimpleSetCorePrompt({required String prompt}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(prompt, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 23, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_bool,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleSetCorePromptConstMeta,
-      argValues: [prompt],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleSetCorePromptConstMeta =>
-      const TaskConstMeta(
-        debugName: "set_core_prompt",
-        argNames: ["prompt"],
-      );
-
-  @override
-  Future<bool> crateApiSimpleSetTemperature({required double temperature}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_f_32(temperature, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 24, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_bool,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleSetTemperatureConstMeta,
-      argValues: [temperature],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleSetTemperatureConstMeta =>
-      const TaskConstMeta(
-        debugName: "set_temperature",
-        argNames: ["temperature"],
-      );
-
-  @override
-  Future<bool> crateApiSimpleSetUserName({required String name}) {
-    return handler.executeNormal(NormalTask(
-      callFfi: (port_) {
-        final serializer = SseSerializer(generalizedFrbRustBinding);
-        sse_encode_String(name, serializer);
-        pdeCallFfi(generalizedFrbRustBinding, serializer,
-            funcId: 25, port: port_);
-      },
-      codec: SseCodec(
-        decodeSuccessData: sse_decode_bool,
-        decodeErrorData: null,
-      ),
-      constMeta: kCrateApiSimpleSetUserNameConstMeta,
-      argValues: [name],
-      apiImpl: this,
-    ));
-  }
-
-  TaskConstMeta get kCrateApiSimpleSetUserNameConstMeta => const TaskConstMeta(
-        debugName: "set_user_name",
-        argNames: ["name"],
-      );
-
-  @protected
-  AnyhowException dco_decode_AnyhowException(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return AnyhowException(raw as String);
-  }
-
-  @protected
-  RustStreamSink<String> dco_decode_StreamSink_String_Sse(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    throw UnimplementedError();
-  }
-
-  @protected
-  String dco_decode_String(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as String;
-  }
-
-  @protected
-  bool dco_decode_bool(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as bool;
-  }
-
-  @protected
-  ChatMessage dco_decode_chat_message(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    final arr = raw as List<dynamic>;
-    if (arr.length != 4)
-      throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
-    return ChatMessage(
-      id: dco_decode_i_64(arr[0]),
-      role: dco_decode_String(arr[1]),
-      content: dco_decode_String(arr[2]),
-      timestamp: dco_decode_String(arr[3]),
-    );
-  }
-
-  @protected
-  double dco_decode_f_32(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as double;
-  }
-
-  @protected
-  PlatformInt64 dco_decode_i_64(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return dcoDecodeI64(raw);
-  }
-
-  @protected
-  List<ChatMessage> dco_decode_list_chat_message(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return (raw as List<dynamic>).map(dco_decode_chat_message).toList();
-  }
-
-  @protected
-  List<MemoryItem> dco_decode_list_memory_item(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return (raw as List<dynamic>).map(dco_decode_memory_item).toList();
-  }
-
-  @protected
-  Uint8List dco_decode_list_prim_u_8_strict(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as Uint8List;
-  }
-
-  @protected
-  List<ProfileTrait> dco_decode_list_profile_trait(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return (raw as List<dynamic>).map(dco_decode_profile_trait).toList();
-  }
-
-  @protected
-  MemoryItem dco_decode_memory_item(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    final arr = raw as List<dynamic>;
-    if (arr.length != 3)
-      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
-    return MemoryItem(
-      id: dco_decode_i_64(arr[0]),
-      content: dco_decode_String(arr[1]),
-      createdAt: dco_decode_String(arr[2]),
-    );
-  }
-
-  @protected
-  ProfileTrait dco_decode_profile_trait(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    final arr = raw as List<dynamic>;
-    if (arr.length != 2)
-      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
-    return ProfileTrait(
-      category: dco_decode_String(arr[0]),
-      content: dco_decode_String(arr[1]),
-    );
-  }
-
-  @protected
-  int dco_decode_u_32(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as int;
-  }
-
-  @protected
-  int dco_decode_u_8(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return raw as int;
-  }
-
-  @protected
-  void dco_decode_unit(dynamic raw) {
-    // Codec=Dco (DartCObject based), see doc to use other codecs
-    return;
-  }
-
-  @protected
-  AnyhowException sse_decode_AnyhowException(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    var inner = sse_decode_String(deserializer);
-    return AnyhowException(inner);
-  }
-
-  @protected
-  RustStreamSink<String> sse_decode_StreamSink_String_Sse(
-      SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    throw UnimplementedError('Unreachable ()');
-  }
-
-  @protected
-  String sse_decode_String(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    var inner = sse_decode_list_prim_u_8_strict(deserializer);
-    return utf8.decoder.convert(inner);
-  }
-
-  @protected
-  bool sse_decode_bool(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getUint8() != 0;
-  }
-
-  @protected
-  ChatMessage sse_decode_chat_message(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    var var_id = sse_decode_i_64(deserializer);
-    var var_role = sse_decode_String(deserializer);
-    var var_content = sse_decode_String(deserializer);
-    var var_timestamp = sse_decode_String(deserializer);
-    return ChatMessage(
-        id: var_id,
-        role: var_role,
-        content: var_content,
-        timestamp: var_timestamp);
-  }
-
-  @protected
-  double sse_decode_f_32(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getFloat32();
-  }
-
-  @protected
-  PlatformInt64 sse_decode_i_64(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getPlatformInt64();
-  }
-
-  @protected
-  List<ChatMessage> sse_decode_list_chat_message(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-
-    var len_ = sse_decode_i_32(deserializer);
-    var ans_ = <ChatMessage>[];
-    for (var idx_ = 0; idx_ < len_; ++idx_) {
-      ans_.add(sse_decode_chat_message(deserializer));
-    }
-    return ans_;
-  }
-
-  @protected
-  List<MemoryItem> sse_decode_list_memory_item(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-
-    var len_ = sse_decode_i_32(deserializer);
-    var ans_ = <MemoryItem>[];
-    for (var idx_ = 0; idx_ < len_; ++idx_) {
-      ans_.add(sse_decode_memory_item(deserializer));
-    }
-    return ans_;
-  }
-
-  @protected
-  Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    var len_ = sse_decode_i_32(deserializer);
-    return deserializer.buffer.getUint8List(len_);
-  }
-
-  @protected
-  List<ProfileTrait> sse_decode_list_profile_trait(
-      SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-
-    var len_ = sse_decode_i_32(deserializer);
-    var ans_ = <ProfileTrait>[];
-    for (var idx_ = 0; idx_ < len_; ++idx_) {
-      ans_.add(sse_decode_profile_trait(deserializer));
-    }
-    return ans_;
-  }
-
-  @protected
-  MemoryItem sse_decode_memory_item(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    var var_id = sse_decode_i_64(deserializer);
-    var var_content = sse_decode_String(deserializer);
-    var var_createdAt = sse_decode_String(deserializer);
-    return MemoryItem(
-        id: var_id, content: var_content, createdAt: var_createdAt);
-  }
-
-  @protected
-  ProfileTrait sse_decode_profile_trait(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    var var_category = sse_decode_String(deserializer);
-    var var_content = sse_decode_String(deserializer);
-    return ProfileTrait(category: var_category, content: var_content);
-  }
-
-  @protected
-  int sse_decode_u_32(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getUint32();
-  }
-
-  @protected
-  int sse_decode_u_8(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getUint8();
-  }
-
-  @protected
-  void sse_decode_unit(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-  }
-
-  @protected
-  int sse_decode_i_32(SseDeserializer deserializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    return deserializer.buffer.getInt32();
-  }
-
-  @protected
-  void sse_encode_AnyhowException(
-      AnyhowException self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_String(self.message, serializer);
-  }
-
-  @protected
-  void sse_encode_StreamSink_String_Sse(
-      RustStreamSink<String> self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_String(
-        self.setupAndSerialize(
-            codec: SseCodec(
+                  @override
+                  int get rustContentHash => 10843265;
+
+                  static const kDefaultExternalLibraryLoaderConfig = ExternalLibraryLoaderConfig(
+                    stem: 'rust_lib_anima',
+                    ioDirectory: 'rust/target/release/',
+                    webPrefix: 'pkg/',
+                  );
+                }
+                
+
+                abstract class RustLibApi extends BaseApi {
+                  Future<void> crateApiSimpleAddProfileTrait({required String category , required String content });
+
+Future<bool> crateApiSimpleCancelGeneration({required String requestId });
+
+Future<bool> crateApiSimpleCancelReembedding();
+
+Future<void> crateApiSimpleClearProfile();
+
+Future<void> crateApiSimpleClearRerankerModel();
+
+Future<void> crateApiSimpleDeleteMemory({required PlatformInt64 id });
+
+Future<String> crateApiSimpleExportBrain();
+
+Future<bool> crateApiSimpleExportDatabase({required String destPath });
+
+Future<bool> crateApiSimpleFactoryReset();
+
+Future<String> crateApiSimpleGenerateProactiveGreeting({required String timeOfDay });
+
+Future<List<MemoryItem>> crateApiSimpleGetAllMemories();
+
+Future<String> crateApiSimpleGetAppLanguage();
+
+Future<BackendSettings> crateApiSimpleGetBackendSettings();
+
+Future<ContextConfig> crateApiSimpleGetChatContextConfig();
+
+Future<List<ChatMessage>> crateApiSimpleGetChatHistory();
+
+Future<String> crateApiSimpleGetCorePrompt();
+
+Future<EmbeddingStatus> crateApiSimpleGetEmbeddingStatus();
+
+Future<InferenceQueueStatus> crateApiSimpleGetInferenceQueue();
+
+Future<double> crateApiSimpleGetLexicalWeight();
+
+Future<LoadStrategy> crateApiSimpleGetLoadStrategy();
+
+Future<MemoryScoringWeights> crateApiSimpleGetMemoryScoringWeights();
+
+Future<double> crateApiSimpleGetMmrLambda();
+
+Future<ModelStatus> crateApiSimpleGetModelStatus();
+
+Future<String> crateApiSimpleGetModelsDir();
+
+Future<bool> crateApiSimpleGetPrefixCacheEnabled();
+
+Future<List<ProfileTrait>> crateApiSimpleGetProfileTraits();
+
+Future<RuntimeSettings> crateApiSimpleGetRuntimeSettings();
+
+Future<SamplingParams> crateApiSimpleGetSamplingParams();
+
+Future<double> crateApiSimpleGetTemperature();
+
+Future<String> crateApiSimpleGetUserName();
+
+String crateApiSimpleGreet({required String name });
+
+Future<void> crateApiSimpleInitApp({required String chatModelPath , required String embeddingModelPath });
+
+Future<List<ModelInfo>> crateApiSimpleListModels();
+
+Future<void> crateApiSimpleLoadModel({required ModelKind kind , required String path });
+
+Future<bool> crateApiSimplePreemptBackgroundJobs();
+
+Future<PromptPreview> crateApiSimplePreviewPrompt({required String message });
+
+Stream<ReembedEvent> crateApiSimpleReembedMemories();
+
+Future<void> crateApiSimpleReloadModel({required ModelKind kind });
+
+Future<bool> crateApiSimpleRunSleepCycle();
+
+Future<void> crateApiSimpleSaveAssistantMessage({required String message });
+
+Future<List<MemoryItem>> crateApiSimpleSearchMemories({required String query });
+
+Future<ModelInfo> crateApiSimpleSelectChatModel({required String id });
+
+Future<ModelInfo> crateApiSimpleSelectEmbeddingModel({required String id });
+
+Future<ModelInfo> crateApiSimpleSelectRerankerModel({required String id });
+
+Future<ChatCompletion> crateApiSimpleSendChat({required ChatRequest request });
+
+Stream<ChatEvent> crateApiSimpleSendChatStream({required ChatRequest request });
+
+Future<String> crateApiSimpleSendMessage({required String message , required double temperature , required int maxTokens , String? requestId });
+
+Stream<ChatEvent> crateApiSimpleSendMessageStream({required String message , required double temperature , required int maxTokens , String? requestId });
+
+Future<void> crateApiSimpleSetAppLanguage({required String lang });
+
+Future<void> crateApiSimpleSetBackendSettings({required BackendSettings settings });
+
+Future<void> crateApiSimpleSetCorePrompt({required String prompt });
+
+Future<void> crateApiSimpleSetLexicalWeight({required double weight });
+
+Future<void> crateApiSimpleSetLoadStrategy({required LoadStrategy strategy });
+
+Future<void> crateApiSimpleSetMemoryScoringWeights({required MemoryScoringWeights weights });
+
+Future<void> crateApiSimpleSetMmrLambda({required double lambda });
+
+Future<void> crateApiSimpleSetModelsDir({required String path });
+
+Future<void> crateApiSimpleSetPrefixCacheEnabled({required bool enabled });
+
+Future<void> crateApiSimpleSetRuntimeSettings({required RuntimeSettings settings });
+
+Future<void> crateApiSimpleSetSamplingParams({required SamplingParams params });
+
+Future<void> crateApiSimpleSetTemperature({required double temperature });
+
+Future<void> crateApiSimpleSetUserName({required String name });
+
+Future<void> crateApiSimpleUnloadModel({required ModelKind kind });
+
+
+                }
+                
+
+                class RustLibApiImpl extends RustLibApiImplPlatform implements RustLibApi {
+                  RustLibApiImpl({
+                    required super.handler,
+                    required super.wire,
+                    required super.generalizedFrbRustBinding,
+                    required super.portManager,
+                  });
+
+                  @override Future<void> crateApiSimpleAddProfileTrait({required String category , required String content })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(category, serializer);
+sse_encode_String(content, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 1, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleAddProfileTraitConstMeta,
+            argValues: [category, content],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleAddProfileTraitConstMeta => const TaskConstMeta(
+            debugName: "add_profile_trait",
+            argNames: ["category", "content"],
+        );
+        
+
+@override Future<bool> crateApiSimpleCancelGeneration({required String requestId })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(requestId, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 2, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_bool,
+          decodeErrorData: null,
+        )
+        ,
+            constMeta: kCrateApiSimpleCancelGenerationConstMeta,
+            argValues: [requestId],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleCancelGenerationConstMeta => const TaskConstMeta(
+            debugName: "cancel_generation",
+            argNames: ["requestId"],
+        );
+        
+
+@override Future<bool> crateApiSimpleCancelReembedding()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 3, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_bool,
+          decodeErrorData: null,
+        )
+        ,
+            constMeta: kCrateApiSimpleCancelReembeddingConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleCancelReembeddingConstMeta => const TaskConstMeta(
+            debugName: "cancel_reembedding",
+            argNames: [],
+        );
+        
+
+@override Future<void> crateApiSimpleClearProfile()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 4, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleClearProfileConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleClearProfileConstMeta => const TaskConstMeta(
+            debugName: "clear_profile",
+            argNames: [],
+        );
+        
+
+@override Future<void> crateApiSimpleClearRerankerModel()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 5, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleClearRerankerModelConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleClearRerankerModelConstMeta => const TaskConstMeta(
+            debugName: "clear_reranker_model",
+            argNames: [],
+        );
+        
+
+@override Future<void> crateApiSimpleDeleteMemory({required PlatformInt64 id })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_i_64(id, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 6, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleDeleteMemoryConstMeta,
+            argValues: [id],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleDeleteMemoryConstMeta => const TaskConstMeta(
+            debugName: "delete_memory",
+            argNames: ["id"],
+        );
+        
+
+@override Future<String> crateApiSimpleExportBrain()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 7, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_String,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleExportBrainConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleExportBrainConstMeta => const TaskConstMeta(
+            debugName: "export_brain",
+            argNames: [],
+        );
+        
+
+@override Future<bool> crateApiSimpleExportDatabase({required String destPath })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(destPath, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 8, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_bool,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleExportDatabaseConstMeta,
+            argValues: [destPath],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleExportDatabaseConstMeta => const TaskConstMeta(
+            debugName: "export_database",
+            argNames: ["destPath"],
+        );
+        
+
+@override Future<bool> crateApiSimpleFactoryReset()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 9, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_bool,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleFactoryResetConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleFactoryResetConstMeta => const TaskConstMeta(
+            debugName: "factory_reset",
+            argNames: [],
+        );
+        
+
+@override Future<String> crateApiSimpleGenerateProactiveGreeting({required String timeOfDay })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(timeOfDay, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 10, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_String,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGenerateProactiveGreetingConstMeta,
+            argValues: [timeOfDay],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGenerateProactiveGreetingConstMeta => const TaskConstMeta(
+            debugName: "generate_proactive_greeting",
+            argNames: ["timeOfDay"],
+        );
+        
+
+@override Future<List<MemoryItem>> crateApiSimpleGetAllMemories()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 11, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_list_memory_item,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetAllMemoriesConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetAllMemoriesConstMeta => const TaskConstMeta(
+            debugName: "get_all_memories",
+            argNames: [],
+        );
+        
+
+@override Future<String> crateApiSimpleGetAppLanguage()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 12, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_String,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetAppLanguageConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetAppLanguageConstMeta => const TaskConstMeta(
+            debugName: "get_app_language",
+            argNames: [],
+        );
+        
+
+@override Future<BackendSettings> crateApiSimpleGetBackendSettings()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 13, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_backend_settings,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetBackendSettingsConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetBackendSettingsConstMeta => const TaskConstMeta(
+            debugName: "get_backend_settings",
+            argNames: [],
+        );
+        
+
+@override Future<ContextConfig> crateApiSimpleGetChatContextConfig()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 14, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_context_config,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetChatContextConfigConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetChatContextConfigConstMeta => const TaskConstMeta(
+            debugName: "get_chat_context_config",
+            argNames: [],
+        );
+        
+
+@override Future<List<ChatMessage>> crateApiSimpleGetChatHistory()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 15, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_list_chat_message,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetChatHistoryConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetChatHistoryConstMeta => const TaskConstMeta(
+            debugName: "get_chat_history",
+            argNames: [],
+        );
+        
+
+@override Future<String> crateApiSimpleGetCorePrompt()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 16, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_String,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetCorePromptConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetCorePromptConstMeta => const TaskConstMeta(
+            debugName: "get_core_prompt",
+            argNames: [],
+        );
+        
+
+@override Future<EmbeddingStatus> crateApiSimpleGetEmbeddingStatus()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 17, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_embedding_status,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetEmbeddingStatusConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetEmbeddingStatusConstMeta => const TaskConstMeta(
+            debugName: "get_embedding_status",
+            argNames: [],
+        );
+        
+
+@override Future<InferenceQueueStatus> crateApiSimpleGetInferenceQueue()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 18, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_inference_queue_status,
+          decodeErrorData: null,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetInferenceQueueConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetInferenceQueueConstMeta => const TaskConstMeta(
+            debugName: "get_inference_queue",
+            argNames: [],
+        );
+        
+
+@override Future<double> crateApiSimpleGetLexicalWeight()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 19, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_f_32,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetLexicalWeightConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetLexicalWeightConstMeta => const TaskConstMeta(
+            debugName: "get_lexical_weight",
+            argNames: [],
+        );
+        
+
+@override Future<LoadStrategy> crateApiSimpleGetLoadStrategy()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 20, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_load_strategy,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetLoadStrategyConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetLoadStrategyConstMeta => const TaskConstMeta(
+            debugName: "get_load_strategy",
+            argNames: [],
+        );
+        
+
+@override Future<MemoryScoringWeights> crateApiSimpleGetMemoryScoringWeights()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 21, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_memory_scoring_weights,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetMemoryScoringWeightsConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetMemoryScoringWeightsConstMeta => const TaskConstMeta(
+            debugName: "get_memory_scoring_weights",
+            argNames: [],
+        );
+        
+
+@override Future<double> crateApiSimpleGetMmrLambda()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 22, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_f_32,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetMmrLambdaConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetMmrLambdaConstMeta => const TaskConstMeta(
+            debugName: "get_mmr_lambda",
+            argNames: [],
+        );
+        
+
+@override Future<ModelStatus> crateApiSimpleGetModelStatus()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 23, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_model_status,
+          decodeErrorData: null,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetModelStatusConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetModelStatusConstMeta => const TaskConstMeta(
+            debugName: "get_model_status",
+            argNames: [],
+        );
+        
+
+@override Future<String> crateApiSimpleGetModelsDir()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 24, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_String,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetModelsDirConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetModelsDirConstMeta => const TaskConstMeta(
+            debugName: "get_models_dir",
+            argNames: [],
+        );
+        
+
+@override Future<bool> crateApiSimpleGetPrefixCacheEnabled()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 25, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_bool,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetPrefixCacheEnabledConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetPrefixCacheEnabledConstMeta => const TaskConstMeta(
+            debugName: "get_prefix_cache_enabled",
+            argNames: [],
+        );
+        
+
+@override Future<List<ProfileTrait>> crateApiSimpleGetProfileTraits()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 26, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_list_profile_trait,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetProfileTraitsConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetProfileTraitsConstMeta => const TaskConstMeta(
+            debugName: "get_profile_traits",
+            argNames: [],
+        );
+        
+
+@override Future<RuntimeSettings> crateApiSimpleGetRuntimeSettings()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 27, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_runtime_settings,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetRuntimeSettingsConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetRuntimeSettingsConstMeta => const TaskConstMeta(
+            debugName: "get_runtime_settings",
+            argNames: [],
+        );
+        
+
+@override Future<SamplingParams> crateApiSimpleGetSamplingParams()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 28, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_sampling_params,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetSamplingParamsConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetSamplingParamsConstMeta => const TaskConstMeta(
+            debugName: "get_sampling_params",
+            argNames: [],
+        );
+        
+
+@override Future<double> crateApiSimpleGetTemperature()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 29, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_f_32,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetTemperatureConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetTemperatureConstMeta => const TaskConstMeta(
+            debugName: "get_temperature",
+            argNames: [],
+        );
+        
+
+@override Future<String> crateApiSimpleGetUserName()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 30, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_String,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleGetUserNameConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGetUserNameConstMeta => const TaskConstMeta(
+            debugName: "get_user_name",
+            argNames: [],
+        );
+        
+
+@override String crateApiSimpleGreet({required String name })  { return handler.executeSync(SyncTask(
+            callFfi: () {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(name, serializer);
+            return pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 31)!;
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_String,
+          decodeErrorData: null,
+        )
+        ,
+            constMeta: kCrateApiSimpleGreetConstMeta,
+            argValues: [name],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleGreetConstMeta => const TaskConstMeta(
+            debugName: "greet",
+            argNames: ["name"],
+        );
+        
+
+@override Future<void> crateApiSimpleInitApp({required String chatModelPath , required String embeddingModelPath })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(chatModelPath, serializer);
+sse_encode_String(embeddingModelPath, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 32, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleInitAppConstMeta,
+            argValues: [chatModelPath, embeddingModelPath],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleInitAppConstMeta => const TaskConstMeta(
+            debugName: "init_app",
+            argNames: ["chatModelPath", "embeddingModelPath"],
+        );
+        
+
+@override Future<List<ModelInfo>> crateApiSimpleListModels()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 33, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_list_model_info,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleListModelsConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleListModelsConstMeta => const TaskConstMeta(
+            debugName: "list_models",
+            argNames: [],
+        );
+        
+
+@override Future<void> crateApiSimpleLoadModel({required ModelKind kind , required String path })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_model_kind(kind, serializer);
+sse_encode_String(path, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 34, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleLoadModelConstMeta,
+            argValues: [kind, path],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleLoadModelConstMeta => const TaskConstMeta(
+            debugName: "load_model",
+            argNames: ["kind", "path"],
+        );
+        
+
+@override Future<bool> crateApiSimplePreemptBackgroundJobs()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 35, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_bool,
+          decodeErrorData: null,
+        )
+        ,
+            constMeta: kCrateApiSimplePreemptBackgroundJobsConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimplePreemptBackgroundJobsConstMeta => const TaskConstMeta(
+            debugName: "preempt_background_jobs",
+            argNames: [],
+        );
+        
+
+@override Future<PromptPreview> crateApiSimplePreviewPrompt({required String message })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(message, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 36, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_prompt_preview,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimplePreviewPromptConstMeta,
+            argValues: [message],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimplePreviewPromptConstMeta => const TaskConstMeta(
+            debugName: "preview_prompt",
+            argNames: ["message"],
+        );
+        
+
+@override Stream<ReembedEvent> crateApiSimpleReembedMemories()  { 
+            final sink = RustStreamSink<ReembedEvent>();
+            unawaited(handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_StreamSink_reembed_event_Sse(sink, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 37, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleReembedMemoriesConstMeta,
+            argValues: [sink],
+            apiImpl: this,
+        )));
+            return sink.stream;
+             }
+
+
+        TaskConstMeta get kCrateApiSimpleReembedMemoriesConstMeta => const TaskConstMeta(
+            debugName: "reembed_memories",
+            argNames: ["sink"],
+        );
+        
+
+@override Future<void> crateApiSimpleReloadModel({required ModelKind kind })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_model_kind(kind, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 38, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleReloadModelConstMeta,
+            argValues: [kind],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleReloadModelConstMeta => const TaskConstMeta(
+            debugName: "reload_model",
+            argNames: ["kind"],
+        );
+        
+
+@override Future<bool> crateApiSimpleRunSleepCycle()  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 39, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_bool,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleRunSleepCycleConstMeta,
+            argValues: [],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleRunSleepCycleConstMeta => const TaskConstMeta(
+            debugName: "run_sleep_cycle",
+            argNames: [],
+        );
+        
+
+@override Future<void> crateApiSimpleSaveAssistantMessage({required String message })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(message, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 40, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSaveAssistantMessageConstMeta,
+            argValues: [message],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSaveAssistantMessageConstMeta => const TaskConstMeta(
+            debugName: "save_assistant_message",
+            argNames: ["message"],
+        );
+        
+
+@override Future<List<MemoryItem>> crateApiSimpleSearchMemories({required String query })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(query, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 41, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_list_memory_item,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSearchMemoriesConstMeta,
+            argValues: [query],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSearchMemoriesConstMeta => const TaskConstMeta(
+            debugName: "search_memories",
+            argNames: ["query"],
+        );
+        
+
+@override Future<ModelInfo> crateApiSimpleSelectChatModel({required String id })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(id, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 42, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_model_info,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSelectChatModelConstMeta,
+            argValues: [id],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSelectChatModelConstMeta => const TaskConstMeta(
+            debugName: "select_chat_model",
+            argNames: ["id"],
+        );
+        
+
+@override Future<ModelInfo> crateApiSimpleSelectEmbeddingModel({required String id })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(id, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 43, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_model_info,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSelectEmbeddingModelConstMeta,
+            argValues: [id],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSelectEmbeddingModelConstMeta => const TaskConstMeta(
+            debugName: "select_embedding_model",
+            argNames: ["id"],
+        );
+        
+
+@override Future<ModelInfo> crateApiSimpleSelectRerankerModel({required String id })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(id, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 44, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_model_info,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSelectRerankerModelConstMeta,
+            argValues: [id],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSelectRerankerModelConstMeta => const TaskConstMeta(
+            debugName: "select_reranker_model",
+            argNames: ["id"],
+        );
+        
+
+@override Future<ChatCompletion> crateApiSimpleSendChat({required ChatRequest request })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_box_autoadd_chat_request(request, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 45, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_chat_completion,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSendChatConstMeta,
+            argValues: [request],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSendChatConstMeta => const TaskConstMeta(
+            debugName: "send_chat",
+            argNames: ["request"],
+        );
+        
+
+@override Stream<ChatEvent> crateApiSimpleSendChatStream({required ChatRequest request })  { 
+            final sink = RustStreamSink<ChatEvent>();
+            unawaited(handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_box_autoadd_chat_request(request, serializer);
+sse_encode_StreamSink_chat_event_Sse(sink, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 46, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSendChatStreamConstMeta,
+            argValues: [request, sink],
+            apiImpl: this,
+        )));
+            return sink.stream;
+             }
+
+
+        TaskConstMeta get kCrateApiSimpleSendChatStreamConstMeta => const TaskConstMeta(
+            debugName: "send_chat_stream",
+            argNames: ["request", "sink"],
+        );
+        
+
+@override Future<String> crateApiSimpleSendMessage({required String message , required double temperature , required int maxTokens , String? requestId })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(message, serializer);
+sse_encode_f_32(temperature, serializer);
+sse_encode_u_32(maxTokens, serializer);
+sse_encode_opt_String(requestId, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 47, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
           decodeSuccessData: sse_decode_String,
-          decodeErrorData: sse_decode_AnyhowException,
-        )),
-        serializer);
-  }
-
-  @protected
-  void sse_encode_String(String self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_list_prim_u_8_strict(utf8.encoder.convert(self), serializer);
-  }
-
-  @protected
-  void sse_encode_bool(bool self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    serializer.buffer.putUint8(self ? 1 : 0);
-  }
-
-  @protected
-  void sse_encode_chat_message(ChatMessage self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_i_64(self.id, serializer);
-    sse_encode_String(self.role, serializer);
-    sse_encode_String(self.content, serializer);
-    sse_encode_String(self.timestamp, serializer);
-  }
-
-  @protected
-  void sse_encode_f_32(double self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    serializer.buffer.putFloat32(self);
-  }
-
-  @protected
-  void sse_encode_i_64(PlatformInt64 self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    serializer.buffer.putPlatformInt64(self);
-  }
-
-  @protected
-  void sse_encode_list_chat_message(
-      List<ChatMessage> self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_i_32(self.length, serializer);
-    for (final item in self) {
-      sse_encode_chat_message(item, serializer);
-    }
-  }
-
-  @protected
-  void sse_encode_list_memory_item(
-      List<MemoryItem> self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_i_32(self.length, serializer);
-    for (final item in self) {
-      sse_encode_memory_item(item, serializer);
-    }
-  }
-
-  @protected
-  void sse_encode_list_prim_u_8_strict(
-      Uint8List self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_i_32(self.length, serializer);
-    serializer.buffer.putUint8List(self);
-  }
-
-  @protected
-  void sse_encode_list_profile_trait(
-      List<ProfileTrait> self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_i_32(self.length, serializer);
-    for (final item in self) {
-      sse_encode_profile_trait(item, serializer);
-    }
-  }
-
-  @protected
-  void sse_encode_memory_item(MemoryItem self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_i_64(self.id, serializer);
-    sse_encode_String(self.content, serializer);
-    sse_encode_String(self.createdAt, serializer);
-  }
-
-  @protected
-  void sse_encode_profile_trait(ProfileTrait self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    sse_encode_String(self.category, serializer);
-    sse_encode_String(self.content, serializer);
-  }
-
-  @protected
-  void sse_encode_u_32(int self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    serializer.buffer.putUint32(self);
-  }
-
-  @protected
-  void sse_encode_u_8(int self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    serializer.buffer.putUint8(self);
-  }
-
-  @protected
-  void sse_encode_unit(void self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-  }
-
-  @protected
-  void sse_encode_i_32(int self, SseSerializer serializer) {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    serializer.buffer.putInt32(self);
-  }
-}
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSendMessageConstMeta,
+            argValues: [message, temperature, maxTokens, requestId],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSendMessageConstMeta => const TaskConstMeta(
+            debugName: "send_message",
+            argNames: ["message", "temperature", "maxTokens", "requestId"],
+        );
+        
+
+@override Stream<ChatEvent> crateApiSimpleSendMessageStream({required String message , required double temperature , required int maxTokens , String? requestId })  { 
+            final sink = RustStreamSink<ChatEvent>();
+            unawaited(handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(message, serializer);
+sse_encode_f_32(temperature, serializer);
+sse_encode_u_32(maxTokens, serializer);
+sse_encode_opt_String(requestId, serializer);
+sse_encode_StreamSink_chat_event_Sse(sink, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 48, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSendMessageStreamConstMeta,
+            argValues: [message, temperature, maxTokens, requestId, sink],
+            apiImpl: this,
+        )));
+            return sink.stream;
+             }
+
+
+        TaskConstMeta get kCrateApiSimpleSendMessageStreamConstMeta => const TaskConstMeta(
+            debugName: "send_message_stream",
+            argNames: ["message", "temperature", "maxTokens", "requestId", "sink"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetAppLanguage({required String lang })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(lang, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 49, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetAppLanguageConstMeta,
+            argValues: [lang],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetAppLanguageConstMeta => const TaskConstMeta(
+            debugName: "set_app_language",
+            argNames: ["lang"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetBackendSettings({required BackendSettings settings })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_box_autoadd_backend_settings(settings, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 50, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetBackendSettingsConstMeta,
+            argValues: [settings],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetBackendSettingsConstMeta => const TaskConstMeta(
+            debugName: "set_backend_settings",
+            argNames: ["settings"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetCorePrompt({required String prompt })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(prompt, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 51, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetCorePromptConstMeta,
+            argValues: [prompt],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetCorePromptConstMeta => const TaskConstMeta(
+            debugName: "set_core_prompt",
+            argNames: ["prompt"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetLexicalWeight({required double weight })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_f_32(weight, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 52, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetLexicalWeightConstMeta,
+            argValues: [weight],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetLexicalWeightConstMeta => const TaskConstMeta(
+            debugName: "set_lexical_weight",
+            argNames: ["weight"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetLoadStrategy({required LoadStrategy strategy })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_load_strategy(strategy, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 53, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetLoadStrategyConstMeta,
+            argValues: [strategy],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetLoadStrategyConstMeta => const TaskConstMeta(
+            debugName: "set_load_strategy",
+            argNames: ["strategy"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetMemoryScoringWeights({required MemoryScoringWeights weights })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_box_autoadd_memory_scoring_weights(weights, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 54, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetMemoryScoringWeightsConstMeta,
+            argValues: [weights],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetMemoryScoringWeightsConstMeta => const TaskConstMeta(
+            debugName: "set_memory_scoring_weights",
+            argNames: ["weights"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetMmrLambda({required double lambda })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_f_32(lambda, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 55, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetMmrLambdaConstMeta,
+            argValues: [lambda],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetMmrLambdaConstMeta => const TaskConstMeta(
+            debugName: "set_mmr_lambda",
+            argNames: ["lambda"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetModelsDir({required String path })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(path, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 56, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetModelsDirConstMeta,
+            argValues: [path],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetModelsDirConstMeta => const TaskConstMeta(
+            debugName: "set_models_dir",
+            argNames: ["path"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetPrefixCacheEnabled({required bool enabled })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_bool(enabled, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 57, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetPrefixCacheEnabledConstMeta,
+            argValues: [enabled],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetPrefixCacheEnabledConstMeta => const TaskConstMeta(
+            debugName: "set_prefix_cache_enabled",
+            argNames: ["enabled"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetRuntimeSettings({required RuntimeSettings settings })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_box_autoadd_runtime_settings(settings, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 58, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetRuntimeSettingsConstMeta,
+            argValues: [settings],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetRuntimeSettingsConstMeta => const TaskConstMeta(
+            debugName: "set_runtime_settings",
+            argNames: ["settings"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetSamplingParams({required SamplingParams params })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_box_autoadd_sampling_params(params, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 59, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetSamplingParamsConstMeta,
+            argValues: [params],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetSamplingParamsConstMeta => const TaskConstMeta(
+            debugName: "set_sampling_params",
+            argNames: ["params"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetTemperature({required double temperature })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_f_32(temperature, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 60, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetTemperatureConstMeta,
+            argValues: [temperature],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetTemperatureConstMeta => const TaskConstMeta(
+            debugName: "set_temperature",
+            argNames: ["temperature"],
+        );
+        
+
+@override Future<void> crateApiSimpleSetUserName({required String name })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_String(name, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 61, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleSetUserNameConstMeta,
+            argValues: [name],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleSetUserNameConstMeta => const TaskConstMeta(
+            debugName: "set_user_name",
+            argNames: ["name"],
+        );
+        
+
+@override Future<void> crateApiSimpleUnloadModel({required ModelKind kind })  { return handler.executeNormal(NormalTask(
+            callFfi: (port_) {
+              
+            final serializer = SseSerializer(generalizedFrbRustBinding);sse_encode_model_kind(kind, serializer);
+            pdeCallFfi(generalizedFrbRustBinding, serializer, funcId: 62, port: port_);
+            
+            },
+            codec: 
+        SseCodec(
+          decodeSuccessData: sse_decode_unit,
+          decodeErrorData: sse_decode_anima_error,
+        )
+        ,
+            constMeta: kCrateApiSimpleUnloadModelConstMeta,
+            argValues: [kind],
+            apiImpl: this,
+        )); }
+
+
+        TaskConstMeta get kCrateApiSimpleUnloadModelConstMeta => const TaskConstMeta(
+            debugName: "unload_model",
+            argNames: ["kind"],
+        );
+        
+
+
+
+                  @protected AnyhowException dco_decode_AnyhowException(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return AnyhowException(raw as String); }
+
+@protected RustStreamSink<ChatEvent> dco_decode_StreamSink_chat_event_Sse(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+throw UnimplementedError(); }
+
+@protected RustStreamSink<ReembedEvent> dco_decode_StreamSink_reembed_event_Sse(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+throw UnimplementedError(); }
+
+@protected String dco_decode_String(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw as String; }
+
+@protected AnimaError dco_decode_anima_error(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+switch (raw[0]) {
+                case 0: return AnimaError_ModelNotLoaded(dco_decode_String(raw[1]),);
+case 1: return AnimaError_ModelLoadFailed(dco_decode_String(raw[1]),);
+case 2: return AnimaError_InvalidModelFile(dco_decode_String(raw[1]),);
+case 3: return AnimaError_ModelTruncated(dco_decode_String(raw[1]),);
+case 4: return AnimaError_ModelChecksumMismatch(dco_decode_String(raw[1]),);
+case 5: return AnimaError_WrongModelKind(dco_decode_String(raw[1]),);
+case 6: return AnimaError_InsufficientMemory(dco_decode_String(raw[1]),);
+case 7: return AnimaError_ContextOverflow(dco_decode_String(raw[1]),);
+case 8: return AnimaError_BackendUnavailable(dco_decode_String(raw[1]),);
+case 9: return AnimaError_EmptyOutput(dco_decode_String(raw[1]),);
+case 10: return AnimaError_GenerationFailed(dco_decode_String(raw[1]),);
+case 11: return AnimaError_EmbeddingFailed(dco_decode_String(raw[1]),);
+case 12: return AnimaError_Cancelled(dco_decode_String(raw[1]),);
+case 13: return AnimaError_Preempted(dco_decode_String(raw[1]),);
+case 14: return AnimaError_DbLocked(dco_decode_String(raw[1]),);
+case 15: return AnimaError_DbIo(dco_decode_String(raw[1]),);
+case 16: return AnimaError_InvalidInput(dco_decode_String(raw[1]),);
+case 17: return AnimaError_Io(dco_decode_String(raw[1]),);
+case 18: return AnimaError_Internal(dco_decode_String(raw[1]),);
+                default: throw Exception("unreachable");
+            } }
+
+@protected BackendKind dco_decode_backend_kind(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return BackendKind.values[raw as int]; }
+
+@protected BackendSettings dco_decode_backend_settings(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 6) throw Exception('unexpected arr length: expect 6 but see ${arr.length}');
+                return BackendSettings(chatBackend: dco_decode_backend_kind(arr[0]),
+embeddingBackend: dco_decode_backend_kind(arr[1]),
+serverUrl: dco_decode_String(arr[2]),
+serverChatModel: dco_decode_String(arr[3]),
+serverEmbeddingModel: dco_decode_String(arr[4]),
+serverContextWindow: dco_decode_u_32(arr[5]),); }
+
+@protected bool dco_decode_bool(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw as bool; }
+
+@protected BackendSettings dco_decode_box_autoadd_backend_settings(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return dco_decode_backend_settings(raw); }
+
+@protected ChatRequest dco_decode_box_autoadd_chat_request(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return dco_decode_chat_request(raw); }
+
+@protected double dco_decode_box_autoadd_f_32(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw as double; }
+
+@protected MemoryScoringWeights dco_decode_box_autoadd_memory_scoring_weights(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return dco_decode_memory_scoring_weights(raw); }
+
+@protected RuntimeSettings dco_decode_box_autoadd_runtime_settings(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return dco_decode_runtime_settings(raw); }
+
+@protected SamplingParams dco_decode_box_autoadd_sampling_params(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return dco_decode_sampling_params(raw); }
+
+@protected int dco_decode_box_autoadd_u_32(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw as int; }
+
+@protected ChatCompletion dco_decode_chat_completion(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 2) throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+                return ChatCompletion(text: dco_decode_String(arr[0]),
+stopReason: dco_decode_stop_reason(arr[1]),); }
+
+@protected ChatEvent dco_decode_chat_event(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+switch (raw[0]) {
+                case 0: return ChatEvent_ContextRetrieved(memories: dco_decode_list_memory_match(raw[1]),);
+case 1: return ChatEvent_Token(dco_decode_String(raw[1]),);
+case 2: return ChatEvent_Done(promptTokens: dco_decode_u_32(raw[1]),completionTokens: dco_decode_u_32(raw[2]),tokensPerSec: dco_decode_f_64(raw[3]),stopReason: dco_decode_stop_reason(raw[4]),);
+case 3: return ChatEvent_Error(code: dco_decode_String(raw[1]),detail: dco_decode_String(raw[2]),);
+                default: throw Exception("unreachable");
+            } }
+
+@protected ChatMessage dco_decode_chat_message(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 4) throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+                return ChatMessage(id: dco_decode_i_64(arr[0]),
+role: dco_decode_String(arr[1]),
+content: dco_decode_String(arr[2]),
+timestamp: dco_decode_String(arr[3]),); }
+
+@protected ChatOptions dco_decode_chat_options(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 2) throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+                return ChatOptions(sampling: dco_decode_opt_box_autoadd_sampling_params(arr[0]),
+maxTokens: dco_decode_u_32(arr[1]),); }
+
+@protected ChatRequest dco_decode_chat_request(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 4) throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+                return ChatRequest(requestId: dco_decode_opt_String(arr[0]),
+history: dco_decode_list_turn(arr[1]),
+message: dco_decode_String(arr[2]),
+options: dco_decode_chat_options(arr[3]),); }
+
+@protected ContextConfig dco_decode_context_config(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 4) throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+                return ContextConfig(nCtx: dco_decode_u_32(arr[0]),
+nBatch: dco_decode_u_32(arr[1]),
+nThreads: dco_decode_u_32(arr[2]),
+nThreadsBatch: dco_decode_u_32(arr[3]),); }
+
+@protected EmbeddingStatus dco_decode_embedding_status(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 3) throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+                return EmbeddingStatus(modelId: dco_decode_String(arr[0]),
+totalMemories: dco_decode_u_32(arr[1]),
+staleMemories: dco_decode_u_32(arr[2]),); }
+
+@protected double dco_decode_f_32(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw as double; }
+
+@protected double dco_decode_f_64(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw as double; }
+
+@protected int dco_decode_i_32(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw as int; }
+
+@protected PlatformInt64 dco_decode_i_64(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return dcoDecodeI64(raw); }
+
+@protected InferenceQueueStatus dco_decode_inference_queue_status(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 3) throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+                return InferenceQueueStatus(chat: dco_decode_list_queued_job(arr[0]),
+embedding: dco_decode_list_queued_job(arr[1]),
+reranker: dco_decode_list_queued_job(arr[2]),); }
+
+@protected JobPriority dco_decode_job_priority(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return JobPriority.values[raw as int]; }
+
+@protected List<ChatMessage> dco_decode_list_chat_message(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return (raw as List<dynamic>).map(dco_decode_chat_message).toList(); }
+
+@protected List<MemoryItem> dco_decode_list_memory_item(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return (raw as List<dynamic>).map(dco_decode_memory_item).toList(); }
+
+@protected List<MemoryMatch> dco_decode_list_memory_match(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return (raw as List<dynamic>).map(dco_decode_memory_match).toList(); }
+
+@protected List<ModelInfo> dco_decode_list_model_info(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return (raw as List<dynamic>).map(dco_decode_model_info).toList(); }
+
+@protected Uint8List dco_decode_list_prim_u_8_strict(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw as Uint8List; }
+
+@protected List<ProfileTrait> dco_decode_list_profile_trait(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return (raw as List<dynamic>).map(dco_decode_profile_trait).toList(); }
+
+@protected List<PromptSectionPreview> dco_decode_list_prompt_section_preview(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return (raw as List<dynamic>).map(dco_decode_prompt_section_preview).toList(); }
+
+@protected List<QueuedJob> dco_decode_list_queued_job(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return (raw as List<dynamic>).map(dco_decode_queued_job).toList(); }
+
+@protected List<Turn> dco_decode_list_turn(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return (raw as List<dynamic>).map(dco_decode_turn).toList(); }
+
+@protected LoadStrategy dco_decode_load_strategy(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return LoadStrategy.values[raw as int]; }
+
+@protected MemoryItem dco_decode_memory_item(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 3) throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+                return MemoryItem(id: dco_decode_i_64(arr[0]),
+content: dco_decode_String(arr[1]),
+createdAt: dco_decode_String(arr[2]),); }
+
+@protected MemoryMatch dco_decode_memory_match(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 12) throw Exception('unexpected arr length: expect 12 but see ${arr.length}');
+                return MemoryMatch(messageId: dco_decode_i_64(arr[0]),
+role: dco_decode_String(arr[1]),
+content: dco_decode_String(arr[2]),
+similarity: dco_decode_f_32(arr[3]),
+relevance: dco_decode_f_32(arr[4]),
+importance: dco_decode_f_32(arr[5]),
+accessCount: dco_decode_u_32(arr[6]),
+score: dco_decode_memory_score(arr[7]),
+rerankScore: dco_decode_opt_box_autoadd_f_32(arr[8]),
+timestamp: dco_decode_String(arr[9]),
+memoryType: dco_decode_String(arr[10]),
+memoryUnixTimestamp: dco_decode_i_64(arr[11]),); }
+
+@protected MemoryScore dco_decode_memory_score(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 5) throw Exception('unexpected arr length: expect 5 but see ${arr.length}');
+                return MemoryScore(relevance: dco_decode_f_32(arr[0]),
+recency: dco_decode_f_32(arr[1]),
+importance: dco_decode_f_32(arr[2]),
+access: dco_decode_f_32(arr[3]),
+total: dco_decode_f_32(arr[4]),); }
+
+@protected MemoryScoringWeights dco_decode_memory_scoring_weights(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 5) throw Exception('unexpected arr length: expect 5 but see ${arr.length}');
+                return MemoryScoringWeights(relevance: dco_decode_f_32(arr[0]),
+recency: dco_decode_f_32(arr[1]),
+importance: dco_decode_f_32(arr[2]),
+access: dco_decode_f_32(arr[3]),
+recencyHalfLifeDays: dco_decode_f_32(arr[4]),); }
+
+@protected ModelInfo dco_decode_model_info(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 11) throw Exception('unexpected arr length: expect 11 but see ${arr.length}');
+                return ModelInfo(id: dco_decode_String(arr[0]),
+path: dco_decode_String(arr[1]),
+sizeBytes: dco_decode_u_64(arr[2]),
+architecture: dco_decode_String(arr[3]),
+parameterCount: dco_decode_u_64(arr[4]),
+quantization: dco_decode_String(arr[5]),
+contextLength: dco_decode_opt_box_autoadd_u_32(arr[6]),
+embeddingLength: dco_decode_opt_box_autoadd_u_32(arr[7]),
+chatTemplate: dco_decode_String(arr[8]),
+hasEmbeddedTemplate: dco_decode_bool(arr[9]),
+suggestedKind: dco_decode_model_kind(arr[10]),); }
+
+@protected ModelKind dco_decode_model_kind(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return ModelKind.values[raw as int]; }
+
+@protected ModelState dco_decode_model_state(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+switch (raw[0]) {
+                case 0: return ModelState_NotLoaded();
+case 1: return ModelState_Loading(path: dco_decode_String(raw[1]),);
+case 2: return ModelState_Ready(path: dco_decode_String(raw[1]),loadStrategy: dco_decode_load_strategy(raw[2]),);
+case 3: return ModelState_Failed(path: dco_decode_String(raw[1]),code: dco_decode_String(raw[2]),detail: dco_decode_String(raw[3]),);
+                default: throw Exception("unreachable");
+            } }
+
+@protected ModelStatus dco_decode_model_status(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 3) throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+                return ModelStatus(chat: dco_decode_model_state(arr[0]),
+embedding: dco_decode_model_state(arr[1]),
+reranker: dco_decode_model_state(arr[2]),); }
+
+@protected String? dco_decode_opt_String(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw == null ? null : dco_decode_String(raw); }
+
+@protected double? dco_decode_opt_box_autoadd_f_32(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw == null ? null : dco_decode_box_autoadd_f_32(raw); }
+
+@protected SamplingParams? dco_decode_opt_box_autoadd_sampling_params(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw == null ? null : dco_decode_box_autoadd_sampling_params(raw); }
+
+@protected int? dco_decode_opt_box_autoadd_u_32(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw == null ? null : dco_decode_box_autoadd_u_32(raw); }
+
+@protected ProfileTrait dco_decode_profile_trait(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 2) throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+                return ProfileTrait(category: dco_decode_String(arr[0]),
+content: dco_decode_String(arr[1]),); }
+
+@protected PromptPreview dco_decode_prompt_preview(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 6) throw Exception('unexpected arr length: expect 6 but see ${arr.length}');
+                return PromptPreview(sections: dco_decode_list_prompt_section_preview(arr[0]),
+memories: dco_decode_list_memory_match(arr[1]),
+renderedPrompt: dco_decode_String(arr[2]),
+totalTokens: dco_decode_u_32(arr[3]),
+contextWindow: dco_decode_u_32(arr[4]),
+reservedForGeneration: dco_decode_u_32(arr[5]),); }
+
+@protected PromptSectionPreview dco_decode_prompt_section_preview(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 7) throw Exception('unexpected arr length: expect 7 but see ${arr.length}');
+                return PromptSectionPreview(name: dco_decode_String(arr[0]),
+text: dco_decode_String(arr[1]),
+tokens: dco_decode_u_32(arr[2]),
+requestedTokens: dco_decode_u_32(arr[3]),
+itemsKept: dco_decode_u_32(arr[4]),
+itemsTotal: dco_decode_u_32(arr[5]),
+truncated: dco_decode_bool(arr[6]),); }
+
+@protected QueuedJob dco_decode_queued_job(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 3) throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+                return QueuedJob(id: dco_decode_u_64(arr[0]),
+priority: dco_decode_job_priority(arr[1]),
+position: dco_decode_u_32(arr[2]),); }
+
+@protected ReembedEvent dco_decode_reembed_event(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+switch (raw[0]) {
+                case 0: return ReembedEvent_Progress(done: dco_decode_u_32(raw[1]),total: dco_decode_u_32(raw[2]),);
+case 1: return ReembedEvent_Done(modelId: dco_decode_String(raw[1]),reembedded: dco_decode_u_32(raw[2]),);
+case 2: return ReembedEvent_Error(code: dco_decode_String(raw[1]),detail: dco_decode_String(raw[2]),);
+                default: throw Exception("unreachable");
+            } }
+
+@protected RuntimeSettings dco_decode_runtime_settings(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 5) throw Exception('unexpected arr length: expect 5 but see ${arr.length}');
+                return RuntimeSettings(autoTune: dco_decode_bool(arr[0]),
+nCtx: dco_decode_u_32(arr[1]),
+nBatch: dco_decode_u_32(arr[2]),
+nThreads: dco_decode_u_32(arr[3]),
+nThreadsBatch: dco_decode_u_32(arr[4]),); }
+
+@protected SamplingParams dco_decode_sampling_params(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 10) throw Exception('unexpected arr length: expect 10 but see ${arr.length}');
+                return SamplingParams(temperature: dco_decode_f_32(arr[0]),
+topK: dco_decode_i_32(arr[1]),
+topP: dco_decode_f_32(arr[2]),
+minP: dco_decode_f_32(arr[3]),
+repeatPenalty: dco_decode_f_32(arr[4]),
+repeatLastN: dco_decode_i_32(arr[5]),
+seed: dco_decode_opt_box_autoadd_u_32(arr[6]),
+mirostat: dco_decode_u_8(arr[7]),
+mirostatTau: dco_decode_f_32(arr[8]),
+mirostatEta: dco_decode_f_32(arr[9]),); }
+
+@protected StopReason dco_decode_stop_reason(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return StopReason.values[raw as int]; }
+
+@protected Turn dco_decode_turn(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+final arr = raw as List<dynamic>;
+                if (arr.length != 2) throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+                return Turn(role: dco_decode_String(arr[0]),
+content: dco_decode_String(arr[1]),); }
+
+@protected int dco_decode_u_32(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw as int; }
+
+@protected BigInt dco_decode_u_64(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return dcoDecodeU64(raw); }
+
+@protected int dco_decode_u_8(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return raw as int; }
+
+@protected void dco_decode_unit(dynamic raw){ // Codec=Dco (DartCObject based), see doc to use other codecs
+return; }
+
+@protected AnyhowException sse_decode_AnyhowException(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var inner = sse_decode_String(deserializer);
+        return AnyhowException(inner); }
+
+@protected RustStreamSink<ChatEvent> sse_decode_StreamSink_chat_event_Sse(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+throw UnimplementedError('Unreachable ()'); }
+
+@protected RustStreamSink<ReembedEvent> sse_decode_StreamSink_reembed_event_Sse(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+throw UnimplementedError('Unreachable ()'); }
+
+@protected String sse_decode_String(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var inner = sse_decode_list_prim_u_8_strict(deserializer);
+        return utf8.decoder.convert(inner); }
+
+@protected AnimaError sse_decode_anima_error(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+            var tag_ = sse_decode_i_32(deserializer);
+            switch (tag_) { case 0: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_ModelNotLoaded(var_field0);case 1: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_ModelLoadFailed(var_field0);case 2: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_InvalidModelFile(var_field0);case 3: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_ModelTruncated(var_field0);case 4: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_ModelChecksumMismatch(var_field0);case 5: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_WrongModelKind(var_field0);case 6: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_InsufficientMemory(var_field0);case 7: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_ContextOverflow(var_field0);case 8: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_BackendUnavailable(var_field0);case 9: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_EmptyOutput(var_field0);case 10: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_GenerationFailed(var_field0);case 11: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_EmbeddingFailed(var_field0);case 12: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_Cancelled(var_field0);case 13: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_Preempted(var_field0);case 14: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_DbLocked(var_field0);case 15: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_DbIo(var_field0);case 16: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_InvalidInput(var_field0);case 17: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_Io(var_field0);case 18: var var_field0 = sse_decode_String(deserializer);
+return AnimaError_Internal(var_field0); default: throw UnimplementedError(''); }
+             }
+
+@protected BackendKind sse_decode_backend_kind(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var inner = sse_decode_i_32(deserializer);
+        return BackendKind.values[inner]; }
+
+@protected BackendSettings sse_decode_backend_settings(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_chatBackend = sse_decode_backend_kind(deserializer);
+var var_embeddingBackend = sse_decode_backend_kind(deserializer);
+var var_serverUrl = sse_decode_String(deserializer);
+var var_serverChatModel = sse_decode_String(deserializer);
+var var_serverEmbeddingModel = sse_decode_String(deserializer);
+var var_serverContextWindow = sse_decode_u_32(deserializer);
+return BackendSettings(chatBackend: var_chatBackend, embeddingBackend: var_embeddingBackend, serverUrl: var_serverUrl, serverChatModel: var_serverChatModel, serverEmbeddingModel: var_serverEmbeddingModel, serverContextWindow: var_serverContextWindow); }
+
+@protected bool sse_decode_bool(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return deserializer.buffer.getUint8() != 0; }
+
+@protected BackendSettings sse_decode_box_autoadd_backend_settings(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return (sse_decode_backend_settings(deserializer)); }
+
+@protected ChatRequest sse_decode_box_autoadd_chat_request(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return (sse_decode_chat_request(deserializer)); }
+
+@protected double sse_decode_box_autoadd_f_32(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return (sse_decode_f_32(deserializer)); }
+
+@protected MemoryScoringWeights sse_decode_box_autoadd_memory_scoring_weights(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return (sse_decode_memory_scoring_weights(deserializer)); }
+
+@protected RuntimeSettings sse_decode_box_autoadd_runtime_settings(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return (sse_decode_runtime_settings(deserializer)); }
+
+@protected SamplingParams sse_decode_box_autoadd_sampling_params(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return (sse_decode_sampling_params(deserializer)); }
+
+@protected int sse_decode_box_autoadd_u_32(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return (sse_decode_u_32(deserializer)); }
+
+@protected ChatCompletion sse_decode_chat_completion(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_text = sse_decode_String(deserializer);
+var var_stopReason = sse_decode_stop_reason(deserializer);
+return ChatCompletion(text: var_text, stopReason: var_stopReason); }
+
+@protected ChatEvent sse_decode_chat_event(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+            var tag_ = sse_decode_i_32(deserializer);
+            switch (tag_) { case 0: var var_memories = sse_decode_list_memory_match(deserializer);
+return ChatEvent_ContextRetrieved(memories: var_memories);case 1: var var_field0 = sse_decode_String(deserializer);
+return ChatEvent_Token(var_field0);case 2: var var_promptTokens = sse_decode_u_32(deserializer);
+var var_completionTokens = sse_decode_u_32(deserializer);
+var var_tokensPerSec = sse_decode_f_64(deserializer);
+var var_stopReason = sse_decode_stop_reason(deserializer);
+return ChatEvent_Done(promptTokens: var_promptTokens, completionTokens: var_completionTokens, tokensPerSec: var_tokensPerSec, stopReason: var_stopReason);case 3: var var_code = sse_decode_String(deserializer);
+var var_detail = sse_decode_String(deserializer);
+return ChatEvent_Error(code: var_code, detail: var_detail); default: throw UnimplementedError(''); }
+             }
+
+@protected ChatMessage sse_decode_chat_message(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_id = sse_decode_i_64(deserializer);
+var var_role = sse_decode_String(deserializer);
+var var_content = sse_decode_String(deserializer);
+var var_timestamp = sse_decode_String(deserializer);
+return ChatMessage(id: var_id, role: var_role, content: var_content, timestamp: var_timestamp); }
+
+@protected ChatOptions sse_decode_chat_options(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_sampling = sse_decode_opt_box_autoadd_sampling_params(deserializer);
+var var_maxTokens = sse_decode_u_32(deserializer);
+return ChatOptions(sampling: var_sampling, maxTokens: var_maxTokens); }
+
+@protected ChatRequest sse_decode_chat_request(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_requestId = sse_decode_opt_String(deserializer);
+var var_history = sse_decode_list_turn(deserializer);
+var var_message = sse_decode_String(deserializer);
+var var_options = sse_decode_chat_options(deserializer);
+return ChatRequest(requestId: var_requestId, history: var_history, message: var_message, options: var_options); }
+
+@protected ContextConfig sse_decode_context_config(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_nCtx = sse_decode_u_32(deserializer);
+var var_nBatch = sse_decode_u_32(deserializer);
+var var_nThreads = sse_decode_u_32(deserializer);
+var var_nThreadsBatch = sse_decode_u_32(deserializer);
+return ContextConfig(nCtx: var_nCtx, nBatch: var_nBatch, nThreads: var_nThreads, nThreadsBatch: var_nThreadsBatch); }
+
+@protected EmbeddingStatus sse_decode_embedding_status(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_modelId = sse_decode_String(deserializer);
+var var_totalMemories = sse_decode_u_32(deserializer);
+var var_staleMemories = sse_decode_u_32(deserializer);
+return EmbeddingStatus(modelId: var_modelId, totalMemories: var_totalMemories, staleMemories: var_staleMemories); }
+
+@protected double sse_decode_f_32(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return deserializer.buffer.getFloat32(); }
+
+@protected double sse_decode_f_64(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return deserializer.buffer.getFloat64(); }
+
+@protected int sse_decode_i_32(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return deserializer.buffer.getInt32(); }
+
+@protected PlatformInt64 sse_decode_i_64(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return deserializer.buffer.getPlatformInt64(); }
+
+@protected InferenceQueueStatus sse_decode_inference_queue_status(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_chat = sse_decode_list_queued_job(deserializer);
+var var_embedding = sse_decode_list_queued_job(deserializer);
+var var_reranker = sse_decode_list_queued_job(deserializer);
+return InferenceQueueStatus(chat: var_chat, embedding: var_embedding, reranker: var_reranker); }
+
+@protected JobPriority sse_decode_job_priority(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var inner = sse_decode_i_32(deserializer);
+        return JobPriority.values[inner]; }
+
+@protected List<ChatMessage> sse_decode_list_chat_message(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+        var len_ = sse_decode_i_32(deserializer);
+        var ans_ = <ChatMessage>[];
+        for (var idx_ = 0; idx_ < len_; ++idx_) { ans_.add(sse_decode_chat_message(deserializer)); }
+        return ans_;
+         }
+
+@protected List<MemoryItem> sse_decode_list_memory_item(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+        var len_ = sse_decode_i_32(deserializer);
+        var ans_ = <MemoryItem>[];
+        for (var idx_ = 0; idx_ < len_; ++idx_) { ans_.add(sse_decode_memory_item(deserializer)); }
+        return ans_;
+         }
+
+@protected List<MemoryMatch> sse_decode_list_memory_match(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+        var len_ = sse_decode_i_32(deserializer);
+        var ans_ = <MemoryMatch>[];
+        for (var idx_ = 0; idx_ < len_; ++idx_) { ans_.add(sse_decode_memory_match(deserializer)); }
+        return ans_;
+         }
+
+@protected List<ModelInfo> sse_decode_list_model_info(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+        var len_ = sse_decode_i_32(deserializer);
+        var ans_ = <ModelInfo>[];
+        for (var idx_ = 0; idx_ < len_; ++idx_) { ans_.add(sse_decode_model_info(deserializer)); }
+        return ans_;
+         }
+
+@protected Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var len_ = sse_decode_i_32(deserializer);
+                return deserializer.buffer.getUint8List(len_); }
+
+@protected List<ProfileTrait> sse_decode_list_profile_trait(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+        var len_ = sse_decode_i_32(deserializer);
+        var ans_ = <ProfileTrait>[];
+        for (var idx_ = 0; idx_ < len_; ++idx_) { ans_.add(sse_decode_profile_trait(deserializer)); }
+        return ans_;
+         }
+
+@protected List<PromptSectionPreview> sse_decode_list_prompt_section_preview(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+        var len_ = sse_decode_i_32(deserializer);
+        var ans_ = <PromptSectionPreview>[];
+        for (var idx_ = 0; idx_ < len_; ++idx_) { ans_.add(sse_decode_prompt_section_preview(deserializer)); }
+        return ans_;
+         }
+
+@protected List<QueuedJob> sse_decode_list_queued_job(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+        var len_ = sse_decode_i_32(deserializer);
+        var ans_ = <QueuedJob>[];
+        for (var idx_ = 0; idx_ < len_; ++idx_) { ans_.add(sse_decode_queued_job(deserializer)); }
+        return ans_;
+         }
+
+@protected List<Turn> sse_decode_list_turn(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+        var len_ = sse_decode_i_32(deserializer);
+        var ans_ = <Turn>[];
+        for (var idx_ = 0; idx_ < len_; ++idx_) { ans_.add(sse_decode_turn(deserializer)); }
+        return ans_;
+         }
+
+@protected LoadStrategy sse_decode_load_strategy(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var inner = sse_decode_i_32(deserializer);
+        return LoadStrategy.values[inner]; }
+
+@protected MemoryItem sse_decode_memory_item(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_id = sse_decode_i_64(deserializer);
+var var_content = sse_decode_String(deserializer);
+var var_createdAt = sse_decode_String(deserializer);
+return MemoryItem(id: var_id, content: var_content, createdAt: var_createdAt); }
+
+@protected MemoryMatch sse_decode_memory_match(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_messageId = sse_decode_i_64(deserializer);
+var var_role = sse_decode_String(deserializer);
+var var_content = sse_decode_String(deserializer);
+var var_similarity = sse_decode_f_32(deserializer);
+var var_relevance = sse_decode_f_32(deserializer);
+var var_importance = sse_decode_f_32(deserializer);
+var var_accessCount = sse_decode_u_32(deserializer);
+var var_score = sse_decode_memory_score(deserializer);
+var var_rerankScore = sse_decode_opt_box_autoadd_f_32(deserializer);
+var var_timestamp = sse_decode_String(deserializer);
+var var_memoryType = sse_decode_String(deserializer);
+var var_memoryUnixTimestamp = sse_decode_i_64(deserializer);
+return MemoryMatch(messageId: var_messageId, role: var_role, content: var_content, similarity: var_similarity, relevance: var_relevance, importance: var_importance, accessCount: var_accessCount, score: var_score, rerankScore: var_rerankScore, timestamp: var_timestamp, memoryType: var_memoryType, memoryUnixTimestamp: var_memoryUnixTimestamp); }
+
+@protected MemoryScore sse_decode_memory_score(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_relevance = sse_decode_f_32(deserializer);
+var var_recency = sse_decode_f_32(deserializer);
+var var_importance = sse_decode_f_32(deserializer);
+var var_access = sse_decode_f_32(deserializer);
+var var_total = sse_decode_f_32(deserializer);
+return MemoryScore(relevance: var_relevance, recency: var_recency, importance: var_importance, access: var_access, total: var_total); }
+
+@protected MemoryScoringWeights sse_decode_memory_scoring_weights(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_relevance = sse_decode_f_32(deserializer);
+var var_recency = sse_decode_f_32(deserializer);
+var var_importance = sse_decode_f_32(deserializer);
+var var_access = sse_decode_f_32(deserializer);
+var var_recencyHalfLifeDays = sse_decode_f_32(deserializer);
+return MemoryScoringWeights(relevance: var_relevance, recency: var_recency, importance: var_importance, access: var_access, recencyHalfLifeDays: var_recencyHalfLifeDays); }
+
+@protected ModelInfo sse_decode_model_info(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_id = sse_decode_String(deserializer);
+var var_path = sse_decode_String(deserializer);
+var var_sizeBytes = sse_decode_u_64(deserializer);
+var var_architecture = sse_decode_String(deserializer);
+var var_parameterCount = sse_decode_u_64(deserializer);
+var var_quantization = sse_decode_String(deserializer);
+var var_contextLength = sse_decode_opt_box_autoadd_u_32(deserializer);
+var var_embeddingLength = sse_decode_opt_box_autoadd_u_32(deserializer);
+var var_chatTemplate = sse_decode_String(deserializer);
+var var_hasEmbeddedTemplate = sse_decode_bool(deserializer);
+var var_suggestedKind = sse_decode_model_kind(deserializer);
+return ModelInfo(id: var_id, path: var_path, sizeBytes: var_sizeBytes, architecture: var_architecture, parameterCount: var_parameterCount, quantization: var_quantization, contextLength: var_contextLength, embeddingLength: var_embeddingLength, chatTemplate: var_chatTemplate, hasEmbeddedTemplate: var_hasEmbeddedTemplate, suggestedKind: var_suggestedKind); }
+
+@protected ModelKind sse_decode_model_kind(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var inner = sse_decode_i_32(deserializer);
+        return ModelKind.values[inner]; }
+
+@protected ModelState sse_decode_model_state(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+            var tag_ = sse_decode_i_32(deserializer);
+            switch (tag_) { case 0: return ModelState_NotLoaded();case 1: var var_path = sse_decode_String(deserializer);
+return ModelState_Loading(path: var_path);case 2: var var_path = sse_decode_String(deserializer);
+var var_loadStrategy = sse_decode_load_strategy(deserializer);
+return ModelState_Ready(path: var_path, loadStrategy: var_loadStrategy);case 3: var var_path = sse_decode_String(deserializer);
+var var_code = sse_decode_String(deserializer);
+var var_detail = sse_decode_String(deserializer);
+return ModelState_Failed(path: var_path, code: var_code, detail: var_detail); default: throw UnimplementedError(''); }
+             }
+
+@protected ModelStatus sse_decode_model_status(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_chat = sse_decode_model_state(deserializer);
+var var_embedding = sse_decode_model_state(deserializer);
+var var_reranker = sse_decode_model_state(deserializer);
+return ModelStatus(chat: var_chat, embedding: var_embedding, reranker: var_reranker); }
+
+@protected String? sse_decode_opt_String(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+            if (sse_decode_bool(deserializer)) {
+                return (sse_decode_String(deserializer));
+            } else {
+                return null;
+            }
+             }
+
+@protected double? sse_decode_opt_box_autoadd_f_32(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+            if (sse_decode_bool(deserializer)) {
+                return (sse_decode_box_autoadd_f_32(deserializer));
+            } else {
+                return null;
+            }
+             }
+
+@protected SamplingParams? sse_decode_opt_box_autoadd_sampling_params(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+            if (sse_decode_bool(deserializer)) {
+                return (sse_decode_box_autoadd_sampling_params(deserializer));
+            } else {
+                return null;
+            }
+             }
+
+@protected int? sse_decode_opt_box_autoadd_u_32(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+            if (sse_decode_bool(deserializer)) {
+                return (sse_decode_box_autoadd_u_32(deserializer));
+            } else {
+                return null;
+            }
+             }
+
+@protected ProfileTrait sse_decode_profile_trait(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_category = sse_decode_String(deserializer);
+var var_content = sse_decode_String(deserializer);
+return ProfileTrait(category: var_category, content: var_content); }
+
+@protected PromptPreview sse_decode_prompt_preview(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_sections = sse_decode_list_prompt_section_preview(deserializer);
+var var_memories = sse_decode_list_memory_match(deserializer);
+var var_renderedPrompt = sse_decode_String(deserializer);
+var var_totalTokens = sse_decode_u_32(deserializer);
+var var_contextWindow = sse_decode_u_32(deserializer);
+var var_reservedForGeneration = sse_decode_u_32(deserializer);
+return PromptPreview(sections: var_sections, memories: var_memories, renderedPrompt: var_renderedPrompt, totalTokens: var_totalTokens, contextWindow: var_contextWindow, reservedForGeneration: var_reservedForGeneration); }
+
+@protected PromptSectionPreview sse_decode_prompt_section_preview(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_name = sse_decode_String(deserializer);
+var var_text = sse_decode_String(deserializer);
+var var_tokens = sse_decode_u_32(deserializer);
+var var_requestedTokens = sse_decode_u_32(deserializer);
+var var_itemsKept = sse_decode_u_32(deserializer);
+var var_itemsTotal = sse_decode_u_32(deserializer);
+var var_truncated = sse_decode_bool(deserializer);
+return PromptSectionPreview(name: var_name, text: var_text, tokens: var_tokens, requestedTokens: var_requestedTokens, itemsKept: var_itemsKept, itemsTotal: var_itemsTotal, truncated: var_truncated); }
+
+@protected QueuedJob sse_decode_queued_job(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_id = sse_decode_u_64(deserializer);
+var var_priority = sse_decode_job_priority(deserializer);
+var var_position = sse_decode_u_32(deserializer);
+return QueuedJob(id: var_id, priority: var_priority, position: var_position); }
+
+@protected ReembedEvent sse_decode_reembed_event(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+            var tag_ = sse_decode_i_32(deserializer);
+            switch (tag_) { case 0: var var_done = sse_decode_u_32(deserializer);
+var var_total = sse_decode_u_32(deserializer);
+return ReembedEvent_Progress(done: var_done, total: var_total);case 1: var var_modelId = sse_decode_String(deserializer);
+var var_reembedded = sse_decode_u_32(deserializer);
+return ReembedEvent_Done(modelId: var_modelId, reembedded: var_reembedded);case 2: var var_code = sse_decode_String(deserializer);
+var var_detail = sse_decode_String(deserializer);
+return ReembedEvent_Error(code: var_code, detail: var_detail); default: throw UnimplementedError(''); }
+             }
+
+@protected RuntimeSettings sse_decode_runtime_settings(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_autoTune = sse_decode_bool(deserializer);
+var var_nCtx = sse_decode_u_32(deserializer);
+var var_nBatch = sse_decode_u_32(deserializer);
+var var_nThreads = sse_decode_u_32(deserializer);
+var var_nThreadsBatch = sse_decode_u_32(deserializer);
+return RuntimeSettings(autoTune: var_autoTune, nCtx: var_nCtx, nBatch: var_nBatch, nThreads: var_nThreads, nThreadsBatch: var_nThreadsBatch); }
+
+@protected SamplingParams sse_decode_sampling_params(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_temperature = sse_decode_f_32(deserializer);
+var var_topK = sse_decode_i_32(deserializer);
+var var_topP = sse_decode_f_32(deserializer);
+var var_minP = sse_decode_f_32(deserializer);
+var var_repeatPenalty = sse_decode_f_32(deserializer);
+var var_repeatLastN = sse_decode_i_32(deserializer);
+var var_seed = sse_decode_opt_box_autoadd_u_32(deserializer);
+var var_mirostat = sse_decode_u_8(deserializer);
+var var_mirostatTau = sse_decode_f_32(deserializer);
+var var_mirostatEta = sse_decode_f_32(deserializer);
+return SamplingParams(temperature: var_temperature, topK: var_topK, topP: var_topP, minP: var_minP, repeatPenalty: var_repeatPenalty, repeatLastN: var_repeatLastN, seed: var_seed, mirostat: var_mirostat, mirostatTau: var_mirostatTau, mirostatEta: var_mirostatEta); }
+
+@protected StopReason sse_decode_stop_reason(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var inner = sse_decode_i_32(deserializer);
+        return StopReason.values[inner]; }
+
+@protected Turn sse_decode_turn(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+var var_role = sse_decode_String(deserializer);
+var var_content = sse_decode_String(deserializer);
+return Turn(role: var_role, content: var_content); }
+
+@protected int sse_decode_u_32(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return deserializer.buffer.getUint32(); }
+
+@protected BigInt sse_decode_u_64(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return deserializer.buffer.getBigUint64(); }
+
+@protected int sse_decode_u_8(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+return deserializer.buffer.getUint8(); }
+
+@protected void sse_decode_unit(SseDeserializer deserializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+ }
+
+@protected void sse_encode_AnyhowException(AnyhowException self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_String(self.message, serializer); }
+
+@protected void sse_encode_StreamSink_chat_event_Sse(RustStreamSink<ChatEvent> self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_String(self.setupAndSerialize(codec: SseCodec(
+            decodeSuccessData: sse_decode_chat_event,
+            decodeErrorData: sse_decode_AnyhowException,
+        )), serializer); }
+
+@protected void sse_encode_StreamSink_reembed_event_Sse(RustStreamSink<ReembedEvent> self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_String(self.setupAndSerialize(codec: SseCodec(
+            decodeSuccessData: sse_decode_reembed_event,
+            decodeErrorData: sse_decode_AnyhowException,
+        )), serializer); }
+
+@protected void sse_encode_String(String self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_list_prim_u_8_strict(utf8.encoder.convert(self), serializer); }
+
+@protected void sse_encode_anima_error(AnimaError self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+switch (self) { case AnimaError_ModelNotLoaded(field0: final field0): sse_encode_i_32(0, serializer); sse_encode_String(field0, serializer);
+case AnimaError_ModelLoadFailed(field0: final field0): sse_encode_i_32(1, serializer); sse_encode_String(field0, serializer);
+case AnimaError_InvalidModelFile(field0: final field0): sse_encode_i_32(2, serializer); sse_encode_String(field0, serializer);
+case AnimaError_ModelTruncated(field0: final field0): sse_encode_i_32(3, serializer); sse_encode_String(field0, serializer);
+case AnimaError_ModelChecksumMismatch(field0: final field0): sse_encode_i_32(4, serializer); sse_encode_String(field0, serializer);
+case AnimaError_WrongModelKind(field0: final field0): sse_encode_i_32(5, serializer); sse_encode_String(field0, serializer);
+case AnimaError_InsufficientMemory(field0: final field0): sse_encode_i_32(6, serializer); sse_encode_String(field0, serializer);
+case AnimaError_ContextOverflow(field0: final field0): sse_encode_i_32(7, serializer); sse_encode_String(field0, serializer);
+case AnimaError_BackendUnavailable(field0: final field0): sse_encode_i_32(8, serializer); sse_encode_String(field0, serializer);
+case AnimaError_EmptyOutput(field0: final field0): sse_encode_i_32(9, serializer); sse_encode_String(field0, serializer);
+case AnimaError_GenerationFailed(field0: final field0): sse_encode_i_32(10, serializer); sse_encode_String(field0, serializer);
+case AnimaError_EmbeddingFailed(field0: final field0): sse_encode_i_32(11, serializer); sse_encode_String(field0, serializer);
+case AnimaError_Cancelled(field0: final field0): sse_encode_i_32(12, serializer); sse_encode_String(field0, serializer);
+case AnimaError_Preempted(field0: final field0): sse_encode_i_32(13, serializer); sse_encode_String(field0, serializer);
+case AnimaError_DbLocked(field0: final field0): sse_encode_i_32(14, serializer); sse_encode_String(field0, serializer);
+case AnimaError_DbIo(field0: final field0): sse_encode_i_32(15, serializer); sse_encode_String(field0, serializer);
+case AnimaError_InvalidInput(field0: final field0): sse_encode_i_32(16, serializer); sse_encode_String(field0, serializer);
+case AnimaError_Io(field0: final field0): sse_encode_i_32(17, serializer); sse_encode_String(field0, serializer);
+case AnimaError_Internal(field0: final field0): sse_encode_i_32(18, serializer); sse_encode_String(field0, serializer);
+  } }
+
+@protected void sse_encode_backend_kind(BackendKind self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.index, serializer); }
+
+@protected void sse_encode_backend_settings(BackendSettings self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_backend_kind(self.chatBackend, serializer);
+sse_encode_backend_kind(self.embeddingBackend, serializer);
+sse_encode_String(self.serverUrl, serializer);
+sse_encode_String(self.serverChatModel, serializer);
+sse_encode_String(self.serverEmbeddingModel, serializer);
+sse_encode_u_32(self.serverContextWindow, serializer);
+ }
+
+@protected void sse_encode_bool(bool self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+serializer.buffer.putUint8(self ? 1 : 0); }
+
+@protected void sse_encode_box_autoadd_backend_settings(BackendSettings self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_backend_settings(self, serializer); }
+
+@protected void sse_encode_box_autoadd_chat_request(ChatRequest self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_chat_request(self, serializer); }
+
+@protected void sse_encode_box_autoadd_f_32(double self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_f_32(self, serializer); }
+
+@protected void sse_encode_box_autoadd_memory_scoring_weights(MemoryScoringWeights self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_memory_scoring_weights(self, serializer); }
+
+@protected void sse_encode_box_autoadd_runtime_settings(RuntimeSettings self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_runtime_settings(self, serializer); }
+
+@protected void sse_encode_box_autoadd_sampling_params(SamplingParams self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_sampling_params(self, serializer); }
+
+@protected void sse_encode_box_autoadd_u_32(int self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_u_32(self, serializer); }
+
+@protected void sse_encode_chat_completion(ChatCompletion self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_String(self.text, serializer);
+sse_encode_stop_reason(self.stopReason, serializer);
+ }
+
+@protected void sse_encode_chat_event(ChatEvent self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+switch (self) { case ChatEvent_ContextRetrieved(memories: final memories): sse_encode_i_32(0, serializer); sse_encode_list_memory_match(memories, serializer);
+case ChatEvent_Token(field0: final field0): sse_encode_i_32(1, serializer); sse_encode_String(field0, serializer);
+case ChatEvent_Done(promptTokens: final promptTokens,completionTokens: final completionTokens,tokensPerSec: final tokensPerSec,stopReason: final stopReason): sse_encode_i_32(2, serializer); sse_encode_u_32(promptTokens, serializer);
+sse_encode_u_32(completionTokens, serializer);
+sse_encode_f_64(tokensPerSec, serializer);
+sse_encode_stop_reason(stopReason, serializer);
+case ChatEvent_Error(code: final code,detail: final detail): sse_encode_i_32(3, serializer); sse_encode_String(code, serializer);
+sse_encode_String(detail, serializer);
+  } }
+
+@protected void sse_encode_chat_message(ChatMessage self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_64(self.id, serializer);
+sse_encode_String(self.role, serializer);
+sse_encode_String(self.content, serializer);
+sse_encode_String(self.timestamp, serializer);
+ }
+
+@protected void sse_encode_chat_options(ChatOptions self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_opt_box_autoadd_sampling_params(self.sampling, serializer);
+sse_encode_u_32(self.maxTokens, serializer);
+ }
+
+@protected void sse_encode_chat_request(ChatRequest self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_opt_String(self.requestId, serializer);
+sse_encode_list_turn(self.history, serializer);
+sse_encode_String(self.message, serializer);
+sse_encode_chat_options(self.options, serializer);
+ }
+
+@protected void sse_encode_context_config(ContextConfig self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_u_32(self.nCtx, serializer);
+sse_encode_u_32(self.nBatch, serializer);
+sse_encode_u_32(self.nThreads, serializer);
+sse_encode_u_32(self.nThreadsBatch, serializer);
+ }
+
+@protected void sse_encode_embedding_status(EmbeddingStatus self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_String(self.modelId, serializer);
+sse_encode_u_32(self.totalMemories, serializer);
+sse_encode_u_32(self.staleMemories, serializer);
+ }
+
+@protected void sse_encode_f_32(double self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+serializer.buffer.putFloat32(self); }
+
+@protected void sse_encode_f_64(double self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+serializer.buffer.putFloat64(self); }
+
+@protected void sse_encode_i_32(int self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+serializer.buffer.putInt32(self); }
+
+@protected void sse_encode_i_64(PlatformInt64 self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+serializer.buffer.putPlatformInt64(self); }
+
+@protected void sse_encode_inference_queue_status(InferenceQueueStatus self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_list_queued_job(self.chat, serializer);
+sse_encode_list_queued_job(self.embedding, serializer);
+sse_encode_list_queued_job(self.reranker, serializer);
+ }
+
+@protected void sse_encode_job_priority(JobPriority self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.index, serializer); }
+
+@protected void sse_encode_list_chat_message(List<ChatMessage> self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.length, serializer);
+        for (final item in self) { sse_encode_chat_message(item, serializer); } }
+
+@protected void sse_encode_list_memory_item(List<MemoryItem> self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.length, serializer);
+        for (final item in self) { sse_encode_memory_item(item, serializer); } }
+
+@protected void sse_encode_list_memory_match(List<MemoryMatch> self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.length, serializer);
+        for (final item in self) { sse_encode_memory_match(item, serializer); } }
+
+@protected void sse_encode_list_model_info(List<ModelInfo> self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.length, serializer);
+        for (final item in self) { sse_encode_model_info(item, serializer); } }
+
+@protected void sse_encode_list_prim_u_8_strict(Uint8List self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.length, serializer);
+                    serializer.buffer.putUint8List(self); }
+
+@protected void sse_encode_list_profile_trait(List<ProfileTrait> self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.length, serializer);
+        for (final item in self) { sse_encode_profile_trait(item, serializer); } }
+
+@protected void sse_encode_list_prompt_section_preview(List<PromptSectionPreview> self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.length, serializer);
+        for (final item in self) { sse_encode_prompt_section_preview(item, serializer); } }
+
+@protected void sse_encode_list_queued_job(List<QueuedJob> self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.length, serializer);
+        for (final item in self) { sse_encode_queued_job(item, serializer); } }
+
+@protected void sse_encode_list_turn(List<Turn> self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.length, serializer);
+        for (final item in self) { sse_encode_turn(item, serializer); } }
+
+@protected void sse_encode_load_strategy(LoadStrategy self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.index, serializer); }
+
+@protected void sse_encode_memory_item(MemoryItem self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_64(self.id, serializer);
+sse_encode_String(self.content, serializer);
+sse_encode_String(self.createdAt, serializer);
+ }
+
+@protected void sse_encode_memory_match(MemoryMatch self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_64(self.messageId, serializer);
+sse_encode_String(self.role, serializer);
+sse_encode_String(self.content, serializer);
+sse_encode_f_32(self.similarity, serializer);
+sse_encode_f_32(self.relevance, serializer);
+sse_encode_f_32(self.importance, serializer);
+sse_encode_u_32(self.accessCount, serializer);
+sse_encode_memory_score(self.score, serializer);
+sse_encode_opt_box_autoadd_f_32(self.rerankScore, serializer);
+sse_encode_String(self.timestamp, serializer);
+sse_encode_String(self.memoryType, serializer);
+sse_encode_i_64(self.memoryUnixTimestamp, serializer);
+ }
+
+@protected void sse_encode_memory_score(MemoryScore self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_f_32(self.relevance, serializer);
+sse_encode_f_32(self.recency, serializer);
+sse_encode_f_32(self.importance, serializer);
+sse_encode_f_32(self.access, serializer);
+sse_encode_f_32(self.total, serializer);
+ }
+
+@protected void sse_encode_memory_scoring_weights(MemoryScoringWeights self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_f_32(self.relevance, serializer);
+sse_encode_f_32(self.recency, serializer);
+sse_encode_f_32(self.importance, serializer);
+sse_encode_f_32(self.access, serializer);
+sse_encode_f_32(self.recencyHalfLifeDays, serializer);
+ }
+
+@protected void sse_encode_model_info(ModelInfo self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_String(self.id, serializer);
+sse_encode_String(self.path, serializer);
+sse_encode_u_64(self.sizeBytes, serializer);
+sse_encode_String(self.architecture, serializer);
+sse_encode_u_64(self.parameterCount, serializer);
+sse_encode_String(self.quantization, serializer);
+sse_encode_opt_box_autoadd_u_32(self.contextLength, serializer);
+sse_encode_opt_box_autoadd_u_32(self.embeddingLength, serializer);
+sse_encode_String(self.chatTemplate, serializer);
+sse_encode_bool(self.hasEmbeddedTemplate, serializer);
+sse_encode_model_kind(self.suggestedKind, serializer);
+ }
+
+@protected void sse_encode_model_kind(ModelKind self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.index, serializer); }
+
+@protected void sse_encode_model_state(ModelState self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+switch (self) { case ModelState_NotLoaded(): sse_encode_i_32(0, serializer); case ModelState_Loading(path: final path): sse_encode_i_32(1, serializer); sse_encode_String(path, serializer);
+case ModelState_Ready(path: final path,loadStrategy: final loadStrategy): sse_encode_i_32(2, serializer); sse_encode_String(path, serializer);
+sse_encode_load_strategy(loadStrategy, serializer);
+case ModelState_Failed(path: final path,code: final code,detail: final detail): sse_encode_i_32(3, serializer); sse_encode_String(path, serializer);
+sse_encode_String(code, serializer);
+sse_encode_String(detail, serializer);
+  } }
+
+@protected void sse_encode_model_status(ModelStatus self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_model_state(self.chat, serializer);
+sse_encode_model_state(self.embedding, serializer);
+sse_encode_model_state(self.reranker, serializer);
+ }
+
+@protected void sse_encode_opt_String(String? self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+                sse_encode_bool(self != null, serializer);
+                if (self != null) {
+                    sse_encode_String(self, serializer);
+                }
+                 }
+
+@protected void sse_encode_opt_box_autoadd_f_32(double? self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+                sse_encode_bool(self != null, serializer);
+                if (self != null) {
+                    sse_encode_box_autoadd_f_32(self, serializer);
+                }
+                 }
+
+@protected void sse_encode_opt_box_autoadd_sampling_params(SamplingParams? self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+                sse_encode_bool(self != null, serializer);
+                if (self != null) {
+                    sse_encode_box_autoadd_sampling_params(self, serializer);
+                }
+                 }
+
+@protected void sse_encode_opt_box_autoadd_u_32(int? self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+
+                sse_encode_bool(self != null, serializer);
+                if (self != null) {
+                    sse_encode_box_autoadd_u_32(self, serializer);
+                }
+                 }
+
+@protected void sse_encode_profile_trait(ProfileTrait self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_String(self.category, serializer);
+sse_encode_String(self.content, serializer);
+ }
+
+@protected void sse_encode_prompt_preview(PromptPreview self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_list_prompt_section_preview(self.sections, serializer);
+sse_encode_list_memory_match(self.memories, serializer);
+sse_encode_String(self.renderedPrompt, serializer);
+sse_encode_u_32(self.totalTokens, serializer);
+sse_encode_u_32(self.contextWindow, serializer);
+sse_encode_u_32(self.reservedForGeneration, serializer);
+ }
+
+@protected void sse_encode_prompt_section_preview(PromptSectionPreview self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_String(self.name, serializer);
+sse_encode_String(self.text, serializer);
+sse_encode_u_32(self.tokens, serializer);
+sse_encode_u_32(self.requestedTokens, serializer);
+sse_encode_u_32(self.itemsKept, serializer);
+sse_encode_u_32(self.itemsTotal, serializer);
+sse_encode_bool(self.truncated, serializer);
+ }
+
+@protected void sse_encode_queued_job(QueuedJob self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_u_64(self.id, serializer);
+sse_encode_job_priority(self.priority, serializer);
+sse_encode_u_32(self.position, serializer);
+ }
+
+@protected void sse_encode_reembed_event(ReembedEvent self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+switch (self) { case ReembedEvent_Progress(done: final done,total: final total): sse_encode_i_32(0, serializer); sse_encode_u_32(done, serializer);
+sse_encode_u_32(total, serializer);
+case ReembedEvent_Done(modelId: final modelId,reembedded: final reembedded): sse_encode_i_32(1, serializer); sse_encode_String(modelId, serializer);
+sse_encode_u_32(reembedded, serializer);
+case ReembedEvent_Error(code: final code,detail: final detail): sse_encode_i_32(2, serializer); sse_encode_String(code, serializer);
+sse_encode_String(detail, serializer);
+  } }
+
+@protected void sse_encode_runtime_settings(RuntimeSettings self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_bool(self.autoTune, serializer);
+sse_encode_u_32(self.nCtx, serializer);
+sse_encode_u_32(self.nBatch, serializer);
+sse_encode_u_32(self.nThreads, serializer);
+sse_encode_u_32(self.nThreadsBatch, serializer);
+ }
+
+@protected void sse_encode_sampling_params(SamplingParams self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_f_32(self.temperature, serializer);
+sse_encode_i_32(self.topK, serializer);
+sse_encode_f_32(self.topP, serializer);
+sse_encode_f_32(self.minP, serializer);
+sse_encode_f_32(self.repeatPenalty, serializer);
+sse_encode_i_32(self.repeatLastN, serializer);
+sse_encode_opt_box_autoadd_u_32(self.seed, serializer);
+sse_encode_u_8(self.mirostat, serializer);
+sse_encode_f_32(self.mirostatTau, serializer);
+sse_encode_f_32(self.mirostatEta, serializer);
+ }
+
+@protected void sse_encode_stop_reason(StopReason self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_i_32(self.index, serializer); }
+
+@protected void sse_encode_turn(Turn self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+sse_encode_String(self.role, serializer);
+sse_encode_String(self.content, serializer);
+ }
+
+@protected void sse_encode_u_32(int self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+serializer.buffer.putUint32(self); }
+
+@protected void sse_encode_u_64(BigInt self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+serializer.buffer.putBigUint64(self); }
+
+@protected void sse_encode_u_8(int self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+serializer.buffer.putUint8(self); }
+
+@protected void sse_encode_unit(void self, SseSerializer serializer){ // Codec=Sse (Serialization based), see doc to use other codecs
+ }
+                }
+                
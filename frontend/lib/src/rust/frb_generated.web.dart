@@ -3,192 +3,430 @@
 
 // ignore_for_file: unused_import, unused_element, unnecessary_import, duplicate_ignore, invalid_use_of_internal_member, annotate_overrides, non_constant_identifier_names, curly_braces_in_flow_control_structures, prefer_const_literals_to_create_immutables, unused_field
 
+
 // Static analysis wrongly picks the IO variant, thus ignore this
 // ignore_for_file: argument_type_not_assignable
 
+import 'ai.dart';
 import 'api/simple.dart';
+import 'backend.dart';
 import 'dart:async';
 import 'dart:convert';
 import 'db.dart';
+import 'error.dart';
 import 'frb_generated.dart';
+import 'job_queue.dart';
+import 'load_strategy.dart';
+import 'model_manager.dart';
+import 'model_registry.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated_web.dart';
+import 'reembed.dart';
+import 'runtime_config.dart';
+
+
+
+
+                abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
+                  RustLibApiImplPlatform({
+                    required super.handler,
+                    required super.wire,
+                    required super.generalizedFrbRustBinding,
+                    required super.portManager,
+                  });
+
+                  
+
+                  @protected AnyhowException dco_decode_AnyhowException(dynamic raw);
+
+@protected RustStreamSink<ChatEvent> dco_decode_StreamSink_chat_event_Sse(dynamic raw);
+
+@protected RustStreamSink<ReembedEvent> dco_decode_StreamSink_reembed_event_Sse(dynamic raw);
+
+@protected String dco_decode_String(dynamic raw);
+
+@protected AnimaError dco_decode_anima_error(dynamic raw);
+
+@protected BackendKind dco_decode_backend_kind(dynamic raw);
+
+@protected BackendSettings dco_decode_backend_settings(dynamic raw);
+
+@protected bool dco_decode_bool(dynamic raw);
+
+@protected BackendSettings dco_decode_box_autoadd_backend_settings(dynamic raw);
+
+@protected ChatRequest dco_decode_box_autoadd_chat_request(dynamic raw);
+
+@protected double dco_decode_box_autoadd_f_32(dynamic raw);
+
+@protected MemoryScoringWeights dco_decode_box_autoadd_memory_scoring_weights(dynamic raw);
+
+@protected RuntimeSettings dco_decode_box_autoadd_runtime_settings(dynamic raw);
+
+@protected SamplingParams dco_decode_box_autoadd_sampling_params(dynamic raw);
+
+@protected int dco_decode_box_autoadd_u_32(dynamic raw);
+
+@protected ChatCompletion dco_decode_chat_completion(dynamic raw);
+
+@protected ChatEvent dco_decode_chat_event(dynamic raw);
+
+@protected ChatMessage dco_decode_chat_message(dynamic raw);
+
+@protected ChatOptions dco_decode_chat_options(dynamic raw);
+
+@protected ChatRequest dco_decode_chat_request(dynamic raw);
+
+@protected ContextConfig dco_decode_context_config(dynamic raw);
+
+@protected EmbeddingStatus dco_decode_embedding_status(dynamic raw);
+
+@protected double dco_decode_f_32(dynamic raw);
+
+@protected double dco_decode_f_64(dynamic raw);
+
+@protected int dco_decode_i_32(dynamic raw);
+
+@protected PlatformInt64 dco_decode_i_64(dynamic raw);
+
+@protected InferenceQueueStatus dco_decode_inference_queue_status(dynamic raw);
+
+@protected JobPriority dco_decode_job_priority(dynamic raw);
+
+@protected List<ChatMessage> dco_decode_list_chat_message(dynamic raw);
+
+@protected List<MemoryItem> dco_decode_list_memory_item(dynamic raw);
+
+@protected List<MemoryMatch> dco_decode_list_memory_match(dynamic raw);
+
+@protected List<ModelInfo> dco_decode_list_model_info(dynamic raw);
+
+@protected Uint8List dco_decode_list_prim_u_8_strict(dynamic raw);
+
+@protected List<ProfileTrait> dco_decode_list_profile_trait(dynamic raw);
+
+@protected List<PromptSectionPreview> dco_decode_list_prompt_section_preview(dynamic raw);
+
+@protected List<QueuedJob> dco_decode_list_queued_job(dynamic raw);
+
+@protected List<Turn> dco_decode_list_turn(dynamic raw);
+
+@protected LoadStrategy dco_decode_load_strategy(dynamic raw);
+
+@protected MemoryItem dco_decode_memory_item(dynamic raw);
+
+@protected MemoryMatch dco_decode_memory_match(dynamic raw);
+
+@protected MemoryScore dco_decode_memory_score(dynamic raw);
+
+@protected MemoryScoringWeights dco_decode_memory_scoring_weights(dynamic raw);
+
+@protected ModelInfo dco_decode_model_info(dynamic raw);
+
+@protected ModelKind dco_decode_model_kind(dynamic raw);
+
+@protected ModelState dco_decode_model_state(dynamic raw);
+
+@protected ModelStatus dco_decode_model_status(dynamic raw);
+
+@protected String? dco_decode_opt_String(dynamic raw);
+
+@protected double? dco_decode_opt_box_autoadd_f_32(dynamic raw);
+
+@protected SamplingParams? dco_decode_opt_box_autoadd_sampling_params(dynamic raw);
+
+@protected int? dco_decode_opt_box_autoadd_u_32(dynamic raw);
+
+@protected ProfileTrait dco_decode_profile_trait(dynamic raw);
+
+@protected PromptPreview dco_decode_prompt_preview(dynamic raw);
+
+@protected PromptSectionPreview dco_decode_prompt_section_preview(dynamic raw);
+
+@protected QueuedJob dco_decode_queued_job(dynamic raw);
+
+@protected ReembedEvent dco_decode_reembed_event(dynamic raw);
+
+@protected RuntimeSettings dco_decode_runtime_settings(dynamic raw);
+
+@protected SamplingParams dco_decode_sampling_params(dynamic raw);
+
+@protected StopReason dco_decode_stop_reason(dynamic raw);
+
+@protected Turn dco_decode_turn(dynamic raw);
+
+@protected int dco_decode_u_32(dynamic raw);
+
+@protected BigInt dco_decode_u_64(dynamic raw);
+
+@protected int dco_decode_u_8(dynamic raw);
+
+@protected void dco_decode_unit(dynamic raw);
+
+@protected AnyhowException sse_decode_AnyhowException(SseDeserializer deserializer);
+
+@protected RustStreamSink<ChatEvent> sse_decode_StreamSink_chat_event_Sse(SseDeserializer deserializer);
+
+@protected RustStreamSink<ReembedEvent> sse_decode_StreamSink_reembed_event_Sse(SseDeserializer deserializer);
+
+@protected String sse_decode_String(SseDeserializer deserializer);
+
+@protected AnimaError sse_decode_anima_error(SseDeserializer deserializer);
+
+@protected BackendKind sse_decode_backend_kind(SseDeserializer deserializer);
+
+@protected BackendSettings sse_decode_backend_settings(SseDeserializer deserializer);
+
+@protected bool sse_decode_bool(SseDeserializer deserializer);
+
+@protected BackendSettings sse_decode_box_autoadd_backend_settings(SseDeserializer deserializer);
+
+@protected ChatRequest sse_decode_box_autoadd_chat_request(SseDeserializer deserializer);
+
+@protected double sse_decode_box_autoadd_f_32(SseDeserializer deserializer);
+
+@protected MemoryScoringWeights sse_decode_box_autoadd_memory_scoring_weights(SseDeserializer deserializer);
+
+@protected RuntimeSettings sse_decode_box_autoadd_runtime_settings(SseDeserializer deserializer);
+
+@protected SamplingParams sse_decode_box_autoadd_sampling_params(SseDeserializer deserializer);
+
+@protected int sse_decode_box_autoadd_u_32(SseDeserializer deserializer);
+
+@protected ChatCompletion sse_decode_chat_completion(SseDeserializer deserializer);
+
+@protected ChatEvent sse_decode_chat_event(SseDeserializer deserializer);
+
+@protected ChatMessage sse_decode_chat_message(SseDeserializer deserializer);
+
+@protected ChatOptions sse_decode_chat_options(SseDeserializer deserializer);
+
+@protected ChatRequest sse_decode_chat_request(SseDeserializer deserializer);
+
+@protected ContextConfig sse_decode_context_config(SseDeserializer deserializer);
+
+@protected EmbeddingStatus sse_decode_embedding_status(SseDeserializer deserializer);
+
+@protected double sse_decode_f_32(SseDeserializer deserializer);
+
+@protected double sse_decode_f_64(SseDeserializer deserializer);
+
+@protected int sse_decode_i_32(SseDeserializer deserializer);
+
+@protected PlatformInt64 sse_decode_i_64(SseDeserializer deserializer);
+
+@protected InferenceQueueStatus sse_decode_inference_queue_status(SseDeserializer deserializer);
+
+@protected JobPriority sse_decode_job_priority(SseDeserializer deserializer);
+
+@protected List<ChatMessage> sse_decode_list_chat_message(SseDeserializer deserializer);
+
+@protected List<MemoryItem> sse_decode_list_memory_item(SseDeserializer deserializer);
+
+@protected List<MemoryMatch> sse_decode_list_memory_match(SseDeserializer deserializer);
+
+@protected List<ModelInfo> sse_decode_list_model_info(SseDeserializer deserializer);
+
+@protected Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer);
+
+@protected List<ProfileTrait> sse_decode_list_profile_trait(SseDeserializer deserializer);
+
+@protected List<PromptSectionPreview> sse_decode_list_prompt_section_preview(SseDeserializer deserializer);
+
+@protected List<QueuedJob> sse_decode_list_queued_job(SseDeserializer deserializer);
+
+@protected List<Turn> sse_decode_list_turn(SseDeserializer deserializer);
+
+@protected LoadStrategy sse_decode_load_strategy(SseDeserializer deserializer);
+
+@protected MemoryItem sse_decode_memory_item(SseDeserializer deserializer);
+
+@protected MemoryMatch sse_decode_memory_match(SseDeserializer deserializer);
+
+@protected MemoryScore sse_decode_memory_score(SseDeserializer deserializer);
+
+@protected MemoryScoringWeights sse_decode_memory_scoring_weights(SseDeserializer deserializer);
+
+@protected ModelInfo sse_decode_model_info(SseDeserializer deserializer);
+
+@protected ModelKind sse_decode_model_kind(SseDeserializer deserializer);
+
+@protected ModelState sse_decode_model_state(SseDeserializer deserializer);
+
+@protected ModelStatus sse_decode_model_status(SseDeserializer deserializer);
+
+@protected String? sse_decode_opt_String(SseDeserializer deserializer);
+
+@protected double? sse_decode_opt_box_autoadd_f_32(SseDeserializer deserializer);
+
+@protected SamplingParams? sse_decode_opt_box_autoadd_sampling_params(SseDeserializer deserializer);
+
+@protected int? sse_decode_opt_box_autoadd_u_32(SseDeserializer deserializer);
+
+@protected ProfileTrait sse_decode_profile_trait(SseDeserializer deserializer);
+
+@protected PromptPreview sse_decode_prompt_preview(SseDeserializer deserializer);
+
+@protected PromptSectionPreview sse_decode_prompt_section_preview(SseDeserializer deserializer);
+
+@protected QueuedJob sse_decode_queued_job(SseDeserializer deserializer);
+
+@protected ReembedEvent sse_decode_reembed_event(SseDeserializer deserializer);
+
+@protected RuntimeSettings sse_decode_runtime_settings(SseDeserializer deserializer);
+
+@protected SamplingParams sse_decode_sampling_params(SseDeserializer deserializer);
+
+@protected StopReason sse_decode_stop_reason(SseDeserializer deserializer);
+
+@protected Turn sse_decode_turn(SseDeserializer deserializer);
+
+@protected int sse_decode_u_32(SseDeserializer deserializer);
+
+@protected BigInt sse_decode_u_64(SseDeserializer deserializer);
+
+@protected int sse_decode_u_8(SseDeserializer deserializer);
+
+@protected void sse_decode_unit(SseDeserializer deserializer);
+
+@protected void sse_encode_AnyhowException(AnyhowException self, SseSerializer serializer);
+
+@protected void sse_encode_StreamSink_chat_event_Sse(RustStreamSink<ChatEvent> self, SseSerializer serializer);
+
+@protected void sse_encode_StreamSink_reembed_event_Sse(RustStreamSink<ReembedEvent> self, SseSerializer serializer);
+
+@protected void sse_encode_String(String self, SseSerializer serializer);
+
+@protected void sse_encode_anima_error(AnimaError self, SseSerializer serializer);
+
+@protected void sse_encode_backend_kind(BackendKind self, SseSerializer serializer);
+
+@protected void sse_encode_backend_settings(BackendSettings self, SseSerializer serializer);
+
+@protected void sse_encode_bool(bool self, SseSerializer serializer);
+
+@protected void sse_encode_box_autoadd_backend_settings(BackendSettings self, SseSerializer serializer);
+
+@protected void sse_encode_box_autoadd_chat_request(ChatRequest self, SseSerializer serializer);
+
+@protected void sse_encode_box_autoadd_f_32(double self, SseSerializer serializer);
+
+@protected void sse_encode_box_autoadd_memory_scoring_weights(MemoryScoringWeights self, SseSerializer serializer);
+
+@protected void sse_encode_box_autoadd_runtime_settings(RuntimeSettings self, SseSerializer serializer);
 
-abstract class RustLibApiImplPlatform extends BaseApiImpl<RustLibWire> {
-  RustLibApiImplPlatform({
-    required super.handler,
-    required super.wire,
-    required super.generalizedFrbRustBinding,
-    required super.portManager,
-  });
+@protected void sse_encode_box_autoadd_sampling_params(SamplingParams self, SseSerializer serializer);
 
-  @protected
-  AnyhowException dco_decode_AnyhowException(dynamic raw);
+@protected void sse_encode_box_autoadd_u_32(int self, SseSerializer serializer);
 
-  @protected
-  RustStreamSink<String> dco_decode_StreamSink_String_Sse(dynamic raw);
+@protected void sse_encode_chat_completion(ChatCompletion self, SseSerializer serializer);
 
-  @protected
-  String dco_decode_String(dynamic raw);
+@protected void sse_encode_chat_event(ChatEvent self, SseSerializer serializer);
 
-  @protected
-  bool dco_decode_bool(dynamic raw);
+@protected void sse_encode_chat_message(ChatMessage self, SseSerializer serializer);
 
-  @protected
-  ChatMessage dco_decode_chat_message(dynamic raw);
+@protected void sse_encode_chat_options(ChatOptions self, SseSerializer serializer);
 
-  @protected
-  double dco_decode_f_32(dynamic raw);
+@protected void sse_encode_chat_request(ChatRequest self, SseSerializer serializer);
 
-  @protected
-  PlatformInt64 dco_decode_i_64(dynamic raw);
+@protected void sse_encode_context_config(ContextConfig self, SseSerializer serializer);
 
-  @protected
-  List<ChatMessage> dco_decode_list_chat_message(dynamic raw);
+@protected void sse_encode_embedding_status(EmbeddingStatus self, SseSerializer serializer);
 
-  @protected
-  List<MemoryItem> dco_decode_list_memory_item(dynamic raw);
+@protected void sse_encode_f_32(double self, SseSerializer serializer);
 
-  @protected
-  Uint8List dco_decode_list_prim_u_8_strict(dynamic raw);
+@protected void sse_encode_f_64(double self, SseSerializer serializer);
 
-  @protected
-  List<ProfileTrait> dco_decode_list_profile_trait(dynamic raw);
+@protected void sse_encode_i_32(int self, SseSerializer serializer);
 
-  @protected
-  MemoryItem dco_decode_memory_item(dynamic raw);
+@protected void sse_encode_i_64(PlatformInt64 self, SseSerializer serializer);
 
-  @protected
-  ProfileTrait dco_decode_profile_trait(dynamic raw);
+@protected void sse_encode_inference_queue_status(InferenceQueueStatus self, SseSerializer serializer);
 
-  @protected
-  int dco_decode_u_32(dynamic raw);
+@protected void sse_encode_job_priority(JobPriority self, SseSerializer serializer);
 
-  @protected
-  int dco_decode_u_8(dynamic raw);
+@protected void sse_encode_list_chat_message(List<ChatMessage> self, SseSerializer serializer);
 
-  @protected
-  void dco_decode_unit(dynamic raw);
+@protected void sse_encode_list_memory_item(List<MemoryItem> self, SseSerializer serializer);
 
-  @protected
-  AnyhowException sse_decode_AnyhowException(SseDeserializer deserializer);
+@protected void sse_encode_list_memory_match(List<MemoryMatch> self, SseSerializer serializer);
 
-  @protected
-  RustStreamSink<String> sse_decode_StreamSink_String_Sse(
-      SseDeserializer deserializer);
+@protected void sse_encode_list_model_info(List<ModelInfo> self, SseSerializer serializer);
 
-  @protected
-  String sse_decode_String(SseDeserializer deserializer);
+@protected void sse_encode_list_prim_u_8_strict(Uint8List self, SseSerializer serializer);
 
-  @protected
-  bool sse_decode_bool(SseDeserializer deserializer);
+@protected void sse_encode_list_profile_trait(List<ProfileTrait> self, SseSerializer serializer);
 
-  @protected
-  ChatMessage sse_decode_chat_message(SseDeserializer deserializer);
+@protected void sse_encode_list_prompt_section_preview(List<PromptSectionPreview> self, SseSerializer serializer);
 
-  @protected
-  double sse_decode_f_32(SseDeserializer deserializer);
+@protected void sse_encode_list_queued_job(List<QueuedJob> self, SseSerializer serializer);
 
-  @protected
-  PlatformInt64 sse_decode_i_64(SseDeserializer deserializer);
+@protected void sse_encode_list_turn(List<Turn> self, SseSerializer serializer);
 
-  @protected
-  List<ChatMessage> sse_decode_list_chat_message(SseDeserializer deserializer);
+@protected void sse_encode_load_strategy(LoadStrategy self, SseSerializer serializer);
 
-  @protected
-  List<MemoryItem> sse_decode_list_memory_item(SseDeserializer deserializer);
+@protected void sse_encode_memory_item(MemoryItem self, SseSerializer serializer);
 
-  @protected
-  Uint8List sse_decode_list_prim_u_8_strict(SseDeserializer deserializer);
+@protected void sse_encode_memory_match(MemoryMatch self, SseSerializer serializer);
 
-  @protected
-  List<ProfileTrait> sse_decode_list_profile_trait(
-      SseDeserializer deserializer);
+@protected void sse_encode_memory_score(MemoryScore self, SseSerializer serializer);
 
-  @protected
-  MemoryItem sse_decode_memory_item(SseDeserializer deserializer);
+@protected void sse_encode_memory_scoring_weights(MemoryScoringWeights self, SseSerializer serializer);
 
-  @protected
-  ProfileTrait sse_decode_profile_trait(SseDeserializer deserializer);
+@protected void sse_encode_model_info(ModelInfo self, SseSerializer serializer);
 
-  @protected
-  int sse_decode_u_32(SseDeserializer deserializer);
+@protected void sse_encode_model_kind(ModelKind self, SseSerializer serializer);
 
-  @protected
-  int sse_decode_u_8(SseDeserializer deserializer);
+@protected void sse_encode_model_state(ModelState self, SseSerializer serializer);
 
-  @protected
-  void sse_decode_unit(SseDeserializer deserializer);
+@protected void sse_encode_model_status(ModelStatus self, SseSerializer serializer);
 
-  @protected
-  int sse_decode_i_32(SseDeserializer deserializer);
+@protected void sse_encode_opt_String(String? self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_AnyhowException(
-      AnyhowException self, SseSerializer serializer);
+@protected void sse_encode_opt_box_autoadd_f_32(double? self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_StreamSink_String_Sse(
-      RustStreamSink<String> self, SseSerializer serializer);
+@protected void sse_encode_opt_box_autoadd_sampling_params(SamplingParams? self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_String(String self, SseSerializer serializer);
+@protected void sse_encode_opt_box_autoadd_u_32(int? self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_bool(bool self, SseSerializer serializer);
+@protected void sse_encode_profile_trait(ProfileTrait self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_chat_message(ChatMessage self, SseSerializer serializer);
+@protected void sse_encode_prompt_preview(PromptPreview self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_f_32(double self, SseSerializer serializer);
+@protected void sse_encode_prompt_section_preview(PromptSectionPreview self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_i_64(PlatformInt64 self, SseSerializer serializer);
+@protected void sse_encode_queued_job(QueuedJob self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_list_chat_message(
-      List<ChatMessage> self, SseSerializer serializer);
+@protected void sse_encode_reembed_event(ReembedEvent self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_list_memory_item(
-      List<MemoryItem> self, SseSerializer serializer);
+@protected void sse_encode_runtime_settings(RuntimeSettings self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_list_prim_u_8_strict(
-      Uint8List self, SseSerializer serializer);
+@protected void sse_encode_sampling_params(SamplingParams self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_list_profile_trait(
-      List<ProfileTrait> self, SseSerializer serializer);
+@protected void sse_encode_stop_reason(StopReason self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_memory_item(MemoryItem self, SseSerializer serializer);
+@protected void sse_encode_turn(Turn self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_profile_trait(ProfileTrait self, SseSerializer serializer);
+@protected void sse_encode_u_32(int self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_u_32(int self, SseSerializer serializer);
+@protected void sse_encode_u_64(BigInt self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_u_8(int self, SseSerializer serializer);
+@protected void sse_encode_u_8(int self, SseSerializer serializer);
 
-  @protected
-  void sse_encode_unit(void self, SseSerializer serializer);
+@protected void sse_encode_unit(void self, SseSerializer serializer);
+                }
+                
 
-  @protected
-  void sse_encode_i_32(int self, SseSerializer serializer);
-}
 
 // Section: wire_class
 
 class RustLibWire implements BaseWire {
-  RustLibWire.fromExternalLibrary(ExternalLibrary lib);
-}
+            RustLibWire.fromExternalLibrary(ExternalLibrary lib);
 
-@JS('wasm_bindgen')
-external RustLibWasmModule get wasmModule;
+            
+        }
+        @JS('wasm_bindgen') external RustLibWasmModule get wasmModule;
 
-@JS()
-@anonymous
-extension type RustLibWasmModule._(JSObject _) implements JSObject {}
+        @JS() @anonymous extension type RustLibWasmModule._(JSObject _) implements JSObject {
+            
+        }
+        
\ No newline at end of file
@@ -0,0 +1,52 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import 'frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+
+
+            
+
+            
+
+            /// Order in which a worker picks up queued jobs, most urgent first.
+enum JobPriority {
+                    /// A chat reply the user is waiting for, or the embedding of their message.
+interactive,
+greeting,
+/// Re-embedding memories after an embedding model change.
+embeddingBackfill,
+sleepCycle,
+                    ;
+                    
+                }
+
+/// One job as the UI sees it.
+class QueuedJob  {
+                final BigInt id;
+final JobPriority priority;
+/// 0 for the running job, then 1, 2, ... in pickup order.
+final int position;
+
+                const QueuedJob({required this.id ,required this.priority ,required this.position ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => id.hashCode^priority.hashCode^position.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is QueuedJob &&
+                runtimeType == other.runtimeType
+                && id == other.id&& priority == other.priority&& position == other.position;
+        
+            }
+            
\ No newline at end of file
@@ -0,0 +1,27 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import 'frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+
+
+            
+
+            
+
+            /// How model weights get into memory.
+enum LoadStrategy {
+                    /// Checks free RAM, then memory-maps on Unix and reads into RAM elsewhere.
+auto,
+/// Memory-mapped: fast start, pages are loaded on demand.
+mmap,
+/// Read fully into RAM before the first token.
+ram,
+/// Memory-mapped and locked so the OS cannot page the weights out.
+mlock,
+                    ;
+                    
+                }
+            
\ No newline at end of file
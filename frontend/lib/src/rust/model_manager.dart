@@ -0,0 +1,92 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import 'frb_generated.dart';
+import 'job_queue.dart';
+import 'load_strategy.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+import 'package:freezed_annotation/freezed_annotation.dart' hide protected;
+part 'model_manager.freezed.dart';
+
+            
+
+            
+
+            /// Jobs waiting on or running in each worker, in pickup order.
+class InferenceQueueStatus  {
+                final List<QueuedJob> chat;
+final List<QueuedJob> embedding;
+final List<QueuedJob> reranker;
+
+                const InferenceQueueStatus({required this.chat ,required this.embedding ,required this.reranker ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => chat.hashCode^embedding.hashCode^reranker.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is InferenceQueueStatus &&
+                runtimeType == other.runtimeType
+                && chat == other.chat&& embedding == other.embedding&& reranker == other.reranker;
+        
+            }
+
+enum ModelKind {
+                    chat,
+embedding,
+/// Optional cross-encoder that reorders retrieved memories.
+reranker,
+                    ;
+                    
+                }
+
+@freezed
+                sealed class ModelState with _$ModelState  {
+                    const ModelState._();
+
+                     const factory ModelState.notLoaded() = ModelState_NotLoaded;
+ const factory ModelState.loading({   required String path , }) = ModelState_Loading;
+ /// `load_strategy` is how the weights were actually loaded, never `Auto`.
+const factory ModelState.ready({   required String path ,  required LoadStrategy loadStrategy , }) = ModelState_Ready;
+ /// `code` is `AnimaError::code()` of the load failure.
+const factory ModelState.failed({   required String path ,  required String code ,  required String detail , }) = ModelState_Failed;
+
+                    
+
+                    
+                }
+
+class ModelStatus  {
+                final ModelState chat;
+final ModelState embedding;
+final ModelState reranker;
+
+                const ModelStatus({required this.chat ,required this.embedding ,required this.reranker ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => chat.hashCode^embedding.hashCode^reranker.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is ModelStatus &&
+                runtimeType == other.runtimeType
+                && chat == other.chat&& embedding == other.embedding&& reranker == other.reranker;
+        
+            }
+            
\ No newline at end of file
@@ -0,0 +1,51 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import 'frb_generated.dart';
+import 'model_manager.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+
+
+            
+
+            
+
+            /// A GGUF file found in the models folder, described from its header alone.
+class ModelInfo  {
+                /// Path relative to the models folder, with `/` separators.
+final String id;
+final String path;
+final BigInt sizeBytes;
+final String architecture;
+final BigInt parameterCount;
+final String quantization;
+final int? contextLength;
+final int? embeddingLength;
+/// Prompt format the chat runtime would use for this model.
+final String chatTemplate;
+final bool hasEmbeddedTemplate;
+/// Best guess from the architecture and pooling metadata.
+final ModelKind suggestedKind;
+
+                const ModelInfo({required this.id ,required this.path ,required this.sizeBytes ,required this.architecture ,required this.parameterCount ,required this.quantization ,this.contextLength ,this.embeddingLength ,required this.chatTemplate ,required this.hasEmbeddedTemplate ,required this.suggestedKind ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => id.hashCode^path.hashCode^sizeBytes.hashCode^architecture.hashCode^parameterCount.hashCode^quantization.hashCode^contextLength.hashCode^embeddingLength.hashCode^chatTemplate.hashCode^hasEmbeddedTemplate.hashCode^suggestedKind.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is ModelInfo &&
+                runtimeType == other.runtimeType
+                && id == other.id&& path == other.path&& sizeBytes == other.sizeBytes&& architecture == other.architecture&& parameterCount == other.parameterCount&& quantization == other.quantization&& contextLength == other.contextLength&& embeddingLength == other.embeddingLength&& chatTemplate == other.chatTemplate&& hasEmbeddedTemplate == other.hasEmbeddedTemplate&& suggestedKind == other.suggestedKind;
+        
+            }
+            
\ No newline at end of file
@@ -0,0 +1,40 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import 'frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+
+
+            
+
+            
+
+            /// How many memories were embedded by the loaded embedding model.
+class EmbeddingStatus  {
+                final String modelId;
+final int totalMemories;
+/// Memories from another model, or from before models were recorded.
+final int staleMemories;
+
+                const EmbeddingStatus({required this.modelId ,required this.totalMemories ,required this.staleMemories ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => modelId.hashCode^totalMemories.hashCode^staleMemories.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is EmbeddingStatus &&
+                runtimeType == other.runtimeType
+                && modelId == other.modelId&& totalMemories == other.totalMemories&& staleMemories == other.staleMemories;
+        
+            }
+            
\ No newline at end of file
@@ -0,0 +1,69 @@
+// This file is automatically generated, so please do not edit it.
+// @generated by `flutter_rust_bridge`@ 2.11.1.
+
+// ignore_for_file: invalid_use_of_internal_member, unused_import, unnecessary_import
+
+import 'frb_generated.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+
+
+            
+
+            
+
+            /// Values a context is actually created with.
+class ContextConfig  {
+                final int nCtx;
+final int nBatch;
+final int nThreads;
+final int nThreadsBatch;
+
+                const ContextConfig({required this.nCtx ,required this.nBatch ,required this.nThreads ,required this.nThreadsBatch ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => nCtx.hashCode^nBatch.hashCode^nThreads.hashCode^nThreadsBatch.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is ContextConfig &&
+                runtimeType == other.runtimeType
+                && nCtx == other.nCtx&& nBatch == other.nBatch&& nThreads == other.nThreads&& nThreadsBatch == other.nThreadsBatch;
+        
+            }
+
+/// Persisted context and thread settings. With `auto_tune` on, the other
+/// fields are ignored and picked from the model and the hardware instead.
+class RuntimeSettings  {
+                final bool autoTune;
+final int nCtx;
+final int nBatch;
+final int nThreads;
+final int nThreadsBatch;
+
+                const RuntimeSettings({required this.autoTune ,required this.nCtx ,required this.nBatch ,required this.nThreads ,required this.nThreadsBatch ,});
+
+                
+                
+
+                
+        @override
+        int get hashCode => autoTune.hashCode^nCtx.hashCode^nBatch.hashCode^nThreads.hashCode^nThreadsBatch.hashCode;
+        
+
+                
+        @override
+        bool operator ==(Object other) =>
+            identical(this, other) ||
+            other is RuntimeSettings &&
+                runtimeType == other.runtimeType
+                && autoTune == other.autoTune&& nCtx == other.nCtx&& nBatch == other.nBatch&& nThreads == other.nThreads&& nThreadsBatch == other.nThreadsBatch;
+        
+            }
+            
\ No newline at end of file
@@ -1,164 +1,326 @@
-use crate::db;
+use crate::backend::{self, GenerationRequest, InferenceBackend};
+use crate::cancellation::CancellationToken;
+use crate::chat_template::{ChatTemplate, ChatTurn};
+use crate::db::{self, SamplingParams};
+use crate::embedding_batch;
+use crate::error::AnimaError;
+use crate::gguf::GgufHeader;
+use crate::job_queue::{JobPriority, JobQueue};
+use crate::json_grammar;
+use crate::load_strategy::{self, LoadPlan, LoadStrategy};
+use crate::memory_index;
+use crate::model_manager::{self, ChatJob, EmbeddingJob, RerankJob};
+use crate::output_filter::OutputFilter;
+use crate::prompt_budget::{self, AssembledPrompt, PromptBudget, PromptParts};
+use crate::prompt_cache::{self, PromptPrefixCache};
+use crate::runtime_config::ContextConfig;
 use chrono::Local;
 use llama_cpp_2::context::params::{LlamaContextParams, LlamaPoolingType};
+use llama_cpp_2::context::LlamaContext;
 use llama_cpp_2::llama_backend::LlamaBackend;
 use llama_cpp_2::llama_batch::LlamaBatch;
 use llama_cpp_2::model::{AddBos, LlamaModel};
 use llama_cpp_2::sampling::LlamaSampler;
-use regex::Regex;
+use llama_cpp_2::token::LlamaToken;
 use serde_json::{json, Value};
-use std::fs::OpenOptions;
-use std::ffi::CString;
 use std::collections::HashSet;
+use std::ffi::CString;
+use std::fs::OpenOptions;
 use std::io::Write;
 use std::num::NonZeroU32;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::Path;
 use std::ptr::NonNull;
-use std::sync::{Mutex, OnceLock};
-use std::time::{SystemTime, UNIX_EPOCH};
-
-const DEFAULT_N_CTX: u32 = 2048;
-const SAFE_N_BATCH: u32 = 512;
-const SAFE_N_THREADS: i32 = 4;
-const REPEAT_PENALTY: f32 = 1.20;
-const REPEAT_LAST_N: i32 = 128;
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Cap on chat replies. Structured output keeps the caller's `max_tokens`,
+/// since a JSON document cut short cannot be parsed.
 const MAX_GENERATION_TOKENS: u32 = 512;
-const STOP_SEQUENCES: [&str; 6] = [
-    "\nAlex:",
-    "\nUser:",
-    "<|im_end|>",
-    "<|im_start|>",
-    "<|eot_id|>",
-    "<|end|>",
-];
+/// Sequences packed into one embedding batch.
+const EMBEDDING_MAX_SEQUENCES: usize = 16;
+/// (query, document) pairs scored in one reranker batch.
+const RERANK_MAX_SEQUENCES: usize = 16;
+/// Bounds how long a background turn keeps restarting while the user chats.
+const MAX_PREEMPTED_RETRIES: u32 = 8;
 const SUBCONSCIOUS_SYSTEM_PROMPT: &str = r#"Analyze the conversation and extract information strictly in JSON format with two keys:
 
 "semantic": Array of strings containing timeless facts, personality traits, rules, fears, and core identity.
 
-"episodic": Array of strings containing daily events, meals, mood, specific tasks done today, or chronological events.
+"episodic": Array of objects for daily events, meals, mood, specific tasks done today, or chronological events. Each object has "text", the event as a string, and "importance", an integer from 1 (routine detail) to 5 (life event the user will care about for years).
 
-Output only valid JSON, with exactly those two keys and string arrays. Do not include markdown, comments, or extra text."#;
+Output only valid JSON, with exactly those two keys. Do not include markdown, comments, or extra text."#;
 
-struct ChatRuntime {
-    model: &'static LlamaModel,
-    context: llama_cpp_2::context::LlamaContext<'static>,
+/// Why the decode loop ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    EndOfTurn,
+    StopSequence,
+    MaxTokens,
+    Cancelled,
 }
 
-unsafe impl Send for ChatRuntime {}
+#[derive(Debug, Clone)]
+pub struct Generation {
+    pub text: String,
+    pub stop_reason: StopReason,
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub decode_duration: Duration,
+}
 
-struct EmbeddingRuntime {
-    model: LlamaModel,
+impl Generation {
+    fn empty(stop_reason: StopReason, prompt_tokens: usize) -> Self {
+        Self {
+            text: String::new(),
+            stop_reason,
+            prompt_tokens: u32::try_from(prompt_tokens).unwrap_or(u32::MAX),
+            completion_tokens: 0,
+            decode_duration: Duration::ZERO,
+        }
+    }
+
+    pub fn tokens_per_sec(&self) -> f64 {
+        let seconds = self.decode_duration.as_secs_f64();
+        if seconds <= f64::EPSILON {
+            return 0.0;
+        }
+        f64::from(self.completion_tokens) / seconds
+    }
 }
 
-static LLAMA_BACKEND: OnceLock<Mutex<LlamaBackend>> = OnceLock::new();
-static CHAT_RUNTIME: OnceLock<Mutex<ChatRuntime>> = OnceLock::new();
-static EMBEDDING_RUNTIME: OnceLock<Mutex<EmbeddingRuntime>> = OnceLock::new();
-static PROMPT_LEAK_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+/// A chat prompt as it would be sent to the model.
+#[derive(Debug, Clone)]
+pub struct RenderedPrompt {
+    pub assembled: AssembledPrompt,
+    pub text: String,
+    pub token_count: usize,
+}
 
-pub fn init_ai_models(chat_model_path: &str, embedding_model_path: &str) -> Result<(), String> {
-    let backend_lock = get_or_init_backend()?;
-    let backend = backend_lock
-        .lock()
-        .map_err(|error| format!("Llama backend mutex is poisoned: {error}"))?;
+/// Chat model state owned by the chat worker thread; see `model_manager`.
+pub(crate) struct ChatRuntime<'m> {
+    model: &'m LlamaModel,
+    context: LlamaContext<'m>,
+    template: ChatTemplate,
+    config: ContextConfig,
+    /// System-prompt prefix still resident in the KV cache from the last turn.
+    prefix_cache: Option<PromptPrefixCache<LlamaToken>>,
+}
 
-    init_chat_model(&backend, chat_model_path)?;
-    init_embedding_model(&backend, embedding_model_path)?;
-    Ok(())
+/// Embedding model state owned by the embedding worker thread.
+pub(crate) struct EmbeddingRuntime<'m> {
+    model: &'m LlamaModel,
+    context: LlamaContext<'m>,
+    config: ContextConfig,
+}
+
+/// Reranker model state owned by the reranker worker thread.
+pub(crate) struct RerankerRuntime<'m> {
+    model: &'m LlamaModel,
+    context: LlamaContext<'m>,
+    config: ContextConfig,
+    tokens: RerankTokens,
+}
+
+/// Special tokens joining a query and a document into one reranker input,
+/// read from the GGUF tokenizer metadata.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct RerankTokens {
+    bos: Option<LlamaToken>,
+    separator: Option<LlamaToken>,
 }
 
-fn get_or_init_backend() -> Result<&'static Mutex<LlamaBackend>, String> {
+impl RerankTokens {
+    pub(crate) fn from_header(header: &GgufHeader) -> Self {
+        let token = |key: &str| {
+            header
+                .get_u64(key)
+                .and_then(|id| i32::try_from(id).ok())
+                .map(LlamaToken)
+        };
+        let add_separator = header.get_bool("tokenizer.ggml.add_sep_token") != Some(false);
+        Self {
+            bos: token("tokenizer.ggml.bos_token_id"),
+            // GGUF spells the key this way.
+            separator: token("tokenizer.ggml.seperator_token_id").filter(|_| add_separator),
+        }
+    }
+}
+
+enum ChatWorkerEvent {
+    Chunk(String),
+    Finished(Result<Generation, AnimaError>),
+}
+
+static LLAMA_BACKEND: OnceLock<Mutex<LlamaBackend>> = OnceLock::new();
+
+fn get_or_init_backend() -> Result<&'static Mutex<LlamaBackend>, AnimaError> {
     if LLAMA_BACKEND.get().is_none() {
         let backend =
             LlamaBackend::init().map_err(|error| {
                 log_debug_error("backend_init", &error);
-                format!("Backend init failed: {error}")
+                AnimaError::ModelLoadFailed(format!("Backend init failed: {error}"))
             })?;
         let _ = LLAMA_BACKEND.set(Mutex::new(backend));
     }
 
     LLAMA_BACKEND
         .get()
-        .ok_or_else(|| "Llama backend is not initialized".to_string())
+        .ok_or_else(|| AnimaError::ModelNotLoaded("Llama backend is not initialized".to_string()))
 }
 
-fn init_chat_model(backend: &LlamaBackend, model_path: &str) -> Result<(), String> {
-    if CHAT_RUNTIME.get().is_some() {
-        return Ok(());
+/// Body of the chat worker thread. The context borrows `model`, so it is
+/// created here and never leaves this thread; jobs run in priority order
+/// until the queue is closed.
+pub(crate) fn run_chat_worker(
+    model: &LlamaModel,
+    template: ChatTemplate,
+    config: ContextConfig,
+    jobs: Arc<JobQueue<ChatJob>>,
+    ready: mpsc::Sender<Result<(), AnimaError>>,
+) {
+    let context = match create_chat_context(model, &config) {
+        Ok(context) => context,
+        Err(error) => {
+            let _ = ready.send(Err(error));
+            return;
+        }
+    };
+    let _ = ready.send(Ok(()));
+
+    let mut runtime = ChatRuntime {
+        model,
+        context,
+        template,
+        config,
+        prefix_cache: None,
+    };
+    while let Some(job) = jobs.next() {
+        if panic::catch_unwind(AssertUnwindSafe(|| job(&mut runtime))).is_err() {
+            append_hard_debug("chat_worker", "Chat job panicked; KV cache reset");
+            runtime.context.clear_kv_cache();
+            runtime.prefix_cache = None;
+        }
     }
+}
 
-    let model_file = Path::new(model_path);
-    if !model_file.exists() {
-        return Err(format!(
-            "Model file not found exactly at: {}",
-            model_file.display()
-        ));
-    }
+fn create_chat_context<'m>(
+    model: &'m LlamaModel,
+    config: &ContextConfig,
+) -> Result<LlamaContext<'m>, AnimaError> {
+    let backend_lock = get_or_init_backend()?;
+    let backend = backend_lock
+        .lock()
+        .map_err(|error| AnimaError::Internal(format!("Llama backend mutex is poisoned: {error}")))?;
 
-    let model = load_model_force_no_mmap(model_file)?;
-    let leaked_model: &'static LlamaModel = Box::leak(Box::new(model));
-
-    let context_params = LlamaContextParams::default()
-        .with_n_ctx(NonZeroU32::new(DEFAULT_N_CTX))
-        .with_n_batch(SAFE_N_BATCH)
-        .with_n_ubatch(SAFE_N_BATCH)
-        .with_n_threads(SAFE_N_THREADS)
-        .with_n_threads_batch(SAFE_N_THREADS);
-    let context = leaked_model
-        .new_context(backend, context_params)
-        .map_err(|error| format!("Context creation failed: {error}"))?;
-
-    CHAT_RUNTIME
-        .set(Mutex::new(ChatRuntime {
-            model: leaked_model,
-            context,
-        }))
-        .map_err(|_| "AI runtime was already initialized (OnceLock::set failed)".to_string())
-}
-
-fn init_embedding_model(_backend: &LlamaBackend, model_path: &str) -> Result<(), String> {
-    if EMBEDDING_RUNTIME.get().is_some() {
-        return Ok(());
-    }
+    append_hard_debug("chat_context", &config.summary());
+    model
+        .new_context(&backend, context_params(config))
+        .map_err(|error| AnimaError::ModelLoadFailed(format!("Context creation failed: {error}")))
+}
 
-    let model_file = Path::new(model_path);
-    if !model_file.exists() {
-        return Err(format!(
-            "Model file not found exactly at: {}",
-            model_file.display()
-        ));
-    }
+fn context_params(config: &ContextConfig) -> LlamaContextParams {
+    let n_threads = i32::try_from(config.n_threads).unwrap_or(1);
+    let n_threads_batch = i32::try_from(config.n_threads_batch).unwrap_or(1);
+    LlamaContextParams::default()
+        .with_n_ctx(NonZeroU32::new(config.n_ctx))
+        .with_n_batch(config.n_batch)
+        .with_n_ubatch(config.n_batch)
+        .with_n_threads(n_threads)
+        .with_n_threads_batch(n_threads_batch)
+}
 
-    let model = load_model_force_no_mmap(model_file)
-        .map_err(|error| format!("Embedding model load failed: {error}"))?;
+pub(crate) fn detect_chat_template(header: &GgufHeader) -> ChatTemplate {
+    let embedded = header.chat_template();
+    let architecture = header.architecture();
+    let template = ChatTemplate::detect(embedded, &header.chat_marker_tokens, architecture);
+    append_hard_debug(
+        "chat_template",
+        &format!(
+            "selected={} embedded={} markers={:?} architecture={}",
+            template.name(),
+            embedded.is_some(),
+            header.chat_marker_tokens,
+            architecture.unwrap_or("unknown")
+        ),
+    );
+    template
+}
 
-    EMBEDDING_RUNTIME
-        .set(Mutex::new(EmbeddingRuntime { model }))
-        .map_err(|_| {
-            "Embedding runtime was already initialized (OnceLock::set failed)".to_string()
-        })
+/// Applies the persisted load strategy to the file; `header` comes from
+/// validating the same file, `n_ctx` sizes the KV cache in the memory check
+/// and `reclaimable_bytes` is what unloading the model it replaces frees.
+pub(crate) fn plan_model_load(
+    model_path: &str,
+    header: &GgufHeader,
+    n_ctx: u32,
+    reclaimable_bytes: u64,
+) -> Result<LoadPlan, AnimaError> {
+    let model_file = Path::new(model_path);
+    let file_len = std::fs::metadata(model_file)
+        .map_err(|error| {
+            AnimaError::ModelLoadFailed(format!(
+                "Model file not found exactly at {}: {error}",
+                model_file.display()
+            ))
+        })?
+        .len();
+
+    let requested = db::get_load_strategy().unwrap_or_else(|error| {
+        eprintln!("Failed to read load strategy, using auto: {error}");
+        LoadStrategy::Auto
+    });
+    let plan =
+        load_strategy::plan_load(requested, header, file_len, n_ctx, reclaimable_bytes)?;
+    append_hard_debug(
+        "model_load",
+        &format!(
+            "path={} requested={} {}",
+            model_file.display(),
+            requested.as_str(),
+            plan.summary()
+        ),
+    );
+    Ok(plan)
+}
+
+/// Loads the weights the way `plan_model_load` decided.
+pub(crate) fn load_model_file(model_path: &str, plan: &LoadPlan) -> Result<LlamaModel, AnimaError> {
+    get_or_init_backend()?;
+    load_model_with_strategy(Path::new(model_path), plan.strategy)
 }
 
-fn load_model_force_no_mmap(model_file: &Path) -> Result<LlamaModel, String> {
-    let path = model_file
-        .to_str()
-        .ok_or_else(|| format!("Invalid model path: {}", model_file.display()))?;
+fn load_model_with_strategy(
+    model_file: &Path,
+    strategy: LoadStrategy,
+) -> Result<LlamaModel, AnimaError> {
+    let path = model_file.to_str().ok_or_else(|| {
+        AnimaError::InvalidInput(format!("Invalid model path: {}", model_file.display()))
+    })?;
 
     let c_path = CString::new(path)
         .map_err(|error| {
             log_debug_error("invalid_model_path_ffi", &error);
-            format!("Invalid model path for FFI: {error}")
+            AnimaError::InvalidInput(format!("Invalid model path for FFI: {error}"))
         })?;
 
     let mut raw_params = unsafe { llama_cpp_sys_2::llama_model_default_params() };
-    raw_params.use_mmap = false;
+    raw_params.use_mmap = strategy.use_mmap();
+    raw_params.use_mlock = strategy.use_mlock();
     raw_params.n_gpu_layers = 0;
 
     let raw_model = unsafe {
         llama_cpp_sys_2::llama_load_model_from_file(c_path.as_ptr(), raw_params)
     };
 
-    let model_ptr: NonNull<llama_cpp_sys_2::llama_model> = NonNull::new(raw_model)
-        .ok_or_else(|| format!("Model load failed (null): {}", model_file.display()))?;
+    let model_ptr: NonNull<llama_cpp_sys_2::llama_model> =
+        NonNull::new(raw_model).ok_or_else(|| {
+            AnimaError::ModelLoadFailed(format!(
+                "Model load failed (null, strategy {}): {}",
+                strategy.as_str(),
+                model_file.display()
+            ))
+        })?;
 
     let model = unsafe {
         std::mem::transmute::<NonNull<llama_cpp_sys_2::llama_model>, LlamaModel>(model_ptr)
@@ -167,51 +329,206 @@ fn load_model_force_no_mmap(model_file: &Path) -> Result<LlamaModel, String> {
     Ok(model)
 }
 
-pub fn generate_embedding(text: &str) -> Result<Vec<f32>, String> {
+/// A vector and the id of the embedding model that produced it; vectors from
+/// different models are not comparable.
+#[derive(Debug, Clone)]
+pub struct Embedding {
+    pub model_id: String,
+    pub vector: Vec<f32>,
+}
+
+/// Embeds text the user is waiting on, ahead of any background embedding.
+pub fn generate_embedding(text: &str) -> Result<Embedding, AnimaError> {
+    generate_embeddings(&[text], JobPriority::Interactive)?
+        .pop()
+        .ok_or_else(|| AnimaError::EmbeddingFailed("Embedding worker returned no vector".to_string()))
+}
+
+/// Embeds every text with the configured embedding backend. The result is in
+/// input order; a text with no tokens gets an empty vector.
+pub fn generate_embeddings(
+    texts: &[&str],
+    priority: JobPriority,
+) -> Result<Vec<Embedding>, AnimaError> {
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+    backend::embedding()?.embed(texts, priority)
+}
+
+/// In-process llama.cpp: generation and embeddings run on the model
+/// manager's worker threads.
+pub(crate) struct LlamaCppBackend;
+
+impl InferenceBackend for LlamaCppBackend {
+    fn name(&self) -> String {
+        "llama.cpp".to_string()
+    }
+
+    fn chat_template(&self) -> Result<ChatTemplate, AnimaError> {
+        model_manager::chat_handle().map(|chat| chat.template)
+    }
+
+    fn context_window(&self) -> Result<usize, AnimaError> {
+        model_manager::chat_handle().map(|chat| chat.context.n_ctx as usize)
+    }
+
+    fn generate_stream(
+        &self,
+        request: &GenerationRequest,
+        cancellation: &CancellationToken,
+        on_chunk: &mut dyn FnMut(&str) -> Result<(), AnimaError>,
+    ) -> Result<Generation, AnimaError> {
+        llama_chat_stream(request, cancellation, on_chunk)
+    }
+
+    fn embedding_model_id(&self) -> Result<String, AnimaError> {
+        model_manager::embedding_handle().map(|handle| handle.model_id)
+    }
+
+    fn embed(&self, texts: &[&str], priority: JobPriority) -> Result<Vec<Embedding>, AnimaError> {
+        llama_embed(texts, priority)
+    }
+
+    fn tokenize(&self, text: &str) -> Result<Vec<i32>, AnimaError> {
+        let chat = model_manager::chat_handle()?;
+        let tokens = chat
+            .model
+            .str_to_token(text, AddBos::Never)
+            .map_err(|error| {
+                AnimaError::GenerationFailed(format!("Prompt tokenization failed: {error}"))
+            })?;
+        Ok(tokens.into_iter().map(|token| token.0).collect())
+    }
+}
+
+fn llama_embed(texts: &[&str], priority: JobPriority) -> Result<Vec<Embedding>, AnimaError> {
+    let texts = texts.iter().map(|text| text.to_string()).collect::<Vec<_>>();
+    let handle = model_manager::embedding_handle()?;
+    let (reply_tx, reply_rx) = mpsc::channel();
+    handle.submit(
+        priority,
+        Box::new(move |runtime| {
+            let _ = reply_tx.send(embed_texts(runtime, &texts));
+        }),
+    )?;
+    let vectors = reply_rx.recv().map_err(|_| {
+        AnimaError::EmbeddingFailed("Embedding worker stopped before replying".to_string())
+    })??;
+    Ok(vectors
+        .into_iter()
+        .map(|vector| Embedding {
+            model_id: handle.model_id.clone(),
+            vector,
+        })
+        .collect())
+}
+
+/// Body of the embedding worker thread; like `run_chat_worker`, the context
+/// is created once and reused for every job.
+pub(crate) fn run_embedding_worker(
+    model: &LlamaModel,
+    config: ContextConfig,
+    jobs: Arc<JobQueue<EmbeddingJob>>,
+    ready: mpsc::Sender<Result<(), AnimaError>>,
+) {
+    let context = match create_embedding_context(model, &config) {
+        Ok(context) => context,
+        Err(error) => {
+            let _ = ready.send(Err(error));
+            return;
+        }
+    };
+    let _ = ready.send(Ok(()));
+
+    let mut runtime = EmbeddingRuntime {
+        model,
+        context,
+        config,
+    };
+    while let Some(job) = jobs.next() {
+        if panic::catch_unwind(AssertUnwindSafe(|| job(&mut runtime))).is_err() {
+            append_hard_debug("embedding_worker", "Embedding job panicked; KV cache reset");
+            runtime.context.clear_kv_cache();
+        }
+    }
+}
+
+fn create_embedding_context<'m>(
+    model: &'m LlamaModel,
+    config: &ContextConfig,
+) -> Result<LlamaContext<'m>, AnimaError> {
     let backend_lock = get_or_init_backend()?;
     let backend = backend_lock
         .lock()
-        .map_err(|error| format!("Llama backend mutex is poisoned: {error}"))?;
+        .map_err(|error| AnimaError::Internal(format!("Llama backend mutex is poisoned: {error}")))?;
 
-    let runtime_lock = EMBEDDING_RUNTIME
-        .get()
-        .ok_or_else(|| "Embedding runtime is not initialized".to_string())?;
-
-    let runtime = runtime_lock
-        .lock()
-        .map_err(|error| format!("Embedding runtime mutex is poisoned: {error}"))?;
-
-    let context_params = LlamaContextParams::default()
-        .with_n_ctx(NonZeroU32::new(DEFAULT_N_CTX))
-        .with_n_batch(SAFE_N_BATCH)
-        .with_n_ubatch(SAFE_N_BATCH)
-        .with_n_threads(SAFE_N_THREADS)
-        .with_n_threads_batch(SAFE_N_THREADS)
+    append_hard_debug("embedding_context", &config.summary());
+    let context_params = context_params(config)
+        .with_n_seq_max(EMBEDDING_MAX_SEQUENCES as u32)
         .with_embeddings(true)
         .with_pooling_type(LlamaPoolingType::Mean);
-
-    let mut context = runtime
-        .model
+    model
         .new_context(&backend, context_params)
         .map_err(|error| {
             log_debug_error("embedding_context_creation", &error);
-            format!("Embedding context creation failed: {error}")
-        })?;
+            AnimaError::EmbeddingFailed(format!("Embedding context creation failed: {error}"))
+        })
+}
 
-    let tokens = runtime
-        .model
-        .str_to_token(text, AddBos::Never)
-        .map_err(|error| {
-            log_debug_error("embedding_tokenization", &error);
-            format!("Embedding tokenization failed: {error}")
-        })?;
+/// Texts longer than one batch are split into chunks whose embeddings are
+/// merged, so long memories are embedded whole instead of failing.
+fn embed_texts(
+    runtime: &mut EmbeddingRuntime<'_>,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, AnimaError> {
+    let chunk_len = runtime.config.n_batch.min(runtime.config.n_ctx).max(1) as usize;
+
+    // (text index, tokens) for every chunk, in input order.
+    let mut chunks = Vec::<(usize, Vec<LlamaToken>)>::new();
+    for (text_index, text) in texts.iter().enumerate() {
+        let tokens = runtime
+            .model
+            .str_to_token(text, AddBos::Never)
+            .map_err(|error| {
+                log_debug_error("embedding_tokenization", &error);
+                AnimaError::EmbeddingFailed(format!("Embedding tokenization failed: {error}"))
+            })?;
+        chunks.extend(
+            tokens
+                .chunks(chunk_len)
+                .map(|chunk| (text_index, chunk.to_vec())),
+        );
+    }
 
-    if tokens.is_empty() {
-        return Ok(Vec::new());
+    let lens = chunks.iter().map(|(_, tokens)| tokens.len()).collect::<Vec<_>>();
+    let mut per_text = vec![Vec::<(Vec<f32>, usize)>::new(); texts.len()];
+    for range in embedding_batch::plan_batches(&lens, chunk_len, EMBEDDING_MAX_SEQUENCES) {
+        let vectors = embed_batch(&mut runtime.context, &chunks[range.clone()]);
+        runtime.context.clear_kv_cache();
+        for ((text_index, tokens), vector) in chunks[range].iter().zip(vectors?) {
+            per_text[*text_index].push((vector, tokens.len()));
+        }
     }
 
-    let mut batch = LlamaBatch::get_one(&tokens)
-        .map_err(|error| format!("Embedding batch init failed: {error}"))?;
+    Ok(per_text.into_iter().map(embedding_batch::merge_chunks).collect())
+}
+
+/// One sequence id per chunk; returns the pooled embedding of each, which
+/// for a rank-pooling reranker is the pair's score.
+fn embed_batch(
+    context: &mut LlamaContext<'_>,
+    chunks: &[(usize, Vec<LlamaToken>)],
+) -> Result<Vec<Vec<f32>>, AnimaError> {
+    let total_tokens = chunks.iter().map(|(_, tokens)| tokens.len()).sum::<usize>();
+    let mut batch = LlamaBatch::new(total_tokens, chunks.len() as i32);
+    for (seq_id, (_, tokens)) in chunks.iter().enumerate() {
+        batch
+            .add_sequence(tokens, seq_id as i32, false)
+            .map_err(|error| {
+                AnimaError::EmbeddingFailed(format!("Embedding batch init failed: {error}"))
+            })?;
+    }
 
     match context.encode(&mut batch) {
         Ok(()) => {}
@@ -220,83 +537,236 @@ pub fn generate_embedding(text: &str) -> Result<Vec<f32>, String> {
                 .decode(&mut batch)
                 .map_err(|decode_error| {
                     log_debug_error("embedding_decode_fallback", &decode_error);
-                    format!(
+                    AnimaError::EmbeddingFailed(format!(
                         "Embedding inference failed after encode error ({encode_error}); decode fallback error: {decode_error}"
-                    )
+                    ))
                 })?;
         }
     }
 
-    match context.embeddings_seq_ith(0) {
-        Ok(vector) => Ok(vector.to_vec()),
-        Err(seq_error) => {
-            let last_token = i32::try_from(tokens.len().saturating_sub(1))
-                .map_err(|error| format!("Embedding token index overflow: {error}"))?;
-            let fallback = context
+    let mut last_token = -1_i32;
+    let mut vectors = Vec::with_capacity(chunks.len());
+    for (seq_id, (_, tokens)) in chunks.iter().enumerate() {
+        last_token += tokens.len() as i32;
+        let vector = match context.embeddings_seq_ith(seq_id as i32) {
+            Ok(vector) => vector.to_vec(),
+            Err(seq_error) => context
                 .embeddings_ith(last_token)
                 .map_err(|error| {
-                    format!(
+                    AnimaError::EmbeddingFailed(format!(
                         "Embedding extraction failed (seq_ith error: {seq_error}, fallback token: {last_token}): {error}"
-                    )
-                })?;
-            Ok(fallback.to_vec())
+                    ))
+                })?
+                .to_vec(),
+        };
+        vectors.push(vector);
+    }
+    Ok(vectors)
+}
+
+/// Scores how well each document answers `query` with the reranker model,
+/// in input order; higher is better. `None` when no reranker is loaded.
+pub fn rerank(
+    query: &str,
+    documents: &[&str],
+    priority: JobPriority,
+) -> Result<Option<Vec<f32>>, AnimaError> {
+    let Some(handle) = model_manager::reranker_handle() else {
+        return Ok(None);
+    };
+    if documents.is_empty() {
+        return Ok(Some(Vec::new()));
+    }
+
+    let query = query.to_string();
+    let documents = documents.iter().map(|text| text.to_string()).collect::<Vec<_>>();
+    let (reply_tx, reply_rx) = mpsc::channel();
+    handle.submit(
+        priority,
+        Box::new(move |runtime| {
+            let _ = reply_tx.send(score_pairs(runtime, &query, &documents));
+        }),
+    )?;
+    let scores = reply_rx.recv().map_err(|_| {
+        AnimaError::EmbeddingFailed("Reranker worker stopped before replying".to_string())
+    })??;
+    Ok(Some(scores))
+}
+
+/// Body of the reranker worker thread; same lifecycle as the embedding worker.
+pub(crate) fn run_reranker_worker(
+    model: &LlamaModel,
+    config: ContextConfig,
+    tokens: RerankTokens,
+    jobs: Arc<JobQueue<RerankJob>>,
+    ready: mpsc::Sender<Result<(), AnimaError>>,
+) {
+    let context = match create_reranker_context(model, &config) {
+        Ok(context) => context,
+        Err(error) => {
+            let _ = ready.send(Err(error));
+            return;
+        }
+    };
+    let _ = ready.send(Ok(()));
+
+    let mut runtime = RerankerRuntime {
+        model,
+        context,
+        config,
+        tokens,
+    };
+    while let Some(job) = jobs.next() {
+        if panic::catch_unwind(AssertUnwindSafe(|| job(&mut runtime))).is_err() {
+            append_hard_debug("reranker_worker", "Rerank job panicked; KV cache reset");
+            runtime.context.clear_kv_cache();
         }
     }
 }
 
-pub fn generate_response(prompt: &str, temperature: f32, max_tokens: u32) -> Result<String, String> {
-    generate_response_with_context(prompt, &[], temperature, max_tokens)
+fn create_reranker_context<'m>(
+    model: &'m LlamaModel,
+    config: &ContextConfig,
+) -> Result<LlamaContext<'m>, AnimaError> {
+    let backend_lock = get_or_init_backend()?;
+    let backend = backend_lock
+        .lock()
+        .map_err(|error| AnimaError::Internal(format!("Llama backend mutex is poisoned: {error}")))?;
+
+    append_hard_debug("reranker_context", &config.summary());
+    let context_params = context_params(config)
+        .with_n_seq_max(RERANK_MAX_SEQUENCES as u32)
+        .with_embeddings(true)
+        .with_pooling_type(LlamaPoolingType::Rank);
+    model
+        .new_context(&backend, context_params)
+        .map_err(|error| {
+            log_debug_error("reranker_context_creation", &error);
+            AnimaError::EmbeddingFailed(format!("Reranker context creation failed: {error}"))
+        })
+}
+
+/// One sequence per (query, document) pair. With rank pooling the pooled
+/// output of a sequence is its relevance score.
+fn score_pairs(
+    runtime: &mut RerankerRuntime<'_>,
+    query: &str,
+    documents: &[String],
+) -> Result<Vec<f32>, AnimaError> {
+    let max_len = runtime.config.n_batch.min(runtime.config.n_ctx).max(1) as usize;
+    let tokenize = |text: &str| {
+        runtime.model.str_to_token(text, AddBos::Always).map_err(|error| {
+            log_debug_error("reranker_tokenization", &error);
+            AnimaError::EmbeddingFailed(format!("Reranker tokenization failed: {error}"))
+        })
+    };
+
+    let query_tokens = tokenize(query)?;
+    let mut pairs = Vec::<(usize, Vec<LlamaToken>)>::with_capacity(documents.len());
+    for (index, document) in documents.iter().enumerate() {
+        let document_tokens = tokenize(document)?;
+        let tokens = rerank_pair(
+            &query_tokens,
+            &document_tokens,
+            runtime.tokens.bos,
+            runtime.tokens.separator,
+            max_len,
+        );
+        pairs.push((index, tokens));
+    }
+
+    let lens = pairs.iter().map(|(_, tokens)| tokens.len()).collect::<Vec<_>>();
+    let mut scores = Vec::with_capacity(pairs.len());
+    for range in embedding_batch::plan_batches(&lens, max_len, RERANK_MAX_SEQUENCES) {
+        let outputs = embed_batch(&mut runtime.context, &pairs[range]);
+        runtime.context.clear_kv_cache();
+        scores.extend(outputs?.into_iter().map(|output| output.first().copied().unwrap_or(0.0)));
+    }
+    Ok(scores)
+}
+
+/// Joins a query and a document, each tokenized with the model's special
+/// tokens, as `query [separator] document` without the document's own `bos`.
+/// The document is cut, keeping its closing token, so the pair fits `max_len`.
+fn rerank_pair<T: Copy + PartialEq>(
+    query: &[T],
+    document: &[T],
+    bos: Option<T>,
+    separator: Option<T>,
+    max_len: usize,
+) -> Vec<T> {
+    let document = match (document.split_first(), bos) {
+        (Some((first, rest)), Some(bos)) if *first == bos => rest,
+        _ => document,
+    };
+    let mut pair = query[..query.len().min(max_len / 2)].to_vec();
+    pair.extend(separator);
+
+    let room = max_len.saturating_sub(pair.len());
+    if document.len() <= room {
+        pair.extend_from_slice(document);
+    } else if let Some((last, body)) = document.split_last().filter(|_| room > 0) {
+        pair.extend_from_slice(&body[..room - 1]);
+        pair.push(*last);
+    }
+    pair
+}
+
+pub fn generate_response(
+    prompt: &str,
+    sampling: &SamplingParams,
+    max_tokens: u32,
+) -> Result<String, AnimaError> {
+    generate_response_with_context(
+        prompt,
+        &[],
+        &[],
+        sampling,
+        max_tokens,
+        &CancellationToken::new(),
+    )
+    .map(|generation| generation.text)
 }
 
 pub fn generate_response_with_context(
     prompt: &str,
+    history: &[ChatTurn],
     relevant_context: &[String],
-    temperature: f32,
+    sampling: &SamplingParams,
     max_tokens: u32,
-) -> Result<String, String> {
+    cancellation: &CancellationToken,
+) -> Result<Generation, AnimaError> {
     generate_response_with_context_stream(
         prompt,
+        history,
         relevant_context,
-        temperature,
+        sampling,
         max_tokens,
+        cancellation,
         |_| Ok(()),
     )
 }
 
-pub fn generate_response_with_context_stream<F>(
+/// Core prompt, profile, memories and history fitted to the context window.
+fn build_chat_prompt(
     prompt: &str,
+    history: &[ChatTurn],
     relevant_context: &[String],
-    temperature: f32,
     max_tokens: u32,
-    mut on_chunk: F,
-) -> Result<String, String>
-where
-    F: FnMut(&str) -> Result<(), String>,
-{
-    let memory_block = if relevant_context.is_empty() {
-        String::new()
-    } else {
-        let lines = relevant_context
-            .iter()
-            .enumerate()
-            .map(|(index, item)| {
-                let normalized = item.trim();
-                if let Some(content) = normalized.strip_prefix("[user] ") {
-                    format!("{}. (user memory) {}", index + 1, content)
-                } else if let Some(content) = normalized.strip_prefix("[assistant] ") {
-                    format!("{}. (assistant memory) {}", index + 1, content)
-                } else {
-                    format!("{}. {}", index + 1, normalized)
-                }
-            })
-            .collect::<Vec<String>>()
-            .join("\n");
-
-        format!(
-            "\n\nMEMORY SNIPPETS (REFERENCE ONLY, NOT DIALOGUE TURNS):\n{}\nUse this context only if relevant to the current user message. Never generate roleplay turns like 'User:' or simulate both sides.",
-            lines
-        )
-    };
+) -> Result<AssembledPrompt, AnimaError> {
+    let memory_lines = relevant_context
+        .iter()
+        .map(|item| {
+            let normalized = item.trim();
+            if let Some(content) = normalized.strip_prefix("[user] ") {
+                format!("(user memory) {content}")
+            } else if let Some(content) = normalized.strip_prefix("[assistant] ") {
+                format!("(assistant memory) {content}")
+            } else {
+                normalized.to_string()
+            }
+        })
+        .collect::<Vec<String>>();
 
     let user_name = db::get_user_name().unwrap_or_default();
     let app_language = db::get_app_language().unwrap_or_else(|_| "Español".to_string());
@@ -317,7 +787,7 @@ NEVER refer to them as 'the user' or in the third person. Use 'you' (or 'tú'/'v
 NEVER use robotic AI disclaimers like 'As an AI language model...', 'How can I assist you today?', or over-apologize. Speak like a highly educated, insightful human peer.
 
 CONTEXT & MEMORY
-CHRONOLOGICAL ANCHOR: Today's exact date and time is {now}.
+CHRONOLOGICAL ANCHOR: Today's exact date and time is given at the end of these instructions.
 
 You have a photographic memory of past conversations. When using retrieved memories or facts about {user_name}, weave them naturally into the conversation. Do not abruptly list facts unless explicitly asked.
 
@@ -334,36 +804,75 @@ You MUST generate ALL your responses, thoughts, and greetings entirely in {langu
 
 {extra}"#,
         language = app_language_for_prompt,
-        now = now_local,
         user_name = user_name,
         extra = user_extra_prompt,
     );
 
-    let consolidated_profile_block = match db::get_profile_traits() {
-        Ok(traits) if !traits.is_empty() => {
-            let lines = traits
-                .into_iter()
-                .map(|trait_item| format!("- [{}]: {}", trait_item.category, trait_item.content))
-                .collect::<Vec<String>>()
-                .join("\n");
-            format!("\n\nPERFIL CONSOLIDADO DEL USUARIO:\n{}", lines)
-        }
-        _ => String::new(),
+    let profile_lines = db::get_profile_traits()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|trait_item| format!("- [{}]: {}", trait_item.category, trait_item.content))
+        .collect::<Vec<String>>();
+
+    let parts = PromptParts {
+        core_prompt,
+        profile_lines,
+        memory_lines,
+        history: history.to_vec(),
+        user_message: prompt.to_string(),
+        system_suffix: format!("\n\nCURRENT DATE AND TIME: {now_local}"),
     };
+    assemble_chat_prompt(&parts, max_tokens)
+}
 
-    let system_prompt = format!("{}{}", core_prompt, consolidated_profile_block);
-    let user_prompt = format!("{}{}", prompt, memory_block);
+/// Renders the chat prompt exactly as `generate_response_with_context` would,
+/// without touching the KV cache or decoding.
+pub fn preview_chat_prompt(
+    prompt: &str,
+    history: &[ChatTurn],
+    relevant_context: &[String],
+    max_tokens: u32,
+) -> Result<RenderedPrompt, AnimaError> {
+    let assembled = build_chat_prompt(prompt, history, relevant_context, max_tokens)?;
+    let chat = backend::chat()?;
+    let text = chat
+        .chat_template()?
+        .render(&assembled.system_prompt(), &assembled.turns());
+    let token_count = chat.count_tokens(&text)?;
+
+    Ok(RenderedPrompt {
+        assembled,
+        text,
+        token_count,
+    })
+}
 
-    generate_with_system_prompt_stream(
-        &system_prompt,
-        &user_prompt,
-        temperature,
+pub fn generate_response_with_context_stream<F>(
+    prompt: &str,
+    history: &[ChatTurn],
+    relevant_context: &[String],
+    sampling: &SamplingParams,
+    max_tokens: u32,
+    cancellation: &CancellationToken,
+    mut on_chunk: F,
+) -> Result<Generation, AnimaError>
+where
+    F: FnMut(&str) -> Result<(), AnimaError>,
+{
+    let assembled = build_chat_prompt(prompt, history, relevant_context, max_tokens)?;
+    let request = GenerationRequest {
+        system_prompt: assembled.system_prompt(),
+        turns: assembled.turns(),
+        sampling: sampling.clone(),
         max_tokens,
-        &mut on_chunk,
-    )
+        priority: JobPriority::Interactive,
+        response_schema: None,
+    };
+
+    generate_chat_stream(&request, cancellation, &mut on_chunk)
 }
 
-pub fn generate_proactive_greeting(time_of_day: &str) -> Result<String, String> {
+pub fn generate_proactive_greeting(time_of_day: &str) -> Result<String, AnimaError> {
     let user_name = db::get_user_name().unwrap_or_default();
     let app_language = db::get_app_language().unwrap_or_else(|_| "Español".to_string());
     let app_language_for_prompt = language_name_for_prompt(&app_language);
@@ -404,28 +913,40 @@ pub fn generate_proactive_greeting(time_of_day: &str) -> Result<String, String>
         user_extra_prompt = user_extra_prompt,
     );
 
-    let effective_temperature = db::get_temperature().unwrap_or(0.7);
+    let sampling = db::get_sampling_params().unwrap_or_default();
     let generated = generate_with_system_prompt(
         &proactive_system_prompt,
         "Genera el saludo inicial ahora.",
-        effective_temperature,
+        &sampling,
         120,
+        JobPriority::Greeting,
     )?;
 
     Ok(generated)
 }
 
-pub fn export_brain() -> Result<String, String> {
-    let profile_traits = db::get_profile_traits().map_err(|error| format!("DB error: {error}"))?;
-    let memories = db::get_all_memories().map_err(|error| format!("DB error: {error}"))?;
+pub fn export_brain() -> Result<String, AnimaError> {
+    let profile_traits = db::get_profile_traits()?;
+    let memories = db::get_all_memories()?;
     let user_name = db::get_user_name().unwrap_or_default();
     let app_language = db::get_app_language().unwrap_or_else(|_| "Español".to_string());
-    let temperature = db::get_temperature().unwrap_or(0.7);
+    let sampling = db::get_sampling_params().unwrap_or_default();
 
     let json_output = json!({
         "user_name": user_name,
         "app_language": app_language,
-        "temperature": temperature,
+        "temperature": sampling.temperature,
+        "sampling": {
+            "top_k": sampling.top_k,
+            "top_p": sampling.top_p,
+            "min_p": sampling.min_p,
+            "repeat_penalty": sampling.repeat_penalty,
+            "repeat_last_n": sampling.repeat_last_n,
+            "seed": sampling.seed,
+            "mirostat": sampling.mirostat,
+            "mirostat_tau": sampling.mirostat_tau,
+            "mirostat_eta": sampling.mirostat_eta,
+        },
         "user_profile": profile_traits
             .into_iter()
             .map(|item| json!({
@@ -448,7 +969,7 @@ pub fn export_brain() -> Result<String, String> {
     });
 
     serde_json::to_string_pretty(&json_output)
-        .map_err(|error| format!("JSON serialization failed: {error}"))
+        .map_err(|error| AnimaError::Internal(format!("JSON serialization failed: {error}")))
 }
 
 fn language_name_for_prompt(language_code_or_name: &str) -> String {
@@ -483,9 +1004,9 @@ fn language_name_for_prompt(language_code_or_name: &str) -> String {
     }
 }
 
-pub fn run_sleep_cycle() -> Result<(), String> {
+pub fn run_sleep_cycle() -> Result<(), AnimaError> {
     eprintln!("[sleep_cycle] start");
-    let conversation_history = db::get_all_messages().map_err(|error| format!("DB error: {error}"))?;
+    let conversation_history = db::get_all_messages()?;
     eprintln!("[sleep_cycle] loaded messages count={}", conversation_history.len());
     if conversation_history.is_empty() {
         eprintln!("[sleep_cycle] no messages to process, finish");
@@ -508,40 +1029,26 @@ pub fn run_sleep_cycle() -> Result<(), String> {
     let subconscious_user_input = format!("CONVERSATION HISTORY:\n{}", conversation_block);
     eprintln!("[sleep_cycle] prompting subconscious model");
 
-    let subconscious_response = match generate_with_system_prompt(
+    let parsed = match generate_structured(
         SUBCONSCIOUS_SYSTEM_PROMPT,
         &subconscious_user_input,
-        0.1,
+        &memory_extraction_schema(),
         1024,
+        JobPriority::SleepCycle,
     ) {
-        Ok(resp) => {
-            eprintln!("[sleep_cycle] model response received length={}", resp.len());
-            resp
+        Ok(parsed) => {
+            eprintln!("[sleep_cycle] structured response received");
+            parsed
         }
         Err(e) => {
             eprintln!("[sleep_cycle] LLM inference failed (skipping memory consolidation): {e}");
+            append_hard_debug("sleep_cycle", &format!("consolidation skipped: {e}"));
             return Ok(()); // still return success so the app can close
         }
     };
 
-    let cleaned_response = clean_json_response(&subconscious_response);
-
-    let parsed: Value = match serde_json::from_str(&cleaned_response) {
-        Ok(v) => {
-            eprintln!("[sleep_cycle] json parsed successfully");
-            v
-        }
-        Err(e) => {
-            eprintln!(
-                "[sleep_cycle] JSON parse failed (skipping memory consolidation): {e}. Raw: {}",
-                subconscious_response
-            );
-            return Ok(()); // not fatal — app still closes cleanly
-        }
-    };
-
     let semantic_items = parse_memory_array(&parsed, "semantic").unwrap_or_default();
-    let episodic_items = parse_memory_array(&parsed, "episodic").unwrap_or_default();
+    let episodic_items = parse_episodic_items(&parsed);
     eprintln!(
         "[sleep_cycle] extracted items semantic={} episodic={}",
         semantic_items.len(),
@@ -562,17 +1069,82 @@ pub fn run_sleep_cycle() -> Result<(), String> {
         }
     }
 
-    for content in episodic_items {
-        if let Err(e) = persist_memory_item(&content, "episodic", now_unix) {
+    let episodic_texts = episodic_items
+        .iter()
+        .map(|(content, _)| content.as_str())
+        .collect::<Vec<_>>();
+    let episodic_embeddings = match generate_embeddings(&episodic_texts, JobPriority::SleepCycle) {
+        Ok(embeddings) => embeddings.into_iter().map(Some).collect(),
+        Err(e) => {
+            eprintln!("[sleep_cycle] Failed to embed episodic memories: {e}");
+            vec![None; episodic_items.len()]
+        }
+    };
+    for ((content, importance), embedding) in episodic_items.iter().zip(&episodic_embeddings) {
+        let persisted =
+            persist_memory_item(content, embedding.as_ref(), "episodic", *importance, now_unix);
+        if let Err(e) = persisted {
             eprintln!("[sleep_cycle] Failed to persist episodic memory: {e}");
         }
     }
+    // The app usually closes right after the sleep cycle.
+    memory_index::flush();
 
     eprintln!("[sleep_cycle] finish ok");
 
     Ok(())
 }
 
+/// Shape `SUBCONSCIOUS_SYSTEM_PROMPT` asks for.
+fn memory_extraction_schema() -> Value {
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+    let events = json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "text": { "type": "string" },
+                "importance": { "enum": [1, 2, 3, 4, 5] },
+            },
+        },
+    });
+    json!({
+        "type": "object",
+        "properties": { "semantic": strings, "episodic": events },
+    })
+}
+
+/// Episodic events with their importance scaled to 0..=1. Plain strings,
+/// as servers ignoring the schema may send, get the default importance.
+fn parse_episodic_items(parsed: &Value) -> Vec<(String, f32)> {
+    let Some(items) = parsed.get("episodic").and_then(Value::as_array) else {
+        eprintln!("[sleep_cycle] response is missing 'episodic' array");
+        return Vec::new();
+    };
+
+    items
+        .iter()
+        .filter_map(|item| {
+            let (text, importance) = match item {
+                Value::String(text) => (text.as_str(), db::DEFAULT_MEMORY_IMPORTANCE),
+                Value::Object(event) => {
+                    let text = event.get("text").and_then(Value::as_str)?;
+                    let importance = event
+                        .get("importance")
+                        .and_then(Value::as_f64)
+                        .map_or(db::DEFAULT_MEMORY_IMPORTANCE, |rating| {
+                            ((rating as f32 - 1.0) / 4.0).clamp(0.0, 1.0)
+                        });
+                    (text, importance)
+                }
+                _ => return None,
+            };
+            let text = text.trim();
+            (!text.is_empty()).then(|| (text.to_string(), importance))
+        })
+        .collect()
+}
+
 fn parse_memory_array(parsed: &Value, key: &str) -> Result<Vec<String>, String> {
     let items = parsed
         .get(key)
@@ -592,7 +1164,15 @@ fn parse_memory_array(parsed: &Value, key: &str) -> Result<Vec<String>, String>
     Ok(output)
 }
 
-fn persist_memory_item(content: &str, memory_type: &str, unix_timestamp: i64) -> Result<(), String> {
+/// Stores the memory text; the vector row is only written when there is a
+/// non-empty `embedding`.
+fn persist_memory_item(
+    content: &str,
+    embedding: Option<&Embedding>,
+    memory_type: &str,
+    importance: f32,
+    unix_timestamp: i64,
+) -> Result<(), AnimaError> {
     let message_role = if memory_type == "semantic" {
         "semantic_memory"
     } else {
@@ -600,15 +1180,23 @@ fn persist_memory_item(content: &str, memory_type: &str, unix_timestamp: i64) ->
     };
 
     let message_id = db::insert_message(message_role, content)
-        .map_err(|error| format!("DB insert memory message failed: {error}"))?;
+        .map_err(|error| AnimaError::from(error).context("DB insert memory message failed"))?;
 
-    let embedding = generate_embedding(content)?;
-    if embedding.is_empty() {
+    let Some(embedding) = embedding.filter(|embedding| !embedding.vector.is_empty()) else {
         return Ok(());
-    }
+    };
 
-    db::insert_memory(message_id, &embedding, memory_type, unix_timestamp)
-        .map_err(|error| format!("DB insert {memory_type} memory failed: {error}"))?;
+    db::insert_memory(
+        message_id,
+        &embedding.vector,
+        &embedding.model_id,
+        memory_type,
+        importance,
+        unix_timestamp,
+    )
+        .map_err(|error| {
+            AnimaError::from(error).context(&format!("DB insert {memory_type} memory failed"))
+        })?;
 
     Ok(())
 }
@@ -616,7 +1204,7 @@ fn persist_memory_item(content: &str, memory_type: &str, unix_timestamp: i64) ->
 fn persist_profile_trait_item(
     content: &str,
     existing_profile_items: &mut HashSet<String>,
-) -> Result<(), String> {
+) -> Result<(), AnimaError> {
     let normalized = content.trim().to_lowercase();
     if normalized.is_empty() {
         return Ok(());
@@ -627,7 +1215,7 @@ fn persist_profile_trait_item(
     }
 
     db::add_profile_trait("Sleep Cycle", content)
-        .map_err(|error| format!("DB insert profile trait failed: {error}"))?;
+        .map_err(|error| AnimaError::from(error).context("DB insert profile trait failed"))?;
 
     existing_profile_items.insert(normalized);
     Ok(())
@@ -750,102 +1338,334 @@ fn extract_first_json_object(text: &str) -> Option<String> {
     None
 }
 
+/// Fits the prompt parts into the chat context, leaving room for `max_tokens`
+/// of output. Trimming decisions are written to the hard debug log.
+fn assemble_chat_prompt(parts: &PromptParts, max_tokens: u32) -> Result<AssembledPrompt, AnimaError> {
+    let chat = backend::chat()?;
+    let template = chat.chat_template()?;
+    let count_tokens = |text: &str| -> usize {
+        chat.count_tokens(text)
+            // Rough chars-per-token fallback so a tokenizer hiccup never blocks the budget.
+            .unwrap_or_else(|_| text.len() / 3 + 1)
+    };
+
+    let base_overhead = count_tokens(&template.render("", &[ChatTurn::user("")]));
+    let two_turns_overhead = count_tokens(&template.render(
+        "",
+        &[ChatTurn::user(""), ChatTurn::assistant(""), ChatTurn::user("")],
+    ));
+    let turn_overhead = two_turns_overhead.saturating_sub(base_overhead) / 2;
+
+    let budget = PromptBudget {
+        context_window: chat.context_window()?,
+        reserved_for_generation: max_tokens.min(MAX_GENERATION_TOKENS) as usize,
+        base_overhead,
+        turn_overhead,
+    };
+    let assembled = prompt_budget::assemble(parts, &budget, count_tokens);
+
+    if assembled.report.was_trimmed() {
+        append_hard_debug("prompt_budget", &assembled.report.summary());
+    }
+
+    Ok(assembled)
+}
+
+/// Single-turn generation whose output nobody watches stream.
 fn generate_with_system_prompt(
     system_prompt: &str,
     user_prompt: &str,
-    temperature: f32,
+    sampling: &SamplingParams,
     max_tokens: u32,
-) -> Result<String, String> {
-    generate_with_system_prompt_stream(system_prompt, user_prompt, temperature, max_tokens, |_| {
-        Ok(())
-    })
+    priority: JobPriority,
+) -> Result<String, AnimaError> {
+    let request = GenerationRequest {
+        system_prompt: system_prompt.to_string(),
+        turns: vec![ChatTurn::user(user_prompt)],
+        sampling: sampling.clone(),
+        max_tokens,
+        priority,
+        response_schema: None,
+    };
+    generate_requeuing_preempted(&request).map(|generation| generation.text)
 }
 
-fn generate_with_system_prompt_stream<F>(
+/// Generates a JSON value matching `schema` (see `json_grammar` for the
+/// supported subset) with near-greedy sampling. Local models are constrained
+/// by a grammar, so nothing else can come out; servers get the schema as
+/// `response_format`.
+pub fn generate_structured(
     system_prompt: &str,
     user_prompt: &str,
-    temperature: f32,
+    schema: &Value,
     max_tokens: u32,
+    priority: JobPriority,
+) -> Result<Value, AnimaError> {
+    let request = GenerationRequest {
+        system_prompt: system_prompt.to_string(),
+        turns: vec![ChatTurn::user(user_prompt)],
+        sampling: SamplingParams::near_greedy(),
+        max_tokens,
+        priority,
+        response_schema: Some(schema.clone()),
+    };
+    let generation = generate_requeuing_preempted(&request)?;
+
+    // The fallback is for servers that ignore `response_format`.
+    serde_json::from_str(&generation.text)
+        .or_else(|_| serde_json::from_str(&clean_json_response(&generation.text)))
+        .map_err(|error| {
+            AnimaError::GenerationFailed(format!(
+                "Structured output is not valid JSON (stop={:?}): {error}",
+                generation.stop_reason
+            ))
+        })
+}
+
+/// A preempted background turn is queued again from scratch, behind the work
+/// that preempted it.
+fn generate_requeuing_preempted(request: &GenerationRequest) -> Result<Generation, AnimaError> {
+    let mut attempts = 0;
+    loop {
+        match generate_chat_stream(request, &CancellationToken::new(), |_| Ok(())) {
+            Err(AnimaError::Preempted(detail)) if attempts < MAX_PREEMPTED_RETRIES => {
+                attempts += 1;
+                append_hard_debug(
+                    "job_queue",
+                    &format!(
+                        "{} turn preempted, requeued ({attempts}): {detail}",
+                        request.priority.as_str()
+                    ),
+                );
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Runs one chat turn on the configured chat backend. Whatever the backend,
+/// the reply goes through one `OutputFilter` that cuts it at the template's
+/// stop sequences and at echoed system instructions; a cut stops the backend.
+fn generate_chat_stream<F>(
+    request: &GenerationRequest,
+    cancellation: &CancellationToken,
     mut on_chunk: F,
-) -> Result<String, String>
+) -> Result<Generation, AnimaError>
 where
-    F: FnMut(&str) -> Result<(), String>,
+    F: FnMut(&str) -> Result<(), AnimaError>,
 {
-    let _requested_temperature = temperature;
-    let sampling_temperature = 0.7_f32;
+    let chat = backend::chat()?;
+    let stop_sequences = chat.chat_template()?.stop_sequences();
+    let mut output = if request.response_schema.is_some() {
+        OutputFilter::verbatim(stop_sequences)
+    } else {
+        OutputFilter::new(stop_sequences)
+    };
+    // A cut cancels only this turn; the caller's token stays as it was.
+    let turn_cancellation = cancellation.child();
+    let mut cut = false;
+    let mut generation = chat
+        .generate_stream(request, &turn_cancellation, &mut |piece| {
+            if !cut && output.push(piece, &mut on_chunk)? {
+                cut = true;
+                turn_cancellation.cancel();
+            }
+            Ok(())
+        })
+        .inspect_err(|error| {
+            append_hard_debug("chat_backend", &format!("{} failed: {error}", chat.name()));
+        })?;
 
-    let _backend_lock = get_or_init_backend()?;
+    generation.text = output.finish("", &mut on_chunk)?;
+    if cut {
+        generation.stop_reason = StopReason::StopSequence;
+    }
+    Ok(generation)
+}
 
-    let runtime_lock = CHAT_RUNTIME
-        .get()
-        .ok_or_else(|| "AI runtime is not initialized in generate_with_system_prompt_stream".to_string())?;
+/// Runs one chat turn on the chat worker thread and relays its chunks to
+/// `on_chunk` on the calling thread. If `on_chunk` fails the turn is cancelled
+/// and that error is returned; a preempted background turn returns
+/// `Preempted`.
+fn llama_chat_stream(
+    request: &GenerationRequest,
+    cancellation: &CancellationToken,
+    on_chunk: &mut dyn FnMut(&str) -> Result<(), AnimaError>,
+) -> Result<Generation, AnimaError> {
+    let (event_tx, event_rx) = mpsc::channel::<ChatWorkerEvent>();
+    let priority = request.priority;
+    let grammar = request
+        .response_schema
+        .as_ref()
+        .map(json_grammar::schema_to_gbnf)
+        .transpose()?;
+    let job_request = request.clone();
+    // Preemption cancels only this job's token, so the caller can queue the
+    // turn again with its own token.
+    let job_cancellation = cancellation.child();
+    let preemption_token = job_cancellation.clone();
+
+    let job: ChatJob = Box::new(move |runtime| {
+        let chunk_tx = event_tx.clone();
+        let result = decode_chat_turn(
+            runtime,
+            &job_request,
+            grammar.as_deref(),
+            &job_cancellation,
+            |chunk| {
+                chunk_tx
+                    .send(ChatWorkerEvent::Chunk(chunk.to_string()))
+                    .map_err(|_| AnimaError::Cancelled("Chat listener went away".to_string()))
+            },
+        );
+        let _ = event_tx.send(ChatWorkerEvent::Finished(result));
+    });
+    let ticket = model_manager::chat_handle()?.submit(priority, preemption_token, job)?;
+
+    let mut listener_error = None;
+    for event in event_rx {
+        match event {
+            ChatWorkerEvent::Chunk(chunk) => {
+                if listener_error.is_none() {
+                    if let Err(error) = on_chunk(&chunk) {
+                        cancellation.cancel();
+                        listener_error = Some(error);
+                    }
+                }
+            }
+            ChatWorkerEvent::Finished(result) => {
+                if let Some(error) = listener_error {
+                    return Err(error);
+                }
+                let stopped_early = match &result {
+                    Ok(generation) => generation.stop_reason == StopReason::Cancelled,
+                    Err(error) => matches!(error, AnimaError::Cancelled(_)),
+                };
+                if stopped_early && ticket.was_preempted() && !cancellation.is_cancelled() {
+                    return Err(AnimaError::Preempted(format!(
+                        "{} job {} gave way to foreground work",
+                        priority.as_str(),
+                        ticket.id
+                    )));
+                }
+                return result;
+            }
+        }
+    }
 
-    let mut runtime = runtime_lock
-        .lock()
-        .map_err(|error| format!("AI runtime mutex is poisoned: {error}"))?;
+    Err(AnimaError::ModelNotLoaded(
+        "Chat model was unloaded before the turn finished".to_string(),
+    ))
+}
 
-    runtime.context.clear_kv_cache();
+fn generation_token_limit(request: &GenerationRequest) -> u32 {
+    if request.response_schema.is_some() {
+        request.max_tokens
+    } else {
+        request.max_tokens.min(MAX_GENERATION_TOKENS)
+    }
+}
 
-    let prompt_text = format!(
-        "<|im_start|>system\n{}<|im_end|>\n<|im_start|>user\n{}<|im_end|>\n<|im_start|>assistant\n",
-        system_prompt, user_prompt
-    );
+fn decode_chat_turn<F>(
+    runtime: &mut ChatRuntime<'_>,
+    request: &GenerationRequest,
+    grammar: Option<&str>,
+    cancellation: &CancellationToken,
+    mut on_chunk: F,
+) -> Result<Generation, AnimaError>
+where
+    F: FnMut(&str) -> Result<(), AnimaError>,
+{
+    // Taken up front: any early return below leaves the KV cache in an unknown
+    // state, so the next turn must start clean unless this one finishes.
+    let previous_prefix = runtime.prefix_cache.take();
+    let system_prompt = request.system_prompt.as_str();
+    let sampling = &request.sampling;
+
+    let template = runtime.template;
+    let prompt_text = template.render(system_prompt, &request.turns);
+    let stop_sequences = template.stop_sequences();
 
     let prompt_tokens = runtime
         .model
         .str_to_token(&prompt_text, AddBos::Never)
         .map_err(|error| {
             log_debug_error("prompt_tokenization", &error);
-            format!("Prompt tokenization failed: {error}")
+            AnimaError::GenerationFailed(format!("Prompt tokenization failed: {error}"))
         })?;
 
     if prompt_tokens.is_empty() {
-        return Ok(String::new());
+        runtime.context.clear_kv_cache();
+        return Ok(Generation::empty(StopReason::EndOfTurn, 0));
     }
 
-    let context_limit = usize::try_from(DEFAULT_N_CTX)
-        .map_err(|error| format!("Invalid context configuration for DEFAULT_N_CTX={DEFAULT_N_CTX}: {error}"))?;
+    let n_ctx = runtime.config.n_ctx;
+    let context_limit = usize::try_from(n_ctx)
+        .map_err(|error| AnimaError::Internal(format!("Invalid context configuration for n_ctx={n_ctx}: {error}")))?;
     if prompt_tokens.len() > context_limit {
-        return Err("El contexto supera el límite de memoria".to_string());
+        return Err(AnimaError::ContextOverflow(format!(
+            "Prompt has {} tokens but the context window is {context_limit}",
+            prompt_tokens.len()
+        )));
     }
     if prompt_tokens.len() >= context_limit.saturating_sub(8) {
-        return Err(
-            "[Error: Límite de memoria alcanzado] El prompt supera la ventana de contexto.".to_string(),
-        );
+        return Err(AnimaError::ContextOverflow(format!(
+            "Prompt has {} tokens, leaving no room to generate in a {context_limit} token window",
+            prompt_tokens.len()
+        )));
     }
 
-    let requested_max_tokens = max_tokens.min(MAX_GENERATION_TOKENS);
+    let requested_max_tokens = generation_token_limit(request);
     let remaining_context = context_limit.saturating_sub(prompt_tokens.len() + 1);
     let effective_max_tokens = usize::try_from(requested_max_tokens)
-        .map_err(|error| format!("Invalid max_tokens value ({requested_max_tokens}): {error}"))?
+        .map_err(|error| AnimaError::Internal(format!("Invalid max_tokens value ({requested_max_tokens}): {error}")))?
         .min(remaining_context);
 
     if effective_max_tokens == 0 {
-        return Err(
-            "[Error: Límite de memoria alcanzado] No hay espacio para generar más tokens.".to_string(),
-        );
+        return Err(AnimaError::ContextOverflow(
+            "No room left in the context window to generate tokens".to_string(),
+        ));
     }
 
-    let mut n_past = 0_i32;
-    for chunk in prompt_tokens.chunks(SAFE_N_BATCH as usize) {
+    let prefix_cache_enabled = db::get_prefix_cache_enabled().unwrap_or(true);
+    let cache_epoch = prompt_cache::current_epoch();
+    let system_prefix_len = if prefix_cache_enabled {
+        system_prefix_token_len(runtime, system_prompt, &prompt_tokens)
+    } else {
+        0
+    };
+    let reused = match &previous_prefix {
+        Some(prefix) if prefix_cache_enabled => prefix.reusable_len(&prompt_tokens, cache_epoch),
+        _ => 0,
+    };
+    let reused = reuse_kv_prefix(&mut runtime.context, reused);
+
+    let mut n_past = i32::try_from(reused)
+        .map_err(|error| AnimaError::Internal(format!("Cached prefix length overflow ({reused}): {error}")))?;
+    for chunk in prompt_tokens[reused..].chunks(runtime.config.n_batch as usize) {
+        if cancellation.is_cancelled() {
+            runtime.context.clear_kv_cache();
+            return Ok(Generation::empty(StopReason::Cancelled, prompt_tokens.len()));
+        }
+
         let mut prompt_batch = LlamaBatch::new(chunk.len(), 1);
 
         for (index, token) in chunk.iter().enumerate() {
             let index_i32 = i32::try_from(index)
-                .map_err(|error| format!("Prompt chunk index overflow (index={index}): {error}"))?;
+                .map_err(|error| AnimaError::Internal(format!("Prompt chunk index overflow (index={index}): {error}")))?;
             let pos = n_past
                 .checked_add(index_i32)
                 .ok_or_else(|| {
-                    format!(
+                    AnimaError::Internal(format!(
                         "Prompt position overflow (n_past={n_past}, chunk_index={index}, chunk_len={})",
                         chunk.len()
-                    )
+                    ))
                 })?;
             let is_last_in_chunk = index + 1 == chunk.len();
 
             prompt_batch
                 .add(*token, pos, &[0], is_last_in_chunk)
-                .map_err(|error| format!("Prompt chunk add failed: {error}"))?;
+                .map_err(|error| AnimaError::Internal(format!("Prompt chunk add failed: {error}")))?;
         }
 
         runtime
@@ -860,35 +1680,30 @@ where
             })?;
 
         let chunk_len_i32 = i32::try_from(chunk.len())
-            .map_err(|error| format!("Prompt chunk length overflow (len={}): {error}", chunk.len()))?;
+            .map_err(|error| AnimaError::Internal(format!("Prompt chunk length overflow (len={}): {error}", chunk.len())))?;
         n_past = n_past
             .checked_add(chunk_len_i32)
             .ok_or_else(|| {
-                format!(
+                AnimaError::Internal(format!(
                     "Prompt accumulated length overflow (n_past={n_past}, chunk_len_i32={chunk_len_i32})"
-                )
+                ))
             })?;
     }
 
-    let mut sampler = if sampling_temperature <= 0.0 {
-        LlamaSampler::greedy()
-    } else {
-        let seed = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|duration| duration.subsec_nanos())
-            .unwrap_or(42);
-        LlamaSampler::chain_simple([
-            LlamaSampler::penalties(REPEAT_LAST_N, REPEAT_PENALTY, 0.0, 0.0),
-            LlamaSampler::top_k(40),
-            LlamaSampler::top_p(0.92, 1),
-            LlamaSampler::temp(sampling_temperature),
-            LlamaSampler::dist(seed),
-        ])
-    };
-
+    // `sample` accepts the token it returns, so generated tokens are never
+    // accepted by hand; a grammar fed a token twice rejects valid output.
+    let mut sampler = build_sampler(runtime.model, &sampling.sanitized());
     sampler.accept_many(prompt_tokens.iter());
+    if let Some(grammar) = grammar {
+        // Added after the prompt was accepted: prompt tokens only seed the
+        // repetition penalties, while the grammar starts at the reply.
+        let grammar = LlamaSampler::grammar(runtime.model, grammar, "root").map_err(|error| {
+            AnimaError::InvalidInput(format!("Output grammar was rejected: {error}"))
+        })?;
+        sampler = LlamaSampler::chain_simple([grammar, sampler]);
+    }
 
-    let stop_token_ids: HashSet<_> = STOP_SEQUENCES
+    let stop_token_ids: HashSet<_> = stop_sequences
         .iter()
         .filter_map(|sequence| {
             runtime
@@ -904,26 +1719,30 @@ where
                 })
         })
         .collect();
-    let max_stop_len = STOP_SEQUENCES
-        .iter()
-        .map(|sequence| sequence.len())
-        .max()
-        .unwrap_or(0);
-
     let mut generated = String::new();
     let mut pending_utf8 = Vec::<u8>::new();
-    let mut emitted_len = 0usize;
     let mut position = n_past;
+    let mut stop_reason = StopReason::MaxTokens;
+    let mut completion_tokens = 0_u32;
+    let decode_started = Instant::now();
 
     for _ in 0..effective_max_tokens {
+        if cancellation.is_cancelled() {
+            stop_reason = StopReason::Cancelled;
+            break;
+        }
+
         let token = sampler.sample(&runtime.context, -1);
 
         if runtime.model.is_eog_token(token) {
+            stop_reason = StopReason::EndOfTurn;
             break;
         }
         if stop_token_ids.contains(&token) {
+            stop_reason = StopReason::StopSequence;
             break;
         }
+        completion_tokens += 1;
 
         #[allow(deprecated)]
         let piece_bytes = runtime
@@ -931,7 +1750,7 @@ where
             .token_to_bytes(token, llama_cpp_2::model::Special::Tokenize)
             .map_err(|error| {
                 log_debug_error("token_to_bytes", &error);
-                format!("Token decode failed: {error}")
+                AnimaError::GenerationFailed(format!("Token decode failed: {error}"))
             })?;
 
         pending_utf8.extend_from_slice(&piece_bytes);
@@ -941,12 +1760,10 @@ where
             pending_utf8.clear();
             decoded
         } else {
-            sampler.accept(token);
-
             let mut token_batch = LlamaBatch::new(1, 1);
             token_batch
                 .add(token, position, &[0], true)
-                .map_err(|error| format!("Token batch add failed (token={token}, position={position}): {error}"))?;
+                .map_err(|error| AnimaError::Internal(format!("Token batch add failed (token={token}, position={position}): {error}")))?;
 
             runtime
                 .context
@@ -962,66 +1779,13 @@ where
             continue;
         };
 
+        on_chunk(&decoded_piece)?;
         generated.push_str(&decoded_piece);
 
-        if let Some(leak_index) = find_prompt_leak_index(&generated) {
-            if leak_index > emitted_len {
-                let leak_boundary = floor_char_boundary(&generated, leak_index);
-                if leak_boundary > emitted_len {
-                    let safe_chunk = sanitize_model_output(&generated[emitted_len..leak_boundary]);
-                    if !safe_chunk.is_empty() {
-                        on_chunk(&safe_chunk)?;
-                    }
-                    emitted_len = leak_boundary;
-                }
-            }
-            generated.truncate(leak_index);
-            sampler.accept(token);
-            break;
-        }
-
-        if let Some(stop_index) = find_stop_index(&generated) {
-            if stop_index > emitted_len {
-                let stop_boundary = floor_char_boundary(&generated, stop_index);
-                if stop_boundary > emitted_len {
-                    let safe_chunk = sanitize_model_output(&generated[emitted_len..stop_boundary]);
-                    if !safe_chunk.is_empty() {
-                        on_chunk(&safe_chunk)?;
-                    }
-                    emitted_len = stop_boundary;
-                }
-            }
-            generated.truncate(stop_index);
-            sampler.accept(token);
-            break;
-        }
-
-        if max_stop_len > 0 {
-            let safe_emit_end = floor_char_boundary(
-                &generated,
-                generated.len().saturating_sub(max_stop_len.saturating_sub(1)),
-            );
-            if safe_emit_end > emitted_len {
-                let safe_chunk = sanitize_model_output(&generated[emitted_len..safe_emit_end]);
-                if !safe_chunk.is_empty() {
-                    on_chunk(&safe_chunk)?;
-                }
-                emitted_len = safe_emit_end;
-            }
-        } else if generated.len() > emitted_len {
-            let safe_chunk = sanitize_model_output(&generated[emitted_len..]);
-            if !safe_chunk.is_empty() {
-                on_chunk(&safe_chunk)?;
-            }
-            emitted_len = generated.len();
-        }
-
-        sampler.accept(token);
-
         let mut token_batch = LlamaBatch::new(1, 1);
         token_batch
             .add(token, position, &[0], true)
-            .map_err(|error| format!("Token batch add failed (token={token}, position={position}): {error}"))?;
+            .map_err(|error| AnimaError::Internal(format!("Token batch add failed (token={token}, position={position}): {error}")))?;
 
         runtime
             .context
@@ -1037,82 +1801,117 @@ where
     }
 
     if !pending_utf8.is_empty() {
-        let recovered = String::from_utf8_lossy(&pending_utf8);
+        let recovered = String::from_utf8_lossy(&pending_utf8).into_owned();
+        on_chunk(&recovered)?;
         generated.push_str(&recovered);
-        pending_utf8.clear();
-    }
-
-    if let Some(stop_index) = find_stop_index(&generated) {
-        generated.truncate(stop_index);
-    }
-
-    if let Some(leak_index) = find_prompt_leak_index(&generated) {
-        generated.truncate(leak_index);
     }
-
-    if generated.len() > emitted_len {
-        let final_emit_end = floor_char_boundary(&generated, generated.len());
-        if final_emit_end > emitted_len {
-            let safe_chunk = sanitize_model_output(&generated[emitted_len..final_emit_end]);
-            if !safe_chunk.is_empty() {
-                on_chunk(&safe_chunk)?;
-            }
-        }
+    if reuse_kv_prefix(&mut runtime.context, system_prefix_len) > 0 {
+        runtime.prefix_cache = Some(PromptPrefixCache::new(
+            &prompt_tokens[..system_prefix_len],
+            cache_epoch,
+        ));
     }
-
-    let final_output = finalize_model_output(&generated);
-    runtime.context.clear_kv_cache();
-    Ok(final_output)
-}
-
-fn find_stop_index(text: &str) -> Option<usize> {
-    STOP_SEQUENCES
-        .iter()
-        .filter_map(|stop| text.find(stop))
-        .min()
-}
-
-fn find_prompt_leak_index(text: &str) -> Option<usize> {
-    let regex = prompt_leak_regex()?;
-    regex.find(text).map(|matched| matched.start())
+    Ok(Generation {
+        text: generated,
+        stop_reason,
+        prompt_tokens: u32::try_from(prompt_tokens.len()).unwrap_or(u32::MAX),
+        completion_tokens,
+        decode_duration: decode_started.elapsed(),
+    })
 }
 
-fn prompt_leak_regex() -> Option<&'static Regex> {
-    PROMPT_LEAK_REGEX
-        .get_or_init(|| {
-            Regex::new(
-                r"(?is)\[[^\]]*(?:System\s+note:|Remember\s+your\s+CRITICAL\s+INSTRUCTION|CRITICAL\s+INSTRUCTION)\s*[^\]]*\]",
-            )
-            .ok()
-        })
-        .as_ref()
+/// Number of leading prompt tokens that belong to the system prompt alone.
+/// Templates that fold the system prompt into the first user turn yield only
+/// their opening tokens here, which simply means less reuse.
+fn system_prefix_token_len(
+    runtime: &ChatRuntime<'_>,
+    system_prompt: &str,
+    prompt_tokens: &[LlamaToken],
+) -> usize {
+    let system_only = runtime.template.render(system_prompt, &[]);
+    match runtime.model.str_to_token(&system_only, AddBos::Never) {
+        Ok(system_tokens) => prompt_cache::common_prefix_len(&system_tokens, prompt_tokens)
+            .min(prompt_tokens.len().saturating_sub(1)),
+        Err(_) => 0,
+    }
 }
 
-fn sanitize_model_output(text: &str) -> String {
-    let removed = if let Some(regex) = prompt_leak_regex() {
-        regex.replace_all(text, "").into_owned()
-    } else {
-        text.to_string()
+/// Keeps the first `keep` positions of sequence 0 and drops the rest of the KV
+/// cache. Falls back to a full clear (returning 0) when `keep` is 0 or the
+/// partial removal is refused, preserving the stateless behaviour.
+fn reuse_kv_prefix(context: &mut LlamaContext<'_>, keep: usize) -> usize {
+    let Ok(keep_u32) = u32::try_from(keep) else {
+        context.clear_kv_cache();
+        return 0;
     };
-    normalize_escaped_newlines(&removed)
+    if keep_u32 == 0 {
+        context.clear_kv_cache();
+        return 0;
+    }
+    match context.clear_kv_cache_seq(Some(0), Some(keep_u32), None) {
+        Ok(true) => keep,
+        Ok(false) | Err(_) => {
+            append_hard_debug("prefix_cache", &format!("Partial KV removal refused at {keep}"));
+            context.clear_kv_cache();
+            0
+        }
+    }
 }
 
-fn finalize_model_output(text: &str) -> String {
-    sanitize_model_output(text).trim().to_string()
-}
+fn build_sampler(model: &LlamaModel, sampling: &SamplingParams) -> LlamaSampler {
+    if sampling.temperature <= 0.0 {
+        return LlamaSampler::greedy();
+    }
 
-fn normalize_escaped_newlines(text: &str) -> String {
-    text.replace("\\r\\n", "\n")
-        .replace("\\n", "\n")
-        .replace("\\t", "\t")
-}
+    let seed = sampling.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.subsec_nanos())
+            .unwrap_or(42)
+    });
 
-fn floor_char_boundary(text: &str, index: usize) -> usize {
-    let mut index = index.min(text.len());
-    while index > 0 && !text.is_char_boundary(index) {
-        index -= 1;
+    let mut samplers = vec![LlamaSampler::penalties(
+        sampling.repeat_last_n,
+        sampling.repeat_penalty,
+        0.0,
+        0.0,
+    )];
+
+    match sampling.mirostat {
+        1 => {
+            samplers.push(LlamaSampler::temp(sampling.temperature));
+            samplers.push(LlamaSampler::mirostat(
+                model.n_vocab(),
+                seed,
+                sampling.mirostat_tau,
+                sampling.mirostat_eta,
+                100,
+            ));
+        }
+        2 => {
+            samplers.push(LlamaSampler::temp(sampling.temperature));
+            samplers.push(LlamaSampler::mirostat_v2(
+                seed,
+                sampling.mirostat_tau,
+                sampling.mirostat_eta,
+            ));
+        }
+        _ => {
+            if sampling.top_k > 0 {
+                samplers.push(LlamaSampler::top_k(sampling.top_k));
+            }
+            if sampling.top_p < 1.0 {
+                samplers.push(LlamaSampler::top_p(sampling.top_p, 1));
+            }
+            if sampling.min_p > 0.0 {
+                samplers.push(LlamaSampler::min_p(sampling.min_p, 1));
+            }
+            samplers.push(LlamaSampler::temp(sampling.temperature));
+            samplers.push(LlamaSampler::dist(seed));
+        }
     }
-    index
+
+    LlamaSampler::chain_simple(samplers)
 }
 
 fn append_hard_debug(tag: &str, message: &str) {
@@ -1130,11 +1929,146 @@ fn log_debug_error<E: std::fmt::Debug>(tag: &str, error: &E) {
     append_hard_debug(tag, &format!("{:?}", error));
 }
 
-fn classify_inference_error(detail: &str) -> String {
+/// Decode failures are reported by llama.cpp as status codes only; a full KV
+/// cache is the common case, so those are surfaced as context overflow.
+fn classify_inference_error(detail: &str) -> AnimaError {
     append_hard_debug("classify_inference_error", detail);
     let lower = detail.to_lowercase();
     if lower.contains("context") || lower.contains("kv") || lower.contains("memory") {
-        return format!("[Error: Límite de memoria alcanzado] {detail}");
+        return AnimaError::ContextOverflow(detail.to_string());
+    }
+    AnimaError::GenerationFailed(detail.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_backend::test_support::MockSession;
+    use crate::model_manager::ModelKind;
+
+    #[test]
+    fn test_rerank_pair_drops_document_bos_and_fits_limit() {
+        // bos 1, eos 2, separator 3, as in an XLM-R style reranker.
+        let query = [1, 10, 11, 2];
+        let document = [1, 20, 21, 22, 23, 2];
+
+        assert_eq!(
+            rerank_pair(&query, &document, Some(1), Some(3), 64),
+            vec![1, 10, 11, 2, 3, 20, 21, 22, 23, 2]
+        );
+        assert_eq!(
+            rerank_pair(&query, &document, Some(1), None, 8),
+            vec![1, 10, 11, 2, 20, 21, 22, 2]
+        );
+    }
+
+    #[test]
+    fn test_sleep_cycle_persists_traits_and_episodic_memories() {
+        let session = MockSession::start();
+        db::insert_message("user", "Hoy fui a escalar con Marta y comí paella").unwrap();
+        session.backend.push_response(
+            r#"{"episodic": [{"importance": 4, "text": "Fue a escalar con Marta y comió paella"}], "semantic": ["Le gusta escalar"]}"#,
+        );
+
+        run_sleep_cycle().unwrap();
+
+        let traits = db::get_profile_traits().unwrap();
+        assert_eq!(traits.len(), 1);
+        assert_eq!(traits[0].content, "Le gusta escalar");
+        let memories = db::get_all_memories().unwrap();
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0].content, "Fue a escalar con Marta y comió paella");
+        let recalled = db::find_top_similar_memories("Marta", &[], "", 1, None).unwrap();
+        assert_eq!(recalled[0].importance, 0.75);
+        let requests = session.backend.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].turns[0].content.contains("escalar con Marta"));
+        assert_eq!(
+            requests[0].response_schema.as_ref(),
+            Some(&memory_extraction_schema())
+        );
+
+        let exported: Value = serde_json::from_str(&export_brain().unwrap()).unwrap();
+        assert_eq!(exported["user_profile"][0]["content"], "Le gusta escalar");
+        assert_eq!(
+            exported["memories"][0]["content"],
+            "Fue a escalar con Marta y comió paella"
+        );
+    }
+
+    #[test]
+    fn test_chat_stream_cuts_stop_sequences_and_prompt_leaks() {
+        let session = MockSession::start();
+        session
+            .backend
+            .push_response("Claro que sí. [System note: stay in character] Ignorado");
+        session.backend.push_response("Hasta luego<|im_end|><|im_start|>user fake");
+
+        for expected in ["Claro que sí.", "Hasta luego"] {
+            let mut streamed = String::new();
+            let generation = generate_response_with_context_stream(
+                "Hola",
+                &[],
+                &[],
+                &SamplingParams::default(),
+                256,
+                &CancellationToken::new(),
+                |chunk| {
+                    streamed.push_str(chunk);
+                    Ok(())
+                },
+            )
+            .unwrap();
+            assert_eq!(generation.stop_reason, StopReason::StopSequence);
+            assert_eq!(generation.text, expected);
+            assert_eq!(streamed.trim(), expected);
+        }
+    }
+
+    #[test]
+    fn test_only_chat_replies_are_capped() {
+        let mut request = GenerationRequest {
+            system_prompt: String::new(),
+            turns: Vec::new(),
+            sampling: SamplingParams::default(),
+            max_tokens: 1024,
+            priority: JobPriority::SleepCycle,
+            response_schema: None,
+        };
+        assert_eq!(generation_token_limit(&request), MAX_GENERATION_TOKENS);
+
+        request.response_schema = Some(memory_extraction_schema());
+        assert_eq!(generation_token_limit(&request), 1024);
+    }
+
+    /// Runs the memory extraction grammar through llama.cpp's grammar sampler;
+    /// a token accepted twice desyncs it and the reply stops parsing.
+    /// `ANIMA_TEST_CHAT_MODEL=path/to/model.gguf cargo test structured -- --ignored`
+    #[test]
+    #[ignore]
+    fn test_structured_output_follows_schema_grammar() {
+        let Ok(path) = std::env::var("ANIMA_TEST_CHAT_MODEL") else {
+            eprintln!("ANIMA_TEST_CHAT_MODEL is not set; skipping");
+            return;
+        };
+        let _session = MockSession::start();
+        backend::set_override(None);
+        model_manager::load(ModelKind::Chat, &path).unwrap();
+
+        let parsed = generate_structured(
+            "Extract facts about the user as JSON.",
+            "USER: My sister Lucia lives in Madrid and I adopted a cat last week.",
+            &memory_extraction_schema(),
+            1024,
+            JobPriority::Interactive,
+        )
+        .unwrap();
+        model_manager::unload(ModelKind::Chat).unwrap();
+
+        assert!(parsed["semantic"].is_array());
+        for event in parsed["episodic"].as_array().unwrap() {
+            assert!(event["text"].is_string());
+            assert!((1..=5).contains(&event["importance"].as_i64().unwrap()));
+        }
     }
-    format!("[Error: Inferencia fallida] {detail}")
 }
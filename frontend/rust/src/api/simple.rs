@@ -1,110 +1,290 @@
 use crate::ai;
+pub use crate::ai::StopReason;
+use crate::backend;
+pub use crate::backend::{BackendKind, BackendSettings};
+use crate::cancellation;
+use crate::chat_template::{ChatTurn, TurnRole};
 use crate::db;
-use chrono::{Local, TimeZone};
 pub use crate::db::ChatMessage;
 pub use crate::db::MemoryItem;
+pub use crate::db::MemoryMatch;
+pub use crate::db::MemoryScore;
+pub use crate::db::MemoryScoringWeights;
 pub use crate::db::ProfileTrait;
+pub use crate::db::SamplingParams;
+pub use crate::error::AnimaError;
 use crate::frb_generated::StreamSink;
+pub use crate::job_queue::{JobPriority, QueuedJob};
+pub use crate::load_strategy::LoadStrategy;
+use crate::local_http::LocalUrl;
+use crate::model_manager;
+pub use crate::model_manager::{InferenceQueueStatus, ModelKind, ModelState, ModelStatus};
+use crate::model_registry;
+pub use crate::model_registry::ModelInfo;
+use crate::prompt_budget::PromptSection;
+use crate::reembed;
+pub use crate::reembed::EmbeddingStatus;
+pub use crate::runtime_config::{ContextConfig, RuntimeSettings};
+use chrono::{Local, TimeZone};
 use std::any::Any;
 use std::panic::{self, AssertUnwindSafe};
 use std::path::PathBuf;
 use std::sync::mpsc;
-use std::sync::{Mutex, OnceLock};
 use std::thread;
 use std::time::Duration;
 
-static INIT_ERROR: OnceLock<Mutex<Option<String>>> = OnceLock::new();
-const HISTORY_START_TAG: &str = "<ANIMA_HISTORY>";
-const HISTORY_END_TAG: &str = "</ANIMA_HISTORY>";
-const USER_START_TAG: &str = "<ANIMA_USER>";
-const USER_END_TAG: &str = "</ANIMA_USER>";
+const MAX_STORED_HISTORY_MESSAGES: usize = 4;
+const MEMORY_CONTEXT_LIMIT: usize = 3;
+/// Candidates the reranker chooses from per injected memory.
+const RERANK_CANDIDATES_PER_RESULT: usize = 4;
+const MAX_CHAT_TOKENS: u32 = 512;
+
+/// One prior dialogue turn; `role` is `"user"` or `"assistant"`.
+#[derive(Debug, Clone)]
+pub struct Turn {
+    pub role: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatOptions {
+    /// Overrides the persisted sampling settings for this request only.
+    pub sampling: Option<SamplingParams>,
+    pub max_tokens: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatRequest {
+    /// Id accepted by `cancel_generation`; requests without one cannot be cancelled.
+    pub request_id: Option<String>,
+    pub history: Vec<Turn>,
+    pub message: String,
+    pub options: ChatOptions,
+}
+
+/// Events pushed by the streaming chat calls, in order: `ContextRetrieved`,
+/// any number of `Token`s, then exactly one `Done` or `Error`.
+#[derive(Debug, Clone)]
+pub enum ChatEvent {
+    ContextRetrieved {
+        memories: Vec<MemoryMatch>,
+    },
+    Token(String),
+    Done {
+        prompt_tokens: u32,
+        completion_tokens: u32,
+        tokens_per_sec: f64,
+        stop_reason: StopReason,
+    },
+    /// `code` is `AnimaError::code()`, so the UI can localize it.
+    Error {
+        code: String,
+        detail: String,
+    },
+}
+
+/// Events pushed by `reembed_memories`: `Progress` after every committed
+/// step, then exactly one `Done` or `Error`.
+#[derive(Debug, Clone)]
+pub enum ReembedEvent {
+    Progress {
+        done: u32,
+        total: u32,
+    },
+    Done {
+        model_id: String,
+        reembedded: u32,
+    },
+    /// `code` is `AnimaError::code()`; `cancelled` when stopped with
+    /// `cancel_reembedding`. Memories done so far keep their new vectors.
+    Error {
+        code: String,
+        detail: String,
+    },
+}
+
+/// Assistant reply; when `stop_reason` is `Cancelled` the text is partial and
+/// has not been stored, so the caller decides whether to keep it.
+#[derive(Debug, Clone)]
+pub struct ChatCompletion {
+    pub text: String,
+    pub stop_reason: StopReason,
+}
+
+/// One prompt section after budgeting; `text` is exactly what the model sees.
+#[derive(Debug, Clone)]
+pub struct PromptSectionPreview {
+    pub name: String,
+    pub text: String,
+    pub tokens: u32,
+    pub requested_tokens: u32,
+    pub items_kept: u32,
+    pub items_total: u32,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct PromptPreview {
+    pub sections: Vec<PromptSectionPreview>,
+    pub memories: Vec<MemoryMatch>,
+    /// The full prompt after the chat template is applied.
+    pub rendered_prompt: String,
+    pub total_tokens: u32,
+    pub context_window: u32,
+    pub reserved_for_generation: u32,
+}
 
 #[flutter_rust_bridge::frb(sync)] // Synchronous mode for simplicity of the demo
 pub fn greet(name: String) -> String {
     format!("Hello, {name}!")
 }
 
+/// `request_id`, when given, is the id `cancel_generation` stops this reply by.
 #[flutter_rust_bridge::frb]
-pub fn send_message(message: String, temperature: f32, max_tokens: u32) -> String {
-    if let Some(error) = current_init_error() {
-        return format!("[Error del Sistema: Motor IA no cargado] {error}");
-    }
-
-    let (_user_message_id, relevant_context, model_prompt) = match prepare_message_context(&message) {
-        Ok(values) => values,
-        Err(detail) => {
-            eprintln!("{detail}");
-            return format!("Error: {detail}");
-        }
+pub fn send_message(
+    message: String,
+    temperature: f32,
+    max_tokens: u32,
+    request_id: Option<String>,
+) -> Result<String, AnimaError> {
+    let request = ChatRequest {
+        request_id,
+        history: load_stored_history(),
+        message,
+        options: ChatOptions {
+            sampling: Some(request_sampling_params(temperature)),
+            max_tokens,
+        },
     };
 
-    let _requested_temperature = temperature;
-    let effective_temperature = 0.7_f32;
+    send_chat(request).map(|completion| completion.text)
+}
+
+#[flutter_rust_bridge::frb]
+pub fn send_message_stream(
+    message: String,
+    temperature: f32,
+    max_tokens: u32,
+    request_id: Option<String>,
+    sink: StreamSink<ChatEvent>,
+) -> Result<(), AnimaError> {
+    send_chat_stream(
+        ChatRequest {
+            request_id,
+            history: load_stored_history(),
+            message,
+            options: ChatOptions {
+                sampling: Some(request_sampling_params(temperature)),
+                max_tokens,
+            },
+        },
+        sink,
+    )
+}
+
+#[flutter_rust_bridge::frb]
+pub fn send_chat(request: ChatRequest) -> Result<ChatCompletion, AnimaError> {
+    backend::ensure_chat_ready()?;
+
+    let (_user_message_id, memories) =
+        prepare_message_context(&request.message).inspect_err(|error| eprintln!("{error}"))?;
+    let relevant_context = format_memory_snippets(&memories);
 
-    let safe_max_tokens = max_tokens.min(512);
+    let history = history_turns(&request.history);
+    let sampling = request_options_sampling(&request.options);
+    let safe_max_tokens = request.options.max_tokens.min(MAX_CHAT_TOKENS);
+    let generation = cancellation::register(request.request_id.as_deref());
 
     let generation_result = panic::catch_unwind(AssertUnwindSafe(|| {
         ai::generate_response_with_context(
-            &model_prompt,
+            request.message.trim(),
+            &history,
             &relevant_context,
-            effective_temperature,
+            &sampling,
             safe_max_tokens,
+            generation.token(),
         )
     }));
 
-    let generated = match generation_result {
-        Ok(Ok(output)) if !output.is_empty() => output,
+    let completion = match generation_result {
+        Ok(Ok(output)) if output.stop_reason == StopReason::Cancelled => {
+            return Ok(ChatCompletion {
+                text: output.text,
+                stop_reason: output.stop_reason,
+            });
+        }
+        Ok(Ok(output)) if !output.text.is_empty() => ChatCompletion {
+            text: output.text,
+            stop_reason: output.stop_reason,
+        },
         Ok(Ok(_)) => {
-            "[Error: Límite de memoria alcanzado] Inferencia devolvió salida vacía.".to_string()
+            return Err(AnimaError::EmptyOutput(
+                "Inference returned empty output".to_string(),
+            ));
         }
         Ok(Err(error)) => {
-            let detail = format!("Error al generar respuesta LLM: {error}");
-            eprintln!("{detail}");
-            format_generation_error(&detail)
+            eprintln!("LLM generation failed: {error}");
+            return Err(error);
         }
         Err(payload) => {
-            let detail = format!(
+            let error = AnimaError::Internal(format!(
                 "Panic during LLM generation: {}",
                 panic_payload_to_string(payload)
-            );
-            eprintln!("{detail}");
-            format_generation_error(&detail)
+            ));
+            eprintln!("{error}");
+            return Err(error);
         }
     };
 
-    if let Err(error) = insert_message_with_timeout("assistant", &generated, Duration::from_secs(5)) {
+    if let Err(error) =
+        insert_message_with_timeout("assistant", &completion.text, Duration::from_secs(5))
+    {
         eprintln!("Failed to store assistant message safely: {error}");
     }
 
-    generated
+    Ok(completion)
 }
 
 #[flutter_rust_bridge::frb]
-pub fn send_message_stream(
-    message: String,
-    temperature: f32,
-    max_tokens: u32,
-    sink: StreamSink<String>,
-) -> Result<(), String> {
-    if let Some(error) = current_init_error() {
-        return Err(format!("[Error del Sistema: Motor IA no cargado] {error}"));
+pub fn send_chat_stream(
+    request: ChatRequest,
+    sink: StreamSink<ChatEvent>,
+) -> Result<(), AnimaError> {
+    if let Err(error) = backend::ensure_chat_ready() {
+        push_stream_error(&sink, &error);
+        return Ok(());
     }
 
-    let (_user_message_id, relevant_context, model_prompt) = prepare_message_context(&message)?;
-
-    let _requested_temperature = temperature;
-    let effective_temperature = 0.7_f32;
+    let (_user_message_id, memories) = match prepare_message_context(&request.message) {
+        Ok(values) => values,
+        Err(error) => {
+            eprintln!("{error}");
+            push_stream_error(&sink, &error);
+            return Ok(());
+        }
+    };
+    let relevant_context = format_memory_snippets(&memories);
+    let _ = sink.add(ChatEvent::ContextRetrieved { memories });
 
-    let safe_max_tokens = max_tokens.min(512);
+    let history = history_turns(&request.history);
+    let sampling = request_options_sampling(&request.options);
+    let safe_max_tokens = request.options.max_tokens.min(MAX_CHAT_TOKENS);
+    let generation = cancellation::register(request.request_id.as_deref());
+    let token = generation.token().clone();
 
     let generation_result = panic::catch_unwind(AssertUnwindSafe(|| {
         ai::generate_response_with_context_stream(
-            &model_prompt,
+            request.message.trim(),
+            &history,
             &relevant_context,
-            effective_temperature,
+            &sampling,
             safe_max_tokens,
+            generation.token(),
             |chunk| {
-                let _ = sink.add(chunk.to_string());
+                if sink.add(ChatEvent::Token(chunk.to_string())).is_err() {
+                    // The Dart listener is gone; stop decoding instead of generating into the void.
+                    token.cancel();
+                }
                 Ok(())
             },
         )
@@ -113,210 +293,273 @@ pub fn send_message_stream(
     let final_output = match generation_result {
         Ok(Ok(output)) => output,
         Ok(Err(error)) => {
-            let detail = format!("Error al generar respuesta LLM: {error}");
-            return Err(format_generation_error(&detail));
+            eprintln!("LLM stream generation failed: {error}");
+            push_stream_error(&sink, &error);
+            return Ok(());
         }
         Err(payload) => {
-            let detail = format!(
+            let error = AnimaError::Internal(format!(
                 "Panic during LLM stream generation: {}",
                 panic_payload_to_string(payload)
-            );
-            eprintln!("{detail}");
-            return Err(format_generation_error(&detail));
+            ));
+            eprintln!("{error}");
+            push_stream_error(&sink, &error);
+            return Ok(());
         }
     };
 
-    if final_output.trim().is_empty() {
-        return Err("[Error: Límite de memoria alcanzado] Inferencia stream devolvió salida vacía.".to_string());
+    if final_output.stop_reason != StopReason::Cancelled && final_output.text.trim().is_empty() {
+        push_stream_error(
+            &sink,
+            &AnimaError::EmptyOutput("Stream inference returned empty output".to_string()),
+        );
+        return Ok(());
     }
 
+    let _ = sink.add(ChatEvent::Done {
+        prompt_tokens: final_output.prompt_tokens,
+        completion_tokens: final_output.completion_tokens,
+        tokens_per_sec: final_output.tokens_per_sec(),
+        stop_reason: final_output.stop_reason,
+    });
+
     Ok(())
 }
 
+/// Dry run of `send_message`: retrieves memories and assembles the prompt
+/// against the stored history, but stores nothing and decodes nothing.
 #[flutter_rust_bridge::frb]
-pub fn get_temperature() -> f32 {
-    match db::get_temperature() {
-        Ok(temperature) => temperature,
-        Err(error) => {
-            eprintln!("Failed to load temperature: {error}");
-            0.7
-        }
-    }
+pub fn preview_prompt(message: String) -> Result<PromptPreview, AnimaError> {
+    backend::ensure_chat_ready()?;
+
+    let message = message.trim();
+    let memories = match embed_message(message)? {
+        Some(embedding) => retrieve_memories(message, &embedding, None)?,
+        None => Vec::new(),
+    };
+    let relevant_context = format_memory_snippets(&memories);
+    let history = history_turns(&load_stored_history());
+
+    let rendered =
+        ai::preview_chat_prompt(message, &history, &relevant_context, MAX_CHAT_TOKENS)?;
+    let assembled = &rendered.assembled;
+
+    let sections = assembled
+        .report
+        .sections
+        .iter()
+        .map(|allocation| {
+            let text = match allocation.section {
+                PromptSection::UserMessage => assembled.user_message.clone(),
+                PromptSection::CorePrompt => {
+                    format!("{}{}", assembled.core_prompt, assembled.system_suffix)
+                }
+                PromptSection::Memories => assembled.memory_block.clone(),
+                PromptSection::History => assembled
+                    .history
+                    .iter()
+                    .map(|turn| format!("[{}] {}", turn.role.as_str(), turn.content))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+                PromptSection::Profile => assembled.profile_block.clone(),
+            };
+            PromptSectionPreview {
+                name: allocation.section.name().to_string(),
+                text,
+                tokens: saturating_u32(allocation.granted_tokens),
+                requested_tokens: saturating_u32(allocation.requested_tokens),
+                items_kept: saturating_u32(allocation.items_kept),
+                items_total: saturating_u32(allocation.items_total),
+                truncated: allocation.truncated,
+            }
+        })
+        .collect();
+
+    Ok(PromptPreview {
+        sections,
+        memories,
+        total_tokens: saturating_u32(rendered.token_count),
+        context_window: saturating_u32(assembled.report.context_window),
+        reserved_for_generation: saturating_u32(assembled.report.reserved_for_generation),
+        rendered_prompt: rendered.text,
+    })
 }
 
 #[flutter_rust_bridge::frb]
-pub fn set_temperature(temperature: f32) -> bool {
-    match db::set_temperature(temperature) {
-        Ok(()) => true,
-        Err(error) => {
-            eprintln!("Failed to save temperature: {error}");
-            false
-        }
-    }
+pub fn cancel_generation(request_id: String) -> bool {
+    cancellation::cancel(&request_id)
 }
 
 #[flutter_rust_bridge::frb]
-pub fn export_brain() -> Result<String, String> {
+pub fn get_temperature() -> Result<f32, AnimaError> {
+    db::get_temperature().map_err(|error| AnimaError::from(error).context("Failed to load temperature"))
+}
+
+#[flutter_rust_bridge::frb]
+pub fn set_temperature(temperature: f32) -> Result<(), AnimaError> {
+    db::set_temperature(temperature).map_err(|error| AnimaError::from(error).context("Failed to save temperature"))
+}
+
+#[flutter_rust_bridge::frb]
+pub fn get_sampling_params() -> Result<SamplingParams, AnimaError> {
+    db::get_sampling_params().map_err(|error| AnimaError::from(error).context("Failed to load sampling params"))
+}
+
+#[flutter_rust_bridge::frb]
+pub fn set_sampling_params(params: SamplingParams) -> Result<(), AnimaError> {
+    db::set_sampling_params(&params).map_err(|error| AnimaError::from(error).context("Failed to save sampling params"))
+}
+
+#[flutter_rust_bridge::frb]
+pub fn get_prefix_cache_enabled() -> Result<bool, AnimaError> {
+    db::get_prefix_cache_enabled()
+        .map_err(|error| AnimaError::from(error).context("Failed to load prefix cache setting"))
+}
+
+/// Turning this off restores a full KV cache clear before and after every turn.
+#[flutter_rust_bridge::frb]
+pub fn set_prefix_cache_enabled(enabled: bool) -> Result<(), AnimaError> {
+    db::set_prefix_cache_enabled(enabled)
+        .map_err(|error| AnimaError::from(error).context("Failed to save prefix cache setting"))
+}
+
+#[flutter_rust_bridge::frb]
+pub fn get_lexical_weight() -> Result<f32, AnimaError> {
+    db::get_lexical_weight()
+        .map_err(|error| AnimaError::from(error).context("Failed to load retrieval weighting"))
+}
+
+/// Share of memory retrieval given to full-text ranking: 0 ranks by meaning
+/// only, 1 by shared words only. Clamped to that range.
+#[flutter_rust_bridge::frb]
+pub fn set_lexical_weight(weight: f32) -> Result<(), AnimaError> {
+    db::set_lexical_weight(weight)
+        .map_err(|error| AnimaError::from(error).context("Failed to save retrieval weighting"))
+}
+
+#[flutter_rust_bridge::frb]
+pub fn get_mmr_lambda() -> Result<f32, AnimaError> {
+    db::get_mmr_lambda()
+        .map_err(|error| AnimaError::from(error).context("Failed to load memory diversity setting"))
+}
+
+/// 1 injects the best-scored memories as they are; lower values trade score
+/// for memories unlike the ones already picked. Clamped to 0..=1.
+#[flutter_rust_bridge::frb]
+pub fn set_mmr_lambda(lambda: f32) -> Result<(), AnimaError> {
+    db::set_mmr_lambda(lambda)
+        .map_err(|error| AnimaError::from(error).context("Failed to save memory diversity setting"))
+}
+
+#[flutter_rust_bridge::frb]
+pub fn get_memory_scoring_weights() -> Result<MemoryScoringWeights, AnimaError> {
+    db::get_memory_scoring_weights()
+        .map_err(|error| AnimaError::from(error).context("Failed to load memory scoring weights"))
+}
+
+/// Weights are clamped to 0..=10 and the half-life to at least 0.1 days.
+#[flutter_rust_bridge::frb]
+pub fn set_memory_scoring_weights(weights: MemoryScoringWeights) -> Result<(), AnimaError> {
+    db::set_memory_scoring_weights(&weights)
+        .map_err(|error| AnimaError::from(error).context("Failed to save memory scoring weights"))
+}
+
+#[flutter_rust_bridge::frb]
+pub fn export_brain() -> Result<String, AnimaError> {
     ai::export_brain()
 }
 
 #[flutter_rust_bridge::frb]
-pub fn save_assistant_message(message: String) -> bool {
-    match insert_message_with_timeout("assistant", &message, Duration::from_secs(5)) {
-        Ok(_) => true,
-        Err(error) => {
-            eprintln!("Failed to store assistant message safely: {error}");
-            false
-        }
-    }
+pub fn save_assistant_message(message: String) -> Result<(), AnimaError> {
+    insert_message_with_timeout("assistant", &message, Duration::from_secs(5))
+        .map(|_| ())
+        .map_err(|error| error.context("Failed to store assistant message"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn generate_proactive_greeting(time_of_day: String) -> Result<String, String> {
-    if let Some(error) = current_init_error() {
-        return Err(format!("[Error del Sistema: Motor IA no cargado] {error}"));
-    }
+pub fn generate_proactive_greeting(time_of_day: String) -> Result<String, AnimaError> {
+    backend::ensure_chat_ready()?;
 
     let generation_result = panic::catch_unwind(AssertUnwindSafe(|| {
         ai::generate_proactive_greeting(&time_of_day)
     }));
 
     match generation_result {
-        Ok(Ok(output)) if output.trim().is_empty() => Err(
-            "[Error del Sistema: Inferencia de saludo devolvió salida vacía (possible OOM, context limit, or sampling collapse)]"
+        Ok(Ok(output)) if output.trim().is_empty() => Err(AnimaError::EmptyOutput(
+            "Greeting inference returned empty output (possible OOM, context limit, or sampling collapse)"
                 .to_string(),
-        ),
+        )),
         Ok(Ok(output)) => Ok(output),
-        Ok(Err(error)) => Err(format!("Error al generar saludo LLM: {error}")),
-        Err(payload) => Err(format!(
-            "Error interno del motor: panic during greeting inference: {}",
+        Ok(Err(error)) => Err(error.context("Greeting generation failed")),
+        Err(payload) => Err(AnimaError::Internal(format!(
+            "Panic during greeting inference: {}",
             panic_payload_to_string(payload)
-        )),
+        ))),
     }
 }
 
 #[flutter_rust_bridge::frb]
-pub fn get_chat_history() -> Vec<ChatMessage> {
-    match db::get_all_messages() {
-        Ok(history) => history,
-        Err(error) => {
-            eprintln!("Failed to fetch chat history: {error}");
-            Vec::new()
-        }
-    }
+pub fn get_chat_history() -> Result<Vec<ChatMessage>, AnimaError> {
+    db::get_all_messages().map_err(|error| AnimaError::from(error).context("Failed to fetch chat history"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn get_all_memories() -> Vec<MemoryItem> {
-    match db::get_all_memories() {
-        Ok(memories) => memories,
-        Err(error) => {
-            eprintln!("Failed to fetch memories: {error}");
-            Vec::new()
-        }
-    }
+pub fn get_all_memories() -> Result<Vec<MemoryItem>, AnimaError> {
+    db::get_all_memories().map_err(|error| AnimaError::from(error).context("Failed to fetch memories"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn search_memories(query: String) -> Result<Vec<MemoryItem>, String> {
-    db::search_memories(&query).map_err(|error| format!("Memory search failed: {error}"))
+pub fn search_memories(query: String) -> Result<Vec<MemoryItem>, AnimaError> {
+    db::search_memories(&query).map_err(|error| AnimaError::from(error).context("Memory search failed"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn delete_memory(id: i64) -> bool {
-    match db::delete_memory(id) {
-        Ok(()) => true,
-        Err(error) => {
-            eprintln!("Failed to delete memory {id}: {error}");
-            false
-        }
-    }
+pub fn delete_memory(id: i64) -> Result<(), AnimaError> {
+    db::delete_memory(id).map_err(|error| AnimaError::from(error).context(&format!("Failed to delete memory {id}")))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn get_core_prompt() -> String {
-    match db::get_core_prompt() {
-        Ok(prompt) => prompt,
-        Err(error) => {
-            eprintln!("Failed to load core prompt: {error}");
-            String::new()
-        }
-    }
+pub fn get_core_prompt() -> Result<String, AnimaError> {
+    db::get_core_prompt().map_err(|error| AnimaError::from(error).context("Failed to load core prompt"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn set_core_prompt(prompt: String) -> bool {
-    match db::set_core_prompt(&prompt) {
-        Ok(()) => true,
-        Err(error) => {
-            eprintln!("Failed to save core prompt: {error}");
-            false
-        }
-    }
+pub fn set_core_prompt(prompt: String) -> Result<(), AnimaError> {
+    db::set_core_prompt(&prompt).map_err(|error| AnimaError::from(error).context("Failed to save core prompt"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn get_user_name() -> String {
-    match db::get_user_name() {
-        Ok(name) => name,
-        Err(error) => {
-            eprintln!("Failed to load user name: {error}");
-            String::new()
-        }
-    }
+pub fn get_user_name() -> Result<String, AnimaError> {
+    db::get_user_name().map_err(|error| AnimaError::from(error).context("Failed to load user name"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn set_user_name(name: String) -> bool {
-    match db::set_user_name(&name) {
-        Ok(()) => true,
-        Err(error) => {
-            eprintln!("Failed to save user name: {error}");
-            false
-        }
-    }
+pub fn set_user_name(name: String) -> Result<(), AnimaError> {
+    db::set_user_name(&name).map_err(|error| AnimaError::from(error).context("Failed to save user name"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn get_app_language() -> String {
-    match db::get_app_language() {
-        Ok(lang) => lang,
-        Err(error) => {
-            eprintln!("Failed to load app language: {error}");
-            "Español".to_string()
-        }
-    }
+pub fn get_app_language() -> Result<String, AnimaError> {
+    db::get_app_language().map_err(|error| AnimaError::from(error).context("Failed to load app language"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn set_app_language(lang: String) -> bool {
-    match db::set_app_language(&lang) {
-        Ok(()) => true,
-        Err(error) => {
-            eprintln!("Failed to save app language: {error}");
-            false
-        }
-    }
+pub fn set_app_language(lang: String) -> Result<(), AnimaError> {
+    db::set_app_language(&lang).map_err(|error| AnimaError::from(error).context("Failed to save app language"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn add_profile_trait(category: String, content: String) -> bool {
-    match db::add_profile_trait(&category, &content) {
-        Ok(()) => true,
-        Err(error) => {
-            eprintln!("Failed to add profile trait: {error}");
-            false
-        }
-    }
+pub fn add_profile_trait(category: String, content: String) -> Result<(), AnimaError> {
+    db::add_profile_trait(&category, &content).map_err(|error| AnimaError::from(error).context("Failed to add profile trait"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn export_database(dest_path: String) -> Result<bool, String> {
-    db::export_database(&dest_path).map_err(|error| format!("Export failed: {error}"))
+pub fn export_database(dest_path: String) -> Result<bool, AnimaError> {
+    db::export_database(&dest_path).map_err(|error| AnimaError::from(error).context("Export failed"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn factory_reset() -> Result<bool, String> {
+pub fn factory_reset() -> Result<bool, AnimaError> {
     eprintln!("[factory_reset_api] request received");
     db::factory_reset().map(|_| {
         eprintln!("[factory_reset_api] completed");
@@ -325,102 +568,329 @@ pub fn factory_reset() -> Result<bool, String> {
 }
 
 #[flutter_rust_bridge::frb]
-pub fn run_sleep_cycle() -> Result<bool, String> {
+pub fn run_sleep_cycle() -> Result<bool, AnimaError> {
     ai::run_sleep_cycle().map(|_| true)
 }
 
 #[flutter_rust_bridge::frb]
-pub fn get_profile_traits() -> Vec<ProfileTrait> {
-    match db::get_profile_traits() {
-        Ok(traits) => traits,
-        Err(error) => {
-            eprintln!("Failed to fetch profile traits: {error}");
-            Vec::new()
-        }
-    }
+pub fn get_profile_traits() -> Result<Vec<ProfileTrait>, AnimaError> {
+    db::get_profile_traits().map_err(|error| AnimaError::from(error).context("Failed to fetch profile traits"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn clear_profile() -> bool {
-    match db::clear_profile() {
-        Ok(()) => true,
-        Err(error) => {
-            eprintln!("Failed to clear profile: {error}");
-            false
-        }
-    }
+pub fn clear_profile() -> Result<(), AnimaError> {
+    db::clear_profile().map_err(|error| AnimaError::from(error).context("Failed to clear profile"))
 }
 
 #[flutter_rust_bridge::frb]
-pub fn init_app(chat_model_path: String, embedding_model_path: String) {
+pub fn init_app(chat_model_path: String, embedding_model_path: String) -> Result<(), AnimaError> {
     // Default utilities - feel free to customize
     flutter_rust_bridge::setup_default_user_utils();
 
     if let Err(error) = db::init_db() {
-        eprintln!("Failed to initialize local SQLite DB: {error}");
+        let error = AnimaError::from(error).context("Failed to initialize local SQLite DB");
+        eprintln!("{error}");
+        return Err(error);
     }
 
-    let resolved_chat = match resolve_model_path(&chat_model_path) {
-        Ok(path) => path,
-        Err(error) => {
-            set_init_error(Some(error.clone()));
-            eprintln!("{error}");
-            return;
-        }
+    // A model picked in the registry wins over the bundled default path.
+    let chat_model_path =
+        model_registry::selected_model_path(ModelKind::Chat).unwrap_or(chat_model_path);
+    let embedding_model_path = model_registry::selected_model_path(ModelKind::Embedding)
+        .unwrap_or(embedding_model_path);
+
+    // Both slots are attempted so the status reports each model separately.
+    // Slots served by a local server or the mock are not loaded at all.
+    let backends = backend::settings();
+    let chat = match backends.chat_backend {
+        BackendKind::Llama => load_model(ModelKind::Chat, chat_model_path),
+        BackendKind::OpenAiServer | BackendKind::Mock => Ok(()),
+    };
+    let embedding = match backends.embedding_backend {
+        BackendKind::Llama => load_model(ModelKind::Embedding, embedding_model_path),
+        BackendKind::OpenAiServer | BackendKind::Mock => Ok(()),
     };
 
-    let resolved_embedding = match resolve_model_path(&embedding_model_path) {
-        Ok(path) => path,
+    // The reranker is optional: it is only loaded once selected, and without
+    // it retrieval keeps its own order.
+    if let Some(reranker_model_path) = model_registry::selected_model_path(ModelKind::Reranker) {
+        if let Err(error) = model_manager::load(ModelKind::Reranker, &reranker_model_path) {
+            eprintln!("[init_app] reranker not loaded, continuing without it: {error}");
+        }
+    }
+    chat.and(embedding)
+}
+
+#[flutter_rust_bridge::frb]
+pub fn get_model_status() -> ModelStatus {
+    model_manager::status()
+}
+
+/// Running and waiting inference jobs per worker; position 0 is running.
+#[flutter_rust_bridge::frb]
+pub fn get_inference_queue() -> InferenceQueueStatus {
+    model_manager::queue_status()
+}
+
+/// Stops a running sleep cycle turn so the user's request starts right away.
+/// Queuing a chat turn already does this; the preempted turn is queued again
+/// behind it.
+#[flutter_rust_bridge::frb]
+pub fn preempt_background_jobs() -> bool {
+    model_manager::preempt_background_jobs()
+}
+
+/// Loads a model into its slot, replacing the current one. Relative paths are
+/// resolved like in `init_app`.
+#[flutter_rust_bridge::frb]
+pub fn load_model(kind: ModelKind, path: String) -> Result<(), AnimaError> {
+    let resolved = resolve_model_path(&path).inspect_err(|error| {
+        eprintln!("{error}");
+        model_manager::record_failure(kind, &path, error);
+    })?;
+    model_manager::load(kind, &resolved)
+}
+
+#[flutter_rust_bridge::frb]
+pub fn reload_model(kind: ModelKind) -> Result<(), AnimaError> {
+    model_manager::reload(kind)
+}
+
+/// Cancels running generations on that model and frees it.
+#[flutter_rust_bridge::frb]
+pub fn unload_model(kind: ModelKind) -> Result<(), AnimaError> {
+    model_manager::unload(kind)
+}
+
+/// Applies to the next load or reload.
+#[flutter_rust_bridge::frb]
+pub fn get_load_strategy() -> Result<LoadStrategy, AnimaError> {
+    db::get_load_strategy()
+        .map_err(|error| AnimaError::from(error).context("Failed to read load strategy"))
+}
+
+#[flutter_rust_bridge::frb]
+pub fn set_load_strategy(strategy: LoadStrategy) -> Result<(), AnimaError> {
+    db::set_load_strategy(strategy)
+        .map_err(|error| AnimaError::from(error).context("Failed to save load strategy"))
+}
+
+/// Context size, batch and thread settings. They apply when the chat context is
+/// next created, i.e. on the next load or `reload_model`.
+#[flutter_rust_bridge::frb]
+pub fn get_runtime_settings() -> Result<RuntimeSettings, AnimaError> {
+    db::get_runtime_settings()
+        .map_err(|error| AnimaError::from(error).context("Failed to read runtime settings"))
+}
+
+#[flutter_rust_bridge::frb]
+pub fn set_runtime_settings(settings: RuntimeSettings) -> Result<(), AnimaError> {
+    db::set_runtime_settings(&settings)
+        .map_err(|error| AnimaError::from(error).context("Failed to save runtime settings"))
+}
+
+/// What the loaded chat context was created with, after auto-tuning.
+#[flutter_rust_bridge::frb]
+pub fn get_chat_context_config() -> Result<ContextConfig, AnimaError> {
+    model_manager::chat_context_config()
+}
+
+/// GGUF files in the models folder, described from their headers.
+#[flutter_rust_bridge::frb]
+pub fn list_models() -> Result<Vec<ModelInfo>, AnimaError> {
+    model_registry::list_models()
+}
+
+/// Loads the model with that registry id as the chat model and keeps using it
+/// on later starts.
+#[flutter_rust_bridge::frb]
+pub fn select_chat_model(id: String) -> Result<ModelInfo, AnimaError> {
+    model_registry::select_model(ModelKind::Chat, &id)
+}
+
+#[flutter_rust_bridge::frb]
+pub fn select_embedding_model(id: String) -> Result<ModelInfo, AnimaError> {
+    model_registry::select_model(ModelKind::Embedding, &id)
+}
+
+/// Loads a cross-encoder reranking model (rank pooling) that reorders the
+/// memories retrieved for each message.
+#[flutter_rust_bridge::frb]
+pub fn select_reranker_model(id: String) -> Result<ModelInfo, AnimaError> {
+    model_registry::select_model(ModelKind::Reranker, &id)
+}
+
+/// Unloads the reranker and forgets the selection, so retrieval runs without
+/// one from now on.
+#[flutter_rust_bridge::frb]
+pub fn clear_reranker_model() -> Result<(), AnimaError> {
+    model_manager::unload(ModelKind::Reranker)?;
+    db::set_reranker_model_id("")
+        .map_err(|error| AnimaError::from(error).context("Failed to save model selection"))
+}
+
+#[flutter_rust_bridge::frb]
+pub fn get_models_dir() -> Result<String, AnimaError> {
+    model_registry::models_dir().map(|dir| dir.to_string_lossy().to_string())
+}
+
+#[flutter_rust_bridge::frb]
+pub fn set_models_dir(path: String) -> Result<(), AnimaError> {
+    model_registry::set_models_dir(&path)
+}
+
+/// Whether memories need `reembed_memories` after an embedding model change.
+#[flutter_rust_bridge::frb]
+pub fn get_embedding_status() -> Result<EmbeddingStatus, AnimaError> {
+    reembed::embedding_status()
+}
+
+/// Re-embeds memories stored by another embedding model with the loaded one.
+/// Safe to stop at any point; calling it again continues with what is left.
+#[flutter_rust_bridge::frb]
+pub fn reembed_memories(sink: StreamSink<ReembedEvent>) -> Result<(), AnimaError> {
+    let result = reembed::run(|progress| {
+        let event = ReembedEvent::Progress {
+            done: progress.done,
+            total: progress.total,
+        };
+        if sink.add(event).is_err() {
+            // Nobody is listening; stop after this step, the rest resumes later.
+            reembed::cancel();
+        }
+    });
+
+    let event = match result {
+        Ok(progress) => ReembedEvent::Done {
+            model_id: progress.model_id,
+            reembedded: progress.done,
+        },
         Err(error) => {
-            set_init_error(Some(error.clone()));
-            eprintln!("{error}");
-            return;
+            eprintln!("Re-embedding failed: {error}");
+            ReembedEvent::Error {
+                code: error.code().to_string(),
+                detail: error.detail().to_string(),
+            }
         }
     };
+    let _ = sink.add(event);
+    Ok(())
+}
 
-    if let Err(error) = ai::init_ai_models(&resolved_chat, &resolved_embedding) {
-        let detail = format!(
-            "Failed to initialize AI models (chat: {resolved_chat}, embeddings: {resolved_embedding}): {error}"
-        );
-        set_init_error(Some(detail.clone()));
-        eprintln!(
-            "{detail}"
-        );
-        return;
+#[flutter_rust_bridge::frb]
+pub fn cancel_reembedding() -> bool {
+    reembed::cancel()
+}
+
+#[flutter_rust_bridge::frb]
+pub fn get_backend_settings() -> Result<BackendSettings, AnimaError> {
+    db::get_backend_settings()
+        .map_err(|error| AnimaError::from(error).context("Failed to load backend settings"))
+}
+
+/// Saves the backend choice. A slot moved to the server or the mock has its
+/// local model unloaded so the same weights are not held twice; moving back
+/// needs a `load_model`.
+#[flutter_rust_bridge::frb]
+pub fn set_backend_settings(settings: BackendSettings) -> Result<(), AnimaError> {
+    let settings = settings.sanitized();
+    if settings.chat_backend == BackendKind::OpenAiServer
+        || settings.embedding_backend == BackendKind::OpenAiServer
+    {
+        LocalUrl::parse(&settings.server_url)?;
+    }
+    if settings.embedding_backend == BackendKind::OpenAiServer
+        && settings.server_embedding_model.is_empty()
+    {
+        return Err(AnimaError::InvalidInput(
+            "Server embeddings need the embedding model's name".to_string(),
+        ));
     }
+    db::set_backend_settings(&settings)
+        .map_err(|error| AnimaError::from(error).context("Failed to save backend settings"))?;
+
+    if settings.chat_backend != BackendKind::Llama {
+        model_manager::unload(ModelKind::Chat)?;
+    }
+    if settings.embedding_backend != BackendKind::Llama {
+        model_manager::unload(ModelKind::Embedding)?;
+    }
+    Ok(())
+}
 
-    set_init_error(None);
+fn persisted_sampling_params() -> SamplingParams {
+    db::get_sampling_params().unwrap_or_else(|error| {
+        eprintln!("Failed to load sampling params, using defaults: {error}");
+        SamplingParams::default()
+    })
 }
 
-fn current_init_error() -> Option<String> {
-    let lock = INIT_ERROR.get_or_init(|| Mutex::new(None));
-    match lock.lock() {
-        Ok(state) => state.clone(),
-        Err(_) => Some("Error interno: estado de inicialización corrupto".to_string()),
+/// Persisted sampling settings with the caller's temperature applied on top.
+/// A negative or non-finite temperature keeps the persisted value.
+fn request_sampling_params(temperature: f32) -> SamplingParams {
+    persisted_sampling_params().with_temperature(temperature)
+}
+
+fn push_stream_error(sink: &StreamSink<ChatEvent>, error: &AnimaError) {
+    let _ = sink.add(ChatEvent::Error {
+        code: error.code().to_string(),
+        detail: error.detail().to_string(),
+    });
+}
+
+fn request_options_sampling(options: &ChatOptions) -> SamplingParams {
+    match &options.sampling {
+        Some(sampling) => sampling.sanitized(),
+        None => persisted_sampling_params(),
     }
 }
 
-fn set_init_error(value: Option<String>) {
-    let lock = INIT_ERROR.get_or_init(|| Mutex::new(None));
-    if let Ok(mut state) = lock.lock() {
-        *state = value;
+/// Recent dialogue from `messages`, used when the caller does not send its own history.
+/// Must run before the current user message is stored.
+fn load_stored_history() -> Vec<Turn> {
+    match db::get_recent_dialogue(MAX_STORED_HISTORY_MESSAGES) {
+        Ok(messages) => messages
+            .into_iter()
+            .map(|message| Turn {
+                role: message.role,
+                content: message.content,
+            })
+            .collect(),
+        Err(error) => {
+            eprintln!("Failed to load recent history: {error}");
+            Vec::new()
+        }
     }
 }
 
-fn resolve_model_path(model_path: &str) -> Result<String, String> {
+fn history_turns(history: &[Turn]) -> Vec<ChatTurn> {
+    history
+        .iter()
+        .filter_map(|turn| {
+            TurnRole::from_message_role(&turn.role).map(|role| ChatTurn {
+                role,
+                content: turn.content.clone(),
+            })
+        })
+        .collect()
+}
+
+fn resolve_model_path(model_path: &str) -> Result<String, AnimaError> {
     let as_path = PathBuf::from(model_path);
     if as_path.is_absolute() {
         if as_path.exists() {
             return Ok(as_path.to_string_lossy().to_string());
         }
-        return Err(format!(
+        return Err(AnimaError::ModelLoadFailed(format!(
             "Model file not found exactly at: {}",
             as_path.display()
-        ));
+        )));
     }
 
     let cwd = std::env::current_dir().map_err(|error| {
-        format!("No se pudo leer el directorio actual para resolver '{model_path}': {error}")
+        AnimaError::Io(format!(
+            "Cannot read the current directory to resolve '{model_path}': {error}"
+        ))
     })?;
 
     let candidates = vec![
@@ -442,123 +912,174 @@ fn resolve_model_path(model_path: &str) -> Result<String, String> {
         .collect::<Vec<String>>()
         .join(" | ");
 
-    Err(format!(
-        "No se encontró el modelo '{model_path}'. CWD actual: '{}'. Rutas probadas: {tried}",
+    Err(AnimaError::ModelLoadFailed(format!(
+        "Model '{model_path}' not found. Current directory: '{}'. Tried: {tried}",
         cwd.display()
-    ))
-}
-
-fn prepare_message_context(message: &str) -> Result<(i64, Vec<String>, String), String> {
-    let (user_message, model_prompt) = parse_client_message_payload(message);
-
-    let user_message_id = insert_message_with_timeout("user", &user_message, Duration::from_secs(5))
-        .map_err(|error| format!("Error de DB al guardar mensaje de usuario: {error}"))?;
-
-    let mut relevant_context = Vec::<String>::new();
-
-    match ai::generate_embedding(&user_message) {
-        Ok(embedding) if !embedding.is_empty() => {
-            db::insert_memory(
-                user_message_id,
-                &embedding,
-                "episodic",
-                db::current_unix_timestamp(),
-            )
-                .map_err(|error| format!("Error de DB al guardar embedding: {error}"))?;
-
-            let matches = db::find_top_similar_memories(&embedding, 3, Some(user_message_id))
-                .map_err(|error| format!("Error al recuperar contexto semántico: {error}"))?;
-
-            relevant_context = matches
-                .into_iter()
-                .map(|memory| {
-                    if memory.memory_type == "semantic" {
-                        format!("- {}", memory.content)
-                    } else {
-                        let dt = Local
-                            .timestamp_opt(memory.memory_unix_timestamp, 0)
-                            .single();
-                        let date_label = dt
-                            .map(|value| value.format("%Y-%m-%d").to_string())
-                            .unwrap_or_else(|| "unknown-date".to_string());
-                        format!("- [{}]: {}", date_label, memory.content)
-                    }
-                })
-                .collect();
-        }
-        Ok(_) => {}
-        Err(error) => {
-            return Err(format!("Error generando embedding: {error}"));
-        }
-    }
-
-    Ok((user_message_id, relevant_context, model_prompt))
+    )))
 }
 
-fn parse_client_message_payload(payload: &str) -> (String, String) {
-    let history = extract_tag_content(payload, HISTORY_START_TAG, HISTORY_END_TAG)
-        .map(|content| content.trim().to_string())
-        .unwrap_or_default();
+fn prepare_message_context(message: &str) -> Result<(i64, Vec<MemoryMatch>), AnimaError> {
+    let user_message = message.trim();
 
-    let user = extract_tag_content(payload, USER_START_TAG, USER_END_TAG)
-        .map(|content| content.trim().to_string())
-        .unwrap_or_else(|| payload.trim().to_string());
+    let user_message_id = insert_message_with_timeout("user", user_message, Duration::from_secs(5))
+        .map_err(|error| error.context("Failed to store user message"))?;
 
-    if user.is_empty() {
-        return (payload.trim().to_string(), payload.trim().to_string());
+    let Some(embedding) = embed_message(user_message)? else {
+        return Ok((user_message_id, Vec::new()));
+    };
+
+    db::insert_memory(
+        user_message_id,
+        &embedding.vector,
+        &embedding.model_id,
+        "episodic",
+        db::DEFAULT_MEMORY_IMPORTANCE,
+        db::current_unix_timestamp(),
+    )
+    .map_err(|error| AnimaError::from(error).context("Failed to store embedding"))?;
+
+    let memories = retrieve_memories(user_message, &embedding, Some(user_message_id))?;
+    let retrieved = memories.iter().map(|memory| memory.message_id).collect::<Vec<_>>();
+    if let Err(error) = db::record_memory_access(&retrieved) {
+        eprintln!("[memory] failed to record retrieval: {error}");
+    }
+
+    Ok((user_message_id, memories))
+}
+
+/// Hybrid retrieval, reordered by the reranker model when one is loaded. The
+/// reranker is optional: without it, or if it fails, retrieval's own order
+/// is kept.
+fn retrieve_memories(
+    query: &str,
+    embedding: &ai::Embedding,
+    exclude_message_id: Option<i64>,
+) -> Result<Vec<MemoryMatch>, AnimaError> {
+    let reranking = model_manager::reranker_handle().is_some();
+    let candidates = if reranking {
+        MEMORY_CONTEXT_LIMIT * RERANK_CANDIDATES_PER_RESULT
+    } else {
+        MEMORY_CONTEXT_LIMIT
+    };
+    let mut memories = db::find_top_similar_memories(
+        query,
+        &embedding.vector,
+        &embedding.model_id,
+        candidates,
+        exclude_message_id,
+    )
+    .map_err(|error| AnimaError::from(error).context("Failed to retrieve semantic context"))?;
+    if !reranking || memories.len() <= 1 {
+        memories.truncate(MEMORY_CONTEXT_LIMIT);
+        return Ok(memories);
     }
 
-    if history.is_empty() {
-        return (user.clone(), user);
+    let documents = memories.iter().map(|memory| memory.content.as_str()).collect::<Vec<_>>();
+    let scores = ai::rerank(query, &documents, JobPriority::Interactive).unwrap_or_else(|error| {
+        eprintln!("[memory] reranking failed, keeping retrieval order: {error}");
+        None
+    });
+    match scores {
+        Some(scores) => {
+            apply_rerank_scores(memories, &scores, &embedding.model_id, MEMORY_CONTEXT_LIMIT)
+                .map_err(|error| {
+                    AnimaError::from(error).context("Failed to diversify reranked memories")
+                })
+        }
+        None => {
+            memories.truncate(MEMORY_CONTEXT_LIMIT);
+            Ok(memories)
+        }
     }
+}
 
-    let model_prompt = format!(
-        "Historial reciente (máximo 4 mensajes):\n{}\n\nMensaje actual del usuario:\n{}",
-        history, user
-    );
+/// Keeps the top `limit` by cross-encoder score, picked by MMR so that
+/// near-duplicates of a kept memory give way to other facts; retrieval order
+/// breaks ties.
+fn apply_rerank_scores(
+    memories: Vec<MemoryMatch>,
+    scores: &[f32],
+    embedding_model: &str,
+    limit: usize,
+) -> rusqlite::Result<Vec<MemoryMatch>> {
+    let reranked = memories
+        .into_iter()
+        .zip(scores)
+        .map(|(memory, score)| MemoryMatch {
+            rerank_score: Some(*score),
+            ..memory
+        })
+        .collect::<Vec<_>>();
+    // Cross-encoder logits can be negative; MMR needs relevance on a 0..1 scale.
+    let relevance = reranked
+        .iter()
+        .map(|memory| {
+            let score = memory.rerank_score.unwrap_or(f32::NEG_INFINITY);
+            1.0 / (1.0 + (-score).exp())
+        })
+        .collect::<Vec<_>>();
+    db::diversify_memories(reranked, &relevance, embedding_model, limit)
+}
+
+/// `None` when the text produces no tokens to embed.
+fn embed_message(text: &str) -> Result<Option<ai::Embedding>, AnimaError> {
+    match ai::generate_embedding(text) {
+        Ok(embedding) if !embedding.vector.is_empty() => Ok(Some(embedding)),
+        Ok(_) => Ok(None),
+        Err(error) => Err(error.context("Failed to embed user message")),
+    }
+}
 
-    (user, model_prompt)
+fn saturating_u32(value: usize) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
 }
 
-fn extract_tag_content<'a>(input: &'a str, start_tag: &str, end_tag: &str) -> Option<&'a str> {
-    let start_index = input.find(start_tag)? + start_tag.len();
-    let end_index = input[start_index..].find(end_tag)? + start_index;
-    Some(&input[start_index..end_index])
+fn format_memory_snippets(memories: &[MemoryMatch]) -> Vec<String> {
+    memories
+        .iter()
+        .map(|memory| {
+            if memory.memory_type == "semantic" {
+                format!("- {}", memory.content)
+            } else {
+                let dt = Local
+                    .timestamp_opt(memory.memory_unix_timestamp, 0)
+                    .single();
+                let date_label = dt
+                    .map(|value| value.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| "unknown-date".to_string());
+                format!("- [{}]: {}", date_label, memory.content)
+            }
+        })
+        .collect()
 }
 
-fn insert_message_with_timeout(role: &str, content: &str, timeout: Duration) -> Result<i64, String> {
+fn insert_message_with_timeout(
+    role: &str,
+    content: &str,
+    timeout: Duration,
+) -> Result<i64, AnimaError> {
     let role_owned = role.to_string();
     let content_owned = content.to_string();
 
-    let (tx, rx) = mpsc::channel::<std::result::Result<i64, String>>();
+    let (tx, rx) = mpsc::channel::<std::result::Result<i64, AnimaError>>();
 
     thread::spawn(move || {
         let result = db::insert_message(&role_owned, &content_owned)
-            .map_err(|error| format!("DB insert failed: {error}"));
+            .map_err(|error| AnimaError::from(error).context("DB insert failed"));
         let _ = tx.send(result);
     });
 
     match rx.recv_timeout(timeout) {
         Ok(result) => result,
-        Err(mpsc::RecvTimeoutError::Timeout) => {
-            Err(format!("DB insert timeout after {}s", timeout.as_secs()))
-        }
-        Err(error) => Err(format!("DB insert channel error: {error}")),
+        // A stalled insert almost always means another connection holds the write lock.
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(AnimaError::DbLocked(format!(
+            "DB insert timeout after {}s",
+            timeout.as_secs()
+        ))),
+        Err(error) => Err(AnimaError::Internal(format!("DB insert channel error: {error}"))),
     }
 }
 
-fn format_generation_error(detail: &str) -> String {
-    let lowered = detail.to_lowercase();
-    if lowered.contains("memory")
-        || lowered.contains("context")
-        || lowered.contains("kv")
-        || lowered.contains("oom")
-    {
-        return format!("[Error: Límite de memoria alcanzado] {detail}");
-    }
-    format!("[Error: Inferencia fallida] {detail}")
-}
-
 fn panic_payload_to_string(payload: Box<dyn Any + Send>) -> String {
     if let Some(message) = payload.downcast_ref::<&str>() {
         return (*message).to_string();
@@ -568,3 +1089,91 @@ fn panic_payload_to_string(payload: Box<dyn Any + Send>) -> String {
     }
     "non-string panic payload".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_backend::test_support::MockSession;
+
+    #[test]
+    fn test_prepare_message_context_retrieves_related_memory() {
+        let _session = MockSession::start();
+
+        let (first_id, first_memories) =
+            prepare_message_context("  I love hiking in the mountains  ").unwrap();
+        assert!(first_memories.is_empty());
+
+        let (second_id, memories) =
+            prepare_message_context("hiking in the mountains this weekend").unwrap();
+        assert_ne!(first_id, second_id);
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0].message_id, first_id);
+        assert_eq!(memories[0].content, "I love hiking in the mountains");
+    }
+
+    #[test]
+    fn test_rerank_scores_reorder_and_truncate() {
+        let _session = MockSession::start();
+        for content in ["red fox", "blue whale", "green frog"] {
+            let id = db::insert_message("user", content).unwrap();
+            db::insert_memory(id, &[1.0, 0.0], "mock", "episodic", 0.5, 0).unwrap();
+        }
+        let memories = db::find_top_similar_memories("", &[1.0, 0.0], "mock", 3, None).unwrap();
+        let retrieved = memories.iter().map(|memory| memory.content.clone()).collect::<Vec<_>>();
+
+        let reranked = apply_rerank_scores(memories, &[0.1, 2.5, 0.1], "mock", 2).unwrap();
+        assert_eq!(reranked.len(), 2);
+        assert_eq!(reranked[0].content, retrieved[1]);
+        assert_eq!(reranked[0].rerank_score, Some(2.5));
+        assert_eq!(reranked[1].content, retrieved[0]);
+    }
+
+    #[test]
+    fn test_reranking_does_not_bring_back_near_duplicates() {
+        let _session = MockSession::start();
+        for (content, vector) in [
+            ("hiking in the alps", [1.0, 0.0, 0.0]),
+            ("hiking in the alps again", [0.99, 0.14, 0.0]),
+            ("my sister is called Lucia", [0.0, 0.0, 1.0]),
+        ] {
+            let id = db::insert_message("user", content).unwrap();
+            db::insert_memory(id, &vector, "mock", "episodic", 0.5, 0).unwrap();
+        }
+        let memories = db::find_top_similar_memories("", &[0.6, 0.0, 0.8], "mock", 3, None).unwrap();
+        let scores = memories
+            .iter()
+            .map(|memory| match memory.content.as_str() {
+                "hiking in the alps" => 3.0,
+                "hiking in the alps again" => 2.9,
+                _ => 1.0,
+            })
+            .collect::<Vec<_>>();
+
+        let reranked = apply_rerank_scores(memories, &scores, "mock", 2).unwrap();
+        let contents = reranked.iter().map(|memory| memory.content.as_str()).collect::<Vec<_>>();
+        assert_eq!(contents, ["hiking in the alps", "my sister is called Lucia"]);
+    }
+
+    #[test]
+    fn test_mock_backend_can_be_selected_in_settings() {
+        let _session = MockSession::start();
+        backend::set_override(None);
+
+        set_backend_settings(BackendSettings {
+            chat_backend: BackendKind::Mock,
+            embedding_backend: BackendKind::Mock,
+            ..BackendSettings::default()
+        })
+        .unwrap();
+        let saved = get_backend_settings().unwrap();
+        assert_eq!(saved.chat_backend, BackendKind::Mock);
+        assert_eq!(saved.embedding_backend, BackendKind::Mock);
+
+        init_app(String::new(), String::new()).unwrap();
+        assert_eq!(backend::chat().unwrap().name(), "mock");
+        assert_eq!(backend::embedding().unwrap().name(), "mock");
+        assert!(!send_message("hola".to_string(), -1.0, 64, None)
+            .unwrap()
+            .is_empty());
+    }
+}
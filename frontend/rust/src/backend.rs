@@ -0,0 +1,225 @@
+use crate::ai::{self, Embedding, Generation};
+use crate::cancellation::CancellationToken;
+use crate::chat_template::{ChatTemplate, ChatTurn};
+use crate::db::{self, SamplingParams};
+use crate::error::AnimaError;
+use crate::job_queue::JobPriority;
+use crate::mock_backend::MockBackend;
+use crate::openai_backend::OpenAiBackend;
+use serde_json::Value;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+const DEFAULT_SERVER_URL: &str = "http://127.0.0.1:8080";
+const DEFAULT_SERVER_CONTEXT_WINDOW: u32 = 4096;
+const MIN_SERVER_CONTEXT_WINDOW: u32 = 512;
+const MAX_SERVER_CONTEXT_WINDOW: u32 = 1_048_576;
+
+static SERVER_BACKEND: Mutex<Option<(BackendSettings, Arc<OpenAiBackend>)>> = Mutex::new(None);
+static BACKEND_OVERRIDE: RwLock<Option<Arc<dyn InferenceBackend>>> = RwLock::new(None);
+static MOCK_BACKEND: OnceLock<Arc<MockBackend>> = OnceLock::new();
+
+/// One chat turn to generate.
+#[derive(Debug, Clone)]
+pub struct GenerationRequest {
+    pub system_prompt: String,
+    pub turns: Vec<ChatTurn>,
+    pub sampling: SamplingParams,
+    pub max_tokens: u32,
+    /// Where the turn queues on a local worker; servers schedule on their own.
+    pub priority: JobPriority,
+    /// JSON Schema the reply must match, for `ai::generate_structured`.
+    pub response_schema: Option<Value>,
+}
+
+/// Where generation, embedding and tokenization run.
+pub trait InferenceBackend: Send + Sync {
+    /// For logs.
+    fn name(&self) -> String;
+
+    /// Template the prompt budget measures per-turn overhead with. Servers
+    /// apply their own template, so there it is an approximation.
+    fn chat_template(&self) -> Result<ChatTemplate, AnimaError>;
+
+    /// Tokens the chat model can attend to, prompt and reply together.
+    fn context_window(&self) -> Result<usize, AnimaError>;
+
+    /// Streams the reply through `on_chunk`. An `on_chunk` error stops the
+    /// generation and is returned.
+    fn generate_stream(
+        &self,
+        request: &GenerationRequest,
+        cancellation: &CancellationToken,
+        on_chunk: &mut dyn FnMut(&str) -> Result<(), AnimaError>,
+    ) -> Result<Generation, AnimaError>;
+
+    /// Id stored next to every vector `embed` returns.
+    fn embedding_model_id(&self) -> Result<String, AnimaError>;
+
+    /// One embedding per text, in input order.
+    fn embed(&self, texts: &[&str], priority: JobPriority) -> Result<Vec<Embedding>, AnimaError>;
+
+    /// Chat model token ids.
+    fn tokenize(&self, text: &str) -> Result<Vec<i32>, AnimaError>;
+
+    fn count_tokens(&self, text: &str) -> Result<usize, AnimaError> {
+        self.tokenize(text).map(|tokens| tokens.len())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendKind {
+    /// llama.cpp in this process, using the models from `load_model`.
+    Llama,
+    /// An OpenAI-compatible server on this machine, e.g. llama-server or Ollama.
+    OpenAiServer,
+    /// Scripted replies and hashed embeddings from `MockBackend`, for running
+    /// the app and its integration tests without model files.
+    Mock,
+}
+
+impl BackendKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BackendKind::Llama => "llama",
+            BackendKind::OpenAiServer => "openai_server",
+            BackendKind::Mock => "mock",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "llama" => Some(BackendKind::Llama),
+            "openai_server" => Some(BackendKind::OpenAiServer),
+            "mock" => Some(BackendKind::Mock),
+            _ => None,
+        }
+    }
+}
+
+/// Persisted backend choice. Chat and embeddings are chosen separately, so a
+/// large served chat model can be combined with the small local embedder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendSettings {
+    pub chat_backend: BackendKind,
+    pub embedding_backend: BackendKind,
+    /// Base URL of the server, e.g. `http://127.0.0.1:8080` for llama-server
+    /// or `http://127.0.0.1:11434` for Ollama. Must be on localhost.
+    pub server_url: String,
+    /// `model` sent with chat requests; llama-server ignores it.
+    pub server_chat_model: String,
+    /// `model` sent with embedding requests. Required when embeddings come
+    /// from the server: it is recorded with every memory, so a different
+    /// embedder behind the same URL is noticed as a model change.
+    pub server_embedding_model: String,
+    /// The server's context size, which it does not report in a standard way.
+    pub server_context_window: u32,
+}
+
+impl Default for BackendSettings {
+    fn default() -> Self {
+        Self {
+            chat_backend: BackendKind::Llama,
+            embedding_backend: BackendKind::Llama,
+            server_url: DEFAULT_SERVER_URL.to_string(),
+            server_chat_model: String::new(),
+            server_embedding_model: String::new(),
+            server_context_window: DEFAULT_SERVER_CONTEXT_WINDOW,
+        }
+    }
+}
+
+impl BackendSettings {
+    pub(crate) fn sanitized(&self) -> Self {
+        let server_url = self.server_url.trim();
+        Self {
+            chat_backend: self.chat_backend,
+            embedding_backend: self.embedding_backend,
+            server_url: if server_url.is_empty() {
+                DEFAULT_SERVER_URL.to_string()
+            } else {
+                server_url.to_string()
+            },
+            server_chat_model: self.server_chat_model.trim().to_string(),
+            server_embedding_model: self.server_embedding_model.trim().to_string(),
+            server_context_window: self
+                .server_context_window
+                .clamp(MIN_SERVER_CONTEXT_WINDOW, MAX_SERVER_CONTEXT_WINDOW),
+        }
+    }
+}
+
+pub fn settings() -> BackendSettings {
+    db::get_backend_settings().unwrap_or_else(|error| {
+        eprintln!("Failed to load backend settings, using defaults: {error}");
+        BackendSettings::default()
+    })
+}
+
+/// Serves both chat and embeddings from `backend`, ignoring the persisted
+/// settings, e.g. `MockBackend` in tests. `None` goes back to the settings.
+pub fn set_override(backend: Option<Arc<dyn InferenceBackend>>) {
+    if let Ok(mut current) = BACKEND_OVERRIDE.write() {
+        *current = backend;
+    }
+}
+
+/// Backend that serves chat generation and prompt token counts.
+pub fn chat() -> Result<Arc<dyn InferenceBackend>, AnimaError> {
+    if let Some(backend) = override_backend() {
+        return Ok(backend);
+    }
+    let settings = settings();
+    select(settings.chat_backend, &settings)
+}
+
+pub fn embedding() -> Result<Arc<dyn InferenceBackend>, AnimaError> {
+    if let Some(backend) = override_backend() {
+        return Ok(backend);
+    }
+    let settings = settings();
+    select(settings.embedding_backend, &settings)
+}
+
+pub fn ensure_chat_ready() -> Result<(), AnimaError> {
+    chat()?.context_window().map(|_| ())
+}
+
+fn override_backend() -> Option<Arc<dyn InferenceBackend>> {
+    BACKEND_OVERRIDE
+        .read()
+        .ok()
+        .and_then(|current| current.clone())
+}
+
+fn select(
+    kind: BackendKind,
+    settings: &BackendSettings,
+) -> Result<Arc<dyn InferenceBackend>, AnimaError> {
+    match kind {
+        BackendKind::Llama => Ok(Arc::new(ai::LlamaCppBackend)),
+        BackendKind::OpenAiServer => Ok(server_backend(settings)?),
+        BackendKind::Mock => Ok(mock_backend()),
+    }
+}
+
+/// One instance for the process, so chat and embeddings share it.
+fn mock_backend() -> Arc<MockBackend> {
+    Arc::clone(MOCK_BACKEND.get_or_init(|| Arc::new(MockBackend::new())))
+}
+
+/// Reused while the settings stay the same, so what it learned about the
+/// server (e.g. a missing tokenize endpoint) is not probed again every call.
+fn server_backend(settings: &BackendSettings) -> Result<Arc<OpenAiBackend>, AnimaError> {
+    let mut cached = SERVER_BACKEND.lock().map_err(|error| {
+        AnimaError::Internal(format!("Backend cache mutex is poisoned: {error}"))
+    })?;
+    if let Some((cached_settings, backend)) = cached.as_ref() {
+        if cached_settings == settings {
+            return Ok(Arc::clone(backend));
+        }
+    }
+
+    let backend = Arc::new(OpenAiBackend::new(settings)?);
+    *cached = Some((settings.clone(), Arc::clone(&backend)));
+    Ok(backend)
+}
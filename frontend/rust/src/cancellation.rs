@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+static ACTIVE_GENERATIONS: OnceLock<Mutex<HashMap<String, CancellationToken>>> = OnceLock::new();
+
+/// Shared flag polled by the decode loop between tokens.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    parent: Option<Arc<CancellationToken>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+            || self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.is_cancelled())
+    }
+
+    /// Token that is also cancelled by this one, but can be cancelled on its
+    /// own without affecting it.
+    pub fn child(&self) -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            parent: Some(Arc::new(self.clone())),
+        }
+    }
+}
+
+/// Keeps a request id cancellable for as long as the guard is alive.
+pub struct ActiveGeneration {
+    request_id: Option<String>,
+    token: CancellationToken,
+}
+
+impl ActiveGeneration {
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+}
+
+impl Drop for ActiveGeneration {
+    fn drop(&mut self) {
+        let Some(request_id) = self.request_id.take() else {
+            return;
+        };
+        if let Ok(mut active) = active_generations().lock() {
+            // A newer request may have reused the id; only remove our own token.
+            if active
+                .get(&request_id)
+                .is_some_and(|token| Arc::ptr_eq(&token.cancelled, &self.token.cancelled))
+            {
+                active.remove(&request_id);
+            }
+        }
+    }
+}
+
+/// Registers a generation under `request_id`. Requests without an id get a
+/// private token that can only be cancelled from inside the process.
+pub fn register(request_id: Option<&str>) -> ActiveGeneration {
+    let token = CancellationToken::new();
+    let request_id = request_id
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
+
+    if let Some(id) = &request_id {
+        if let Ok(mut active) = active_generations().lock() {
+            active.insert(id.clone(), token.clone());
+        }
+    }
+
+    ActiveGeneration { request_id, token }
+}
+
+/// Returns `false` when no generation with that id is running.
+pub fn cancel(request_id: &str) -> bool {
+    let Ok(active) = active_generations().lock() else {
+        return false;
+    };
+    match active.get(request_id.trim()) {
+        Some(token) => {
+            token.cancel();
+            true
+        }
+        None => false,
+    }
+}
+
+fn active_generations() -> &'static Mutex<HashMap<String, CancellationToken>> {
+    ACTIVE_GENERATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_reaches_registered_token() {
+        let generation = register(Some("cancel-test-1"));
+        assert!(!generation.token().is_cancelled());
+        assert!(cancel("cancel-test-1"));
+        assert!(generation.token().is_cancelled());
+    }
+
+    #[test]
+    fn test_child_follows_parent_but_not_the_reverse() {
+        let parent = CancellationToken::new();
+        let child = parent.child();
+        child.cancel();
+        assert!(!parent.is_cancelled());
+
+        let child = parent.child();
+        parent.cancel();
+        assert!(child.is_cancelled());
+    }
+
+    #[test]
+    fn test_guard_unregisters_on_drop() {
+        {
+            let _generation = register(Some("cancel-test-2"));
+        }
+        assert!(!cancel("cancel-test-2"));
+    }
+
+    #[test]
+    fn test_anonymous_generation_is_not_addressable() {
+        let generation = register(None);
+        assert!(!cancel(""));
+        assert!(!generation.token().is_cancelled());
+    }
+}
@@ -0,0 +1,469 @@
+/// Prompt formats the chat runtime knows how to render.
+///
+/// The template is picked from the model's embedded `tokenizer.chat_template`
+/// metadata when present, falling back to the chat marker tokens in its
+/// vocabulary, then `general.architecture` and finally ChatML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatTemplate {
+    ChatMl,
+    Llama3,
+    Mistral,
+    Gemma,
+    Phi3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatTurn {
+    pub role: TurnRole,
+    pub content: String,
+}
+
+impl ChatTurn {
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: TurnRole::User,
+            content: content.into(),
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: TurnRole::Assistant,
+            content: content.into(),
+        }
+    }
+}
+
+impl TurnRole {
+    /// Stored `messages.role` values that are dialogue, with the role each
+    /// maps to; `"anima"` is the legacy assistant role.
+    pub const MESSAGE_ROLES: [(&'static str, TurnRole); 3] = [
+        ("user", TurnRole::User),
+        ("assistant", TurnRole::Assistant),
+        ("anima", TurnRole::Assistant),
+    ];
+
+    /// Maps a stored `messages.role` value; memory rows and unknown roles are not dialogue.
+    pub fn from_message_role(role: &str) -> Option<Self> {
+        let role = role.trim().to_lowercase();
+        Self::MESSAGE_ROLES
+            .iter()
+            .find(|(name, _)| *name == role)
+            .map(|(_, turn_role)| *turn_role)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TurnRole::User => "user",
+            TurnRole::Assistant => "assistant",
+        }
+    }
+}
+
+/// Makes a turn list safe for strictly alternating templates: empty turns are
+/// dropped, leading assistant turns are discarded and consecutive turns from the
+/// same role are merged.
+pub fn normalize_turns(turns: Vec<ChatTurn>) -> Vec<ChatTurn> {
+    let mut normalized = Vec::<ChatTurn>::with_capacity(turns.len());
+    for turn in turns {
+        let content = turn.content.trim();
+        if content.is_empty() {
+            continue;
+        }
+        if normalized.is_empty() && turn.role == TurnRole::Assistant {
+            continue;
+        }
+        match normalized.last_mut() {
+            Some(previous) if previous.role == turn.role => {
+                previous.content.push_str("\n\n");
+                previous.content.push_str(content);
+            }
+            _ => normalized.push(ChatTurn {
+                role: turn.role,
+                content: content.to_string(),
+            }),
+        }
+    }
+    normalized
+}
+
+/// Role-play markers some models emit regardless of their native template.
+const COMMON_STOP_SEQUENCES: [&str; 2] = ["\nAlex:", "\nUser:"];
+
+impl ChatTemplate {
+    pub const ALL: [ChatTemplate; 5] = [
+        ChatTemplate::ChatMl,
+        ChatTemplate::Llama3,
+        ChatTemplate::Mistral,
+        ChatTemplate::Gemma,
+        ChatTemplate::Phi3,
+    ];
+
+    /// Vocabulary tokens that only exist for one template's turn markers,
+    /// checked in this order. Llama 3 and Mistral conversions often ship
+    /// without `tokenizer.chat_template` and with a generic architecture.
+    pub const MARKER_TOKENS: [(&'static str, ChatTemplate); 2] = [
+        ("<|start_header_id|>", ChatTemplate::Llama3),
+        ("[INST]", ChatTemplate::Mistral),
+    ];
+
+    /// `vocabulary_markers` are the `MARKER_TOKENS` the model's vocabulary
+    /// has, as in `GgufHeader::chat_marker_tokens`.
+    pub fn detect(
+        embedded_template: Option<&str>,
+        vocabulary_markers: &[String],
+        architecture: Option<&str>,
+    ) -> Self {
+        if let Some(template) = embedded_template.and_then(Self::from_jinja) {
+            return template;
+        }
+        if let Some((_, template)) = Self::MARKER_TOKENS
+            .iter()
+            .find(|(token, _)| vocabulary_markers.iter().any(|marker| marker == token))
+        {
+            return *template;
+        }
+
+        match architecture
+            .map(|value| value.trim().to_lowercase())
+            .as_deref()
+        {
+            Some("gemma") | Some("gemma2") | Some("gemma3") => ChatTemplate::Gemma,
+            Some("phi3") => ChatTemplate::Phi3,
+            _ => ChatTemplate::ChatMl,
+        }
+    }
+
+    fn from_jinja(template: &str) -> Option<Self> {
+        if template.contains("<|start_header_id|>") {
+            Some(ChatTemplate::Llama3)
+        } else if template.contains("<|im_start|>") {
+            Some(ChatTemplate::ChatMl)
+        } else if template.contains("<start_of_turn>") {
+            Some(ChatTemplate::Gemma)
+        } else if template.contains("<|assistant|>") && template.contains("<|end|>") {
+            Some(ChatTemplate::Phi3)
+        } else if template.contains("[INST]") {
+            Some(ChatTemplate::Mistral)
+        } else {
+            None
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|template| template.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ChatTemplate::ChatMl => "chatml",
+            ChatTemplate::Llama3 => "llama3",
+            ChatTemplate::Mistral => "mistral",
+            ChatTemplate::Gemma => "gemma",
+            ChatTemplate::Phi3 => "phi3",
+        }
+    }
+
+    /// Renders the system prompt and turns, ending with an open assistant turn.
+    pub fn render(self, system_prompt: &str, turns: &[ChatTurn]) -> String {
+        match self {
+            ChatTemplate::ChatMl => {
+                let mut output = format!("<|im_start|>system\n{system_prompt}<|im_end|>\n");
+                for turn in turns {
+                    let role = match turn.role {
+                        TurnRole::User => "user",
+                        TurnRole::Assistant => "assistant",
+                    };
+                    output.push_str(&format!("<|im_start|>{role}\n{}<|im_end|>\n", turn.content));
+                }
+                output.push_str("<|im_start|>assistant\n");
+                output
+            }
+            ChatTemplate::Llama3 => {
+                let mut output = format!(
+                    "<|begin_of_text|><|start_header_id|>system<|end_header_id|>\n\n{system_prompt}<|eot_id|>"
+                );
+                for turn in turns {
+                    let role = match turn.role {
+                        TurnRole::User => "user",
+                        TurnRole::Assistant => "assistant",
+                    };
+                    output.push_str(&format!(
+                        "<|start_header_id|>{role}<|end_header_id|>\n\n{}<|eot_id|>",
+                        turn.content
+                    ));
+                }
+                output.push_str("<|start_header_id|>assistant<|end_header_id|>\n\n");
+                output
+            }
+            ChatTemplate::Mistral => {
+                // No system role: the system prompt is folded into the first user turn.
+                let mut output = String::from("<s>");
+                let mut pending_system = Some(system_prompt).filter(|value| !value.is_empty());
+                for turn in turns {
+                    match turn.role {
+                        TurnRole::User => {
+                            let content = match pending_system.take() {
+                                Some(system) => format!("{system}\n\n{}", turn.content),
+                                None => turn.content.to_string(),
+                            };
+                            output.push_str(&format!("[INST] {content} [/INST]"));
+                        }
+                        TurnRole::Assistant => {
+                            output.push_str(&format!(" {}</s>", turn.content));
+                        }
+                    }
+                }
+                output
+            }
+            ChatTemplate::Gemma => {
+                // No system role: the system prompt is folded into the first user turn.
+                let mut output = String::from("<bos>");
+                let mut pending_system = Some(system_prompt).filter(|value| !value.is_empty());
+                for turn in turns {
+                    match turn.role {
+                        TurnRole::User => {
+                            let content = match pending_system.take() {
+                                Some(system) => format!("{system}\n\n{}", turn.content),
+                                None => turn.content.to_string(),
+                            };
+                            output.push_str(&format!(
+                                "<start_of_turn>user\n{content}<end_of_turn>\n"
+                            ));
+                        }
+                        TurnRole::Assistant => {
+                            output.push_str(&format!(
+                                "<start_of_turn>model\n{}<end_of_turn>\n",
+                                turn.content
+                            ));
+                        }
+                    }
+                }
+                output.push_str("<start_of_turn>model\n");
+                output
+            }
+            ChatTemplate::Phi3 => {
+                let mut output = format!("<|system|>\n{system_prompt}<|end|>\n");
+                for turn in turns {
+                    let role = match turn.role {
+                        TurnRole::User => "user",
+                        TurnRole::Assistant => "assistant",
+                    };
+                    output.push_str(&format!("<|{role}|>\n{}<|end|>\n", turn.content));
+                }
+                output.push_str("<|assistant|>\n");
+                output
+            }
+        }
+    }
+
+    /// Markers that end the assistant turn for this template, plus common role-play leaks.
+    pub fn stop_sequences(self) -> Vec<&'static str> {
+        let template_stops: &[&str] = match self {
+            ChatTemplate::ChatMl => &["<|im_end|>", "<|im_start|>"],
+            ChatTemplate::Llama3 => &["<|eot_id|>", "<|start_header_id|>", "<|end_of_text|>"],
+            ChatTemplate::Mistral => &["</s>", "[INST]"],
+            ChatTemplate::Gemma => &["<end_of_turn>", "<start_of_turn>"],
+            ChatTemplate::Phi3 => &["<|end|>", "<|user|>", "<|endoftext|>"],
+        };
+
+        COMMON_STOP_SEQUENCES
+            .iter()
+            .chain(template_stops.iter())
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYSTEM: &str = "You are Anima.";
+
+    fn sample_turns() -> Vec<ChatTurn> {
+        vec![
+            ChatTurn::user("Hola"),
+            ChatTurn::assistant("¡Hola! ¿Qué tal?"),
+            ChatTurn::user("Bien"),
+        ]
+    }
+
+    #[test]
+    fn test_render_chatml() {
+        assert_eq!(
+            ChatTemplate::ChatMl.render(SYSTEM, &sample_turns()),
+            "<|im_start|>system\nYou are Anima.<|im_end|>\n\
+             <|im_start|>user\nHola<|im_end|>\n\
+             <|im_start|>assistant\n¡Hola! ¿Qué tal?<|im_end|>\n\
+             <|im_start|>user\nBien<|im_end|>\n\
+             <|im_start|>assistant\n"
+        );
+    }
+
+    #[test]
+    fn test_render_llama3() {
+        assert_eq!(
+            ChatTemplate::Llama3.render(SYSTEM, &sample_turns()),
+            "<|begin_of_text|><|start_header_id|>system<|end_header_id|>\n\nYou are Anima.<|eot_id|>\
+             <|start_header_id|>user<|end_header_id|>\n\nHola<|eot_id|>\
+             <|start_header_id|>assistant<|end_header_id|>\n\n¡Hola! ¿Qué tal?<|eot_id|>\
+             <|start_header_id|>user<|end_header_id|>\n\nBien<|eot_id|>\
+             <|start_header_id|>assistant<|end_header_id|>\n\n"
+        );
+    }
+
+    #[test]
+    fn test_render_mistral() {
+        assert_eq!(
+            ChatTemplate::Mistral.render(SYSTEM, &sample_turns()),
+            "<s>[INST] You are Anima.\n\nHola [/INST] ¡Hola! ¿Qué tal?</s>[INST] Bien [/INST]"
+        );
+    }
+
+    #[test]
+    fn test_render_gemma() {
+        assert_eq!(
+            ChatTemplate::Gemma.render(SYSTEM, &sample_turns()),
+            "<bos><start_of_turn>user\nYou are Anima.\n\nHola<end_of_turn>\n\
+             <start_of_turn>model\n¡Hola! ¿Qué tal?<end_of_turn>\n\
+             <start_of_turn>user\nBien<end_of_turn>\n\
+             <start_of_turn>model\n"
+        );
+    }
+
+    #[test]
+    fn test_render_phi3() {
+        assert_eq!(
+            ChatTemplate::Phi3.render(SYSTEM, &sample_turns()),
+            "<|system|>\nYou are Anima.<|end|>\n\
+             <|user|>\nHola<|end|>\n\
+             <|assistant|>\n¡Hola! ¿Qué tal?<|end|>\n\
+             <|user|>\nBien<|end|>\n\
+             <|assistant|>\n"
+        );
+    }
+
+    #[test]
+    fn test_detect_from_embedded_template() {
+        let llama3 = "{% set content = '<|start_header_id|>' + message['role'] + '<|end_header_id|>\\n\\n' %}";
+        let chatml =
+            "{% for message in messages %}{{'<|im_start|>' + message['role'] }}{% endfor %}";
+        let gemma = "{{ '<start_of_turn>' + role + '\\n' }}";
+        let mistral = "{{ '[INST] ' + message['content'] + ' [/INST]' }}";
+        let phi3 = "{{'<|' + message['role'] + '|>' + '\\n' + message['content'] + '<|end|>'}}{{ '<|assistant|>' }}";
+
+        assert_eq!(
+            ChatTemplate::detect(Some(llama3), &[], None),
+            ChatTemplate::Llama3
+        );
+        assert_eq!(
+            ChatTemplate::detect(Some(chatml), &[], None),
+            ChatTemplate::ChatMl
+        );
+        assert_eq!(
+            ChatTemplate::detect(Some(gemma), &[], None),
+            ChatTemplate::Gemma
+        );
+        assert_eq!(
+            ChatTemplate::detect(Some(mistral), &[], None),
+            ChatTemplate::Mistral
+        );
+        assert_eq!(
+            ChatTemplate::detect(Some(phi3), &[], None),
+            ChatTemplate::Phi3
+        );
+    }
+
+    #[test]
+    fn test_detect_falls_back_to_architecture_then_chatml() {
+        assert_eq!(
+            ChatTemplate::detect(None, &[], Some("gemma2")),
+            ChatTemplate::Gemma
+        );
+        assert_eq!(
+            ChatTemplate::detect(Some("unknown"), &[], Some("phi3")),
+            ChatTemplate::Phi3
+        );
+        assert_eq!(
+            ChatTemplate::detect(None, &[], Some("llama")),
+            ChatTemplate::ChatMl
+        );
+        assert_eq!(ChatTemplate::detect(None, &[], None), ChatTemplate::ChatMl);
+    }
+
+    #[test]
+    fn test_detect_from_vocabulary_markers_before_architecture() {
+        let markers = |tokens: &[&str]| {
+            tokens
+                .iter()
+                .map(|token| token.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            ChatTemplate::detect(None, &markers(&["<|start_header_id|>"]), Some("llama")),
+            ChatTemplate::Llama3
+        );
+        assert_eq!(
+            ChatTemplate::detect(None, &markers(&["[INST]"]), Some("llama")),
+            ChatTemplate::Mistral
+        );
+        assert_eq!(
+            ChatTemplate::detect(None, &markers(&["[INST]", "<|start_header_id|>"]), None),
+            ChatTemplate::Llama3
+        );
+        assert_eq!(
+            ChatTemplate::detect(Some("<|im_start|>"), &markers(&["[INST]"]), None),
+            ChatTemplate::ChatMl
+        );
+    }
+
+    #[test]
+    fn test_stop_sequences_cover_turn_terminator() {
+        for template in ChatTemplate::ALL {
+            let rendered = template.render(SYSTEM, &sample_turns());
+            let stops = template.stop_sequences();
+            assert!(
+                stops.iter().any(|stop| rendered.contains(stop)),
+                "{} stop sequences never appear in its own rendering",
+                template.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_turns_enforces_alternation() {
+        let turns = vec![
+            ChatTurn::assistant("Buenos días"),
+            ChatTurn::user("Hola"),
+            ChatTurn::user("  "),
+            ChatTurn::user("¿Sigues ahí?"),
+            ChatTurn::assistant("Sí"),
+        ];
+
+        assert_eq!(
+            normalize_turns(turns),
+            vec![
+                ChatTurn::user("Hola\n\n¿Sigues ahí?"),
+                ChatTurn::assistant("Sí"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_name_round_trips() {
+        for template in ChatTemplate::ALL {
+            assert_eq!(ChatTemplate::from_name(template.name()), Some(template));
+        }
+        assert_eq!(ChatTemplate::from_name("unknown"), None);
+    }
+}
@@ -1,20 +1,77 @@
+use crate::backend::{BackendKind, BackendSettings};
+use crate::chat_template::TurnRole;
+use crate::error::{self, AnimaError};
+use crate::load_strategy::LoadStrategy;
+use crate::memory_index;
+use crate::prompt_cache;
+use crate::runtime_config::RuntimeSettings;
 use chrono::Utc;
 use rusqlite::{params, Connection, Result, TransactionBehavior};
 use std::cmp::Ordering;
-use std::path::Path;
-use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
 use std::thread::sleep;
 use std::time::Duration;
 
 const DB_PATH: &str = "anima_chat.db";
 const MIN_SIMILARITY_THRESHOLD: f32 = 0.35;
+/// Damping constant of reciprocal-rank fusion, from Cormack et al. (2009).
+const RRF_K: f32 = 60.0;
+/// Candidates each retriever contributes to the fusion per requested result.
+const FUSION_CANDIDATES_PER_RESULT: usize = 4;
+const MAX_FTS_TERMS: usize = 32;
+const DEFAULT_LEXICAL_WEIGHT: f32 = 0.5;
+const DEFAULT_MMR_LAMBDA: f32 = 0.7;
+/// Retrievals after which the access signal stops growing.
+const ACCESS_COUNT_SATURATION: f32 = 20.0;
+const SECONDS_PER_DAY: f32 = 86_400.0;
+/// Importance of memories stored without a judgement, such as raw chat lines.
+pub const DEFAULT_MEMORY_IMPORTANCE: f32 = 0.5;
 const CORE_PROMPT_KEY: &str = "core_prompt";
 const USER_NAME_KEY: &str = "user_name";
 const APP_LANGUAGE_KEY: &str = "app_language";
 const TEMPERATURE_KEY: &str = "temperature";
+const TOP_K_KEY: &str = "sampling_top_k";
+const TOP_P_KEY: &str = "sampling_top_p";
+const MIN_P_KEY: &str = "sampling_min_p";
+const REPEAT_PENALTY_KEY: &str = "sampling_repeat_penalty";
+const REPEAT_LAST_N_KEY: &str = "sampling_repeat_last_n";
+const SEED_KEY: &str = "sampling_seed";
+const MIROSTAT_KEY: &str = "sampling_mirostat";
+const MIROSTAT_TAU_KEY: &str = "sampling_mirostat_tau";
+const MIROSTAT_ETA_KEY: &str = "sampling_mirostat_eta";
+const PREFIX_CACHE_KEY: &str = "kv_prefix_cache";
+const MODELS_DIR_KEY: &str = "models_dir";
+const LEXICAL_WEIGHT_KEY: &str = "retrieval_lexical_weight";
+const MMR_LAMBDA_KEY: &str = "retrieval_mmr_lambda";
+const RELEVANCE_WEIGHT_KEY: &str = "scoring_relevance_weight";
+const RECENCY_WEIGHT_KEY: &str = "scoring_recency_weight";
+const IMPORTANCE_WEIGHT_KEY: &str = "scoring_importance_weight";
+const ACCESS_WEIGHT_KEY: &str = "scoring_access_weight";
+const RECENCY_HALF_LIFE_KEY: &str = "scoring_recency_half_life_days";
+const CHAT_MODEL_ID_KEY: &str = "chat_model_id";
+const EMBEDDING_MODEL_ID_KEY: &str = "embedding_model_id";
+const RERANKER_MODEL_ID_KEY: &str = "reranker_model_id";
+const LOAD_STRATEGY_KEY: &str = "model_load_strategy";
+const AUTO_TUNE_KEY: &str = "runtime_auto_tune";
+const N_CTX_KEY: &str = "runtime_n_ctx";
+const N_BATCH_KEY: &str = "runtime_n_batch";
+const N_THREADS_KEY: &str = "runtime_n_threads";
+const N_THREADS_BATCH_KEY: &str = "runtime_n_threads_batch";
+const CHAT_BACKEND_KEY: &str = "chat_backend";
+const EMBEDDING_BACKEND_KEY: &str = "embedding_backend";
+const SERVER_URL_KEY: &str = "server_url";
+const SERVER_CHAT_MODEL_KEY: &str = "server_chat_model";
+const SERVER_EMBEDDING_MODEL_KEY: &str = "server_embedding_model";
+const SERVER_CONTEXT_WINDOW_KEY: &str = "server_context_window";
 const DEFAULT_APP_LANGUAGE: &str = "Español";
 const DEFAULT_TEMPERATURE: f32 = 0.7;
+/// 0 is greedy decoding; above 2 output is noise.
+const MIN_TEMPERATURE: f32 = 0.0;
+const MAX_TEMPERATURE: f32 = 2.0;
 static SCHEMA_INITIALIZED: OnceLock<()> = OnceLock::new();
+static DB_PATH_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
 
 #[derive(Debug, Clone)]
 pub struct ChatMessage {
@@ -29,12 +86,111 @@ pub struct MemoryMatch {
     pub message_id: i64,
     pub role: String,
     pub content: String,
+    /// Cosine similarity to the query; 0 for lexical-only hits from another
+    /// embedding model.
     pub similarity: f32,
+    /// Fused rank score of the similarity and full-text rankings; only
+    /// comparable within one query.
+    pub relevance: f32,
+    pub importance: f32,
+    pub access_count: u32,
+    /// What the results are ordered by.
+    pub score: MemoryScore,
+    /// Cross-encoder score when a reranker model reordered the results; it
+    /// then takes precedence over `score`.
+    pub rerank_score: Option<f32>,
     pub timestamp: String,
     pub memory_type: String,
     pub memory_unix_timestamp: i64,
 }
 
+/// Per-signal contributions to a retrieved memory's score, each already
+/// multiplied by its weight, so they add up to `total`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MemoryScore {
+    pub relevance: f32,
+    pub recency: f32,
+    pub importance: f32,
+    pub access: f32,
+    pub total: f32,
+}
+
+/// How retrieval trades relevance to the query against the age, importance
+/// and retrieval count of a memory. A weight of 0 ignores that signal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryScoringWeights {
+    pub relevance: f32,
+    pub recency: f32,
+    pub importance: f32,
+    pub access: f32,
+    /// Age at which the recency signal has halved.
+    pub recency_half_life_days: f32,
+}
+
+impl Default for MemoryScoringWeights {
+    fn default() -> Self {
+        Self {
+            relevance: 1.0,
+            recency: 0.25,
+            importance: 0.25,
+            access: 0.1,
+            recency_half_life_days: 30.0,
+        }
+    }
+}
+
+impl MemoryScoringWeights {
+    pub(crate) fn sanitized(&self) -> Self {
+        let defaults = Self::default();
+        let weight = |value: f32, fallback: f32| {
+            if value.is_finite() {
+                value.clamp(0.0, 10.0)
+            } else {
+                fallback
+            }
+        };
+
+        Self {
+            relevance: weight(self.relevance, defaults.relevance),
+            recency: weight(self.recency, defaults.recency),
+            importance: weight(self.importance, defaults.importance),
+            access: weight(self.access, defaults.access),
+            recency_half_life_days: if self.recency_half_life_days.is_finite() {
+                self.recency_half_life_days.clamp(0.1, 36_500.0)
+            } else {
+                defaults.recency_half_life_days
+            },
+        }
+    }
+
+    /// Each signal is scaled to 0..=1 before weighting: `relevance` is the
+    /// fused rank score over its best possible value, recency decays
+    /// exponentially with `age_seconds`, and access grows logarithmically up
+    /// to `ACCESS_COUNT_SATURATION` retrievals.
+    pub(crate) fn score(
+        &self,
+        relevance: f32,
+        age_seconds: i64,
+        importance: f32,
+        access_count: u32,
+    ) -> MemoryScore {
+        let relevance = (relevance * (RRF_K + 1.0)).clamp(0.0, 1.0);
+        let age_days = age_seconds.max(0) as f32 / SECONDS_PER_DAY;
+        let recency = (-std::f32::consts::LN_2 * age_days / self.recency_half_life_days).exp();
+        let access = ((access_count as f32).ln_1p() / ACCESS_COUNT_SATURATION.ln_1p()).min(1.0);
+
+        let mut score = MemoryScore {
+            relevance: self.relevance * relevance,
+            recency: self.recency * recency,
+            importance: self.importance * importance.clamp(0.0, 1.0),
+            access: self.access * access,
+            total: 0.0,
+        };
+        score.total = score.relevance + score.recency + score.importance + score.access;
+        score
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MemoryItem {
     pub id: i64,
@@ -48,8 +204,81 @@ pub struct ProfileTrait {
     pub content: String,
 }
 
+/// Sampler configuration used for a single generation.
+///
+/// `temperature <= 0` selects greedy decoding, `mirostat` is 0 (off), 1 or 2,
+/// and a `seed` of `None` draws a fresh seed per request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SamplingParams {
+    pub temperature: f32,
+    pub top_k: i32,
+    pub top_p: f32,
+    pub min_p: f32,
+    pub repeat_penalty: f32,
+    pub repeat_last_n: i32,
+    pub seed: Option<u32>,
+    pub mirostat: u8,
+    pub mirostat_tau: f32,
+    pub mirostat_eta: f32,
+}
+
+impl Default for SamplingParams {
+    fn default() -> Self {
+        Self {
+            temperature: DEFAULT_TEMPERATURE,
+            top_k: 40,
+            top_p: 0.92,
+            min_p: 0.0,
+            repeat_penalty: 1.20,
+            repeat_last_n: 128,
+            seed: None,
+            mirostat: 0,
+            mirostat_tau: 5.0,
+            mirostat_eta: 0.1,
+        }
+    }
+}
+
+impl SamplingParams {
+    /// Low-entropy preset for extraction tasks such as the sleep cycle.
+    pub(crate) fn near_greedy() -> Self {
+        Self {
+            temperature: 0.1,
+            top_k: 10,
+            top_p: 0.9,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn with_temperature(mut self, temperature: f32) -> Self {
+        if temperature.is_finite() && temperature >= 0.0 {
+            self.temperature = temperature;
+        }
+        self
+    }
+
+    pub(crate) fn sanitized(&self) -> Self {
+        let defaults = Self::default();
+        let finite_or = |value: f32, fallback: f32| if value.is_finite() { value } else { fallback };
+
+        Self {
+            temperature: finite_or(self.temperature, defaults.temperature)
+                .clamp(MIN_TEMPERATURE, MAX_TEMPERATURE),
+            top_k: self.top_k.max(0),
+            top_p: finite_or(self.top_p, defaults.top_p).clamp(0.0, 1.0),
+            min_p: finite_or(self.min_p, defaults.min_p).clamp(0.0, 1.0),
+            repeat_penalty: finite_or(self.repeat_penalty, defaults.repeat_penalty).clamp(1.0, 2.0),
+            repeat_last_n: self.repeat_last_n.clamp(-1, 4096),
+            seed: self.seed,
+            mirostat: self.mirostat.min(2),
+            mirostat_tau: finite_or(self.mirostat_tau, defaults.mirostat_tau).clamp(0.0, 20.0),
+            mirostat_eta: finite_or(self.mirostat_eta, defaults.mirostat_eta).clamp(0.0, 1.0),
+        }
+    }
+}
+
 pub fn init_db() -> Result<()> {
-    let conn = Connection::open(DB_PATH)?;
+    let conn = Connection::open(db_path())?;
     init_schema(&conn)?;
     let _ = SCHEMA_INITIALIZED.set(());
     Ok(())
@@ -68,90 +297,325 @@ pub fn current_unix_timestamp() -> i64 {
     Utc::now().timestamp()
 }
 
+/// `embedding_model` is the id of the model that produced `embedding`;
+/// `importance` runs from 0 (trivia) to 1 (life events).
 pub fn insert_memory(
     message_id: i64,
     embedding: &[f32],
+    embedding_model: &str,
     memory_type: &str,
+    importance: f32,
     unix_timestamp: i64,
 ) -> Result<()> {
     let conn = open_connection()?;
     let embedding_blob = f32_slice_to_blob(embedding);
     let normalized_type = normalize_memory_type(memory_type);
+    let importance = if importance.is_finite() {
+        importance.clamp(0.0, 1.0)
+    } else {
+        DEFAULT_MEMORY_IMPORTANCE
+    };
 
     conn.execute(
-        "INSERT OR REPLACE INTO memories (message_id, embedding, memory_type, timestamp) VALUES (?1, ?2, ?3, ?4)",
-        params![message_id, embedding_blob, normalized_type, unix_timestamp],
+        "INSERT OR REPLACE INTO memories (message_id, embedding, embedding_model, embedding_dim, memory_type, importance, timestamp)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            message_id,
+            embedding_blob,
+            embedding_model,
+            embedding.len() as i64,
+            normalized_type,
+            importance,
+            unix_timestamp
+        ],
     )?;
+    memory_index::record_upsert(message_id, embedding_model, embedding);
 
     Ok(())
 }
 
+/// Ranks memories for `query_text` by reciprocal-rank fusion of two
+/// retrievers: vector similarity to `query_embedding` and BM25 over the text
+/// (`messages_fts`). Their weighting is `get_lexical_weight`.
+///
+/// Only rows embedded by `embedding_model` with the query's dimension are
+/// compared by vector, with candidates from the approximate `memory_index`
+/// (every row is scanned if it cannot be used). Rows from before models were
+/// recorded (empty model id) are stale like those of another model: only
+/// lexical hits reach them until `reembed_memories` tags them.
+/// Lexical hits need no vector, so they can surface names, dates and rare
+/// words the embedding misses.
+///
+/// The fused candidates are then scored by `MemoryScoringWeights::score`,
+/// which also weighs in age, importance and past retrievals, and picked in
+/// `mmr_order` so the results are not near-duplicates of each other.
 pub fn find_top_similar_memories(
+    query_text: &str,
     query_embedding: &[f32],
+    embedding_model: &str,
     limit: usize,
     exclude_message_id: Option<i64>,
 ) -> Result<Vec<MemoryMatch>> {
-    if query_embedding.is_empty() || limit == 0 {
+    if limit == 0 {
         return Ok(Vec::new());
     }
 
     let conn = open_connection()?;
-    let mut statement = conn.prepare(
-        "SELECT m.message_id, msg.role, msg.content, msg.timestamp, m.embedding, m.memory_type, m.timestamp
-         FROM memories m
-         JOIN messages msg ON msg.id = m.message_id",
-    )?;
+    let lexical_weight = read_lexical_weight(&conn)?;
+    let weights = read_memory_scoring_weights(&conn)?;
+    let now = current_unix_timestamp();
+    let candidate_limit =
+        limit * FUSION_CANDIDATES_PER_RESULT + usize::from(exclude_message_id.is_some());
+    let vector_ranked =
+        if query_embedding.is_empty() || embedding_model.is_empty() || lexical_weight >= 1.0 {
+            Vec::new()
+        } else {
+            vector_candidates(&conn, query_embedding, embedding_model, candidate_limit)?
+        };
+    let lexical_ranked = match fts_match_expression(query_text, false) {
+        Some(expression) if lexical_weight > 0.0 => {
+            lexical_candidates(&conn, &expression, candidate_limit)?
+        }
+        _ => Vec::new(),
+    };
+
+    let mut relevance = HashMap::<i64, f32>::new();
+    for (ranked, weight) in [
+        (&vector_ranked, 1.0 - lexical_weight),
+        (&lexical_ranked, lexical_weight),
+    ] {
+        for (rank, message_id) in ranked.iter().enumerate() {
+            *relevance.entry(*message_id).or_default() += weight / (RRF_K + rank as f32 + 1.0);
+        }
+    }
+    if let Some(excluded) = exclude_message_id {
+        relevance.remove(&excluded);
+    }
+    if relevance.is_empty() {
+        return Ok(Vec::new());
+    }
 
+    let ids = relevance
+        .keys()
+        .map(|message_id| message_id.to_string())
+        .collect::<Vec<_>>();
+    let mut statement = conn.prepare(&format!(
+        "SELECT m.message_id, msg.role, msg.content, msg.timestamp, m.embedding, m.memory_type, m.timestamp,
+                m.importance, m.access_count, m.embedding_model
+         FROM memories m
+         JOIN messages msg ON msg.id = m.message_id
+         WHERE m.message_id IN ({})",
+        ids.join(",")
+    ))?;
     let rows = statement.query_map([], |row| {
-        let embedding_blob: Vec<u8> = row.get(4)?;
-        let embedding = blob_to_f32_vec(&embedding_blob);
-
-        Ok((
-            row.get::<_, i64>(0)?,
-            row.get::<_, String>(1)?,
-            row.get::<_, String>(2)?,
-            row.get::<_, String>(3)?,
-            embedding,
-            row.get::<_, String>(5)?,
-            row.get::<_, i64>(6)?,
-        ))
+        let message_id = row.get::<_, i64>(0)?;
+        // Vectors of another model are in a different space: they are not
+        // compared with the query nor, in `mmr_order`, with each other.
+        let embedding = if row.get::<_, String>(9)? == embedding_model {
+            blob_to_f32_vec(&row.get::<_, Vec<u8>>(4)?)
+        } else {
+            Vec::new()
+        };
+        let relevance = relevance.get(&message_id).copied().unwrap_or_default();
+        let memory_unix_timestamp = row.get::<_, i64>(6)?;
+        let importance = row.get::<_, f32>(7)?;
+        let access_count = u32::try_from(row.get::<_, i64>(8)?).unwrap_or(0);
+        let similarity = cosine_similarity(query_embedding, &embedding);
+        let memory = MemoryMatch {
+            message_id,
+            role: row.get(1)?,
+            content: row.get(2)?,
+            similarity,
+            relevance,
+            importance,
+            access_count,
+            score: weights.score(
+                relevance,
+                now - memory_unix_timestamp,
+                importance,
+                access_count,
+            ),
+            rerank_score: None,
+            timestamp: row.get(3)?,
+            memory_type: row.get(5)?,
+            memory_unix_timestamp,
+        };
+        Ok((memory, embedding))
     })?;
 
-    let mut scored = Vec::<MemoryMatch>::new();
-    for row in rows {
-        let (message_id, role, content, timestamp, candidate_embedding, memory_type, memory_unix_timestamp) =
-            row?;
+    let mut scored = rows.collect::<Result<Vec<_>>>()?;
+    scored.sort_by(|(a, _), (b, _)| {
+        b.score
+            .total
+            .partial_cmp(&a.score.total)
+            .unwrap_or(Ordering::Equal)
+            .then(b.message_id.cmp(&a.message_id))
+    });
+    let scores = scored.iter().map(|(memory, _)| memory.score.total).collect::<Vec<_>>();
+    let embeddings = scored.iter().map(|(_, embedding)| embedding.as_slice()).collect::<Vec<_>>();
+    let order = mmr_order(&scores, &embeddings, read_mmr_lambda(&conn)?, limit);
+
+    let mut slots = scored.into_iter().map(Some).collect::<Vec<_>>();
+    Ok(order
+        .into_iter()
+        .filter_map(|index| slots[index].take())
+        .map(|(memory, _)| memory)
+        .collect())
+}
 
-        if exclude_message_id.is_some_and(|excluded| excluded == message_id) {
-            continue;
-        }
+/// Picks up to `limit` of `memories` in `mmr_order`, with `relevance` (one
+/// non-negative value per memory) in place of their scores. Used after a
+/// reranker rescored them, so its order does not bring back the
+/// near-duplicates `find_top_similar_memories` kept out. As there, only
+/// vectors of `embedding_model` are compared.
+pub fn diversify_memories(
+    memories: Vec<MemoryMatch>,
+    relevance: &[f32],
+    embedding_model: &str,
+    limit: usize,
+) -> Result<Vec<MemoryMatch>> {
+    if memories.is_empty() || limit == 0 {
+        return Ok(Vec::new());
+    }
 
-        let similarity = cosine_similarity(query_embedding, &candidate_embedding);
-        if similarity.is_finite() && similarity >= MIN_SIMILARITY_THRESHOLD {
-            scored.push(MemoryMatch {
-                message_id,
-                role,
-                content,
-                similarity,
-                timestamp,
-                memory_type,
-                memory_unix_timestamp,
-            });
+    let conn = open_connection()?;
+    let ids = memories
+        .iter()
+        .map(|memory| memory.message_id.to_string())
+        .collect::<Vec<_>>();
+    let mut statement = conn.prepare(&format!(
+        "SELECT message_id, embedding FROM memories
+         WHERE embedding_model = ?1 AND message_id IN ({})",
+        ids.join(",")
+    ))?;
+    let vectors = statement
+        .query_map(params![embedding_model], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                blob_to_f32_vec(&row.get::<_, Vec<u8>>(1)?),
+            ))
+        })?
+        .collect::<Result<HashMap<_, _>>>()?;
+    let embeddings = memories
+        .iter()
+        .map(|memory| vectors.get(&memory.message_id).map_or(&[][..], Vec::as_slice))
+        .collect::<Vec<_>>();
+    let order = mmr_order(relevance, &embeddings, read_mmr_lambda(&conn)?, limit);
+
+    let mut slots = memories.into_iter().map(Some).collect::<Vec<_>>();
+    Ok(order
+        .into_iter()
+        .filter_map(|index| slots[index].take())
+        .collect())
+}
+
+/// Maximal marginal relevance (Carbonell & Goldstein, 1998): picks up to
+/// `limit` indices one at a time, each maximizing
+/// `lambda * score - (1 - lambda) * max cosine similarity to the picks so far`,
+/// so near-duplicates of a chosen memory give way to other facts. Scores are
+/// divided by the best one to share the similarity's scale; `lambda` 1 keeps
+/// the score order.
+fn mmr_order(scores: &[f32], embeddings: &[&[f32]], lambda: f32, limit: usize) -> Vec<usize> {
+    let best = scores.iter().copied().fold(f32::EPSILON, f32::max);
+    let mut redundancy = vec![0.0_f32; scores.len()];
+    let mut remaining = (0..scores.len()).collect::<Vec<_>>();
+    let mut order = Vec::with_capacity(limit.min(scores.len()));
+
+    while order.len() < limit {
+        let marginal =
+            |index: usize| lambda * scores[index] / best - (1.0 - lambda) * redundancy[index];
+        let Some((position, &picked)) = remaining.iter().enumerate().max_by(|(_, a), (_, b)| {
+            marginal(**a)
+                .partial_cmp(&marginal(**b))
+                .unwrap_or(Ordering::Equal)
+                // Ties go to the earlier, higher-scored candidate.
+                .then(b.cmp(a))
+        }) else {
+            break;
+        };
+        remaining.swap_remove(position);
+        order.push(picked);
+
+        for &index in &remaining {
+            let similarity = cosine_similarity(embeddings[picked], embeddings[index]);
+            redundancy[index] = redundancy[index].max(similarity);
         }
     }
+    order
+}
 
-    scored.sort_by(|a, b| {
-        b.similarity
-            .partial_cmp(&a.similarity)
-            .unwrap_or(Ordering::Equal)
+/// Message ids at or above `MIN_SIMILARITY_THRESHOLD`, most similar first.
+fn vector_candidates(
+    conn: &Connection,
+    query_embedding: &[f32],
+    embedding_model: &str,
+    limit: usize,
+) -> Result<Vec<i64>> {
+    let indexed = memory_index::search(conn, &db_path(), query_embedding, embedding_model, limit);
+    let mut scored = match indexed {
+        Ok(candidates) => candidates,
+        Err(error) => {
+            eprintln!("[memory_index] falling back to a full scan: {error}");
+            scan_similar_memories(conn, query_embedding, embedding_model)?
+        }
+    };
+    scored.retain(|(_, similarity)| {
+        similarity.is_finite() && *similarity >= MIN_SIMILARITY_THRESHOLD
     });
+    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
     scored.truncate(limit);
-    Ok(scored)
+    Ok(scored.into_iter().map(|(message_id, _)| message_id).collect())
 }
 
+fn scan_similar_memories(
+    conn: &Connection,
+    query_embedding: &[f32],
+    embedding_model: &str,
+) -> Result<Vec<(i64, f32)>> {
+    let mut statement = conn.prepare(
+        "SELECT message_id, embedding FROM memories
+         WHERE embedding_dim = ?1 AND embedding_model = ?2",
+    )?;
+    let rows = statement.query_map(params![query_embedding.len() as i64, embedding_model], |row| {
+        let embedding = blob_to_f32_vec(&row.get::<_, Vec<u8>>(1)?);
+        Ok((row.get(0)?, cosine_similarity(query_embedding, &embedding)))
+    })?;
+    rows.collect()
+}
+
+/// Message ids of memories matching `expression`, best BM25 score first.
+fn lexical_candidates(conn: &Connection, expression: &str, limit: usize) -> Result<Vec<i64>> {
+    let mut statement = conn.prepare(
+        "SELECT messages_fts.rowid
+         FROM messages_fts
+         JOIN memories m ON m.message_id = messages_fts.rowid
+         WHERE messages_fts MATCH ?1
+         ORDER BY bm25(messages_fts)
+         LIMIT ?2",
+    )?;
+    let rows = statement.query_map(params![expression, limit as i64], |row| row.get(0))?;
+    rows.collect()
+}
+
+/// FTS5 query matching any word of `text`, or `None` without words. Each
+/// word is quoted so user text cannot use FTS5 syntax. With `prefix`, for
+/// search-as-you-type, every word must match the start of a word, so each
+/// word typed narrows the results.
+fn fts_match_expression(text: &str, prefix: bool) -> Option<String> {
+    let (suffix, separator) = if prefix { ("*", " ") } else { ("", " OR ") };
+    let terms = text
+        .split(|character: char| !character.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(MAX_FTS_TERMS)
+        .map(|word| format!("\"{word}\"{suffix}"))
+        .collect::<Vec<_>>();
+    (!terms.is_empty()).then(|| terms.join(separator))
+}
+
+/// 0 for vectors of different dimensions, which come from different models.
 pub fn cosine_similarity(query: &[f32], candidate: &[f32]) -> f32 {
-    let dimensions = query.len().min(candidate.len());
-    if dimensions == 0 {
+    let dimensions = query.len();
+    if dimensions == 0 || candidate.len() != dimensions {
         return 0.0;
     }
 
@@ -192,6 +656,37 @@ pub fn get_all_messages() -> Result<Vec<ChatMessage>> {
     rows.collect()
 }
 
+/// Most recent `limit` user/assistant messages, oldest first.
+pub fn get_recent_dialogue(limit: usize) -> Result<Vec<ChatMessage>> {
+    let conn = open_connection()?;
+    let roles = TurnRole::MESSAGE_ROLES
+        .iter()
+        .map(|(role, _)| format!("'{role}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut statement = conn.prepare(&format!(
+        "SELECT id, role, content, timestamp FROM (
+             SELECT id, role, content, timestamp
+             FROM messages
+             WHERE lower(trim(role)) IN ({roles})
+             ORDER BY datetime(timestamp) DESC, id DESC
+             LIMIT ?1
+         )
+         ORDER BY datetime(timestamp) ASC, id ASC"
+    ))?;
+
+    let rows = statement.query_map(params![limit as i64], |row| {
+        Ok(ChatMessage {
+            id: row.get(0)?,
+            role: row.get(1)?,
+            content: row.get(2)?,
+            timestamp: row.get(3)?,
+        })
+    })?;
+
+    rows.collect()
+}
+
 pub fn get_all_memories() -> Result<Vec<MemoryItem>> {
     let conn = open_connection()?;
     let mut statement = conn.prepare(
@@ -212,22 +707,36 @@ pub fn get_all_memories() -> Result<Vec<MemoryItem>> {
     rows.collect()
 }
 
+/// Full-text search for the memory screen: every word must match as a
+/// prefix, ignoring case and accents, best BM25 score first. Dates match
+/// `created_at`; a query without words (only symbols or punctuation) matches
+/// the content as a substring; an empty query lists everything, newest first.
 pub fn search_memories(query: &str) -> Result<Vec<MemoryItem>> {
     let conn = open_connection()?;
     let normalized_query = query.trim();
+    let expression = fts_match_expression(normalized_query, true);
+    let lexical_hits = match expression {
+        Some(_) => {
+            "SELECT rowid, bm25(messages_fts) AS score FROM messages_fts
+             WHERE messages_fts MATCH ?2"
+        }
+        None => "SELECT NULL AS rowid, NULL AS score WHERE ?2 IS NULL",
+    };
 
-    let mut statement = conn.prepare(
+    let mut statement = conn.prepare(&format!(
         "SELECT msg.id, msg.content, mem.created_at
          FROM memories mem
          JOIN messages msg ON msg.id = mem.message_id
+         LEFT JOIN ({lexical_hits}) fts ON fts.rowid = msg.id
          WHERE (?1 = '')
-            OR msg.content LIKE '%' || ?1 || '%'
+            OR fts.rowid IS NOT NULL
+            OR (?2 IS NULL AND msg.content LIKE '%' || ?1 || '%')
             OR mem.created_at LIKE '%' || ?1 || '%'
             OR CAST(mem.timestamp AS TEXT) LIKE '%' || ?1 || '%'
-         ORDER BY mem.timestamp DESC, mem.message_id DESC",
-    )?;
+         ORDER BY fts.score IS NULL, fts.score, mem.timestamp DESC, mem.message_id DESC"
+    ))?;
 
-    let rows = statement.query_map(params![normalized_query], |row| {
+    let rows = statement.query_map(params![normalized_query, expression], |row| {
         Ok(MemoryItem {
             id: row.get(0)?,
             content: row.get(1)?,
@@ -238,16 +747,103 @@ pub fn search_memories(query: &str) -> Result<Vec<MemoryItem>> {
     rows.collect()
 }
 
+/// Counts one retrieval of each memory, for the access signal of
+/// `MemoryScoringWeights::score`.
+pub fn record_memory_access(message_ids: &[i64]) -> Result<()> {
+    if message_ids.is_empty() {
+        return Ok(());
+    }
+    let mut conn = open_connection()?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    {
+        let mut statement = tx.prepare(
+            "UPDATE memories SET access_count = access_count + 1, last_accessed = ?1
+             WHERE message_id = ?2",
+        )?;
+        let now = current_unix_timestamp();
+        for message_id in message_ids {
+            statement.execute(params![now, message_id])?;
+        }
+    }
+    tx.commit()
+}
+
 pub fn delete_memory(memory_id: i64) -> Result<()> {
     let conn = open_connection()?;
     conn.execute("DELETE FROM memories WHERE message_id = ?1", params![memory_id])?;
+    memory_index::record_delete(memory_id);
     Ok(())
 }
 
-pub fn clear_all_raw_memories() -> std::result::Result<bool, String> {
-    let conn = open_connection().map_err(|error| format!("DB open failed: {error}"))?;
+/// Memories not yet embedded by `embedding_model`, including rows from before
+/// models were recorded.
+pub fn count_stale_memories(embedding_model: &str) -> Result<usize> {
+    let conn = open_connection()?;
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM memories WHERE embedding_model != ?1",
+        params![embedding_model],
+        |row| row.get(0),
+    )?;
+    Ok(usize::try_from(count).unwrap_or(0))
+}
+
+pub fn count_memories() -> Result<usize> {
+    let conn = open_connection()?;
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM memories", [], |row| row.get(0))?;
+    Ok(usize::try_from(count).unwrap_or(0))
+}
+
+/// Up to `limit` (message id, content) pairs of stale memories, oldest first.
+pub fn get_stale_memories(embedding_model: &str, limit: usize) -> Result<Vec<(i64, String)>> {
+    let conn = open_connection()?;
+    let mut statement = conn.prepare(
+        "SELECT mem.message_id, msg.content
+         FROM memories mem
+         JOIN messages msg ON msg.id = mem.message_id
+         WHERE mem.embedding_model != ?1
+         ORDER BY mem.message_id ASC
+         LIMIT ?2",
+    )?;
+
+    let rows = statement.query_map(params![embedding_model, limit as i64], |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    })?;
+
+    rows.collect()
+}
+
+/// Replaces the vectors of existing memories in one transaction, keeping
+/// their type and timestamp.
+pub fn update_memory_embeddings(embedding_model: &str, updates: &[(i64, &[f32])]) -> Result<()> {
+    let mut conn = open_connection()?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    {
+        let mut statement = tx.prepare(
+            "UPDATE memories
+             SET embedding = ?1, embedding_model = ?2, embedding_dim = ?3
+             WHERE message_id = ?4",
+        )?;
+        for (message_id, embedding) in updates {
+            statement.execute(params![
+                f32_slice_to_blob(embedding),
+                embedding_model,
+                embedding.len() as i64,
+                message_id
+            ])?;
+        }
+    }
+    tx.commit()?;
+    for (message_id, embedding) in updates {
+        memory_index::record_upsert(*message_id, embedding_model, embedding);
+    }
+    Ok(())
+}
+
+pub fn clear_all_raw_memories() -> std::result::Result<bool, AnimaError> {
+    let conn = open_connection().map_err(|error| AnimaError::from(error).context("DB open failed"))?;
     conn.execute("DELETE FROM memories", [])
-        .map_err(|error| format!("Raw memory purge failed: {error}"))?;
+        .map_err(|error| AnimaError::from(error).context("Raw memory purge failed"))?;
+    memory_index::invalidate();
     Ok(true)
 }
 
@@ -271,6 +867,7 @@ pub fn set_core_prompt(prompt: &str) -> Result<()> {
          ON CONFLICT(key) DO UPDATE SET value = excluded.value",
         params![CORE_PROMPT_KEY, prompt],
     )?;
+    prompt_cache::invalidate();
     Ok(())
 }
 
@@ -294,6 +891,7 @@ pub fn set_user_name(name: &str) -> Result<()> {
          ON CONFLICT(key) DO UPDATE SET value = excluded.value",
         params![USER_NAME_KEY, name],
     )?;
+    prompt_cache::invalidate();
     Ok(())
 }
 
@@ -318,6 +916,7 @@ pub fn set_app_language(lang: &str) -> Result<()> {
          ON CONFLICT(key) DO UPDATE SET value = excluded.value",
         params![APP_LANGUAGE_KEY, lang],
     )?;
+    prompt_cache::invalidate();
     Ok(())
 }
 
@@ -335,7 +934,7 @@ pub fn get_temperature() -> Result<f32> {
 
 pub fn set_temperature(temperature: f32) -> Result<()> {
     let conn = open_connection()?;
-    let sanitized = temperature.clamp(0.1, 1.0);
+    let sanitized = temperature.clamp(MIN_TEMPERATURE, MAX_TEMPERATURE);
     conn.execute(
         "INSERT INTO config(key, value)
          VALUES (?1, ?2)
@@ -345,9 +944,303 @@ pub fn set_temperature(temperature: f32) -> Result<()> {
     Ok(())
 }
 
+/// Whether the chat runtime may keep the system-prompt prefix in the KV cache
+/// between turns. Enabled unless explicitly turned off.
+pub fn get_prefix_cache_enabled() -> Result<bool> {
+    let conn = open_connection()?;
+    Ok(read_config_parsed(&conn, PREFIX_CACHE_KEY)?.unwrap_or(true))
+}
+
+pub fn set_prefix_cache_enabled(enabled: bool) -> Result<()> {
+    let conn = open_connection()?;
+    write_config_value(&conn, PREFIX_CACHE_KEY, &enabled.to_string())?;
+    prompt_cache::invalidate();
+    Ok(())
+}
+
+/// Share of memory retrieval given to full-text (BM25) ranking, from 0 (vector
+/// similarity only) to 1 (full-text only).
+pub fn get_lexical_weight() -> Result<f32> {
+    let conn = open_connection()?;
+    read_lexical_weight(&conn)
+}
+
+pub fn set_lexical_weight(weight: f32) -> Result<()> {
+    let conn = open_connection()?;
+    let sanitized = if weight.is_finite() {
+        weight.clamp(0.0, 1.0)
+    } else {
+        DEFAULT_LEXICAL_WEIGHT
+    };
+    write_config_value(&conn, LEXICAL_WEIGHT_KEY, &format!("{sanitized:.3}"))
+}
+
+pub fn get_memory_scoring_weights() -> Result<MemoryScoringWeights> {
+    let conn = open_connection()?;
+    read_memory_scoring_weights(&conn)
+}
+
+pub fn set_memory_scoring_weights(weights: &MemoryScoringWeights) -> Result<()> {
+    let sanitized = weights.sanitized();
+    let mut conn = open_connection()?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    write_config_value(&tx, RELEVANCE_WEIGHT_KEY, &sanitized.relevance.to_string())?;
+    write_config_value(&tx, RECENCY_WEIGHT_KEY, &sanitized.recency.to_string())?;
+    write_config_value(&tx, IMPORTANCE_WEIGHT_KEY, &sanitized.importance.to_string())?;
+    write_config_value(&tx, ACCESS_WEIGHT_KEY, &sanitized.access.to_string())?;
+    write_config_value(
+        &tx,
+        RECENCY_HALF_LIFE_KEY,
+        &sanitized.recency_half_life_days.to_string(),
+    )?;
+    tx.commit()
+}
+
+fn read_memory_scoring_weights(conn: &Connection) -> Result<MemoryScoringWeights> {
+    let mut weights = MemoryScoringWeights::default();
+    if let Some(value) = read_config_parsed(conn, RELEVANCE_WEIGHT_KEY)? {
+        weights.relevance = value;
+    }
+    if let Some(value) = read_config_parsed(conn, RECENCY_WEIGHT_KEY)? {
+        weights.recency = value;
+    }
+    if let Some(value) = read_config_parsed(conn, IMPORTANCE_WEIGHT_KEY)? {
+        weights.importance = value;
+    }
+    if let Some(value) = read_config_parsed(conn, ACCESS_WEIGHT_KEY)? {
+        weights.access = value;
+    }
+    if let Some(value) = read_config_parsed(conn, RECENCY_HALF_LIFE_KEY)? {
+        weights.recency_half_life_days = value;
+    }
+    Ok(weights.sanitized())
+}
+
+/// Relevance/diversity balance of the memories injected into a prompt: 1
+/// keeps the score order, lower values skip near-duplicates of memories
+/// already picked.
+pub fn get_mmr_lambda() -> Result<f32> {
+    let conn = open_connection()?;
+    read_mmr_lambda(&conn)
+}
+
+pub fn set_mmr_lambda(lambda: f32) -> Result<()> {
+    let conn = open_connection()?;
+    let sanitized = if lambda.is_finite() {
+        lambda.clamp(0.0, 1.0)
+    } else {
+        DEFAULT_MMR_LAMBDA
+    };
+    write_config_value(&conn, MMR_LAMBDA_KEY, &format!("{sanitized:.3}"))
+}
+
+fn read_mmr_lambda(conn: &Connection) -> Result<f32> {
+    Ok(read_config_parsed::<f32>(conn, MMR_LAMBDA_KEY)?
+        .filter(|lambda| lambda.is_finite())
+        .map_or(DEFAULT_MMR_LAMBDA, |lambda| lambda.clamp(0.0, 1.0)))
+}
+
+fn read_lexical_weight(conn: &Connection) -> Result<f32> {
+    Ok(read_config_parsed::<f32>(conn, LEXICAL_WEIGHT_KEY)?
+        .filter(|weight| weight.is_finite())
+        .map_or(DEFAULT_LEXICAL_WEIGHT, |weight| weight.clamp(0.0, 1.0)))
+}
+
+/// Folder scanned by the model registry; `None` until the user picks one.
+pub fn get_models_dir() -> Result<Option<String>> {
+    let conn = open_connection()?;
+    Ok(read_config_value(&conn, MODELS_DIR_KEY)?.filter(|value| !value.trim().is_empty()))
+}
+
+pub fn set_models_dir(path: &str) -> Result<()> {
+    let conn = open_connection()?;
+    write_config_value(&conn, MODELS_DIR_KEY, path.trim())
+}
+
+/// Registry id of the chat model chosen with `select_chat_model`.
+pub fn get_chat_model_id() -> Result<Option<String>> {
+    let conn = open_connection()?;
+    Ok(read_config_value(&conn, CHAT_MODEL_ID_KEY)?.filter(|value| !value.is_empty()))
+}
+
+pub fn set_chat_model_id(id: &str) -> Result<()> {
+    let conn = open_connection()?;
+    write_config_value(&conn, CHAT_MODEL_ID_KEY, id)
+}
+
+/// Registry id of the embedding model chosen with `select_embedding_model`.
+pub fn get_embedding_model_id() -> Result<Option<String>> {
+    let conn = open_connection()?;
+    Ok(read_config_value(&conn, EMBEDDING_MODEL_ID_KEY)?.filter(|value| !value.is_empty()))
+}
+
+pub fn set_embedding_model_id(id: &str) -> Result<()> {
+    let conn = open_connection()?;
+    write_config_value(&conn, EMBEDDING_MODEL_ID_KEY, id)
+}
+
+/// Registry id of the reranker chosen with `select_reranker_model`; an empty
+/// id means retrieval runs without one.
+pub fn get_reranker_model_id() -> Result<Option<String>> {
+    let conn = open_connection()?;
+    Ok(read_config_value(&conn, RERANKER_MODEL_ID_KEY)?.filter(|value| !value.is_empty()))
+}
+
+pub fn set_reranker_model_id(id: &str) -> Result<()> {
+    let conn = open_connection()?;
+    write_config_value(&conn, RERANKER_MODEL_ID_KEY, id)
+}
+
+pub fn get_load_strategy() -> Result<LoadStrategy> {
+    let conn = open_connection()?;
+    Ok(read_config_value(&conn, LOAD_STRATEGY_KEY)?
+        .and_then(|value| LoadStrategy::parse(&value))
+        .unwrap_or(LoadStrategy::Auto))
+}
+
+pub fn set_load_strategy(strategy: LoadStrategy) -> Result<()> {
+    let conn = open_connection()?;
+    write_config_value(&conn, LOAD_STRATEGY_KEY, strategy.as_str())
+}
+
+pub fn get_sampling_params() -> Result<SamplingParams> {
+    let conn = open_connection()?;
+    let mut sampling = SamplingParams::default();
+
+    if let Some(value) = read_config_value(&conn, TEMPERATURE_KEY)? {
+        sampling.temperature = parse_temperature(&value).unwrap_or(DEFAULT_TEMPERATURE);
+    }
+    if let Some(value) = read_config_parsed(&conn, TOP_K_KEY)? {
+        sampling.top_k = value;
+    }
+    if let Some(value) = read_config_parsed(&conn, TOP_P_KEY)? {
+        sampling.top_p = value;
+    }
+    if let Some(value) = read_config_parsed(&conn, MIN_P_KEY)? {
+        sampling.min_p = value;
+    }
+    if let Some(value) = read_config_parsed(&conn, REPEAT_PENALTY_KEY)? {
+        sampling.repeat_penalty = value;
+    }
+    if let Some(value) = read_config_parsed(&conn, REPEAT_LAST_N_KEY)? {
+        sampling.repeat_last_n = value;
+    }
+    sampling.seed = read_config_parsed(&conn, SEED_KEY)?;
+    if let Some(value) = read_config_parsed(&conn, MIROSTAT_KEY)? {
+        sampling.mirostat = value;
+    }
+    if let Some(value) = read_config_parsed(&conn, MIROSTAT_TAU_KEY)? {
+        sampling.mirostat_tau = value;
+    }
+    if let Some(value) = read_config_parsed(&conn, MIROSTAT_ETA_KEY)? {
+        sampling.mirostat_eta = value;
+    }
+
+    Ok(sampling.sanitized())
+}
+
+pub fn set_sampling_params(sampling: &SamplingParams) -> Result<()> {
+    let sanitized = sampling.sanitized();
+    let temperature = sanitized.temperature;
+    let seed = sanitized.seed.map(|seed| seed.to_string()).unwrap_or_default();
+
+    let mut conn = open_connection()?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    write_config_value(&tx, TEMPERATURE_KEY, &format!("{temperature:.3}"))?;
+    write_config_value(&tx, TOP_K_KEY, &sanitized.top_k.to_string())?;
+    write_config_value(&tx, TOP_P_KEY, &sanitized.top_p.to_string())?;
+    write_config_value(&tx, MIN_P_KEY, &sanitized.min_p.to_string())?;
+    write_config_value(&tx, REPEAT_PENALTY_KEY, &sanitized.repeat_penalty.to_string())?;
+    write_config_value(&tx, REPEAT_LAST_N_KEY, &sanitized.repeat_last_n.to_string())?;
+    write_config_value(&tx, SEED_KEY, &seed)?;
+    write_config_value(&tx, MIROSTAT_KEY, &sanitized.mirostat.to_string())?;
+    write_config_value(&tx, MIROSTAT_TAU_KEY, &sanitized.mirostat_tau.to_string())?;
+    write_config_value(&tx, MIROSTAT_ETA_KEY, &sanitized.mirostat_eta.to_string())?;
+    tx.commit()
+}
+
+pub fn get_runtime_settings() -> Result<RuntimeSettings> {
+    let conn = open_connection()?;
+    let mut settings = RuntimeSettings::default();
+
+    if let Some(value) = read_config_parsed(&conn, AUTO_TUNE_KEY)? {
+        settings.auto_tune = value;
+    }
+    if let Some(value) = read_config_parsed(&conn, N_CTX_KEY)? {
+        settings.n_ctx = value;
+    }
+    if let Some(value) = read_config_parsed(&conn, N_BATCH_KEY)? {
+        settings.n_batch = value;
+    }
+    if let Some(value) = read_config_parsed(&conn, N_THREADS_KEY)? {
+        settings.n_threads = value;
+    }
+    if let Some(value) = read_config_parsed(&conn, N_THREADS_BATCH_KEY)? {
+        settings.n_threads_batch = value;
+    }
+
+    Ok(settings.sanitized())
+}
+
+pub fn set_runtime_settings(settings: &RuntimeSettings) -> Result<()> {
+    let sanitized = settings.sanitized();
+
+    let mut conn = open_connection()?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    write_config_value(&tx, AUTO_TUNE_KEY, &sanitized.auto_tune.to_string())?;
+    write_config_value(&tx, N_CTX_KEY, &sanitized.n_ctx.to_string())?;
+    write_config_value(&tx, N_BATCH_KEY, &sanitized.n_batch.to_string())?;
+    write_config_value(&tx, N_THREADS_KEY, &sanitized.n_threads.to_string())?;
+    write_config_value(&tx, N_THREADS_BATCH_KEY, &sanitized.n_threads_batch.to_string())?;
+    tx.commit()
+}
+
+pub fn get_backend_settings() -> Result<BackendSettings> {
+    let conn = open_connection()?;
+    let mut settings = BackendSettings::default();
+
+    if let Some(kind) = read_config_value(&conn, CHAT_BACKEND_KEY)?.and_then(|value| BackendKind::parse(&value)) {
+        settings.chat_backend = kind;
+    }
+    if let Some(kind) =
+        read_config_value(&conn, EMBEDDING_BACKEND_KEY)?.and_then(|value| BackendKind::parse(&value))
+    {
+        settings.embedding_backend = kind;
+    }
+    if let Some(value) = read_config_value(&conn, SERVER_URL_KEY)? {
+        settings.server_url = value;
+    }
+    if let Some(value) = read_config_value(&conn, SERVER_CHAT_MODEL_KEY)? {
+        settings.server_chat_model = value;
+    }
+    if let Some(value) = read_config_value(&conn, SERVER_EMBEDDING_MODEL_KEY)? {
+        settings.server_embedding_model = value;
+    }
+    if let Some(value) = read_config_parsed(&conn, SERVER_CONTEXT_WINDOW_KEY)? {
+        settings.server_context_window = value;
+    }
+
+    Ok(settings.sanitized())
+}
+
+pub fn set_backend_settings(settings: &BackendSettings) -> Result<()> {
+    let sanitized = settings.sanitized();
+
+    let mut conn = open_connection()?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    write_config_value(&tx, CHAT_BACKEND_KEY, sanitized.chat_backend.as_str())?;
+    write_config_value(&tx, EMBEDDING_BACKEND_KEY, sanitized.embedding_backend.as_str())?;
+    write_config_value(&tx, SERVER_URL_KEY, &sanitized.server_url)?;
+    write_config_value(&tx, SERVER_CHAT_MODEL_KEY, &sanitized.server_chat_model)?;
+    write_config_value(&tx, SERVER_EMBEDDING_MODEL_KEY, &sanitized.server_embedding_model)?;
+    write_config_value(&tx, SERVER_CONTEXT_WINDOW_KEY, &sanitized.server_context_window.to_string())?;
+    tx.commit()
+}
+
 pub fn clear_profile() -> Result<()> {
     let conn = open_connection()?;
     conn.execute("DELETE FROM profile_traits", [])?;
+    prompt_cache::invalidate();
     Ok(())
 }
 
@@ -357,6 +1250,7 @@ pub fn add_profile_trait(category: &str, content: &str) -> Result<()> {
         "INSERT INTO profile_traits (category, content) VALUES (?1, ?2)",
         params![category, content],
     )?;
+    prompt_cache::invalidate();
     Ok(())
 }
 
@@ -401,78 +1295,84 @@ pub fn export_database(dest_path: &str) -> Result<bool> {
     Ok(destination.exists())
 }
 
-pub fn factory_reset() -> std::result::Result<(), String> {
+pub fn factory_reset() -> std::result::Result<(), AnimaError> {
     eprintln!("[factory_reset] start");
-    let mut last_error = String::new();
+    let mut last_error = None;
 
     for attempt in 0..4 {
-        let mut conn = open_connection().map_err(|error| format!("DB open failed: {error}"))?;
-        let tx = conn
-            .transaction_with_behavior(TransactionBehavior::Immediate)
-            .map_err(|error| format!("DB transaction start failed: {error}"))?;
-
-        let result = (|| -> std::result::Result<(), String> {
-            tx.execute("DELETE FROM memories", [])
-                .map_err(|error| format!("Factory reset failed clearing memories: {error}"))?;
-            tx.execute("DELETE FROM profile_traits", [])
-                .map_err(|error| format!("Factory reset failed clearing profile_traits: {error}"))?;
-            tx.execute("DELETE FROM config", [])
-                .map_err(|error| format!("Factory reset failed clearing config: {error}"))?;
-            tx.execute("DELETE FROM messages", [])
-                .map_err(|error| format!("Factory reset failed clearing messages: {error}"))?;
+        let result = (|| -> Result<()> {
+            let mut conn = open_connection()?;
+            let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+
+            tx.execute("DELETE FROM memories", [])?;
+            tx.execute("DELETE FROM profile_traits", [])?;
+            tx.execute("DELETE FROM config", [])?;
+            tx.execute("DELETE FROM messages", [])?;
 
             match tx.execute("DELETE FROM sqlite_sequence", []) {
                 Ok(_) => {}
                 Err(rusqlite::Error::SqliteFailure(_, Some(message)))
                     if message.contains("no such table: sqlite_sequence") => {}
-                Err(error) => {
-                    return Err(format!("Factory reset failed resetting sqlite_sequence: {error}"));
-                }
+                Err(error) => return Err(error),
             }
 
             tx.commit()
-                .map_err(|error| format!("Factory reset commit failed: {error}"))?;
-
-            Ok(())
         })();
 
         match result {
             Ok(()) => {
+                prompt_cache::invalidate();
+                memory_index::invalidate();
                 eprintln!("[factory_reset] soft reset completed");
                 return Ok(());
             }
             Err(error) => {
-                let lower = error.to_lowercase();
-                let is_locked = lower.contains("database is locked")
-                    || lower.contains("database table is locked")
-                    || lower.contains("sqlite_busy")
-                    || lower.contains("sqlite_locked");
-
-                if is_locked && attempt < 3 {
+                if error::is_lock_error(&error) && attempt < 3 {
                     eprintln!(
                         "[factory_reset] locked on attempt {}/4, retrying: {}",
                         attempt + 1,
                         error
                     );
-                    last_error = error;
+                    last_error = Some(error);
                     sleep(Duration::from_millis(200 * (attempt + 1) as u64));
                     continue;
                 }
 
-                return Err(error);
+                return Err(AnimaError::from(error).context("Factory reset failed"));
             }
         }
     }
 
-    Err(if last_error.is_empty() {
-        "Factory reset failed after retries".to_string()
-    } else {
-        last_error
+    Err(match last_error {
+        Some(error) => AnimaError::from(error).context("Factory reset failed after retries"),
+        None => AnimaError::DbLocked("Factory reset failed after retries".to_string()),
     })
 }
 
+/// Points the database at another file and creates the schema there. The app
+/// keeps `anima_chat.db` in the working directory; tests use a fresh file each.
+pub fn set_db_path(path: &Path) -> Result<()> {
+    let conn = Connection::open(path)?;
+    init_schema(&conn)?;
+    if let Ok(mut db_path) = DB_PATH_OVERRIDE.write() {
+        *db_path = Some(path.to_path_buf());
+    }
+    let _ = SCHEMA_INITIALIZED.set(());
+    prompt_cache::invalidate();
+    memory_index::invalidate();
+    Ok(())
+}
+
+fn db_path() -> PathBuf {
+    DB_PATH_OVERRIDE
+        .read()
+        .ok()
+        .and_then(|db_path| db_path.clone())
+        .unwrap_or_else(|| PathBuf::from(DB_PATH))
+}
+
 fn open_connection() -> Result<Connection> {
-    let conn = Connection::open(DB_PATH)?;
+    let conn = Connection::open(db_path())?;
     conn.busy_timeout(Duration::from_secs(8))?;
     conn.pragma_update(None, "foreign_keys", "ON")?;
 
@@ -499,7 +1399,12 @@ fn init_schema(conn: &Connection) -> Result<()> {
         "CREATE TABLE IF NOT EXISTS memories (
             message_id INTEGER PRIMARY KEY,
             embedding BLOB NOT NULL,
+            embedding_model TEXT NOT NULL DEFAULT '',
+            embedding_dim INTEGER NOT NULL DEFAULT 0,
             memory_type TEXT NOT NULL DEFAULT 'episodic' CHECK(memory_type IN ('semantic','episodic')),
+            importance REAL NOT NULL DEFAULT 0.5,
+            access_count INTEGER NOT NULL DEFAULT 0,
+            last_accessed INTEGER,
             timestamp INTEGER NOT NULL DEFAULT (strftime('%s','now')),
             created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
             FOREIGN KEY(message_id) REFERENCES messages(id) ON DELETE CASCADE
@@ -521,6 +1426,46 @@ fn init_schema(conn: &Connection) -> Result<()> {
         )?;
     }
 
+    if !table_has_column(conn, "memories", "embedding_model")? {
+        conn.execute(
+            "ALTER TABLE memories ADD COLUMN embedding_model TEXT NOT NULL DEFAULT ''",
+            [],
+        )?;
+    }
+
+    if !table_has_column(conn, "memories", "embedding_dim")? {
+        conn.execute(
+            "ALTER TABLE memories ADD COLUMN embedding_dim INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+
+    if !table_has_column(conn, "memories", "importance")? {
+        conn.execute(
+            "ALTER TABLE memories ADD COLUMN importance REAL NOT NULL DEFAULT 0.5",
+            [],
+        )?;
+    }
+
+    if !table_has_column(conn, "memories", "access_count")? {
+        conn.execute(
+            "ALTER TABLE memories ADD COLUMN access_count INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+
+    if !table_has_column(conn, "memories", "last_accessed")? {
+        conn.execute("ALTER TABLE memories ADD COLUMN last_accessed INTEGER", [])?;
+    }
+
+    // Vectors are little-endian f32, so the blob length gives the dimension.
+    conn.execute(
+        "UPDATE memories
+         SET embedding_dim = length(embedding) / 4
+         WHERE embedding_dim = 0 AND length(embedding) > 0",
+        [],
+    )?;
+
     conn.execute(
         "UPDATE memories
          SET memory_type = CASE
@@ -570,6 +1515,41 @@ fn init_schema(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_memories_embedding_model ON memories(embedding_model, embedding_dim)",
+        [],
+    )?;
+
+    // Full-text index over message text, stored once in `messages` and kept
+    // in sync by triggers. Diacritics are folded so "cafe" finds "café".
+    let fts_existed = table_exists(conn, "messages_fts")?;
+    conn.execute(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS messages_fts USING fts5(
+            content,
+            content='messages',
+            content_rowid='id',
+            tokenize='unicode61 remove_diacritics 2'
+        )",
+        [],
+    )?;
+    conn.execute_batch(
+        "CREATE TRIGGER IF NOT EXISTS messages_fts_insert AFTER INSERT ON messages BEGIN
+             INSERT INTO messages_fts(rowid, content) VALUES (new.id, new.content);
+         END;
+         CREATE TRIGGER IF NOT EXISTS messages_fts_delete AFTER DELETE ON messages BEGIN
+             INSERT INTO messages_fts(messages_fts, rowid, content)
+             VALUES ('delete', old.id, old.content);
+         END;
+         CREATE TRIGGER IF NOT EXISTS messages_fts_update AFTER UPDATE OF content ON messages BEGIN
+             INSERT INTO messages_fts(messages_fts, rowid, content)
+             VALUES ('delete', old.id, old.content);
+             INSERT INTO messages_fts(rowid, content) VALUES (new.id, new.content);
+         END;",
+    )?;
+    if !fts_existed {
+        conn.execute("INSERT INTO messages_fts(messages_fts) VALUES ('rebuild')", [])?;
+    }
+
     conn.execute(
         "INSERT OR IGNORE INTO config (key, value) VALUES (?1, ?2)",
         params![TEMPERATURE_KEY, DEFAULT_TEMPERATURE.to_string()],
@@ -578,10 +1558,33 @@ fn init_schema(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+fn read_config_value(conn: &Connection, key: &str) -> Result<Option<String>> {
+    let mut statement = conn.prepare("SELECT value FROM config WHERE key = ?1 LIMIT 1")?;
+    match statement.query_row(params![key], |row| row.get::<_, String>(0)) {
+        Ok(value) => Ok(Some(value)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+fn read_config_parsed<T: std::str::FromStr>(conn: &Connection, key: &str) -> Result<Option<T>> {
+    Ok(read_config_value(conn, key)?.and_then(|value| value.trim().parse::<T>().ok()))
+}
+
+fn write_config_value(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO config(key, value)
+         VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![key, value],
+    )?;
+    Ok(())
+}
+
 fn parse_temperature(value: &str) -> Option<f32> {
     let parsed = value.trim().parse::<f32>().ok()?;
     if parsed.is_finite() {
-        Some(parsed.clamp(0.1, 1.0))
+        Some(parsed.clamp(MIN_TEMPERATURE, MAX_TEMPERATURE))
     } else {
         None
     }
@@ -595,6 +1598,14 @@ fn normalize_memory_type(memory_type: &str) -> &'static str {
     }
 }
 
+fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE name = ?1)",
+        params![table],
+        |row| row.get(0),
+    )
+}
+
 fn table_has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
     let pragma = format!("PRAGMA table_info({table})");
     let mut statement = conn.prepare(&pragma)?;
@@ -611,7 +1622,7 @@ fn table_has_column(conn: &Connection, table: &str, column: &str) -> Result<bool
 }
 
 fn f32_slice_to_blob(vector: &[f32]) -> Vec<u8> {
-    let mut bytes = Vec::with_capacity(vector.len() * std::mem::size_of::<f32>());
+    let mut bytes = Vec::with_capacity(std::mem::size_of_val(vector));
     for value in vector {
         bytes.extend_from_slice(&value.to_le_bytes());
     }
@@ -623,3 +1634,217 @@ fn blob_to_f32_vec(blob: &[u8]) -> Vec<f32> {
         .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_backend::test_support::MockSession;
+
+    #[test]
+    fn test_cosine_similarity_rejects_mismatched_dimensions() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[1.0, 0.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn test_sampling_params_round_trip_keeps_full_temperature_range() {
+        let _session = MockSession::start();
+        let sampling = SamplingParams {
+            temperature: 0.0,
+            top_k: 20,
+            seed: Some(42),
+            mirostat: 2,
+            ..SamplingParams::default()
+        };
+        set_sampling_params(&sampling).unwrap();
+        assert_eq!(get_sampling_params().unwrap(), sampling);
+
+        set_temperature(1.6).unwrap();
+        assert_eq!(get_temperature().unwrap(), 1.6);
+        assert_eq!(get_sampling_params().unwrap().temperature, 1.6);
+
+        set_temperature(9.0).unwrap();
+        assert_eq!(get_temperature().unwrap(), MAX_TEMPERATURE);
+    }
+
+    #[test]
+    fn test_recent_dialogue_includes_legacy_assistant_role() {
+        let _session = MockSession::start();
+        insert_message("user", "hola").unwrap();
+        insert_message("anima", "hola, ¿qué tal?").unwrap();
+        insert_message("memory", "not dialogue").unwrap();
+
+        let dialogue = get_recent_dialogue(10).unwrap();
+        let roles = dialogue.iter().map(|message| message.role.as_str()).collect::<Vec<_>>();
+        assert_eq!(roles, ["user", "anima"]);
+    }
+
+    #[test]
+    fn test_embedding_blob_round_trip() {
+        let vector = vec![0.5_f32, -1.25, 3.0];
+        let blob = f32_slice_to_blob(&vector);
+        assert_eq!(blob.len(), vector.len() * 4);
+        assert_eq!(blob_to_f32_vec(&blob), vector);
+    }
+
+    #[test]
+    fn test_fts_match_expression_quotes_every_word() {
+        assert_eq!(
+            fts_match_expression("Lucía's \"cat\" OR dog*", false).as_deref(),
+            Some(r#""Lucía" OR "s" OR "cat" OR "OR" OR "dog""#)
+        );
+        assert_eq!(fts_match_expression("luc", true).as_deref(), Some(r#""luc"*"#));
+        assert_eq!(
+            fts_match_expression("mi herm", true).as_deref(),
+            Some(r#""mi"* "herm"*"#)
+        );
+        assert_eq!(fts_match_expression(" ?! ", false), None);
+    }
+
+    #[test]
+    fn test_hybrid_retrieval_finds_rare_words_the_vectors_miss() {
+        let _session = MockSession::start();
+        let sister = insert_message("user", "Mi hermana se llama Lucía").unwrap();
+        let tea = insert_message("user", "I like green tea").unwrap();
+        insert_memory(sister, &[1.0, 0.0, 0.0], "test", "episodic", 0.5, 10).unwrap();
+        insert_memory(tea, &[0.0, 1.0, 0.0], "test", "episodic", 0.5, 10).unwrap();
+
+        let query = [0.1, 1.0, 0.0];
+        set_lexical_weight(0.3).unwrap();
+        let found = find_top_similar_memories("Who is Lucia?", &query, "test", 5, None).unwrap();
+        let ids = found.iter().map(|memory| memory.message_id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![tea, sister]);
+        assert!(found[0].relevance > found[1].relevance);
+        assert!(found[1].similarity < MIN_SIMILARITY_THRESHOLD);
+
+        set_lexical_weight(0.0).unwrap();
+        let vector_only = find_top_similar_memories("Lucia", &query, "test", 5, None).unwrap();
+        assert_eq!(vector_only.len(), 1);
+
+        let listed = search_memories("luc").unwrap();
+        assert_eq!(listed.iter().map(|item| item.id).collect::<Vec<_>>(), vec![sister]);
+        assert_eq!(search_memories("").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_browse_search_needs_every_word_and_falls_back_to_substring() {
+        let _session = MockSession::start();
+        let sister = insert_message("user", "Mi hermana se llama Lucía").unwrap();
+        let mood = insert_message("user", "Hoy me siento :-)").unwrap();
+        insert_memory(sister, &[1.0, 0.0], "test", "episodic", 0.5, 10).unwrap();
+        insert_memory(mood, &[0.0, 1.0], "test", "episodic", 0.5, 10).unwrap();
+
+        let ids = |query: &str| {
+            search_memories(query)
+                .unwrap()
+                .iter()
+                .map(|item| item.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("herm luc"), vec![sister]);
+        assert!(ids("herm siento").is_empty());
+        assert_eq!(ids(":-)"), vec![mood]);
+    }
+
+    #[test]
+    fn test_untagged_memories_are_stale_until_reembedded() {
+        let _session = MockSession::start();
+        let legacy = insert_message("user", "My cat is called Miso").unwrap();
+        insert_memory(legacy, &[1.0, 0.0], "", "episodic", 0.5, 10).unwrap();
+
+        set_lexical_weight(0.0).unwrap();
+        assert!(find_top_similar_memories("", &[1.0, 0.0], "test", 5, None).unwrap().is_empty());
+        assert_eq!(count_stale_memories("test").unwrap(), 1);
+
+        set_lexical_weight(0.5).unwrap();
+        let found = find_top_similar_memories("Miso", &[1.0, 0.0], "test", 5, None).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].similarity, 0.0);
+    }
+
+    #[test]
+    fn test_mmr_skips_near_duplicates_of_picked_memories() {
+        let hiking_a: &[f32] = &[1.0, 0.0, 0.0];
+        let hiking_b: &[f32] = &[0.99, 0.14, 0.0];
+        let sister: &[f32] = &[0.0, 0.0, 1.0];
+        let scores = [1.0, 0.95, 0.6];
+        let embeddings = [hiking_a, hiking_b, sister];
+
+        assert_eq!(mmr_order(&scores, &embeddings, 1.0, 2), vec![0, 1]);
+        assert_eq!(mmr_order(&scores, &embeddings, 0.7, 2), vec![0, 2]);
+        assert_eq!(mmr_order(&scores, &embeddings, 0.7, 5), vec![0, 2, 1]);
+        assert!(mmr_order(&[], &[], 0.7, 3).is_empty());
+    }
+
+    #[test]
+    fn test_vectors_of_another_model_never_count_as_near_duplicates() {
+        let _session = MockSession::start();
+        let ids = [
+            ("hiking in the alps", "test"),
+            ("hiking in the alps before", "old-model"),
+            ("hiking with my sister", "test"),
+        ]
+        .map(|(content, model)| {
+            let id = insert_message("user", content).unwrap();
+            let vector = if content.contains("sister") {
+                [0.0, 1.0]
+            } else {
+                [1.0, 0.0]
+            };
+            insert_memory(id, &vector, model, "episodic", 0.5, 10).unwrap();
+            id
+        });
+
+        set_lexical_weight(1.0).unwrap();
+        let mut memories =
+            find_top_similar_memories("hiking", &[1.0, 0.0], "test", 3, None).unwrap();
+        memories.sort_by_key(|memory| memory.message_id);
+        assert_eq!(memories.len(), 3);
+
+        let picked = diversify_memories(memories, &[1.0, 0.95, 0.6], "test", 2).unwrap();
+        let picked = picked.iter().map(|memory| memory.message_id).collect::<Vec<_>>();
+        assert_eq!(picked, vec![ids[0], ids[1]]);
+    }
+
+    #[test]
+    fn test_memory_score_decays_with_age_and_saturates_with_access() {
+        let weights = MemoryScoringWeights::default();
+        let fresh = weights.score(1.0 / (RRF_K + 1.0), 0, 1.0, 0);
+        assert!((fresh.relevance - 1.0).abs() < 1e-6);
+        assert!((fresh.recency - 0.25).abs() < 1e-6);
+        assert_eq!(fresh.access, 0.0);
+
+        let month_old = weights.score(0.0, 30 * 86_400, 0.0, 1_000);
+        assert!((month_old.recency - 0.125).abs() < 1e-4);
+        assert!((month_old.access - 0.1).abs() < 1e-6);
+        assert_eq!(month_old.total, month_old.recency + month_old.access);
+    }
+
+    #[test]
+    fn test_recent_important_memory_outranks_slightly_closer_old_one() {
+        let _session = MockSession::start();
+        let now = current_unix_timestamp();
+        let old = insert_message("user", "a throwaway line").unwrap();
+        let recent = insert_message("user", "an important event").unwrap();
+        insert_memory(old, &[1.0, 0.0], "test", "episodic", 0.0, now - 3 * 365 * 86_400).unwrap();
+        insert_memory(recent, &[0.9, 0.4], "test", "episodic", 1.0, now - 86_400).unwrap();
+
+        let query = [1.0, 0.0];
+        let found = find_top_similar_memories("", &query, "test", 2, None).unwrap();
+        assert_eq!(found[0].message_id, recent);
+        assert!(found[0].similarity < found[1].similarity);
+        assert!(found[0].score.importance > found[1].score.importance);
+
+        set_memory_scoring_weights(&MemoryScoringWeights {
+            recency: 0.0,
+            importance: 0.0,
+            ..MemoryScoringWeights::default()
+        })
+        .unwrap();
+        record_memory_access(&[old, old]).unwrap();
+        let found = find_top_similar_memories("", &query, "test", 2, None).unwrap();
+        assert_eq!(found[0].message_id, old);
+        assert_eq!(found[0].access_count, 2);
+    }
+}
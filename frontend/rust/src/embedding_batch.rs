@@ -0,0 +1,88 @@
+use std::ops::Range;
+
+/// Groups consecutive sequences into batches of at most `max_tokens` tokens
+/// and `max_sequences` sequences. A sequence longer than `max_tokens` gets a
+/// batch of its own; callers chunk texts so that does not happen.
+pub fn plan_batches(
+    sequence_lens: &[usize],
+    max_tokens: usize,
+    max_sequences: usize,
+) -> Vec<Range<usize>> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut tokens = 0;
+
+    for (index, &len) in sequence_lens.iter().enumerate() {
+        let full = index > start && (tokens + len > max_tokens || index - start >= max_sequences);
+        if full {
+            batches.push(start..index);
+            start = index;
+            tokens = 0;
+        }
+        tokens += len;
+    }
+    if start < sequence_lens.len() {
+        batches.push(start..sequence_lens.len());
+    }
+    batches
+}
+
+/// Combines the embeddings of a text's chunks into one vector, weighting each
+/// chunk by its token count. A single chunk is returned unchanged; multiple
+/// chunks are L2-normalized first so no chunk dominates by magnitude.
+pub fn merge_chunks(chunks: Vec<(Vec<f32>, usize)>) -> Vec<f32> {
+    if chunks.len() <= 1 {
+        return chunks
+            .into_iter()
+            .next()
+            .map(|(vector, _)| vector)
+            .unwrap_or_default();
+    }
+
+    let dimension = chunks
+        .iter()
+        .map(|(vector, _)| vector.len())
+        .max()
+        .unwrap_or(0);
+    let mut merged = vec![0.0_f32; dimension];
+    let mut total_weight = 0.0_f32;
+    for (vector, tokens) in &chunks {
+        let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+        if norm <= f32::EPSILON {
+            continue;
+        }
+        let weight = *tokens as f32;
+        for (slot, value) in merged.iter_mut().zip(vector) {
+            *slot += value / norm * weight;
+        }
+        total_weight += weight;
+    }
+    if total_weight > 0.0 {
+        for slot in &mut merged {
+            *slot /= total_weight;
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_batches_respects_token_and_sequence_limits() {
+        assert_eq!(plan_batches(&[3, 3, 3, 3], 7, 8), vec![0..2, 2..4]);
+        assert_eq!(plan_batches(&[1, 1, 1], 100, 2), vec![0..2, 2..3]);
+        assert_eq!(plan_batches(&[10, 2], 4, 8), vec![0..1, 1..2]);
+        assert!(plan_batches(&[], 4, 8).is_empty());
+    }
+
+    #[test]
+    fn test_merge_chunks_weights_by_tokens() {
+        assert_eq!(merge_chunks(vec![(vec![2.0, 0.0], 5)]), vec![2.0, 0.0]);
+
+        let merged = merge_chunks(vec![(vec![3.0, 0.0], 3), (vec![0.0, 2.0], 1)]);
+        assert!((merged[0] - 0.75).abs() < 1e-6);
+        assert!((merged[1] - 0.25).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+
+/// Error surfaced across the FRB boundary.
+///
+/// The variant is the stable machine code the UI switches on to pick a
+/// localized message; the payload is a technical detail meant for logs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnimaError {
+    ModelNotLoaded(String),
+    ModelLoadFailed(String),
+    /// The file is not a readable GGUF model.
+    InvalidModelFile(String),
+    /// The file is shorter than its header declares, e.g. an interrupted download.
+    ModelTruncated(String),
+    /// The file does not match the SHA-256 in its `.sha256` sidecar.
+    ModelChecksumMismatch(String),
+    /// An embedding model was given to the chat slot or the other way round.
+    WrongModelKind(String),
+    /// Not enough free RAM for the model with the chosen load strategy.
+    InsufficientMemory(String),
+    ContextOverflow(String),
+    /// The configured local inference server did not answer or answered with an error.
+    BackendUnavailable(String),
+    EmptyOutput(String),
+    GenerationFailed(String),
+    EmbeddingFailed(String),
+    Cancelled(String),
+    /// A background job gave way to more urgent work before finishing.
+    Preempted(String),
+    DbLocked(String),
+    DbIo(String),
+    InvalidInput(String),
+    Io(String),
+    Internal(String),
+}
+
+impl AnimaError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AnimaError::ModelNotLoaded(_) => "model_not_loaded",
+            AnimaError::ModelLoadFailed(_) => "model_load_failed",
+            AnimaError::InvalidModelFile(_) => "invalid_model_file",
+            AnimaError::ModelTruncated(_) => "model_truncated",
+            AnimaError::ModelChecksumMismatch(_) => "model_checksum_mismatch",
+            AnimaError::WrongModelKind(_) => "wrong_model_kind",
+            AnimaError::InsufficientMemory(_) => "insufficient_memory",
+            AnimaError::ContextOverflow(_) => "context_overflow",
+            AnimaError::BackendUnavailable(_) => "backend_unavailable",
+            AnimaError::EmptyOutput(_) => "empty_output",
+            AnimaError::GenerationFailed(_) => "generation_failed",
+            AnimaError::EmbeddingFailed(_) => "embedding_failed",
+            AnimaError::Cancelled(_) => "cancelled",
+            AnimaError::Preempted(_) => "preempted",
+            AnimaError::DbLocked(_) => "db_locked",
+            AnimaError::DbIo(_) => "db_io",
+            AnimaError::InvalidInput(_) => "invalid_input",
+            AnimaError::Io(_) => "io",
+            AnimaError::Internal(_) => "internal",
+        }
+    }
+
+    pub fn detail(&self) -> &str {
+        match self {
+            AnimaError::ModelNotLoaded(detail)
+            | AnimaError::ModelLoadFailed(detail)
+            | AnimaError::InvalidModelFile(detail)
+            | AnimaError::ModelTruncated(detail)
+            | AnimaError::ModelChecksumMismatch(detail)
+            | AnimaError::WrongModelKind(detail)
+            | AnimaError::InsufficientMemory(detail)
+            | AnimaError::ContextOverflow(detail)
+            | AnimaError::BackendUnavailable(detail)
+            | AnimaError::EmptyOutput(detail)
+            | AnimaError::GenerationFailed(detail)
+            | AnimaError::EmbeddingFailed(detail)
+            | AnimaError::Cancelled(detail)
+            | AnimaError::Preempted(detail)
+            | AnimaError::DbLocked(detail)
+            | AnimaError::DbIo(detail)
+            | AnimaError::InvalidInput(detail)
+            | AnimaError::Io(detail)
+            | AnimaError::Internal(detail) => detail,
+        }
+    }
+
+    /// Prefixes the detail with where the failure happened, keeping the code.
+    pub fn context(self, prefix: &str) -> Self {
+        let with_prefix = |detail: String| format!("{prefix}: {detail}");
+        match self {
+            AnimaError::ModelNotLoaded(detail) => AnimaError::ModelNotLoaded(with_prefix(detail)),
+            AnimaError::ModelLoadFailed(detail) => AnimaError::ModelLoadFailed(with_prefix(detail)),
+            AnimaError::InvalidModelFile(detail) => {
+                AnimaError::InvalidModelFile(with_prefix(detail))
+            }
+            AnimaError::ModelTruncated(detail) => AnimaError::ModelTruncated(with_prefix(detail)),
+            AnimaError::ModelChecksumMismatch(detail) => {
+                AnimaError::ModelChecksumMismatch(with_prefix(detail))
+            }
+            AnimaError::WrongModelKind(detail) => AnimaError::WrongModelKind(with_prefix(detail)),
+            AnimaError::InsufficientMemory(detail) => {
+                AnimaError::InsufficientMemory(with_prefix(detail))
+            }
+            AnimaError::ContextOverflow(detail) => AnimaError::ContextOverflow(with_prefix(detail)),
+            AnimaError::BackendUnavailable(detail) => {
+                AnimaError::BackendUnavailable(with_prefix(detail))
+            }
+            AnimaError::EmptyOutput(detail) => AnimaError::EmptyOutput(with_prefix(detail)),
+            AnimaError::GenerationFailed(detail) => {
+                AnimaError::GenerationFailed(with_prefix(detail))
+            }
+            AnimaError::EmbeddingFailed(detail) => AnimaError::EmbeddingFailed(with_prefix(detail)),
+            AnimaError::Cancelled(detail) => AnimaError::Cancelled(with_prefix(detail)),
+            AnimaError::Preempted(detail) => AnimaError::Preempted(with_prefix(detail)),
+            AnimaError::DbLocked(detail) => AnimaError::DbLocked(with_prefix(detail)),
+            AnimaError::DbIo(detail) => AnimaError::DbIo(with_prefix(detail)),
+            AnimaError::InvalidInput(detail) => AnimaError::InvalidInput(with_prefix(detail)),
+            AnimaError::Io(detail) => AnimaError::Io(with_prefix(detail)),
+            AnimaError::Internal(detail) => AnimaError::Internal(with_prefix(detail)),
+        }
+    }
+}
+
+impl fmt::Display for AnimaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code(), self.detail())
+    }
+}
+
+impl std::error::Error for AnimaError {}
+
+impl From<rusqlite::Error> for AnimaError {
+    fn from(error: rusqlite::Error) -> Self {
+        if is_lock_error(&error) {
+            AnimaError::DbLocked(error.to_string())
+        } else {
+            AnimaError::DbIo(error.to_string())
+        }
+    }
+}
+
+pub fn is_lock_error(error: &rusqlite::Error) -> bool {
+    matches!(
+        error,
+        rusqlite::Error::SqliteFailure(failure, _)
+            if failure.code == rusqlite::ErrorCode::DatabaseBusy
+                || failure.code == rusqlite::ErrorCode::DatabaseLocked
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqlite_busy_maps_to_db_locked() {
+        let busy = rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            Some("database is locked".to_string()),
+        );
+        let error = AnimaError::from(busy);
+        assert_eq!(error.code(), "db_locked");
+    }
+
+    #[test]
+    fn test_context_keeps_code() {
+        let error = AnimaError::EmbeddingFailed("tokenizer".to_string()).context("sleep cycle");
+        assert_eq!(error.code(), "embedding_failed");
+        assert_eq!(error.detail(), "sleep cycle: tokenizer");
+    }
+}
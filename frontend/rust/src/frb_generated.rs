@@ -37,7 +37,7 @@ flutter_rust_bridge::frb_generated_boilerplate!(
     default_rust_auto_opaque = RustAutoOpaqueMoi,
 );
 pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_VERSION: &str = "2.11.1";
-pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_CONTENT_HASH: i32 = -1117369052;
+pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_CONTENT_HASH: i32 = 10843265;
 
 // Section: executor
 
@@ -70,12 +70,76 @@ fn wire__crate__api__simple__add_profile_trait_impl(
             let api_category = <String>::sse_decode(&mut deserializer);
             let api_content = <String>::sse_decode(&mut deserializer);
             deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok =
+                        crate::api::simple::add_profile_trait(api_category, api_content)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__cancel_generation_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "cancel_generation",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_request_id = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok =
+                        Result::<_, ()>::Ok(crate::api::simple::cancel_generation(api_request_id))?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__cancel_reembedding_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "cancel_reembedding",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::add_profile_trait(
-                        api_category,
-                        api_content,
-                    ))?;
+                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::cancel_reembedding())?;
                     Ok(output_ok)
                 })())
             }
@@ -106,8 +170,40 @@ fn wire__crate__api__simple__clear_profile_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::clear_profile())?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::clear_profile()?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__clear_reranker_model_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "clear_reranker_model",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::clear_reranker_model()?;
                     Ok(output_ok)
                 })())
             }
@@ -139,8 +235,8 @@ fn wire__crate__api__simple__delete_memory_impl(
             let api_id = <i64>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::delete_memory(api_id))?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::delete_memory(api_id)?;
                     Ok(output_ok)
                 })())
             }
@@ -171,7 +267,7 @@ fn wire__crate__api__simple__export_brain_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, String>((move || {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
                     let output_ok = crate::api::simple::export_brain()?;
                     Ok(output_ok)
                 })())
@@ -204,7 +300,7 @@ fn wire__crate__api__simple__export_database_impl(
             let api_dest_path = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, String>((move || {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
                     let output_ok = crate::api::simple::export_database(api_dest_path)?;
                     Ok(output_ok)
                 })())
@@ -236,7 +332,7 @@ fn wire__crate__api__simple__factory_reset_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, String>((move || {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
                     let output_ok = crate::api::simple::factory_reset()?;
                     Ok(output_ok)
                 })())
@@ -269,7 +365,7 @@ fn wire__crate__api__simple__generate_proactive_greeting_impl(
             let api_time_of_day = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, String>((move || {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
                     let output_ok =
                         crate::api::simple::generate_proactive_greeting(api_time_of_day)?;
                     Ok(output_ok)
@@ -302,8 +398,8 @@ fn wire__crate__api__simple__get_all_memories_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_all_memories())?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_all_memories()?;
                     Ok(output_ok)
                 })())
             }
@@ -334,8 +430,72 @@ fn wire__crate__api__simple__get_app_language_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_app_language())?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_app_language()?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__get_backend_settings_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_backend_settings",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_backend_settings()?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__get_chat_context_config_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_chat_context_config",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_chat_context_config()?;
                     Ok(output_ok)
                 })())
             }
@@ -366,8 +526,8 @@ fn wire__crate__api__simple__get_chat_history_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_chat_history())?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_chat_history()?;
                     Ok(output_ok)
                 })())
             }
@@ -398,15 +558,15 @@ fn wire__crate__api__simple__get_core_prompt_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_core_prompt())?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_core_prompt()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__get_profile_traits_impl(
+fn wire__crate__api__simple__get_embedding_status_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -414,7 +574,7 @@ fn wire__crate__api__simple__get_profile_traits_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_profile_traits",
+            debug_name: "get_embedding_status",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -430,15 +590,15 @@ fn wire__crate__api__simple__get_profile_traits_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_profile_traits())?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_embedding_status()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__get_temperature_impl(
+fn wire__crate__api__simple__get_inference_queue_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -446,7 +606,7 @@ fn wire__crate__api__simple__get_temperature_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_temperature",
+            debug_name: "get_inference_queue",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -463,14 +623,14 @@ fn wire__crate__api__simple__get_temperature_impl(
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_temperature())?;
+                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_inference_queue())?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__get_user_name_impl(
+fn wire__crate__api__simple__get_lexical_weight_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -478,7 +638,7 @@ fn wire__crate__api__simple__get_user_name_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_user_name",
+            debug_name: "get_lexical_weight",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -494,24 +654,25 @@ fn wire__crate__api__simple__get_user_name_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_user_name())?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_lexical_weight()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__greet_impl(
+fn wire__crate__api__simple__get_load_strategy_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
     data_len_: i32,
-) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "greet",
-            port: None,
-            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+            debug_name: "get_load_strategy",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
         move || {
             let message = unsafe {
@@ -523,16 +684,17 @@ fn wire__crate__api__simple__greet_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_name = <String>::sse_decode(&mut deserializer);
             deserializer.end();
-            transform_result_sse::<_, ()>((move || {
-                let output_ok = Result::<_, ()>::Ok(crate::api::simple::greet(api_name))?;
-                Ok(output_ok)
-            })())
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_load_strategy()?;
+                    Ok(output_ok)
+                })())
+            }
         },
     )
 }
-fn wire__crate__api__simple__init_app_impl(
+fn wire__crate__api__simple__get_memory_scoring_weights_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -540,7 +702,7 @@ fn wire__crate__api__simple__init_app_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "init_app",
+            debug_name: "get_memory_scoring_weights",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -554,21 +716,17 @@ fn wire__crate__api__simple__init_app_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_chat_model_path = <String>::sse_decode(&mut deserializer);
-            let api_embedding_model_path = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok({
-                        crate::api::simple::init_app(api_chat_model_path, api_embedding_model_path);
-                    })?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_memory_scoring_weights()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__run_sleep_cycle_impl(
+fn wire__crate__api__simple__get_mmr_lambda_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -576,7 +734,7 @@ fn wire__crate__api__simple__run_sleep_cycle_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "run_sleep_cycle",
+            debug_name: "get_mmr_lambda",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -592,15 +750,15 @@ fn wire__crate__api__simple__run_sleep_cycle_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, String>((move || {
-                    let output_ok = crate::api::simple::run_sleep_cycle()?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_mmr_lambda()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__save_assistant_message_impl(
+fn wire__crate__api__simple__get_model_status_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -608,7 +766,7 @@ fn wire__crate__api__simple__save_assistant_message_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "save_assistant_message",
+            debug_name: "get_model_status",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -622,20 +780,17 @@ fn wire__crate__api__simple__save_assistant_message_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_message = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
                 transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(
-                        crate::api::simple::save_assistant_message(api_message),
-                    )?;
+                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::get_model_status())?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__search_memories_impl(
+fn wire__crate__api__simple__get_models_dir_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -643,7 +798,7 @@ fn wire__crate__api__simple__search_memories_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "search_memories",
+            debug_name: "get_models_dir",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -657,18 +812,17 @@ fn wire__crate__api__simple__search_memories_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_query = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, String>((move || {
-                    let output_ok = crate::api::simple::search_memories(api_query)?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_models_dir()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__send_message_impl(
+fn wire__crate__api__simple__get_prefix_cache_enabled_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -676,7 +830,7 @@ fn wire__crate__api__simple__send_message_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "send_message",
+            debug_name: "get_prefix_cache_enabled",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -690,24 +844,17 @@ fn wire__crate__api__simple__send_message_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_message = <String>::sse_decode(&mut deserializer);
-            let api_temperature = <f32>::sse_decode(&mut deserializer);
-            let api_max_tokens = <u32>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok(crate::api::simple::send_message(
-                        api_message,
-                        api_temperature,
-                        api_max_tokens,
-                    ))?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_prefix_cache_enabled()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__send_message_stream_impl(
+fn wire__crate__api__simple__get_profile_traits_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -715,7 +862,7 @@ fn wire__crate__api__simple__send_message_stream_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "send_message_stream",
+            debug_name: "get_profile_traits",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -729,29 +876,17 @@ fn wire__crate__api__simple__send_message_stream_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_message = <String>::sse_decode(&mut deserializer);
-            let api_temperature = <f32>::sse_decode(&mut deserializer);
-            let api_max_tokens = <u32>::sse_decode(&mut deserializer);
-            let api_sink =
-                <StreamSink<String, flutter_rust_bridge::for_generated::SseCodec>>::sse_decode(
-                    &mut deserializer,
-                );
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, String>((move || {
-                    let output_ok = crate::api::simple::send_message_stream(
-                        api_message,
-                        api_temperature,
-                        api_max_tokens,
-                        api_sink,
-                    )?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_profile_traits()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__set_app_language_impl(
+fn wire__crate__api__simple__get_runtime_settings_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -759,7 +894,7 @@ fn wire__crate__api__simple__set_app_language_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "set_app_language",
+            debug_name: "get_runtime_settings",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -773,19 +908,17 @@ fn wire__crate__api__simple__set_app_language_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_lang = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok =
-                        Result::<_, ()>::Ok(crate::api::simple::set_app_language(api_lang))?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_runtime_settings()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__set_core_prompt_impl(
+fn wire__crate__api__simple__get_sampling_params_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -793,7 +926,7 @@ fn wire__crate__api__simple__set_core_prompt_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "set_core_prompt",
+            debug_name: "get_sampling_params",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -807,19 +940,17 @@ fn wire__crate__api__simple__set_core_prompt_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_prompt = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok =
-                        Result::<_, ()>::Ok(crate::api::simple::set_core_prompt(api_prompt))?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_sampling_params()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__set_temperature_impl(
+fn wire__crate__api__simple__get_temperature_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -827,7 +958,7 @@ fn wire__crate__api__simple__set_temperature_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "set_temperature",
+            debug_name: "get_temperature",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -841,19 +972,17 @@ fn wire__crate__api__simple__set_temperature_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_temperature = <f32>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok =
-                        Result::<_, ()>::Ok(crate::api::simple::set_temperature(api_temperature))?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_temperature()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
-fn wire__crate__api__simple__set_user_name_impl(
+fn wire__crate__api__simple__get_user_name_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -861,7 +990,7 @@ fn wire__crate__api__simple__set_user_name_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "set_user_name",
+            debug_name: "get_user_name",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -875,398 +1004,3598 @@ fn wire__crate__api__simple__set_user_name_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_name = <String>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok =
-                        Result::<_, ()>::Ok(crate::api::simple::set_user_name(api_name))?;
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::get_user_name()?;
                     Ok(output_ok)
                 })())
             }
         },
     )
 }
+fn wire__crate__api__simple__greet_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "greet",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_name = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, ()>((move || {
+                let output_ok = Result::<_, ()>::Ok(crate::api::simple::greet(api_name))?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__simple__init_app_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "init_app",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_chat_model_path = <String>::sse_decode(&mut deserializer);
+            let api_embedding_model_path = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::init_app(
+                        api_chat_model_path,
+                        api_embedding_model_path,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__list_models_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "list_models",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::list_models()?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__load_model_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "load_model",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_kind = <crate::model_manager::ModelKind>::sse_decode(&mut deserializer);
+            let api_path = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::load_model(api_kind, api_path)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__preempt_background_jobs_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "preempt_background_jobs",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok =
+                        Result::<_, ()>::Ok(crate::api::simple::preempt_background_jobs())?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__preview_prompt_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "preview_prompt",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_message = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::preview_prompt(api_message)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__reembed_memories_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "reembed_memories",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_sink = <StreamSink<
+                crate::api::simple::ReembedEvent,
+                flutter_rust_bridge::for_generated::SseCodec,
+            >>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::reembed_memories(api_sink)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__reload_model_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "reload_model",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_kind = <crate::model_manager::ModelKind>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::reload_model(api_kind)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__run_sleep_cycle_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "run_sleep_cycle",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::run_sleep_cycle()?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__save_assistant_message_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "save_assistant_message",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_message = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::save_assistant_message(api_message)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__search_memories_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "search_memories",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_query = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::search_memories(api_query)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__select_chat_model_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "select_chat_model",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_id = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::select_chat_model(api_id)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__select_embedding_model_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "select_embedding_model",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_id = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::select_embedding_model(api_id)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__select_reranker_model_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "select_reranker_model",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_id = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::select_reranker_model(api_id)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__send_chat_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "send_chat",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_request = <crate::api::simple::ChatRequest>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::send_chat(api_request)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__send_chat_stream_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "send_chat_stream",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_request = <crate::api::simple::ChatRequest>::sse_decode(&mut deserializer);
+            let api_sink = <StreamSink<
+                crate::api::simple::ChatEvent,
+                flutter_rust_bridge::for_generated::SseCodec,
+            >>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::send_chat_stream(api_request, api_sink)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__send_message_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "send_message",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_message = <String>::sse_decode(&mut deserializer);
+            let api_temperature = <f32>::sse_decode(&mut deserializer);
+            let api_max_tokens = <u32>::sse_decode(&mut deserializer);
+            let api_request_id = <Option<String>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::send_message(
+                        api_message,
+                        api_temperature,
+                        api_max_tokens,
+                        api_request_id,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__send_message_stream_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "send_message_stream",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_message = <String>::sse_decode(&mut deserializer);
+            let api_temperature = <f32>::sse_decode(&mut deserializer);
+            let api_max_tokens = <u32>::sse_decode(&mut deserializer);
+            let api_request_id = <Option<String>>::sse_decode(&mut deserializer);
+            let api_sink = <StreamSink<
+                crate::api::simple::ChatEvent,
+                flutter_rust_bridge::for_generated::SseCodec,
+            >>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::send_message_stream(
+                        api_message,
+                        api_temperature,
+                        api_max_tokens,
+                        api_request_id,
+                        api_sink,
+                    )?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_app_language_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_app_language",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_lang = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_app_language(api_lang)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_backend_settings_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_backend_settings",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_settings = <crate::backend::BackendSettings>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_backend_settings(api_settings)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_core_prompt_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_core_prompt",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_prompt = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_core_prompt(api_prompt)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_lexical_weight_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_lexical_weight",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_weight = <f32>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_lexical_weight(api_weight)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_load_strategy_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_load_strategy",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_strategy = <crate::load_strategy::LoadStrategy>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_load_strategy(api_strategy)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_memory_scoring_weights_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_memory_scoring_weights",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_weights = <crate::db::MemoryScoringWeights>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_memory_scoring_weights(api_weights)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_mmr_lambda_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_mmr_lambda",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_lambda = <f32>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_mmr_lambda(api_lambda)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_models_dir_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_models_dir",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_path = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_models_dir(api_path)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_prefix_cache_enabled_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_prefix_cache_enabled",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_enabled = <bool>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_prefix_cache_enabled(api_enabled)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_runtime_settings_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_runtime_settings",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_settings =
+                <crate::runtime_config::RuntimeSettings>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_runtime_settings(api_settings)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_sampling_params_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_sampling_params",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_params = <crate::db::SamplingParams>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_sampling_params(api_params)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_temperature_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_temperature",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_temperature = <f32>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_temperature(api_temperature)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__set_user_name_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_user_name",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_name = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::set_user_name(api_name)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__simple__unload_model_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "unload_model",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_kind = <crate::model_manager::ModelKind>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, crate::error::AnimaError>((move || {
+                    let output_ok = crate::api::simple::unload_model(api_kind)?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+
+// Section: dart2rust
+
+impl SseDecode for flutter_rust_bridge::for_generated::anyhow::Error {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <String>::sse_decode(deserializer);
+        return flutter_rust_bridge::for_generated::anyhow::anyhow!("{}", inner);
+    }
+}
+
+impl SseDecode
+    for StreamSink<crate::api::simple::ChatEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <String>::sse_decode(deserializer);
+        return StreamSink::deserialize(inner);
+    }
+}
+
+impl SseDecode
+    for StreamSink<crate::api::simple::ReembedEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <String>::sse_decode(deserializer);
+        return StreamSink::deserialize(inner);
+    }
+}
+
+impl SseDecode for String {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <Vec<u8>>::sse_decode(deserializer);
+        return String::from_utf8(inner).unwrap();
+    }
+}
+
+impl SseDecode for crate::error::AnimaError {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::ModelNotLoaded(var_field0);
+            }
+            1 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::ModelLoadFailed(var_field0);
+            }
+            2 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::InvalidModelFile(var_field0);
+            }
+            3 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::ModelTruncated(var_field0);
+            }
+            4 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::ModelChecksumMismatch(var_field0);
+            }
+            5 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::WrongModelKind(var_field0);
+            }
+            6 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::InsufficientMemory(var_field0);
+            }
+            7 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::ContextOverflow(var_field0);
+            }
+            8 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::BackendUnavailable(var_field0);
+            }
+            9 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::EmptyOutput(var_field0);
+            }
+            10 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::GenerationFailed(var_field0);
+            }
+            11 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::EmbeddingFailed(var_field0);
+            }
+            12 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::Cancelled(var_field0);
+            }
+            13 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::Preempted(var_field0);
+            }
+            14 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::DbLocked(var_field0);
+            }
+            15 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::DbIo(var_field0);
+            }
+            16 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::InvalidInput(var_field0);
+            }
+            17 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::Io(var_field0);
+            }
+            18 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::error::AnimaError::Internal(var_field0);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::backend::BackendKind {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::backend::BackendKind::Llama,
+            1 => crate::backend::BackendKind::OpenAiServer,
+            2 => crate::backend::BackendKind::Mock,
+            _ => unreachable!("Invalid variant for BackendKind: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::backend::BackendSettings {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_chatBackend = <crate::backend::BackendKind>::sse_decode(deserializer);
+        let mut var_embeddingBackend = <crate::backend::BackendKind>::sse_decode(deserializer);
+        let mut var_serverUrl = <String>::sse_decode(deserializer);
+        let mut var_serverChatModel = <String>::sse_decode(deserializer);
+        let mut var_serverEmbeddingModel = <String>::sse_decode(deserializer);
+        let mut var_serverContextWindow = <u32>::sse_decode(deserializer);
+        return crate::backend::BackendSettings {
+            chat_backend: var_chatBackend,
+            embedding_backend: var_embeddingBackend,
+            server_url: var_serverUrl,
+            server_chat_model: var_serverChatModel,
+            server_embedding_model: var_serverEmbeddingModel,
+            server_context_window: var_serverContextWindow,
+        };
+    }
+}
+
+impl SseDecode for bool {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_u8().unwrap() != 0
+    }
+}
+
+impl SseDecode for crate::api::simple::ChatCompletion {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_text = <String>::sse_decode(deserializer);
+        let mut var_stopReason = <crate::ai::StopReason>::sse_decode(deserializer);
+        return crate::api::simple::ChatCompletion {
+            text: var_text,
+            stop_reason: var_stopReason,
+        };
+    }
+}
+
+impl SseDecode for crate::api::simple::ChatEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                let mut var_memories = <Vec<crate::db::MemoryMatch>>::sse_decode(deserializer);
+                return crate::api::simple::ChatEvent::ContextRetrieved {
+                    memories: var_memories,
+                };
+            }
+            1 => {
+                let mut var_field0 = <String>::sse_decode(deserializer);
+                return crate::api::simple::ChatEvent::Token(var_field0);
+            }
+            2 => {
+                let mut var_promptTokens = <u32>::sse_decode(deserializer);
+                let mut var_completionTokens = <u32>::sse_decode(deserializer);
+                let mut var_tokensPerSec = <f64>::sse_decode(deserializer);
+                let mut var_stopReason = <crate::ai::StopReason>::sse_decode(deserializer);
+                return crate::api::simple::ChatEvent::Done {
+                    prompt_tokens: var_promptTokens,
+                    completion_tokens: var_completionTokens,
+                    tokens_per_sec: var_tokensPerSec,
+                    stop_reason: var_stopReason,
+                };
+            }
+            3 => {
+                let mut var_code = <String>::sse_decode(deserializer);
+                let mut var_detail = <String>::sse_decode(deserializer);
+                return crate::api::simple::ChatEvent::Error {
+                    code: var_code,
+                    detail: var_detail,
+                };
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::db::ChatMessage {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_id = <i64>::sse_decode(deserializer);
+        let mut var_role = <String>::sse_decode(deserializer);
+        let mut var_content = <String>::sse_decode(deserializer);
+        let mut var_timestamp = <String>::sse_decode(deserializer);
+        return crate::db::ChatMessage {
+            id: var_id,
+            role: var_role,
+            content: var_content,
+            timestamp: var_timestamp,
+        };
+    }
+}
+
+impl SseDecode for crate::api::simple::ChatOptions {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_sampling = <Option<crate::db::SamplingParams>>::sse_decode(deserializer);
+        let mut var_maxTokens = <u32>::sse_decode(deserializer);
+        return crate::api::simple::ChatOptions {
+            sampling: var_sampling,
+            max_tokens: var_maxTokens,
+        };
+    }
+}
+
+impl SseDecode for crate::api::simple::ChatRequest {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_requestId = <Option<String>>::sse_decode(deserializer);
+        let mut var_history = <Vec<crate::api::simple::Turn>>::sse_decode(deserializer);
+        let mut var_message = <String>::sse_decode(deserializer);
+        let mut var_options = <crate::api::simple::ChatOptions>::sse_decode(deserializer);
+        return crate::api::simple::ChatRequest {
+            request_id: var_requestId,
+            history: var_history,
+            message: var_message,
+            options: var_options,
+        };
+    }
+}
+
+impl SseDecode for crate::runtime_config::ContextConfig {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_nCtx = <u32>::sse_decode(deserializer);
+        let mut var_nBatch = <u32>::sse_decode(deserializer);
+        let mut var_nThreads = <u32>::sse_decode(deserializer);
+        let mut var_nThreadsBatch = <u32>::sse_decode(deserializer);
+        return crate::runtime_config::ContextConfig {
+            n_ctx: var_nCtx,
+            n_batch: var_nBatch,
+            n_threads: var_nThreads,
+            n_threads_batch: var_nThreadsBatch,
+        };
+    }
+}
+
+impl SseDecode for crate::reembed::EmbeddingStatus {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_modelId = <String>::sse_decode(deserializer);
+        let mut var_totalMemories = <u32>::sse_decode(deserializer);
+        let mut var_staleMemories = <u32>::sse_decode(deserializer);
+        return crate::reembed::EmbeddingStatus {
+            model_id: var_modelId,
+            total_memories: var_totalMemories,
+            stale_memories: var_staleMemories,
+        };
+    }
+}
+
+impl SseDecode for f32 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_f32::<NativeEndian>().unwrap()
+    }
+}
+
+impl SseDecode for f64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_f64::<NativeEndian>().unwrap()
+    }
+}
+
+impl SseDecode for i32 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_i32::<NativeEndian>().unwrap()
+    }
+}
+
+impl SseDecode for i64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_i64::<NativeEndian>().unwrap()
+    }
+}
+
+impl SseDecode for crate::model_manager::InferenceQueueStatus {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_chat = <Vec<crate::job_queue::QueuedJob>>::sse_decode(deserializer);
+        let mut var_embedding = <Vec<crate::job_queue::QueuedJob>>::sse_decode(deserializer);
+        let mut var_reranker = <Vec<crate::job_queue::QueuedJob>>::sse_decode(deserializer);
+        return crate::model_manager::InferenceQueueStatus {
+            chat: var_chat,
+            embedding: var_embedding,
+            reranker: var_reranker,
+        };
+    }
+}
+
+impl SseDecode for crate::job_queue::JobPriority {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::job_queue::JobPriority::Interactive,
+            1 => crate::job_queue::JobPriority::Greeting,
+            2 => crate::job_queue::JobPriority::EmbeddingBackfill,
+            3 => crate::job_queue::JobPriority::SleepCycle,
+            _ => unreachable!("Invalid variant for JobPriority: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for Vec<crate::db::ChatMessage> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::db::ChatMessage>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::db::MemoryItem> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::db::MemoryItem>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::db::MemoryMatch> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::db::MemoryMatch>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::model_registry::ModelInfo> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::model_registry::ModelInfo>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<u8> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<u8>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::db::ProfileTrait> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::db::ProfileTrait>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::api::simple::PromptSectionPreview> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::api::simple::PromptSectionPreview>::sse_decode(
+                deserializer,
+            ));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::job_queue::QueuedJob> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::job_queue::QueuedJob>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::api::simple::Turn> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = vec![];
+        for idx_ in 0..len_ {
+            ans_.push(<crate::api::simple::Turn>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for crate::load_strategy::LoadStrategy {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::load_strategy::LoadStrategy::Auto,
+            1 => crate::load_strategy::LoadStrategy::Mmap,
+            2 => crate::load_strategy::LoadStrategy::Ram,
+            3 => crate::load_strategy::LoadStrategy::Mlock,
+            _ => unreachable!("Invalid variant for LoadStrategy: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::db::MemoryItem {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_id = <i64>::sse_decode(deserializer);
+        let mut var_content = <String>::sse_decode(deserializer);
+        let mut var_createdAt = <String>::sse_decode(deserializer);
+        return crate::db::MemoryItem {
+            id: var_id,
+            content: var_content,
+            created_at: var_createdAt,
+        };
+    }
+}
+
+impl SseDecode for crate::db::MemoryMatch {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_messageId = <i64>::sse_decode(deserializer);
+        let mut var_role = <String>::sse_decode(deserializer);
+        let mut var_content = <String>::sse_decode(deserializer);
+        let mut var_similarity = <f32>::sse_decode(deserializer);
+        let mut var_relevance = <f32>::sse_decode(deserializer);
+        let mut var_importance = <f32>::sse_decode(deserializer);
+        let mut var_accessCount = <u32>::sse_decode(deserializer);
+        let mut var_score = <crate::db::MemoryScore>::sse_decode(deserializer);
+        let mut var_rerankScore = <Option<f32>>::sse_decode(deserializer);
+        let mut var_timestamp = <String>::sse_decode(deserializer);
+        let mut var_memoryType = <String>::sse_decode(deserializer);
+        let mut var_memoryUnixTimestamp = <i64>::sse_decode(deserializer);
+        return crate::db::MemoryMatch {
+            message_id: var_messageId,
+            role: var_role,
+            content: var_content,
+            similarity: var_similarity,
+            relevance: var_relevance,
+            importance: var_importance,
+            access_count: var_accessCount,
+            score: var_score,
+            rerank_score: var_rerankScore,
+            timestamp: var_timestamp,
+            memory_type: var_memoryType,
+            memory_unix_timestamp: var_memoryUnixTimestamp,
+        };
+    }
+}
+
+impl SseDecode for crate::db::MemoryScore {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_relevance = <f32>::sse_decode(deserializer);
+        let mut var_recency = <f32>::sse_decode(deserializer);
+        let mut var_importance = <f32>::sse_decode(deserializer);
+        let mut var_access = <f32>::sse_decode(deserializer);
+        let mut var_total = <f32>::sse_decode(deserializer);
+        return crate::db::MemoryScore {
+            relevance: var_relevance,
+            recency: var_recency,
+            importance: var_importance,
+            access: var_access,
+            total: var_total,
+        };
+    }
+}
+
+impl SseDecode for crate::db::MemoryScoringWeights {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_relevance = <f32>::sse_decode(deserializer);
+        let mut var_recency = <f32>::sse_decode(deserializer);
+        let mut var_importance = <f32>::sse_decode(deserializer);
+        let mut var_access = <f32>::sse_decode(deserializer);
+        let mut var_recencyHalfLifeDays = <f32>::sse_decode(deserializer);
+        return crate::db::MemoryScoringWeights {
+            relevance: var_relevance,
+            recency: var_recency,
+            importance: var_importance,
+            access: var_access,
+            recency_half_life_days: var_recencyHalfLifeDays,
+        };
+    }
+}
+
+impl SseDecode for crate::model_registry::ModelInfo {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_id = <String>::sse_decode(deserializer);
+        let mut var_path = <String>::sse_decode(deserializer);
+        let mut var_sizeBytes = <u64>::sse_decode(deserializer);
+        let mut var_architecture = <String>::sse_decode(deserializer);
+        let mut var_parameterCount = <u64>::sse_decode(deserializer);
+        let mut var_quantization = <String>::sse_decode(deserializer);
+        let mut var_contextLength = <Option<u32>>::sse_decode(deserializer);
+        let mut var_embeddingLength = <Option<u32>>::sse_decode(deserializer);
+        let mut var_chatTemplate = <String>::sse_decode(deserializer);
+        let mut var_hasEmbeddedTemplate = <bool>::sse_decode(deserializer);
+        let mut var_suggestedKind = <crate::model_manager::ModelKind>::sse_decode(deserializer);
+        return crate::model_registry::ModelInfo {
+            id: var_id,
+            path: var_path,
+            size_bytes: var_sizeBytes,
+            architecture: var_architecture,
+            parameter_count: var_parameterCount,
+            quantization: var_quantization,
+            context_length: var_contextLength,
+            embedding_length: var_embeddingLength,
+            chat_template: var_chatTemplate,
+            has_embedded_template: var_hasEmbeddedTemplate,
+            suggested_kind: var_suggestedKind,
+        };
+    }
+}
+
+impl SseDecode for crate::model_manager::ModelKind {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::model_manager::ModelKind::Chat,
+            1 => crate::model_manager::ModelKind::Embedding,
+            2 => crate::model_manager::ModelKind::Reranker,
+            _ => unreachable!("Invalid variant for ModelKind: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::model_manager::ModelState {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                return crate::model_manager::ModelState::NotLoaded;
+            }
+            1 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                return crate::model_manager::ModelState::Loading { path: var_path };
+            }
+            2 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                let mut var_loadStrategy =
+                    <crate::load_strategy::LoadStrategy>::sse_decode(deserializer);
+                return crate::model_manager::ModelState::Ready {
+                    path: var_path,
+                    load_strategy: var_loadStrategy,
+                };
+            }
+            3 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                let mut var_code = <String>::sse_decode(deserializer);
+                let mut var_detail = <String>::sse_decode(deserializer);
+                return crate::model_manager::ModelState::Failed {
+                    path: var_path,
+                    code: var_code,
+                    detail: var_detail,
+                };
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::model_manager::ModelStatus {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_chat = <crate::model_manager::ModelState>::sse_decode(deserializer);
+        let mut var_embedding = <crate::model_manager::ModelState>::sse_decode(deserializer);
+        let mut var_reranker = <crate::model_manager::ModelState>::sse_decode(deserializer);
+        return crate::model_manager::ModelStatus {
+            chat: var_chat,
+            embedding: var_embedding,
+            reranker: var_reranker,
+        };
+    }
+}
+
+impl SseDecode for Option<String> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<String>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<f32> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<f32>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<crate::db::SamplingParams> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<crate::db::SamplingParams>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<u32> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<u32>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for crate::db::ProfileTrait {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_category = <String>::sse_decode(deserializer);
+        let mut var_content = <String>::sse_decode(deserializer);
+        return crate::db::ProfileTrait {
+            category: var_category,
+            content: var_content,
+        };
+    }
+}
+
+impl SseDecode for crate::api::simple::PromptPreview {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_sections =
+            <Vec<crate::api::simple::PromptSectionPreview>>::sse_decode(deserializer);
+        let mut var_memories = <Vec<crate::db::MemoryMatch>>::sse_decode(deserializer);
+        let mut var_renderedPrompt = <String>::sse_decode(deserializer);
+        let mut var_totalTokens = <u32>::sse_decode(deserializer);
+        let mut var_contextWindow = <u32>::sse_decode(deserializer);
+        let mut var_reservedForGeneration = <u32>::sse_decode(deserializer);
+        return crate::api::simple::PromptPreview {
+            sections: var_sections,
+            memories: var_memories,
+            rendered_prompt: var_renderedPrompt,
+            total_tokens: var_totalTokens,
+            context_window: var_contextWindow,
+            reserved_for_generation: var_reservedForGeneration,
+        };
+    }
+}
+
+impl SseDecode for crate::api::simple::PromptSectionPreview {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_name = <String>::sse_decode(deserializer);
+        let mut var_text = <String>::sse_decode(deserializer);
+        let mut var_tokens = <u32>::sse_decode(deserializer);
+        let mut var_requestedTokens = <u32>::sse_decode(deserializer);
+        let mut var_itemsKept = <u32>::sse_decode(deserializer);
+        let mut var_itemsTotal = <u32>::sse_decode(deserializer);
+        let mut var_truncated = <bool>::sse_decode(deserializer);
+        return crate::api::simple::PromptSectionPreview {
+            name: var_name,
+            text: var_text,
+            tokens: var_tokens,
+            requested_tokens: var_requestedTokens,
+            items_kept: var_itemsKept,
+            items_total: var_itemsTotal,
+            truncated: var_truncated,
+        };
+    }
+}
+
+impl SseDecode for crate::job_queue::QueuedJob {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_id = <u64>::sse_decode(deserializer);
+        let mut var_priority = <crate::job_queue::JobPriority>::sse_decode(deserializer);
+        let mut var_position = <u32>::sse_decode(deserializer);
+        return crate::job_queue::QueuedJob {
+            id: var_id,
+            priority: var_priority,
+            position: var_position,
+        };
+    }
+}
+
+impl SseDecode for crate::api::simple::ReembedEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                let mut var_done = <u32>::sse_decode(deserializer);
+                let mut var_total = <u32>::sse_decode(deserializer);
+                return crate::api::simple::ReembedEvent::Progress {
+                    done: var_done,
+                    total: var_total,
+                };
+            }
+            1 => {
+                let mut var_modelId = <String>::sse_decode(deserializer);
+                let mut var_reembedded = <u32>::sse_decode(deserializer);
+                return crate::api::simple::ReembedEvent::Done {
+                    model_id: var_modelId,
+                    reembedded: var_reembedded,
+                };
+            }
+            2 => {
+                let mut var_code = <String>::sse_decode(deserializer);
+                let mut var_detail = <String>::sse_decode(deserializer);
+                return crate::api::simple::ReembedEvent::Error {
+                    code: var_code,
+                    detail: var_detail,
+                };
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::runtime_config::RuntimeSettings {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_autoTune = <bool>::sse_decode(deserializer);
+        let mut var_nCtx = <u32>::sse_decode(deserializer);
+        let mut var_nBatch = <u32>::sse_decode(deserializer);
+        let mut var_nThreads = <u32>::sse_decode(deserializer);
+        let mut var_nThreadsBatch = <u32>::sse_decode(deserializer);
+        return crate::runtime_config::RuntimeSettings {
+            auto_tune: var_autoTune,
+            n_ctx: var_nCtx,
+            n_batch: var_nBatch,
+            n_threads: var_nThreads,
+            n_threads_batch: var_nThreadsBatch,
+        };
+    }
+}
+
+impl SseDecode for crate::db::SamplingParams {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_temperature = <f32>::sse_decode(deserializer);
+        let mut var_topK = <i32>::sse_decode(deserializer);
+        let mut var_topP = <f32>::sse_decode(deserializer);
+        let mut var_minP = <f32>::sse_decode(deserializer);
+        let mut var_repeatPenalty = <f32>::sse_decode(deserializer);
+        let mut var_repeatLastN = <i32>::sse_decode(deserializer);
+        let mut var_seed = <Option<u32>>::sse_decode(deserializer);
+        let mut var_mirostat = <u8>::sse_decode(deserializer);
+        let mut var_mirostatTau = <f32>::sse_decode(deserializer);
+        let mut var_mirostatEta = <f32>::sse_decode(deserializer);
+        return crate::db::SamplingParams {
+            temperature: var_temperature,
+            top_k: var_topK,
+            top_p: var_topP,
+            min_p: var_minP,
+            repeat_penalty: var_repeatPenalty,
+            repeat_last_n: var_repeatLastN,
+            seed: var_seed,
+            mirostat: var_mirostat,
+            mirostat_tau: var_mirostatTau,
+            mirostat_eta: var_mirostatEta,
+        };
+    }
+}
+
+impl SseDecode for crate::ai::StopReason {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::ai::StopReason::EndOfTurn,
+            1 => crate::ai::StopReason::StopSequence,
+            2 => crate::ai::StopReason::MaxTokens,
+            3 => crate::ai::StopReason::Cancelled,
+            _ => unreachable!("Invalid variant for StopReason: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::api::simple::Turn {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_role = <String>::sse_decode(deserializer);
+        let mut var_content = <String>::sse_decode(deserializer);
+        return crate::api::simple::Turn {
+            role: var_role,
+            content: var_content,
+        };
+    }
+}
+
+impl SseDecode for u32 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_u32::<NativeEndian>().unwrap()
+    }
+}
+
+impl SseDecode for u64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_u64::<NativeEndian>().unwrap()
+    }
+}
+
+impl SseDecode for u8 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_u8().unwrap()
+    }
+}
+
+impl SseDecode for () {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {}
+}
+
+fn pde_ffi_dispatcher_primary_impl(
+    func_id: i32,
+    port: flutter_rust_bridge::for_generated::MessagePort,
+    ptr: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len: i32,
+    data_len: i32,
+) {
+    // Codec=Pde (Serialization + dispatch), see doc to use other codecs
+    match func_id {
+        1 => wire__crate__api__simple__add_profile_trait_impl(port, ptr, rust_vec_len, data_len),
+        2 => wire__crate__api__simple__cancel_generation_impl(port, ptr, rust_vec_len, data_len),
+        3 => wire__crate__api__simple__cancel_reembedding_impl(port, ptr, rust_vec_len, data_len),
+        4 => wire__crate__api__simple__clear_profile_impl(port, ptr, rust_vec_len, data_len),
+        5 => wire__crate__api__simple__clear_reranker_model_impl(port, ptr, rust_vec_len, data_len),
+        6 => wire__crate__api__simple__delete_memory_impl(port, ptr, rust_vec_len, data_len),
+        7 => wire__crate__api__simple__export_brain_impl(port, ptr, rust_vec_len, data_len),
+        8 => wire__crate__api__simple__export_database_impl(port, ptr, rust_vec_len, data_len),
+        9 => wire__crate__api__simple__factory_reset_impl(port, ptr, rust_vec_len, data_len),
+        10 => wire__crate__api__simple__generate_proactive_greeting_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        11 => wire__crate__api__simple__get_all_memories_impl(port, ptr, rust_vec_len, data_len),
+        12 => wire__crate__api__simple__get_app_language_impl(port, ptr, rust_vec_len, data_len),
+        13 => {
+            wire__crate__api__simple__get_backend_settings_impl(port, ptr, rust_vec_len, data_len)
+        }
+        14 => wire__crate__api__simple__get_chat_context_config_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        15 => wire__crate__api__simple__get_chat_history_impl(port, ptr, rust_vec_len, data_len),
+        16 => wire__crate__api__simple__get_core_prompt_impl(port, ptr, rust_vec_len, data_len),
+        17 => {
+            wire__crate__api__simple__get_embedding_status_impl(port, ptr, rust_vec_len, data_len)
+        }
+        18 => wire__crate__api__simple__get_inference_queue_impl(port, ptr, rust_vec_len, data_len),
+        19 => wire__crate__api__simple__get_lexical_weight_impl(port, ptr, rust_vec_len, data_len),
+        20 => wire__crate__api__simple__get_load_strategy_impl(port, ptr, rust_vec_len, data_len),
+        21 => wire__crate__api__simple__get_memory_scoring_weights_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        22 => wire__crate__api__simple__get_mmr_lambda_impl(port, ptr, rust_vec_len, data_len),
+        23 => wire__crate__api__simple__get_model_status_impl(port, ptr, rust_vec_len, data_len),
+        24 => wire__crate__api__simple__get_models_dir_impl(port, ptr, rust_vec_len, data_len),
+        25 => wire__crate__api__simple__get_prefix_cache_enabled_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        26 => wire__crate__api__simple__get_profile_traits_impl(port, ptr, rust_vec_len, data_len),
+        27 => {
+            wire__crate__api__simple__get_runtime_settings_impl(port, ptr, rust_vec_len, data_len)
+        }
+        28 => wire__crate__api__simple__get_sampling_params_impl(port, ptr, rust_vec_len, data_len),
+        29 => wire__crate__api__simple__get_temperature_impl(port, ptr, rust_vec_len, data_len),
+        30 => wire__crate__api__simple__get_user_name_impl(port, ptr, rust_vec_len, data_len),
+        32 => wire__crate__api__simple__init_app_impl(port, ptr, rust_vec_len, data_len),
+        33 => wire__crate__api__simple__list_models_impl(port, ptr, rust_vec_len, data_len),
+        34 => wire__crate__api__simple__load_model_impl(port, ptr, rust_vec_len, data_len),
+        35 => wire__crate__api__simple__preempt_background_jobs_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        36 => wire__crate__api__simple__preview_prompt_impl(port, ptr, rust_vec_len, data_len),
+        37 => wire__crate__api__simple__reembed_memories_impl(port, ptr, rust_vec_len, data_len),
+        38 => wire__crate__api__simple__reload_model_impl(port, ptr, rust_vec_len, data_len),
+        39 => wire__crate__api__simple__run_sleep_cycle_impl(port, ptr, rust_vec_len, data_len),
+        40 => {
+            wire__crate__api__simple__save_assistant_message_impl(port, ptr, rust_vec_len, data_len)
+        }
+        41 => wire__crate__api__simple__search_memories_impl(port, ptr, rust_vec_len, data_len),
+        42 => wire__crate__api__simple__select_chat_model_impl(port, ptr, rust_vec_len, data_len),
+        43 => {
+            wire__crate__api__simple__select_embedding_model_impl(port, ptr, rust_vec_len, data_len)
+        }
+        44 => {
+            wire__crate__api__simple__select_reranker_model_impl(port, ptr, rust_vec_len, data_len)
+        }
+        45 => wire__crate__api__simple__send_chat_impl(port, ptr, rust_vec_len, data_len),
+        46 => wire__crate__api__simple__send_chat_stream_impl(port, ptr, rust_vec_len, data_len),
+        47 => wire__crate__api__simple__send_message_impl(port, ptr, rust_vec_len, data_len),
+        48 => wire__crate__api__simple__send_message_stream_impl(port, ptr, rust_vec_len, data_len),
+        49 => wire__crate__api__simple__set_app_language_impl(port, ptr, rust_vec_len, data_len),
+        50 => {
+            wire__crate__api__simple__set_backend_settings_impl(port, ptr, rust_vec_len, data_len)
+        }
+        51 => wire__crate__api__simple__set_core_prompt_impl(port, ptr, rust_vec_len, data_len),
+        52 => wire__crate__api__simple__set_lexical_weight_impl(port, ptr, rust_vec_len, data_len),
+        53 => wire__crate__api__simple__set_load_strategy_impl(port, ptr, rust_vec_len, data_len),
+        54 => wire__crate__api__simple__set_memory_scoring_weights_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        55 => wire__crate__api__simple__set_mmr_lambda_impl(port, ptr, rust_vec_len, data_len),
+        56 => wire__crate__api__simple__set_models_dir_impl(port, ptr, rust_vec_len, data_len),
+        57 => wire__crate__api__simple__set_prefix_cache_enabled_impl(
+            port,
+            ptr,
+            rust_vec_len,
+            data_len,
+        ),
+        58 => {
+            wire__crate__api__simple__set_runtime_settings_impl(port, ptr, rust_vec_len, data_len)
+        }
+        59 => wire__crate__api__simple__set_sampling_params_impl(port, ptr, rust_vec_len, data_len),
+        60 => wire__crate__api__simple__set_temperature_impl(port, ptr, rust_vec_len, data_len),
+        61 => wire__crate__api__simple__set_user_name_impl(port, ptr, rust_vec_len, data_len),
+        62 => wire__crate__api__simple__unload_model_impl(port, ptr, rust_vec_len, data_len),
+        _ => unreachable!(),
+    }
+}
+
+fn pde_ffi_dispatcher_sync_impl(
+    func_id: i32,
+    ptr: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len: i32,
+    data_len: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    // Codec=Pde (Serialization + dispatch), see doc to use other codecs
+    match func_id {
+        31 => wire__crate__api__simple__greet_impl(ptr, rust_vec_len, data_len),
+        _ => unreachable!(),
+    }
+}
+
+// Section: rust2dart
+
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::error::AnimaError {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            crate::error::AnimaError::ModelNotLoaded(field0) => {
+                [0.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::ModelLoadFailed(field0) => {
+                [1.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::InvalidModelFile(field0) => {
+                [2.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::ModelTruncated(field0) => {
+                [3.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::ModelChecksumMismatch(field0) => {
+                [4.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::WrongModelKind(field0) => {
+                [5.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::InsufficientMemory(field0) => {
+                [6.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::ContextOverflow(field0) => {
+                [7.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::BackendUnavailable(field0) => {
+                [8.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::EmptyOutput(field0) => {
+                [9.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::GenerationFailed(field0) => {
+                [10.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::EmbeddingFailed(field0) => {
+                [11.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::Cancelled(field0) => {
+                [12.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::Preempted(field0) => {
+                [13.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::DbLocked(field0) => {
+                [14.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::DbIo(field0) => {
+                [15.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::InvalidInput(field0) => {
+                [16.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::Io(field0) => {
+                [17.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::error::AnimaError::Internal(field0) => {
+                [18.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::error::AnimaError {}
+impl flutter_rust_bridge::IntoIntoDart<crate::error::AnimaError> for crate::error::AnimaError {
+    fn into_into_dart(self) -> crate::error::AnimaError {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::backend::BackendKind {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Llama => 0.into_dart(),
+            Self::OpenAiServer => 1.into_dart(),
+            Self::Mock => 2.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::backend::BackendKind {}
+impl flutter_rust_bridge::IntoIntoDart<crate::backend::BackendKind>
+    for crate::backend::BackendKind
+{
+    fn into_into_dart(self) -> crate::backend::BackendKind {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::backend::BackendSettings {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.chat_backend.into_into_dart().into_dart(),
+            self.embedding_backend.into_into_dart().into_dart(),
+            self.server_url.into_into_dart().into_dart(),
+            self.server_chat_model.into_into_dart().into_dart(),
+            self.server_embedding_model.into_into_dart().into_dart(),
+            self.server_context_window.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::backend::BackendSettings
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::backend::BackendSettings>
+    for crate::backend::BackendSettings
+{
+    fn into_into_dart(self) -> crate::backend::BackendSettings {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::ChatCompletion {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.text.into_into_dart().into_dart(),
+            self.stop_reason.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::simple::ChatCompletion
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::ChatCompletion>
+    for crate::api::simple::ChatCompletion
+{
+    fn into_into_dart(self) -> crate::api::simple::ChatCompletion {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::ChatEvent {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            crate::api::simple::ChatEvent::ContextRetrieved { memories } => {
+                [0.into_dart(), memories.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::simple::ChatEvent::Token(field0) => {
+                [1.into_dart(), field0.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::simple::ChatEvent::Done {
+                prompt_tokens,
+                completion_tokens,
+                tokens_per_sec,
+                stop_reason,
+            } => [
+                2.into_dart(),
+                prompt_tokens.into_into_dart().into_dart(),
+                completion_tokens.into_into_dart().into_dart(),
+                tokens_per_sec.into_into_dart().into_dart(),
+                stop_reason.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::simple::ChatEvent::Error { code, detail } => [
+                3.into_dart(),
+                code.into_into_dart().into_dart(),
+                detail.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::api::simple::ChatEvent {}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::ChatEvent>
+    for crate::api::simple::ChatEvent
+{
+    fn into_into_dart(self) -> crate::api::simple::ChatEvent {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::db::ChatMessage {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.id.into_into_dart().into_dart(),
+            self.role.into_into_dart().into_dart(),
+            self.content.into_into_dart().into_dart(),
+            self.timestamp.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::db::ChatMessage {}
+impl flutter_rust_bridge::IntoIntoDart<crate::db::ChatMessage> for crate::db::ChatMessage {
+    fn into_into_dart(self) -> crate::db::ChatMessage {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::ChatOptions {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.sampling.into_into_dart().into_dart(),
+            self.max_tokens.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::simple::ChatOptions
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::ChatOptions>
+    for crate::api::simple::ChatOptions
+{
+    fn into_into_dart(self) -> crate::api::simple::ChatOptions {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::ChatRequest {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.request_id.into_into_dart().into_dart(),
+            self.history.into_into_dart().into_dart(),
+            self.message.into_into_dart().into_dart(),
+            self.options.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::simple::ChatRequest
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::ChatRequest>
+    for crate::api::simple::ChatRequest
+{
+    fn into_into_dart(self) -> crate::api::simple::ChatRequest {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::runtime_config::ContextConfig {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.n_ctx.into_into_dart().into_dart(),
+            self.n_batch.into_into_dart().into_dart(),
+            self.n_threads.into_into_dart().into_dart(),
+            self.n_threads_batch.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::runtime_config::ContextConfig
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::runtime_config::ContextConfig>
+    for crate::runtime_config::ContextConfig
+{
+    fn into_into_dart(self) -> crate::runtime_config::ContextConfig {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::reembed::EmbeddingStatus {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.model_id.into_into_dart().into_dart(),
+            self.total_memories.into_into_dart().into_dart(),
+            self.stale_memories.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::reembed::EmbeddingStatus
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::reembed::EmbeddingStatus>
+    for crate::reembed::EmbeddingStatus
+{
+    fn into_into_dart(self) -> crate::reembed::EmbeddingStatus {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::model_manager::InferenceQueueStatus {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.chat.into_into_dart().into_dart(),
+            self.embedding.into_into_dart().into_dart(),
+            self.reranker.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::model_manager::InferenceQueueStatus
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::model_manager::InferenceQueueStatus>
+    for crate::model_manager::InferenceQueueStatus
+{
+    fn into_into_dart(self) -> crate::model_manager::InferenceQueueStatus {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::job_queue::JobPriority {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Interactive => 0.into_dart(),
+            Self::Greeting => 1.into_dart(),
+            Self::EmbeddingBackfill => 2.into_dart(),
+            Self::SleepCycle => 3.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::job_queue::JobPriority {}
+impl flutter_rust_bridge::IntoIntoDart<crate::job_queue::JobPriority>
+    for crate::job_queue::JobPriority
+{
+    fn into_into_dart(self) -> crate::job_queue::JobPriority {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::load_strategy::LoadStrategy {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Auto => 0.into_dart(),
+            Self::Mmap => 1.into_dart(),
+            Self::Ram => 2.into_dart(),
+            Self::Mlock => 3.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::load_strategy::LoadStrategy
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::load_strategy::LoadStrategy>
+    for crate::load_strategy::LoadStrategy
+{
+    fn into_into_dart(self) -> crate::load_strategy::LoadStrategy {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::db::MemoryItem {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.id.into_into_dart().into_dart(),
+            self.content.into_into_dart().into_dart(),
+            self.created_at.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::db::MemoryItem {}
+impl flutter_rust_bridge::IntoIntoDart<crate::db::MemoryItem> for crate::db::MemoryItem {
+    fn into_into_dart(self) -> crate::db::MemoryItem {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::db::MemoryMatch {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.message_id.into_into_dart().into_dart(),
+            self.role.into_into_dart().into_dart(),
+            self.content.into_into_dart().into_dart(),
+            self.similarity.into_into_dart().into_dart(),
+            self.relevance.into_into_dart().into_dart(),
+            self.importance.into_into_dart().into_dart(),
+            self.access_count.into_into_dart().into_dart(),
+            self.score.into_into_dart().into_dart(),
+            self.rerank_score.into_into_dart().into_dart(),
+            self.timestamp.into_into_dart().into_dart(),
+            self.memory_type.into_into_dart().into_dart(),
+            self.memory_unix_timestamp.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::db::MemoryMatch {}
+impl flutter_rust_bridge::IntoIntoDart<crate::db::MemoryMatch> for crate::db::MemoryMatch {
+    fn into_into_dart(self) -> crate::db::MemoryMatch {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::db::MemoryScore {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.relevance.into_into_dart().into_dart(),
+            self.recency.into_into_dart().into_dart(),
+            self.importance.into_into_dart().into_dart(),
+            self.access.into_into_dart().into_dart(),
+            self.total.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::db::MemoryScore {}
+impl flutter_rust_bridge::IntoIntoDart<crate::db::MemoryScore> for crate::db::MemoryScore {
+    fn into_into_dart(self) -> crate::db::MemoryScore {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::db::MemoryScoringWeights {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.relevance.into_into_dart().into_dart(),
+            self.recency.into_into_dart().into_dart(),
+            self.importance.into_into_dart().into_dart(),
+            self.access.into_into_dart().into_dart(),
+            self.recency_half_life_days.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::db::MemoryScoringWeights
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::db::MemoryScoringWeights>
+    for crate::db::MemoryScoringWeights
+{
+    fn into_into_dart(self) -> crate::db::MemoryScoringWeights {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::model_registry::ModelInfo {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.id.into_into_dart().into_dart(),
+            self.path.into_into_dart().into_dart(),
+            self.size_bytes.into_into_dart().into_dart(),
+            self.architecture.into_into_dart().into_dart(),
+            self.parameter_count.into_into_dart().into_dart(),
+            self.quantization.into_into_dart().into_dart(),
+            self.context_length.into_into_dart().into_dart(),
+            self.embedding_length.into_into_dart().into_dart(),
+            self.chat_template.into_into_dart().into_dart(),
+            self.has_embedded_template.into_into_dart().into_dart(),
+            self.suggested_kind.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::model_registry::ModelInfo
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::model_registry::ModelInfo>
+    for crate::model_registry::ModelInfo
+{
+    fn into_into_dart(self) -> crate::model_registry::ModelInfo {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::model_manager::ModelKind {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Chat => 0.into_dart(),
+            Self::Embedding => 1.into_dart(),
+            Self::Reranker => 2.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::model_manager::ModelKind
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::model_manager::ModelKind>
+    for crate::model_manager::ModelKind
+{
+    fn into_into_dart(self) -> crate::model_manager::ModelKind {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::model_manager::ModelState {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            crate::model_manager::ModelState::NotLoaded => [0.into_dart()].into_dart(),
+            crate::model_manager::ModelState::Loading { path } => {
+                [1.into_dart(), path.into_into_dart().into_dart()].into_dart()
+            }
+            crate::model_manager::ModelState::Ready {
+                path,
+                load_strategy,
+            } => [
+                2.into_dart(),
+                path.into_into_dart().into_dart(),
+                load_strategy.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::model_manager::ModelState::Failed { path, code, detail } => [
+                3.into_dart(),
+                path.into_into_dart().into_dart(),
+                code.into_into_dart().into_dart(),
+                detail.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::model_manager::ModelState
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::model_manager::ModelState>
+    for crate::model_manager::ModelState
+{
+    fn into_into_dart(self) -> crate::model_manager::ModelState {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::model_manager::ModelStatus {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.chat.into_into_dart().into_dart(),
+            self.embedding.into_into_dart().into_dart(),
+            self.reranker.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::model_manager::ModelStatus
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::model_manager::ModelStatus>
+    for crate::model_manager::ModelStatus
+{
+    fn into_into_dart(self) -> crate::model_manager::ModelStatus {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::db::ProfileTrait {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.category.into_into_dart().into_dart(),
+            self.content.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::db::ProfileTrait {}
+impl flutter_rust_bridge::IntoIntoDart<crate::db::ProfileTrait> for crate::db::ProfileTrait {
+    fn into_into_dart(self) -> crate::db::ProfileTrait {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::PromptPreview {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.sections.into_into_dart().into_dart(),
+            self.memories.into_into_dart().into_dart(),
+            self.rendered_prompt.into_into_dart().into_dart(),
+            self.total_tokens.into_into_dart().into_dart(),
+            self.context_window.into_into_dart().into_dart(),
+            self.reserved_for_generation.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::simple::PromptPreview
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::PromptPreview>
+    for crate::api::simple::PromptPreview
+{
+    fn into_into_dart(self) -> crate::api::simple::PromptPreview {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::PromptSectionPreview {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.name.into_into_dart().into_dart(),
+            self.text.into_into_dart().into_dart(),
+            self.tokens.into_into_dart().into_dart(),
+            self.requested_tokens.into_into_dart().into_dart(),
+            self.items_kept.into_into_dart().into_dart(),
+            self.items_total.into_into_dart().into_dart(),
+            self.truncated.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::simple::PromptSectionPreview
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::PromptSectionPreview>
+    for crate::api::simple::PromptSectionPreview
+{
+    fn into_into_dart(self) -> crate::api::simple::PromptSectionPreview {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::job_queue::QueuedJob {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.id.into_into_dart().into_dart(),
+            self.priority.into_into_dart().into_dart(),
+            self.position.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::job_queue::QueuedJob {}
+impl flutter_rust_bridge::IntoIntoDart<crate::job_queue::QueuedJob>
+    for crate::job_queue::QueuedJob
+{
+    fn into_into_dart(self) -> crate::job_queue::QueuedJob {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::ReembedEvent {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            crate::api::simple::ReembedEvent::Progress { done, total } => [
+                0.into_dart(),
+                done.into_into_dart().into_dart(),
+                total.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::simple::ReembedEvent::Done {
+                model_id,
+                reembedded,
+            } => [
+                1.into_dart(),
+                model_id.into_into_dart().into_dart(),
+                reembedded.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::simple::ReembedEvent::Error { code, detail } => [
+                2.into_dart(),
+                code.into_into_dart().into_dart(),
+                detail.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::simple::ReembedEvent
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::ReembedEvent>
+    for crate::api::simple::ReembedEvent
+{
+    fn into_into_dart(self) -> crate::api::simple::ReembedEvent {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::runtime_config::RuntimeSettings {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.auto_tune.into_into_dart().into_dart(),
+            self.n_ctx.into_into_dart().into_dart(),
+            self.n_batch.into_into_dart().into_dart(),
+            self.n_threads.into_into_dart().into_dart(),
+            self.n_threads_batch.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::runtime_config::RuntimeSettings
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::runtime_config::RuntimeSettings>
+    for crate::runtime_config::RuntimeSettings
+{
+    fn into_into_dart(self) -> crate::runtime_config::RuntimeSettings {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::db::SamplingParams {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.temperature.into_into_dart().into_dart(),
+            self.top_k.into_into_dart().into_dart(),
+            self.top_p.into_into_dart().into_dart(),
+            self.min_p.into_into_dart().into_dart(),
+            self.repeat_penalty.into_into_dart().into_dart(),
+            self.repeat_last_n.into_into_dart().into_dart(),
+            self.seed.into_into_dart().into_dart(),
+            self.mirostat.into_into_dart().into_dart(),
+            self.mirostat_tau.into_into_dart().into_dart(),
+            self.mirostat_eta.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::db::SamplingParams {}
+impl flutter_rust_bridge::IntoIntoDart<crate::db::SamplingParams> for crate::db::SamplingParams {
+    fn into_into_dart(self) -> crate::db::SamplingParams {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::ai::StopReason {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::EndOfTurn => 0.into_dart(),
+            Self::StopSequence => 1.into_dart(),
+            Self::MaxTokens => 2.into_dart(),
+            Self::Cancelled => 3.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::ai::StopReason {}
+impl flutter_rust_bridge::IntoIntoDart<crate::ai::StopReason> for crate::ai::StopReason {
+    fn into_into_dart(self) -> crate::ai::StopReason {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::simple::Turn {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.role.into_into_dart().into_dart(),
+            self.content.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::api::simple::Turn {}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::simple::Turn> for crate::api::simple::Turn {
+    fn into_into_dart(self) -> crate::api::simple::Turn {
+        self
+    }
+}
 
-// Section: dart2rust
-
-impl SseDecode for flutter_rust_bridge::for_generated::anyhow::Error {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <String>::sse_decode(deserializer);
-        return flutter_rust_bridge::for_generated::anyhow::anyhow!("{}", inner);
-    }
-}
-
-impl SseDecode for StreamSink<String, flutter_rust_bridge::for_generated::SseCodec> {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <String>::sse_decode(deserializer);
-        return StreamSink::deserialize(inner);
-    }
-}
-
-impl SseDecode for String {
+impl SseEncode for flutter_rust_bridge::for_generated::anyhow::Error {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <Vec<u8>>::sse_decode(deserializer);
-        return String::from_utf8(inner).unwrap();
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(format!("{:?}", self), serializer);
     }
 }
 
-impl SseDecode for bool {
+impl SseEncode
+    for StreamSink<crate::api::simple::ChatEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        deserializer.cursor.read_u8().unwrap() != 0
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        unimplemented!("")
     }
 }
 
-impl SseDecode for crate::db::ChatMessage {
+impl SseEncode
+    for StreamSink<crate::api::simple::ReembedEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut var_id = <i64>::sse_decode(deserializer);
-        let mut var_role = <String>::sse_decode(deserializer);
-        let mut var_content = <String>::sse_decode(deserializer);
-        let mut var_timestamp = <String>::sse_decode(deserializer);
-        return crate::db::ChatMessage {
-            id: var_id,
-            role: var_role,
-            content: var_content,
-            timestamp: var_timestamp,
-        };
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        unimplemented!("")
     }
 }
 
-impl SseDecode for f32 {
+impl SseEncode for String {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        deserializer.cursor.read_f32::<NativeEndian>().unwrap()
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <Vec<u8>>::sse_encode(self.into_bytes(), serializer);
+    }
+}
+
+impl SseEncode for crate::error::AnimaError {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::error::AnimaError::ModelNotLoaded(field0) => {
+                <i32>::sse_encode(0, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::ModelLoadFailed(field0) => {
+                <i32>::sse_encode(1, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::InvalidModelFile(field0) => {
+                <i32>::sse_encode(2, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::ModelTruncated(field0) => {
+                <i32>::sse_encode(3, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::ModelChecksumMismatch(field0) => {
+                <i32>::sse_encode(4, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::WrongModelKind(field0) => {
+                <i32>::sse_encode(5, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::InsufficientMemory(field0) => {
+                <i32>::sse_encode(6, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::ContextOverflow(field0) => {
+                <i32>::sse_encode(7, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::BackendUnavailable(field0) => {
+                <i32>::sse_encode(8, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::EmptyOutput(field0) => {
+                <i32>::sse_encode(9, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::GenerationFailed(field0) => {
+                <i32>::sse_encode(10, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::EmbeddingFailed(field0) => {
+                <i32>::sse_encode(11, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::Cancelled(field0) => {
+                <i32>::sse_encode(12, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::Preempted(field0) => {
+                <i32>::sse_encode(13, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::DbLocked(field0) => {
+                <i32>::sse_encode(14, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::DbIo(field0) => {
+                <i32>::sse_encode(15, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::InvalidInput(field0) => {
+                <i32>::sse_encode(16, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::Io(field0) => {
+                <i32>::sse_encode(17, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::error::AnimaError::Internal(field0) => {
+                <i32>::sse_encode(18, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseEncode for crate::backend::BackendKind {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::backend::BackendKind::Llama => 0,
+                crate::backend::BackendKind::OpenAiServer => 1,
+                crate::backend::BackendKind::Mock => 2,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::backend::BackendSettings {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <crate::backend::BackendKind>::sse_encode(self.chat_backend, serializer);
+        <crate::backend::BackendKind>::sse_encode(self.embedding_backend, serializer);
+        <String>::sse_encode(self.server_url, serializer);
+        <String>::sse_encode(self.server_chat_model, serializer);
+        <String>::sse_encode(self.server_embedding_model, serializer);
+        <u32>::sse_encode(self.server_context_window, serializer);
+    }
+}
+
+impl SseEncode for bool {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_u8(self as _).unwrap();
+    }
+}
+
+impl SseEncode for crate::api::simple::ChatCompletion {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.text, serializer);
+        <crate::ai::StopReason>::sse_encode(self.stop_reason, serializer);
+    }
+}
+
+impl SseEncode for crate::api::simple::ChatEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::api::simple::ChatEvent::ContextRetrieved { memories } => {
+                <i32>::sse_encode(0, serializer);
+                <Vec<crate::db::MemoryMatch>>::sse_encode(memories, serializer);
+            }
+            crate::api::simple::ChatEvent::Token(field0) => {
+                <i32>::sse_encode(1, serializer);
+                <String>::sse_encode(field0, serializer);
+            }
+            crate::api::simple::ChatEvent::Done {
+                prompt_tokens,
+                completion_tokens,
+                tokens_per_sec,
+                stop_reason,
+            } => {
+                <i32>::sse_encode(2, serializer);
+                <u32>::sse_encode(prompt_tokens, serializer);
+                <u32>::sse_encode(completion_tokens, serializer);
+                <f64>::sse_encode(tokens_per_sec, serializer);
+                <crate::ai::StopReason>::sse_encode(stop_reason, serializer);
+            }
+            crate::api::simple::ChatEvent::Error { code, detail } => {
+                <i32>::sse_encode(3, serializer);
+                <String>::sse_encode(code, serializer);
+                <String>::sse_encode(detail, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseEncode for crate::db::ChatMessage {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i64>::sse_encode(self.id, serializer);
+        <String>::sse_encode(self.role, serializer);
+        <String>::sse_encode(self.content, serializer);
+        <String>::sse_encode(self.timestamp, serializer);
+    }
+}
+
+impl SseEncode for crate::api::simple::ChatOptions {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <Option<crate::db::SamplingParams>>::sse_encode(self.sampling, serializer);
+        <u32>::sse_encode(self.max_tokens, serializer);
+    }
+}
+
+impl SseEncode for crate::api::simple::ChatRequest {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <Option<String>>::sse_encode(self.request_id, serializer);
+        <Vec<crate::api::simple::Turn>>::sse_encode(self.history, serializer);
+        <String>::sse_encode(self.message, serializer);
+        <crate::api::simple::ChatOptions>::sse_encode(self.options, serializer);
+    }
+}
+
+impl SseEncode for crate::runtime_config::ContextConfig {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <u32>::sse_encode(self.n_ctx, serializer);
+        <u32>::sse_encode(self.n_batch, serializer);
+        <u32>::sse_encode(self.n_threads, serializer);
+        <u32>::sse_encode(self.n_threads_batch, serializer);
+    }
+}
+
+impl SseEncode for crate::reembed::EmbeddingStatus {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.model_id, serializer);
+        <u32>::sse_encode(self.total_memories, serializer);
+        <u32>::sse_encode(self.stale_memories, serializer);
+    }
+}
+
+impl SseEncode for f32 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_f32::<NativeEndian>(self).unwrap();
+    }
+}
+
+impl SseEncode for f64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_f64::<NativeEndian>(self).unwrap();
+    }
+}
+
+impl SseEncode for i32 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_i32::<NativeEndian>(self).unwrap();
+    }
+}
+
+impl SseEncode for i64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_i64::<NativeEndian>(self).unwrap();
+    }
+}
+
+impl SseEncode for crate::model_manager::InferenceQueueStatus {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <Vec<crate::job_queue::QueuedJob>>::sse_encode(self.chat, serializer);
+        <Vec<crate::job_queue::QueuedJob>>::sse_encode(self.embedding, serializer);
+        <Vec<crate::job_queue::QueuedJob>>::sse_encode(self.reranker, serializer);
+    }
+}
+
+impl SseEncode for crate::job_queue::JobPriority {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::job_queue::JobPriority::Interactive => 0,
+                crate::job_queue::JobPriority::Greeting => 1,
+                crate::job_queue::JobPriority::EmbeddingBackfill => 2,
+                crate::job_queue::JobPriority::SleepCycle => 3,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for Vec<crate::db::ChatMessage> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::db::ChatMessage>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Vec<crate::db::MemoryItem> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::db::MemoryItem>::sse_encode(item, serializer);
+        }
     }
 }
 
-impl SseDecode for i64 {
+impl SseEncode for Vec<crate::db::MemoryMatch> {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        deserializer.cursor.read_i64::<NativeEndian>().unwrap()
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::db::MemoryMatch>::sse_encode(item, serializer);
+        }
     }
 }
 
-impl SseDecode for Vec<crate::db::ChatMessage> {
+impl SseEncode for Vec<crate::model_registry::ModelInfo> {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut len_ = <i32>::sse_decode(deserializer);
-        let mut ans_ = vec![];
-        for idx_ in 0..len_ {
-            ans_.push(<crate::db::ChatMessage>::sse_decode(deserializer));
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::model_registry::ModelInfo>::sse_encode(item, serializer);
         }
-        return ans_;
     }
 }
 
-impl SseDecode for Vec<crate::db::MemoryItem> {
+impl SseEncode for Vec<u8> {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut len_ = <i32>::sse_decode(deserializer);
-        let mut ans_ = vec![];
-        for idx_ in 0..len_ {
-            ans_.push(<crate::db::MemoryItem>::sse_decode(deserializer));
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <u8>::sse_encode(item, serializer);
         }
-        return ans_;
     }
 }
 
-impl SseDecode for Vec<u8> {
+impl SseEncode for Vec<crate::db::ProfileTrait> {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut len_ = <i32>::sse_decode(deserializer);
-        let mut ans_ = vec![];
-        for idx_ in 0..len_ {
-            ans_.push(<u8>::sse_decode(deserializer));
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::db::ProfileTrait>::sse_encode(item, serializer);
         }
-        return ans_;
     }
 }
 
-impl SseDecode for Vec<crate::db::ProfileTrait> {
+impl SseEncode for Vec<crate::api::simple::PromptSectionPreview> {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut len_ = <i32>::sse_decode(deserializer);
-        let mut ans_ = vec![];
-        for idx_ in 0..len_ {
-            ans_.push(<crate::db::ProfileTrait>::sse_decode(deserializer));
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::api::simple::PromptSectionPreview>::sse_encode(item, serializer);
         }
-        return ans_;
     }
 }
 
-impl SseDecode for crate::db::MemoryItem {
+impl SseEncode for Vec<crate::job_queue::QueuedJob> {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut var_id = <i64>::sse_decode(deserializer);
-        let mut var_content = <String>::sse_decode(deserializer);
-        let mut var_createdAt = <String>::sse_decode(deserializer);
-        return crate::db::MemoryItem {
-            id: var_id,
-            content: var_content,
-            created_at: var_createdAt,
-        };
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::job_queue::QueuedJob>::sse_encode(item, serializer);
+        }
     }
 }
 
-impl SseDecode for crate::db::ProfileTrait {
+impl SseEncode for Vec<crate::api::simple::Turn> {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut var_category = <String>::sse_decode(deserializer);
-        let mut var_content = <String>::sse_decode(deserializer);
-        return crate::db::ProfileTrait {
-            category: var_category,
-            content: var_content,
-        };
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::api::simple::Turn>::sse_encode(item, serializer);
+        }
     }
 }
 
-impl SseDecode for u32 {
+impl SseEncode for crate::load_strategy::LoadStrategy {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        deserializer.cursor.read_u32::<NativeEndian>().unwrap()
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::load_strategy::LoadStrategy::Auto => 0,
+                crate::load_strategy::LoadStrategy::Mmap => 1,
+                crate::load_strategy::LoadStrategy::Ram => 2,
+                crate::load_strategy::LoadStrategy::Mlock => 3,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
     }
 }
 
-impl SseDecode for u8 {
+impl SseEncode for crate::db::MemoryItem {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        deserializer.cursor.read_u8().unwrap()
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i64>::sse_encode(self.id, serializer);
+        <String>::sse_encode(self.content, serializer);
+        <String>::sse_encode(self.created_at, serializer);
     }
 }
 
-impl SseDecode for () {
+impl SseEncode for crate::db::MemoryMatch {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {}
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i64>::sse_encode(self.message_id, serializer);
+        <String>::sse_encode(self.role, serializer);
+        <String>::sse_encode(self.content, serializer);
+        <f32>::sse_encode(self.similarity, serializer);
+        <f32>::sse_encode(self.relevance, serializer);
+        <f32>::sse_encode(self.importance, serializer);
+        <u32>::sse_encode(self.access_count, serializer);
+        <crate::db::MemoryScore>::sse_encode(self.score, serializer);
+        <Option<f32>>::sse_encode(self.rerank_score, serializer);
+        <String>::sse_encode(self.timestamp, serializer);
+        <String>::sse_encode(self.memory_type, serializer);
+        <i64>::sse_encode(self.memory_unix_timestamp, serializer);
+    }
 }
 
-impl SseDecode for i32 {
+impl SseEncode for crate::db::MemoryScore {
     // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        deserializer.cursor.read_i32::<NativeEndian>().unwrap()
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <f32>::sse_encode(self.relevance, serializer);
+        <f32>::sse_encode(self.recency, serializer);
+        <f32>::sse_encode(self.importance, serializer);
+        <f32>::sse_encode(self.access, serializer);
+        <f32>::sse_encode(self.total, serializer);
     }
 }
 
-fn pde_ffi_dispatcher_primary_impl(
-    func_id: i32,
-    port: flutter_rust_bridge::for_generated::MessagePort,
-    ptr: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
-    rust_vec_len: i32,
-    data_len: i32,
-) {
-    // Codec=Pde (Serialization + dispatch), see doc to use other codecs
-    match func_id {
-        1 => wire__crate__api__simple__add_profile_trait_impl(port, ptr, rust_vec_len, data_len),
-        2 => wire__crate__api__simple__clear_profile_impl(port, ptr, rust_vec_len, data_len),
-        3 => wire__crate__api__simple__delete_memory_impl(port, ptr, rust_vec_len, data_len),
-        4 => wire__crate__api__simple__export_brain_impl(port, ptr, rust_vec_len, data_len),
-        5 => wire__crate__api__simple__export_database_impl(port, ptr, rust_vec_len, data_len),
-        6 => wire__crate__api__simple__factory_reset_impl(port, ptr, rust_vec_len, data_len),
-        7 => wire__crate__api__simple__generate_proactive_greeting_impl(
-            port,
-            ptr,
-            rust_vec_len,
-            data_len,
-        ),
-        8 => wire__crate__api__simple__get_all_memories_impl(port, ptr, rust_vec_len, data_len),
-        9 => wire__crate__api__simple__get_app_language_impl(port, ptr, rust_vec_len, data_len),
-        10 => wire__crate__api__simple__get_chat_history_impl(port, ptr, rust_vec_len, data_len),
-        11 => wire__crate__api__simple__get_core_prompt_impl(port, ptr, rust_vec_len, data_len),
-        12 => wire__crate__api__simple__get_profile_traits_impl(port, ptr, rust_vec_len, data_len),
-        13 => wire__crate__api__simple__get_temperature_impl(port, ptr, rust_vec_len, data_len),
-        14 => wire__crate__api__simple__get_user_name_impl(port, ptr, rust_vec_len, data_len),
-        16 => wire__crate__api__simple__init_app_impl(port, ptr, rust_vec_len, data_len),
-        17 => wire__crate__api__simple__run_sleep_cycle_impl(port, ptr, rust_vec_len, data_len),
-        18 => {
-            wire__crate__api__simple__save_assistant_message_impl(port, ptr, rust_vec_len, data_len)
-        }
-        19 => wire__crate__api__simple__search_memories_impl(port, ptr, rust_vec_len, data_len),
-        20 => wire__crate__api__simple__send_message_impl(port, ptr, rust_vec_len, data_len),
-        21 => wire__crate__api__simple__send_message_stream_impl(port, ptr, rust_vec_len, data_len),
-        22 => wire__crate__api__simple__set_app_language_impl(port, ptr, rust_vec_len, data_len),
-        23 => wire__crate__api__simple__set_core_prompt_impl(port, ptr, rust_vec_len, data_len),
-        24 => wire__crate__api__simple__set_temperature_impl(port, ptr, rust_vec_len, data_len),
-        25 => wire__crate__api__simple__set_user_name_impl(port, ptr, rust_vec_len, data_len),
-        _ => unreachable!(),
+impl SseEncode for crate::db::MemoryScoringWeights {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <f32>::sse_encode(self.relevance, serializer);
+        <f32>::sse_encode(self.recency, serializer);
+        <f32>::sse_encode(self.importance, serializer);
+        <f32>::sse_encode(self.access, serializer);
+        <f32>::sse_encode(self.recency_half_life_days, serializer);
     }
 }
 
-fn pde_ffi_dispatcher_sync_impl(
-    func_id: i32,
-    ptr: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
-    rust_vec_len: i32,
-    data_len: i32,
-) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
-    // Codec=Pde (Serialization + dispatch), see doc to use other codecs
-    match func_id {
-        15 => wire__crate__api__simple__greet_impl(ptr, rust_vec_len, data_len),
-        _ => unreachable!(),
+impl SseEncode for crate::model_registry::ModelInfo {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.id, serializer);
+        <String>::sse_encode(self.path, serializer);
+        <u64>::sse_encode(self.size_bytes, serializer);
+        <String>::sse_encode(self.architecture, serializer);
+        <u64>::sse_encode(self.parameter_count, serializer);
+        <String>::sse_encode(self.quantization, serializer);
+        <Option<u32>>::sse_encode(self.context_length, serializer);
+        <Option<u32>>::sse_encode(self.embedding_length, serializer);
+        <String>::sse_encode(self.chat_template, serializer);
+        <bool>::sse_encode(self.has_embedded_template, serializer);
+        <crate::model_manager::ModelKind>::sse_encode(self.suggested_kind, serializer);
     }
 }
 
-// Section: rust2dart
-
-// Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for crate::db::ChatMessage {
-    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
-        [
-            self.id.into_into_dart().into_dart(),
-            self.role.into_into_dart().into_dart(),
-            self.content.into_into_dart().into_dart(),
-            self.timestamp.into_into_dart().into_dart(),
-        ]
-        .into_dart()
-    }
-}
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::db::ChatMessage {}
-impl flutter_rust_bridge::IntoIntoDart<crate::db::ChatMessage> for crate::db::ChatMessage {
-    fn into_into_dart(self) -> crate::db::ChatMessage {
-        self
-    }
-}
-// Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for crate::db::MemoryItem {
-    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
-        [
-            self.id.into_into_dart().into_dart(),
-            self.content.into_into_dart().into_dart(),
-            self.created_at.into_into_dart().into_dart(),
-        ]
-        .into_dart()
-    }
-}
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::db::MemoryItem {}
-impl flutter_rust_bridge::IntoIntoDart<crate::db::MemoryItem> for crate::db::MemoryItem {
-    fn into_into_dart(self) -> crate::db::MemoryItem {
-        self
+impl SseEncode for crate::model_manager::ModelKind {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::model_manager::ModelKind::Chat => 0,
+                crate::model_manager::ModelKind::Embedding => 1,
+                crate::model_manager::ModelKind::Reranker => 2,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
     }
 }
-// Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for crate::db::ProfileTrait {
-    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
-        [
-            self.category.into_into_dart().into_dart(),
-            self.content.into_into_dart().into_dart(),
-        ]
-        .into_dart()
+
+impl SseEncode for crate::model_manager::ModelState {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::model_manager::ModelState::NotLoaded => {
+                <i32>::sse_encode(0, serializer);
+            }
+            crate::model_manager::ModelState::Loading { path } => {
+                <i32>::sse_encode(1, serializer);
+                <String>::sse_encode(path, serializer);
+            }
+            crate::model_manager::ModelState::Ready {
+                path,
+                load_strategy,
+            } => {
+                <i32>::sse_encode(2, serializer);
+                <String>::sse_encode(path, serializer);
+                <crate::load_strategy::LoadStrategy>::sse_encode(load_strategy, serializer);
+            }
+            crate::model_manager::ModelState::Failed { path, code, detail } => {
+                <i32>::sse_encode(3, serializer);
+                <String>::sse_encode(path, serializer);
+                <String>::sse_encode(code, serializer);
+                <String>::sse_encode(detail, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
     }
 }
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::db::ProfileTrait {}
-impl flutter_rust_bridge::IntoIntoDart<crate::db::ProfileTrait> for crate::db::ProfileTrait {
-    fn into_into_dart(self) -> crate::db::ProfileTrait {
-        self
+
+impl SseEncode for crate::model_manager::ModelStatus {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <crate::model_manager::ModelState>::sse_encode(self.chat, serializer);
+        <crate::model_manager::ModelState>::sse_encode(self.embedding, serializer);
+        <crate::model_manager::ModelState>::sse_encode(self.reranker, serializer);
     }
 }
 
-impl SseEncode for flutter_rust_bridge::for_generated::anyhow::Error {
+impl SseEncode for Option<String> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <String>::sse_encode(format!("{:?}", self), serializer);
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <String>::sse_encode(value, serializer);
+        }
     }
 }
 
-impl SseEncode for StreamSink<String, flutter_rust_bridge::for_generated::SseCodec> {
+impl SseEncode for Option<f32> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        unimplemented!("")
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <f32>::sse_encode(value, serializer);
+        }
     }
 }
 
-impl SseEncode for String {
+impl SseEncode for Option<crate::db::SamplingParams> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <Vec<u8>>::sse_encode(self.into_bytes(), serializer);
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <crate::db::SamplingParams>::sse_encode(value, serializer);
+        }
     }
 }
 
-impl SseEncode for bool {
+impl SseEncode for Option<u32> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        serializer.cursor.write_u8(self as _).unwrap();
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <u32>::sse_encode(value, serializer);
+        }
     }
 }
 
-impl SseEncode for crate::db::ChatMessage {
+impl SseEncode for crate::db::ProfileTrait {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <i64>::sse_encode(self.id, serializer);
-        <String>::sse_encode(self.role, serializer);
+        <String>::sse_encode(self.category, serializer);
         <String>::sse_encode(self.content, serializer);
-        <String>::sse_encode(self.timestamp, serializer);
     }
 }
 
-impl SseEncode for f32 {
+impl SseEncode for crate::api::simple::PromptPreview {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        serializer.cursor.write_f32::<NativeEndian>(self).unwrap();
+        <Vec<crate::api::simple::PromptSectionPreview>>::sse_encode(self.sections, serializer);
+        <Vec<crate::db::MemoryMatch>>::sse_encode(self.memories, serializer);
+        <String>::sse_encode(self.rendered_prompt, serializer);
+        <u32>::sse_encode(self.total_tokens, serializer);
+        <u32>::sse_encode(self.context_window, serializer);
+        <u32>::sse_encode(self.reserved_for_generation, serializer);
     }
 }
 
-impl SseEncode for i64 {
+impl SseEncode for crate::api::simple::PromptSectionPreview {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        serializer.cursor.write_i64::<NativeEndian>(self).unwrap();
+        <String>::sse_encode(self.name, serializer);
+        <String>::sse_encode(self.text, serializer);
+        <u32>::sse_encode(self.tokens, serializer);
+        <u32>::sse_encode(self.requested_tokens, serializer);
+        <u32>::sse_encode(self.items_kept, serializer);
+        <u32>::sse_encode(self.items_total, serializer);
+        <bool>::sse_encode(self.truncated, serializer);
     }
 }
 
-impl SseEncode for Vec<crate::db::ChatMessage> {
+impl SseEncode for crate::job_queue::QueuedJob {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <i32>::sse_encode(self.len() as _, serializer);
-        for item in self {
-            <crate::db::ChatMessage>::sse_encode(item, serializer);
-        }
+        <u64>::sse_encode(self.id, serializer);
+        <crate::job_queue::JobPriority>::sse_encode(self.priority, serializer);
+        <u32>::sse_encode(self.position, serializer);
     }
 }
 
-impl SseEncode for Vec<crate::db::MemoryItem> {
+impl SseEncode for crate::api::simple::ReembedEvent {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <i32>::sse_encode(self.len() as _, serializer);
-        for item in self {
-            <crate::db::MemoryItem>::sse_encode(item, serializer);
+        match self {
+            crate::api::simple::ReembedEvent::Progress { done, total } => {
+                <i32>::sse_encode(0, serializer);
+                <u32>::sse_encode(done, serializer);
+                <u32>::sse_encode(total, serializer);
+            }
+            crate::api::simple::ReembedEvent::Done {
+                model_id,
+                reembedded,
+            } => {
+                <i32>::sse_encode(1, serializer);
+                <String>::sse_encode(model_id, serializer);
+                <u32>::sse_encode(reembedded, serializer);
+            }
+            crate::api::simple::ReembedEvent::Error { code, detail } => {
+                <i32>::sse_encode(2, serializer);
+                <String>::sse_encode(code, serializer);
+                <String>::sse_encode(detail, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
         }
     }
 }
 
-impl SseEncode for Vec<u8> {
+impl SseEncode for crate::runtime_config::RuntimeSettings {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <i32>::sse_encode(self.len() as _, serializer);
-        for item in self {
-            <u8>::sse_encode(item, serializer);
-        }
+        <bool>::sse_encode(self.auto_tune, serializer);
+        <u32>::sse_encode(self.n_ctx, serializer);
+        <u32>::sse_encode(self.n_batch, serializer);
+        <u32>::sse_encode(self.n_threads, serializer);
+        <u32>::sse_encode(self.n_threads_batch, serializer);
     }
 }
 
-impl SseEncode for Vec<crate::db::ProfileTrait> {
+impl SseEncode for crate::db::SamplingParams {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <i32>::sse_encode(self.len() as _, serializer);
-        for item in self {
-            <crate::db::ProfileTrait>::sse_encode(item, serializer);
-        }
+        <f32>::sse_encode(self.temperature, serializer);
+        <i32>::sse_encode(self.top_k, serializer);
+        <f32>::sse_encode(self.top_p, serializer);
+        <f32>::sse_encode(self.min_p, serializer);
+        <f32>::sse_encode(self.repeat_penalty, serializer);
+        <i32>::sse_encode(self.repeat_last_n, serializer);
+        <Option<u32>>::sse_encode(self.seed, serializer);
+        <u8>::sse_encode(self.mirostat, serializer);
+        <f32>::sse_encode(self.mirostat_tau, serializer);
+        <f32>::sse_encode(self.mirostat_eta, serializer);
     }
 }
 
-impl SseEncode for crate::db::MemoryItem {
+impl SseEncode for crate::ai::StopReason {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <i64>::sse_encode(self.id, serializer);
-        <String>::sse_encode(self.content, serializer);
-        <String>::sse_encode(self.created_at, serializer);
+        <i32>::sse_encode(
+            match self {
+                crate::ai::StopReason::EndOfTurn => 0,
+                crate::ai::StopReason::StopSequence => 1,
+                crate::ai::StopReason::MaxTokens => 2,
+                crate::ai::StopReason::Cancelled => 3,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
     }
 }
 
-impl SseEncode for crate::db::ProfileTrait {
+impl SseEncode for crate::api::simple::Turn {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <String>::sse_encode(self.category, serializer);
+        <String>::sse_encode(self.role, serializer);
         <String>::sse_encode(self.content, serializer);
     }
 }
@@ -1278,6 +4607,13 @@ impl SseEncode for u32 {
     }
 }
 
+impl SseEncode for u64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_u64::<NativeEndian>(self).unwrap();
+    }
+}
+
 impl SseEncode for u8 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1290,13 +4626,6 @@ impl SseEncode for () {
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {}
 }
 
-impl SseEncode for i32 {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        serializer.cursor.write_i32::<NativeEndian>(self).unwrap();
-    }
-}
-
 #[cfg(not(target_family = "wasm"))]
 mod io {
     // This file is automatically generated, so please do not edit it.
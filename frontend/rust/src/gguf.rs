@@ -0,0 +1,547 @@
+use crate::chat_template::ChatTemplate;
+use crate::error::AnimaError;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+/// Upper bounds that no real model gets near; anything above means a corrupt header.
+const MAX_STRING_LEN: u64 = 64 * 1024 * 1024;
+const MAX_ARRAY_LEN: u64 = 64 * 1024 * 1024;
+const MAX_TENSOR_DIMS: u32 = 8;
+const DEFAULT_ALIGNMENT: u64 = 32;
+const VOCABULARY_KEY: &str = "tokenizer.ggml.tokens";
+
+/// A metadata value. Arrays keep only their length; the registry never needs
+/// their items and the tokenizer vocabularies are large.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GgufValue {
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Array { len: u64 },
+}
+
+#[derive(Debug, Clone)]
+pub struct GgufHeader {
+    pub version: u32,
+    pub tensor_count: u64,
+    pub metadata: HashMap<String, GgufValue>,
+    /// Sum of the element counts of every tensor.
+    pub parameter_count: u64,
+    /// ggml type id of the tensor holding the most parameters.
+    pub dominant_tensor_type: Option<u32>,
+    /// File offset where tensor data starts, after alignment padding.
+    pub data_offset: u64,
+    /// Bytes of tensor data the header describes. A lower bound when a tensor
+    /// type is unknown to this parser.
+    pub data_len: u64,
+    /// Tokens of `tokenizer.ggml.tokens` that are in
+    /// `ChatTemplate::MARKER_TOKENS`; the rest of the vocabulary is not kept.
+    pub chat_marker_tokens: Vec<String>,
+}
+
+impl GgufHeader {
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        match self.metadata.get(key)? {
+            GgufValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        match self.metadata.get(key)? {
+            GgufValue::UInt(value) => Some(*value),
+            GgufValue::Int(value) => u64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.metadata.get(key)? {
+            GgufValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn architecture(&self) -> Option<&str> {
+        self.get_str("general.architecture")
+    }
+
+    /// Reads `<architecture>.<suffix>`, e.g. `llama.context_length`.
+    pub fn get_arch_u64(&self, suffix: &str) -> Option<u64> {
+        let architecture = self.architecture()?;
+        self.get_u64(&format!("{architecture}.{suffix}"))
+    }
+
+    pub fn context_length(&self) -> Option<u64> {
+        self.get_arch_u64("context_length")
+    }
+
+    pub fn embedding_length(&self) -> Option<u64> {
+        self.get_arch_u64("embedding_length")
+    }
+
+    pub fn chat_template(&self) -> Option<&str> {
+        self.get_str("tokenizer.chat_template")
+    }
+
+    /// Smallest file size that holds every tensor the header declares.
+    pub fn required_file_len(&self) -> u64 {
+        self.data_offset.saturating_add(self.data_len)
+    }
+
+    /// Encoder architectures, or any model declaring a pooling type, only
+    /// produce embeddings and cannot chat.
+    pub fn is_embedding_model(&self) -> bool {
+        const EMBEDDING_ARCHITECTURES: [&str; 5] = [
+            "bert",
+            "nomic-bert",
+            "jina-bert-v2",
+            "t5encoder",
+            "modern-bert",
+        ];
+        self.architecture()
+            .is_some_and(|architecture| EMBEDDING_ARCHITECTURES.contains(&architecture))
+            || self
+                .get_arch_u64("pooling_type")
+                .is_some_and(|pooling| pooling > 0)
+    }
+
+    /// Cross-encoders converted with rank pooling score a (query, document)
+    /// pair instead of embedding one text.
+    pub fn is_reranker_model(&self) -> bool {
+        const RANK_POOLING: u64 = 4;
+        self.get_arch_u64("pooling_type") == Some(RANK_POOLING)
+    }
+
+    /// Quantization label from `general.file_type`, falling back to the type
+    /// of the largest tensor.
+    pub fn quantization(&self) -> String {
+        self.get_u64("general.file_type")
+            .and_then(|file_type| u32::try_from(file_type).ok())
+            .and_then(file_type_name)
+            .or_else(|| self.dominant_tensor_type.and_then(tensor_type_name))
+            .unwrap_or("unknown")
+            .to_string()
+    }
+}
+
+pub fn read_header(path: &Path) -> Result<GgufHeader, AnimaError> {
+    let file = File::open(path).map_err(|error| {
+        AnimaError::Io(format!(
+            "Cannot open model file {}: {error}",
+            path.display()
+        ))
+    })?;
+    parse_header(&mut BufReader::new(file))
+        .map_err(|error| error.context(&path.display().to_string()))
+}
+
+pub fn parse_header<R: Read>(reader: &mut R) -> Result<GgufHeader, AnimaError> {
+    let reader = &mut CountingReader {
+        inner: reader,
+        position: 0,
+    };
+
+    let mut magic = [0_u8; 4];
+    reader.read_exact(&mut magic).map_err(header_error)?;
+    if &magic != GGUF_MAGIC {
+        return Err(AnimaError::InvalidModelFile(
+            "Not a GGUF file (bad magic)".to_string(),
+        ));
+    }
+
+    let version = read_u32(reader)?;
+    if !(2..=3).contains(&version) {
+        return Err(AnimaError::InvalidModelFile(format!(
+            "Unsupported GGUF version {version}"
+        )));
+    }
+
+    let tensor_count = read_u64(reader)?;
+    let kv_count = read_u64(reader)?;
+
+    let mut metadata = HashMap::new();
+    let mut chat_marker_tokens = Vec::new();
+    for _ in 0..kv_count {
+        let key = read_string(reader)?;
+        let value_type = read_u32(reader)?;
+        let value = if key == VOCABULARY_KEY && value_type == 9 {
+            read_array_items(reader, |item| match item {
+                GgufValue::String(token)
+                    if ChatTemplate::MARKER_TOKENS
+                        .iter()
+                        .any(|(marker, _)| *marker == token) =>
+                {
+                    chat_marker_tokens.push(token);
+                }
+                _ => {}
+            })?
+        } else {
+            read_value(reader, value_type)?
+        };
+        metadata.insert(key, value);
+    }
+
+    let mut parameter_count = 0_u64;
+    let mut data_len = 0_u64;
+    let mut params_by_type = HashMap::<u32, u64>::new();
+    for _ in 0..tensor_count {
+        let _name = read_string(reader)?;
+        let n_dims = read_u32(reader)?;
+        if n_dims > MAX_TENSOR_DIMS {
+            return Err(AnimaError::InvalidModelFile(format!(
+                "Tensor has {n_dims} dimensions"
+            )));
+        }
+        let mut elements = 1_u64;
+        for _ in 0..n_dims {
+            elements = elements.saturating_mul(read_u64(reader)?);
+        }
+        let tensor_type = read_u32(reader)?;
+        let offset = read_u64(reader)?;
+
+        let tensor_end = offset.saturating_add(tensor_byte_len(tensor_type, elements).unwrap_or(0));
+        data_len = data_len.max(tensor_end);
+        parameter_count = parameter_count.saturating_add(elements);
+        *params_by_type.entry(tensor_type).or_default() += elements;
+    }
+
+    let dominant_tensor_type = params_by_type
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(tensor_type, _)| tensor_type);
+
+    let alignment = match metadata.get("general.alignment") {
+        Some(GgufValue::UInt(alignment)) if *alignment > 0 => *alignment,
+        _ => DEFAULT_ALIGNMENT,
+    };
+    let data_offset = reader
+        .position
+        .div_ceil(alignment)
+        .saturating_mul(alignment);
+
+    Ok(GgufHeader {
+        version,
+        tensor_count,
+        metadata,
+        parameter_count,
+        dominant_tensor_type,
+        data_offset,
+        data_len,
+        chat_marker_tokens,
+    })
+}
+
+struct CountingReader<'a, R> {
+    inner: &'a mut R,
+    position: u64,
+}
+
+impl<R: Read> Read for CountingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+fn read_value<R: Read>(reader: &mut R, value_type: u32) -> Result<GgufValue, AnimaError> {
+    Ok(match value_type {
+        0 => GgufValue::UInt(u64::from(read_array::<1, R>(reader)?[0])),
+        1 => GgufValue::Int(i64::from(read_array::<1, R>(reader)?[0] as i8)),
+        2 => GgufValue::UInt(u64::from(u16::from_le_bytes(read_array(reader)?))),
+        3 => GgufValue::Int(i64::from(i16::from_le_bytes(read_array(reader)?))),
+        4 => GgufValue::UInt(u64::from(read_u32(reader)?)),
+        5 => GgufValue::Int(i64::from(i32::from_le_bytes(read_array(reader)?))),
+        6 => GgufValue::Float(f64::from(f32::from_le_bytes(read_array(reader)?))),
+        7 => GgufValue::Bool(read_array::<1, R>(reader)?[0] != 0),
+        8 => GgufValue::String(read_string(reader)?),
+        9 => read_array_items(reader, |_| {})?,
+        10 => GgufValue::UInt(read_u64(reader)?),
+        11 => GgufValue::Int(i64::from_le_bytes(read_array(reader)?)),
+        12 => GgufValue::Float(f64::from_le_bytes(read_array(reader)?)),
+        other => {
+            return Err(AnimaError::InvalidModelFile(format!(
+                "Unknown metadata value type {other}"
+            )))
+        }
+    })
+}
+
+/// Reads an array value, handing each item to `on_item`.
+fn read_array_items<R: Read>(
+    reader: &mut R,
+    mut on_item: impl FnMut(GgufValue),
+) -> Result<GgufValue, AnimaError> {
+    let item_type = read_u32(reader)?;
+    let len = read_u64(reader)?;
+    if len > MAX_ARRAY_LEN {
+        return Err(AnimaError::InvalidModelFile(format!(
+            "Metadata array of {len} items"
+        )));
+    }
+    for _ in 0..len {
+        on_item(read_value(reader, item_type)?);
+    }
+    Ok(GgufValue::Array { len })
+}
+
+fn read_string<R: Read>(reader: &mut R) -> Result<String, AnimaError> {
+    let len = read_u64(reader)?;
+    if len > MAX_STRING_LEN {
+        return Err(AnimaError::InvalidModelFile(format!(
+            "Metadata string of {len} bytes"
+        )));
+    }
+    let mut bytes = vec![0_u8; len as usize];
+    reader.read_exact(&mut bytes).map_err(header_error)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, AnimaError> {
+    Ok(u32::from_le_bytes(read_array(reader)?))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, AnimaError> {
+    Ok(u64::from_le_bytes(read_array(reader)?))
+}
+
+fn read_array<const N: usize, R: Read>(reader: &mut R) -> Result<[u8; N], AnimaError> {
+    let mut buffer = [0_u8; N];
+    reader.read_exact(&mut buffer).map_err(header_error)?;
+    Ok(buffer)
+}
+
+fn header_error(error: io::Error) -> AnimaError {
+    if error.kind() == io::ErrorKind::UnexpectedEof {
+        AnimaError::ModelTruncated("File ends inside the GGUF header".to_string())
+    } else {
+        AnimaError::Io(format!("GGUF header read failed: {error}"))
+    }
+}
+
+/// `llama_ftype` names as printed by llama.cpp.
+fn file_type_name(file_type: u32) -> Option<&'static str> {
+    Some(match file_type {
+        0 => "F32",
+        1 => "F16",
+        2 => "Q4_0",
+        3 => "Q4_1",
+        7 => "Q8_0",
+        8 => "Q5_0",
+        9 => "Q5_1",
+        10 => "Q2_K",
+        11 => "Q3_K_S",
+        12 => "Q3_K_M",
+        13 => "Q3_K_L",
+        14 => "Q4_K_S",
+        15 => "Q4_K_M",
+        16 => "Q5_K_S",
+        17 => "Q5_K_M",
+        18 => "Q6_K",
+        19 => "IQ2_XXS",
+        20 => "IQ2_XS",
+        21 => "Q2_K_S",
+        22 => "IQ3_XS",
+        23 => "IQ3_XXS",
+        24 => "IQ1_S",
+        25 => "IQ4_NL",
+        26 => "IQ3_S",
+        27 => "IQ3_M",
+        28 => "IQ2_S",
+        29 => "IQ2_M",
+        30 => "IQ4_XS",
+        31 => "IQ1_M",
+        32 => "BF16",
+        _ => return None,
+    })
+}
+
+/// Size in bytes of a tensor, from the `ggml_type` block layout.
+fn tensor_byte_len(tensor_type: u32, elements: u64) -> Option<u64> {
+    let (block_elements, block_bytes) = match tensor_type {
+        0 => (1, 4),
+        1 => (1, 2),
+        2 => (32, 18),
+        3 => (32, 20),
+        6 => (32, 22),
+        7 => (32, 24),
+        8 => (32, 34),
+        9 => (32, 36),
+        10 => (256, 84),
+        11 => (256, 110),
+        12 => (256, 144),
+        13 => (256, 176),
+        14 => (256, 210),
+        15 => (256, 292),
+        16 => (256, 66),
+        17 => (256, 74),
+        18 => (256, 98),
+        19 => (256, 50),
+        20 => (32, 18),
+        21 => (256, 110),
+        22 => (256, 82),
+        23 => (256, 136),
+        24 => (1, 1),
+        25 => (1, 2),
+        26 => (1, 4),
+        27 => (1, 8),
+        28 => (1, 8),
+        29 => (256, 56),
+        30 => (1, 2),
+        _ => return None,
+    };
+    Some(
+        elements
+            .div_ceil(block_elements)
+            .saturating_mul(block_bytes),
+    )
+}
+
+/// `ggml_type` names for the common tensor types.
+fn tensor_type_name(tensor_type: u32) -> Option<&'static str> {
+    Some(match tensor_type {
+        0 => "F32",
+        1 => "F16",
+        2 => "Q4_0",
+        3 => "Q4_1",
+        6 => "Q5_0",
+        7 => "Q5_1",
+        8 => "Q8_0",
+        10 => "Q2_K",
+        11 => "Q3_K",
+        12 => "Q4_K",
+        13 => "Q5_K",
+        14 => "Q6_K",
+        30 => "BF16",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Minimal GGUF v3 header with the given metadata and one 4x8 tensor.
+    pub(crate) fn synthetic_gguf(entries: &[(&str, GgufValue)]) -> Vec<u8> {
+        synthetic_gguf_with_vocabulary(entries, &[])
+    }
+
+    /// Like `synthetic_gguf`; array items start with `vocabulary` and are
+    /// `tok{index}` after it.
+    pub(crate) fn synthetic_gguf_with_vocabulary(
+        entries: &[(&str, GgufValue)],
+        vocabulary: &[&str],
+    ) -> Vec<u8> {
+        fn push_string(bytes: &mut Vec<u8>, value: &str) {
+            bytes.extend_from_slice(&(value.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(value.as_bytes());
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(GGUF_MAGIC);
+        bytes.extend_from_slice(&3_u32.to_le_bytes());
+        bytes.extend_from_slice(&1_u64.to_le_bytes());
+        bytes.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+        for (key, value) in entries {
+            push_string(&mut bytes, key);
+            match value {
+                GgufValue::UInt(value) => {
+                    bytes.extend_from_slice(&4_u32.to_le_bytes());
+                    bytes.extend_from_slice(&(*value as u32).to_le_bytes());
+                }
+                GgufValue::String(value) => {
+                    bytes.extend_from_slice(&8_u32.to_le_bytes());
+                    push_string(&mut bytes, value);
+                }
+                GgufValue::Array { len } => {
+                    bytes.extend_from_slice(&9_u32.to_le_bytes());
+                    bytes.extend_from_slice(&8_u32.to_le_bytes());
+                    bytes.extend_from_slice(&len.to_le_bytes());
+                    for index in 0..*len {
+                        let token = match vocabulary.get(index as usize) {
+                            Some(token) => token.to_string(),
+                            None => format!("tok{index}"),
+                        };
+                        push_string(&mut bytes, &token);
+                    }
+                }
+                other => panic!("unsupported test value {other:?}"),
+            }
+        }
+        push_string(&mut bytes, "token_embd.weight");
+        bytes.extend_from_slice(&2_u32.to_le_bytes());
+        bytes.extend_from_slice(&4_u64.to_le_bytes());
+        bytes.extend_from_slice(&8_u64.to_le_bytes());
+        bytes.extend_from_slice(&12_u32.to_le_bytes());
+        bytes.extend_from_slice(&0_u64.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_parses_metadata_and_tensors() {
+        let bytes = synthetic_gguf(&[
+            (
+                "general.architecture",
+                GgufValue::String("llama".to_string()),
+            ),
+            ("general.file_type", GgufValue::UInt(15)),
+            ("llama.context_length", GgufValue::UInt(8192)),
+            ("llama.embedding_length", GgufValue::UInt(4096)),
+            ("tokenizer.ggml.tokens", GgufValue::Array { len: 3 }),
+        ]);
+        let header = parse_header(&mut bytes.as_slice()).unwrap();
+        assert_eq!(header.architecture(), Some("llama"));
+        assert_eq!(header.context_length(), Some(8192));
+        assert_eq!(header.embedding_length(), Some(4096));
+        assert_eq!(header.quantization(), "Q4_K_M");
+        assert_eq!(header.parameter_count, 32);
+        // One Q4_K block after the header, padded to 32 bytes.
+        assert_eq!(header.data_offset % 32, 0);
+        assert!(header.data_offset >= bytes.len() as u64);
+        assert_eq!(header.required_file_len(), header.data_offset + 144);
+        assert_eq!(
+            header.metadata.get("tokenizer.ggml.tokens"),
+            Some(&GgufValue::Array { len: 3 })
+        );
+    }
+
+    #[test]
+    fn test_keeps_only_chat_marker_tokens_of_the_vocabulary() {
+        let bytes = synthetic_gguf_with_vocabulary(
+            &[("tokenizer.ggml.tokens", GgufValue::Array { len: 4 })],
+            &["<s>", "[INST]", "[/INST]"],
+        );
+        let header = parse_header(&mut bytes.as_slice()).unwrap();
+        assert_eq!(header.chat_marker_tokens, vec!["[INST]".to_string()]);
+        assert_eq!(
+            header.metadata.get("tokenizer.ggml.tokens"),
+            Some(&GgufValue::Array { len: 4 })
+        );
+
+        let plain = parse_header(&mut synthetic_gguf(&[]).as_slice()).unwrap();
+        assert!(plain.chat_marker_tokens.is_empty());
+    }
+
+    #[test]
+    fn test_quantization_falls_back_to_tensor_type() {
+        let bytes = synthetic_gguf(&[]);
+        let header = parse_header(&mut bytes.as_slice()).unwrap();
+        assert_eq!(header.quantization(), "Q4_K");
+    }
+
+    #[test]
+    fn test_rejects_bad_magic_and_truncation() {
+        let error = parse_header(&mut b"GGML\x03\x00\x00\x00".as_slice()).unwrap_err();
+        assert_eq!(error.code(), "invalid_model_file");
+
+        let bytes = synthetic_gguf(&[("general.architecture", GgufValue::String("llama".into()))]);
+        let error = parse_header(&mut &bytes[..bytes.len() - 5]).unwrap_err();
+        assert_eq!(error.code(), "model_truncated");
+    }
+}
@@ -0,0 +1,319 @@
+use crate::cancellation::CancellationToken;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Order in which a worker picks up queued jobs, most urgent first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JobPriority {
+    /// A chat reply the user is waiting for, or the embedding of their message.
+    Interactive,
+    Greeting,
+    /// Re-embedding memories after an embedding model change.
+    EmbeddingBackfill,
+    SleepCycle,
+}
+
+impl JobPriority {
+    /// Background jobs give way: a running one is preempted as soon as a
+    /// foreground job is queued behind it.
+    pub fn is_background(self) -> bool {
+        matches!(
+            self,
+            JobPriority::EmbeddingBackfill | JobPriority::SleepCycle
+        )
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobPriority::Interactive => "interactive",
+            JobPriority::Greeting => "greeting",
+            JobPriority::EmbeddingBackfill => "embedding_backfill",
+            JobPriority::SleepCycle => "sleep_cycle",
+        }
+    }
+}
+
+/// One job as the UI sees it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedJob {
+    pub id: u64,
+    pub priority: JobPriority,
+    /// 0 for the running job, then 1, 2, ... in pickup order.
+    pub position: u32,
+}
+
+/// Handed back by `JobQueue::push` so the submitter can tell a preempted job
+/// from one cancelled by the user.
+#[derive(Debug, Clone)]
+pub(crate) struct JobTicket {
+    pub id: u64,
+    preempted: Arc<AtomicBool>,
+}
+
+impl JobTicket {
+    pub fn was_preempted(&self) -> bool {
+        self.preempted.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Clone)]
+struct Preemption {
+    token: Option<CancellationToken>,
+    flag: Arc<AtomicBool>,
+}
+
+impl Preemption {
+    fn trigger(&self) {
+        self.flag.store(true, Ordering::SeqCst);
+        if let Some(token) = &self.token {
+            token.cancel();
+        }
+    }
+}
+
+struct Entry<J> {
+    id: u64,
+    priority: JobPriority,
+    preemption: Preemption,
+    job: J,
+}
+
+struct Running {
+    id: u64,
+    priority: JobPriority,
+    preemption: Preemption,
+}
+
+struct QueueState<J> {
+    /// Sorted by priority, then by submission order.
+    pending: Vec<Entry<J>>,
+    running: Option<Running>,
+    closed: bool,
+}
+
+/// Priority queue feeding one worker thread. Jobs of equal priority run in
+/// submission order.
+pub(crate) struct JobQueue<J> {
+    state: Mutex<QueueState<J>>,
+    available: Condvar,
+}
+
+impl<J> JobQueue<J> {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                pending: Vec::new(),
+                running: None,
+                closed: false,
+            }),
+            available: Condvar::new(),
+        }
+    }
+
+    /// Queues `job`; `None` once the queue is closed. `cancellation` is
+    /// cancelled if the job is preempted while it runs, which only happens to
+    /// background priorities.
+    pub fn push(
+        &self,
+        priority: JobPriority,
+        cancellation: Option<CancellationToken>,
+        job: J,
+    ) -> Option<JobTicket> {
+        let mut state = self.lock();
+        if state.closed {
+            return None;
+        }
+
+        let id = NEXT_JOB_ID.fetch_add(1, Ordering::SeqCst);
+        let preemption = Preemption {
+            token: cancellation,
+            flag: Arc::new(AtomicBool::new(false)),
+        };
+        let ticket = JobTicket {
+            id,
+            preempted: Arc::clone(&preemption.flag),
+        };
+        let index = state
+            .pending
+            .iter()
+            .position(|entry| entry.priority > priority)
+            .unwrap_or(state.pending.len());
+        state.pending.insert(
+            index,
+            Entry {
+                id,
+                priority,
+                preemption,
+                job,
+            },
+        );
+
+        if let Some(running) = &state.running {
+            if running.priority.is_background() && !priority.is_background() {
+                running.preemption.trigger();
+            }
+        }
+        self.available.notify_one();
+        Some(ticket)
+    }
+
+    /// Blocks until a job is queued and marks it running; the previous job is
+    /// considered finished. `None` once the queue is closed and drained.
+    pub fn next(&self) -> Option<J> {
+        let mut state = self.lock();
+        state.running = None;
+        loop {
+            if !state.pending.is_empty() {
+                let entry = state.pending.remove(0);
+                state.running = Some(Running {
+                    id: entry.id,
+                    priority: entry.priority,
+                    preemption: entry.preemption,
+                });
+                return Some(entry.job);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self
+                .available
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+
+    /// Refuses new jobs, drops the queued ones and cancels the running one,
+    /// so the worker exits as soon as that job stops. Submitters of dropped
+    /// jobs see their reply channel close.
+    pub fn shut_down(&self) {
+        let dropped = {
+            let mut state = self.lock();
+            state.closed = true;
+            if let Some(token) = state
+                .running
+                .as_ref()
+                .and_then(|running| running.preemption.token.as_ref())
+            {
+                token.cancel();
+            }
+            std::mem::take(&mut state.pending)
+        };
+        self.available.notify_all();
+        drop(dropped);
+    }
+
+    /// Preempts the running job if it is a background one that can be stopped.
+    pub fn preempt_background(&self) -> bool {
+        let state = self.lock();
+        match &state.running {
+            Some(running)
+                if running.priority.is_background() && running.preemption.token.is_some() =>
+            {
+                running.preemption.trigger();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn snapshot(&self) -> Vec<QueuedJob> {
+        let state = self.lock();
+        let running = state
+            .running
+            .iter()
+            .map(|running| (running.id, running.priority));
+        let pending = state.pending.iter().map(|entry| (entry.id, entry.priority));
+        let offset = u32::from(state.running.is_none());
+        running
+            .chain(pending)
+            .enumerate()
+            .map(|(index, (id, priority))| QueuedJob {
+                id,
+                priority,
+                position: u32::try_from(index).unwrap_or(u32::MAX) + offset,
+            })
+            .collect()
+    }
+
+    // Entries stay consistent across a panicking holder, so poisoning is ignored.
+    fn lock(&self) -> MutexGuard<'_, QueueState<J>> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs_run_by_priority_then_submission_order() {
+        let queue = JobQueue::new();
+        queue.push(JobPriority::SleepCycle, None, "sleep").unwrap();
+        queue
+            .push(JobPriority::Interactive, None, "chat 1")
+            .unwrap();
+        queue.push(JobPriority::Greeting, None, "greeting").unwrap();
+        queue
+            .push(JobPriority::Interactive, None, "chat 2")
+            .unwrap();
+
+        let positions = queue
+            .snapshot()
+            .iter()
+            .map(|job| (job.priority, job.position))
+            .collect::<Vec<_>>();
+        assert_eq!(positions[0], (JobPriority::Interactive, 1));
+        assert_eq!(positions[3], (JobPriority::SleepCycle, 4));
+
+        let order = (0..4).filter_map(|_| queue.next()).collect::<Vec<_>>();
+        assert_eq!(order, vec!["chat 1", "chat 2", "greeting", "sleep"]);
+    }
+
+    #[test]
+    fn test_shut_down_cancels_running_job_and_drops_queued_ones() {
+        let queue = JobQueue::new();
+        let running = CancellationToken::new();
+        let (reply_tx, reply_rx) = std::sync::mpsc::channel::<()>();
+        queue
+            .push(JobPriority::Interactive, Some(running.clone()), None)
+            .unwrap();
+        queue
+            .push(JobPriority::Interactive, None, Some(reply_tx))
+            .unwrap();
+        assert!(queue.next().is_some());
+
+        queue.shut_down();
+        assert!(running.is_cancelled());
+        assert!(reply_rx.recv().is_err());
+        assert!(queue.next().is_none());
+        assert!(queue.push(JobPriority::Interactive, None, None).is_none());
+    }
+
+    #[test]
+    fn test_urgent_job_preempts_running_background_job() {
+        let queue = JobQueue::new();
+        let token = CancellationToken::new();
+        let sleep = queue
+            .push(JobPriority::SleepCycle, Some(token.clone()), "sleep")
+            .unwrap();
+        assert_eq!(queue.next(), Some("sleep"));
+        assert_eq!(queue.snapshot()[0].position, 0);
+
+        queue
+            .push(JobPriority::EmbeddingBackfill, None, "backfill")
+            .unwrap();
+        assert!(!sleep.was_preempted());
+
+        queue.push(JobPriority::Interactive, None, "chat").unwrap();
+        assert!(sleep.was_preempted());
+        assert!(token.is_cancelled());
+
+        // A running interactive job is never preempted.
+        assert_eq!(queue.next(), Some("chat"));
+        assert!(!queue.preempt_background());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::error::AnimaError;
+use serde_json::{Map, Value};
+
+/// Shared terminals, after llama.cpp's `grammars/json.gbnf`. Whitespace is
+/// bounded so a model cannot stall by emitting it forever.
+const PRIMITIVE_RULES: &str = r#"ws ::= | " " | "\n" [ \t]{0,20}
+string ::= "\"" ( [^"\\\x7F\x00-\x1F] | "\\" ( ["\\/bfnrt] | "u" [0-9a-fA-F]{4} ) )* "\"" ws
+number ::= "-"? ( [0-9] | [1-9] [0-9]{0,15} ) ( "." [0-9]+ )? ( [eE] [-+]? [0-9]{1,15} )? ws
+integer ::= "-"? ( [0-9] | [1-9] [0-9]{0,15} ) ws
+boolean ::= ( "true" | "false" ) ws
+null ::= "null" ws
+"#;
+
+/// Converts a JSON Schema into a GBNF grammar whose `root` rule only accepts
+/// matching JSON.
+///
+/// Only the subset structured generation needs is supported: `type` object,
+/// array, string, number, integer, boolean and null, plus `properties`,
+/// `items`, `enum`, `const`, `anyOf` and `oneOf`. Every listed property is
+/// required and emitted in key order, as `serde_json` iterates them.
+pub fn schema_to_gbnf(schema: &Value) -> Result<String, AnimaError> {
+    let mut converter = Converter { rules: Vec::new() };
+    let root = converter.visit(schema, "root")?;
+    let mut grammar = format!("root ::= {root}\n");
+    for (name, body) in &converter.rules {
+        grammar.push_str(&format!("{name} ::= {body}\n"));
+    }
+    grammar.push_str(PRIMITIVE_RULES);
+    Ok(grammar)
+}
+
+struct Converter {
+    rules: Vec<(String, String)>,
+}
+
+impl Converter {
+    /// Returns a rule body for `schema`; nested objects and arrays get named
+    /// rules derived from `path`.
+    fn visit(&mut self, schema: &Value, path: &str) -> Result<String, AnimaError> {
+        let schema = schema
+            .as_object()
+            .ok_or_else(|| unsupported(path, "schema must be an object"))?;
+
+        if let Some(value) = schema.get("const") {
+            return Ok(format!("{} ws", literal(value)));
+        }
+        if let Some(values) = schema.get("enum") {
+            let values = values
+                .as_array()
+                .filter(|values| !values.is_empty())
+                .ok_or_else(|| unsupported(path, "enum must be a non-empty array"))?;
+            let alternatives = values.iter().map(literal).collect::<Vec<_>>();
+            return Ok(format!("( {} ) ws", alternatives.join(" | ")));
+        }
+        if let Some(variants) = schema.get("anyOf").or_else(|| schema.get("oneOf")) {
+            let variants = variants
+                .as_array()
+                .filter(|variants| !variants.is_empty())
+                .ok_or_else(|| unsupported(path, "anyOf/oneOf must be a non-empty array"))?;
+            let mut alternatives = Vec::with_capacity(variants.len());
+            for (index, variant) in variants.iter().enumerate() {
+                let name = self.add_rule(&format!("{path}-{index}"), |converter, name| {
+                    converter.visit(variant, name)
+                })?;
+                alternatives.push(name);
+            }
+            return Ok(format!("( {} )", alternatives.join(" | ")));
+        }
+
+        match schema.get("type").and_then(Value::as_str) {
+            Some("object") => self.object(schema, path),
+            Some("array") => {
+                let items = schema
+                    .get("items")
+                    .ok_or_else(|| unsupported(path, "array needs items"))?;
+                let item = self.add_rule(&format!("{path}-item"), |converter, name| {
+                    converter.visit(items, name)
+                })?;
+                Ok(format!(r#""[" ws ( {item} ( "," ws {item} )* )? "]" ws"#))
+            }
+            Some(primitive @ ("string" | "number" | "integer" | "boolean" | "null")) => {
+                Ok(primitive.to_string())
+            }
+            Some(other) => Err(unsupported(path, &format!("type '{other}'"))),
+            None => Err(unsupported(path, "missing type")),
+        }
+    }
+
+    fn object(&mut self, schema: &Map<String, Value>, path: &str) -> Result<String, AnimaError> {
+        let properties = match schema.get("properties") {
+            Some(properties) => properties
+                .as_object()
+                .ok_or_else(|| unsupported(path, "properties must be an object"))?,
+            None => return Ok(r#""{" ws "}" ws"#.to_string()),
+        };
+
+        let mut members = Vec::with_capacity(properties.len());
+        for (key, property) in properties {
+            let value = self
+                .add_rule(&format!("{path}-{}", rule_name(key)), |converter, name| {
+                    converter.visit(property, name)
+                })?;
+            members.push(format!(
+                r#"{} ws ":" ws {value}"#,
+                literal(&Value::from(key.as_str()))
+            ));
+        }
+        Ok(format!(
+            r#""{{" ws {} "}}" ws"#,
+            members.join(r#" "," ws "#)
+        ))
+    }
+
+    fn add_rule(
+        &mut self,
+        name: &str,
+        body: impl FnOnce(&mut Self, &str) -> Result<String, AnimaError>,
+    ) -> Result<String, AnimaError> {
+        let mut unique = name.to_string();
+        let mut suffix = 1;
+        while self.rules.iter().any(|(existing, _)| *existing == unique) {
+            suffix += 1;
+            unique = format!("{name}{suffix}");
+        }
+        // Reserved before recursing so nested rules cannot take the name.
+        let index = self.rules.len();
+        self.rules.push((unique.clone(), String::new()));
+        self.rules[index].1 = body(self, &unique)?;
+        Ok(unique)
+    }
+}
+
+/// GBNF string literal matching `value` serialized as compact JSON.
+fn literal(value: &Value) -> String {
+    let json = value.to_string();
+    let mut escaped = String::with_capacity(json.len() + 2);
+    escaped.push('"');
+    for character in json.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            other => escaped.push(other),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn rule_name(key: &str) -> String {
+    let name = key
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() {
+                character.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    if name.is_empty() {
+        "prop".to_string()
+    } else {
+        name
+    }
+}
+
+fn unsupported(path: &str, reason: &str) -> AnimaError {
+    AnimaError::InvalidInput(format!("Unsupported JSON schema at {path}: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_object_of_string_arrays() {
+        let grammar = schema_to_gbnf(&json!({
+            "type": "object",
+            "properties": {
+                "semantic": { "type": "array", "items": { "type": "string" } },
+                "episodic": { "type": "array", "items": { "type": "string" } }
+            }
+        }))
+        .unwrap();
+
+        let rules = grammar.lines().collect::<Vec<_>>();
+        assert_eq!(
+            rules[0],
+            r#"root ::= "{" ws "\"episodic\"" ws ":" ws root-episodic "," ws "\"semantic\"" ws ":" ws root-semantic "}" ws"#
+        );
+        assert!(rules.contains(
+            &r#"root-semantic ::= "[" ws ( root-semantic-item ( "," ws root-semantic-item )* )? "]" ws"#
+        ));
+        assert!(rules.contains(&"root-semantic-item ::= string"));
+        assert!(rules.iter().any(|rule| rule.starts_with("string ::= ")));
+    }
+
+    #[test]
+    fn test_enum_literals_are_escaped_and_unknown_types_rejected() {
+        let grammar = schema_to_gbnf(&json!({ "enum": ["a\"b", 1, null] })).unwrap();
+        assert!(grammar.starts_with(r#"root ::= ( "\"a\\\"b\"" | "1" | "null" ) ws"#));
+
+        let error = schema_to_gbnf(&json!({ "type": "date" })).unwrap_err();
+        assert_eq!(error.code(), "invalid_input");
+    }
+}
@@ -1,4 +1,25 @@
-pub mod api;
 pub mod ai;
+pub mod api;
+pub mod backend;
+pub mod cancellation;
+pub mod chat_template;
 pub mod db;
+pub mod embedding_batch;
+pub mod error;
 mod frb_generated;
+pub mod gguf;
+pub mod job_queue;
+pub mod json_grammar;
+pub mod load_strategy;
+pub mod local_http;
+pub mod memory_index;
+pub mod mock_backend;
+pub mod model_manager;
+pub mod model_registry;
+pub mod model_validation;
+pub mod openai_backend;
+pub mod output_filter;
+pub mod prompt_budget;
+pub mod prompt_cache;
+pub mod reembed;
+pub mod runtime_config;
@@ -0,0 +1,211 @@
+use crate::error::AnimaError;
+use crate::gguf::GgufHeader;
+
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+/// Scratch buffers llama.cpp allocates next to the weights and KV cache.
+const COMPUTE_OVERHEAD_BYTES: u64 = 256 * BYTES_PER_MIB;
+/// Left free for the OS and the UI so a load does not push the device into swap.
+const HEADROOM_BYTES: u64 = 512 * BYTES_PER_MIB;
+/// K and V entries are stored as f16.
+const KV_BYTES_PER_VALUE: u64 = 2;
+
+/// How model weights get into memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadStrategy {
+    /// Checks free RAM, then memory-maps on Unix and reads into RAM elsewhere.
+    Auto,
+    /// Memory-mapped: fast start, pages are loaded on demand.
+    Mmap,
+    /// Read fully into RAM before the first token.
+    Ram,
+    /// Memory-mapped and locked so the OS cannot page the weights out.
+    Mlock,
+}
+
+impl LoadStrategy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LoadStrategy::Auto => "auto",
+            LoadStrategy::Mmap => "mmap",
+            LoadStrategy::Ram => "ram",
+            LoadStrategy::Mlock => "mlock",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(LoadStrategy::Auto),
+            "mmap" => Some(LoadStrategy::Mmap),
+            "ram" => Some(LoadStrategy::Ram),
+            "mlock" => Some(LoadStrategy::Mlock),
+            _ => None,
+        }
+    }
+
+    pub fn use_mmap(self) -> bool {
+        !matches!(self, LoadStrategy::Ram)
+    }
+
+    pub fn use_mlock(self) -> bool {
+        matches!(self, LoadStrategy::Mlock)
+    }
+}
+
+/// The strategy a load actually used and the numbers it was based on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadPlan {
+    /// Never `Auto`.
+    pub strategy: LoadStrategy,
+    pub required_bytes: u64,
+    /// `None` where the platform does not report it.
+    pub available_bytes: Option<u64>,
+}
+
+impl LoadPlan {
+    pub fn summary(&self) -> String {
+        let available = self
+            .available_bytes
+            .map(|bytes| format!("{} MiB", bytes / BYTES_PER_MIB))
+            .unwrap_or_else(|| "unknown".to_string());
+        format!(
+            "strategy={} required={} MiB available={available}",
+            self.strategy.as_str(),
+            self.required_bytes / BYTES_PER_MIB
+        )
+    }
+
+    /// RAM that unloading the model frees beyond what `MemAvailable` already
+    /// reports: unlocked memory-mapped weights sit in the page cache, which
+    /// it counts as available.
+    pub fn released_bytes(&self, file_len: u64) -> u64 {
+        if self.strategy == LoadStrategy::Mmap {
+            self.required_bytes.saturating_sub(file_len)
+        } else {
+            self.required_bytes
+        }
+    }
+}
+
+/// `reclaimable_bytes` is what unloading the model being replaced will free.
+pub fn plan_load(
+    requested: LoadStrategy,
+    header: &GgufHeader,
+    file_len: u64,
+    n_ctx: u32,
+    reclaimable_bytes: u64,
+) -> Result<LoadPlan, AnimaError> {
+    let required_bytes = file_len
+        .saturating_add(estimate_kv_cache_bytes(header, n_ctx))
+        .saturating_add(COMPUTE_OVERHEAD_BYTES);
+    resolve(
+        requested,
+        required_bytes,
+        available_after_release(reclaimable_bytes),
+    )
+}
+
+fn resolve(
+    requested: LoadStrategy,
+    required_bytes: u64,
+    available_bytes: Option<u64>,
+) -> Result<LoadPlan, AnimaError> {
+    let fits =
+        available_bytes.map(|available| required_bytes.saturating_add(HEADROOM_BYTES) <= available);
+
+    let strategy = match requested {
+        LoadStrategy::Auto if cfg!(unix) => LoadStrategy::Mmap,
+        LoadStrategy::Auto => LoadStrategy::Ram,
+        explicit => explicit,
+    };
+
+    // An explicit mmap is allowed to page; every other choice commits the
+    // whole model to RAM.
+    if fits == Some(false) && requested != LoadStrategy::Mmap {
+        return Err(AnimaError::InsufficientMemory(format!(
+            "Model needs about {} MiB but only {} MiB are available",
+            required_bytes.saturating_add(HEADROOM_BYTES) / BYTES_PER_MIB,
+            available_bytes.unwrap_or(0) / BYTES_PER_MIB
+        )));
+    }
+
+    Ok(LoadPlan {
+        strategy,
+        required_bytes,
+        available_bytes,
+    })
+}
+
+/// f16 K and V for every layer; grouped-query models store fewer KV heads.
+pub(crate) fn estimate_kv_cache_bytes(header: &GgufHeader, n_ctx: u32) -> u64 {
+    let n_layer = header.get_arch_u64("block_count").unwrap_or(0);
+    let n_embd = header.embedding_length().unwrap_or(0);
+    let n_head = header.get_arch_u64("attention.head_count").unwrap_or(0);
+    let n_head_kv = header
+        .get_arch_u64("attention.head_count_kv")
+        .unwrap_or(n_head);
+    let kv_dim = n_embd
+        .saturating_mul(n_head_kv)
+        .checked_div(n_head)
+        .unwrap_or(n_embd);
+
+    2_u64
+        .saturating_mul(n_layer)
+        .saturating_mul(u64::from(n_ctx))
+        .saturating_mul(kv_dim)
+        .saturating_mul(KV_BYTES_PER_VALUE)
+}
+
+/// `MemAvailable` from `/proc/meminfo`; `None` on platforms without it.
+pub fn available_memory_bytes() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    parse_mem_available(&meminfo)
+}
+
+/// Free RAM once `reclaimable_bytes` held by a model about to be unloaded
+/// are given back.
+pub fn available_after_release(reclaimable_bytes: u64) -> Option<u64> {
+    available_memory_bytes().map(|available| available.saturating_add(reclaimable_bytes))
+}
+
+fn parse_mem_available(meminfo: &str) -> Option<u64> {
+    let line = meminfo
+        .lines()
+        .find(|line| line.starts_with("MemAvailable:"))?;
+    let kib = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kib.saturating_mul(1024))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: u64 = 1024 * BYTES_PER_MIB;
+
+    #[test]
+    fn test_auto_refuses_when_model_does_not_fit() {
+        let error = resolve(LoadStrategy::Auto, 5 * GIB, Some(4 * GIB)).unwrap_err();
+        assert_eq!(error.code(), "insufficient_memory");
+
+        let plan = resolve(LoadStrategy::Auto, 2 * GIB, Some(8 * GIB)).unwrap();
+        assert_ne!(plan.strategy, LoadStrategy::Auto);
+
+        // Explicit mmap may page, and an unknown amount of RAM is not a refusal.
+        assert!(resolve(LoadStrategy::Mmap, 5 * GIB, Some(4 * GIB)).is_ok());
+        assert!(resolve(LoadStrategy::Ram, 5 * GIB, None).is_ok());
+    }
+
+    #[test]
+    fn test_memory_mapped_weights_are_not_counted_twice_on_release() {
+        let plan = resolve(LoadStrategy::Mmap, 3 * GIB, Some(8 * GIB)).unwrap();
+        assert_eq!(plan.released_bytes(2 * GIB), GIB);
+        let plan = resolve(LoadStrategy::Ram, 3 * GIB, Some(8 * GIB)).unwrap();
+        assert_eq!(plan.released_bytes(2 * GIB), 3 * GIB);
+    }
+
+    #[test]
+    fn test_parses_mem_available() {
+        let meminfo = "MemTotal:       16318480 kB\nMemFree:         1048576 kB\nMemAvailable:    8159240 kB\n";
+        assert_eq!(parse_mem_available(meminfo), Some(8_159_240 * 1024));
+        assert_eq!(parse_mem_available("MemTotal: 1 kB\n"), None);
+    }
+}
@@ -0,0 +1,405 @@
+use crate::cancellation::CancellationToken;
+use crate::error::AnimaError;
+use serde_json::Value;
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{IpAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Long enough for a large model's prompt processing before the first byte.
+const READ_TIMEOUT: Duration = Duration::from_secs(300);
+/// How often a cancellable request wakes from a silent socket to check its
+/// token.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_HEADER_LINES: usize = 100;
+
+/// Plain-HTTP base URL of a server on this machine. TLS and remote hosts are
+/// deliberately unsupported: the server is only a stand-in for the local model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalUrl {
+    host: String,
+    port: u16,
+    base_path: String,
+}
+
+impl LocalUrl {
+    pub fn parse(url: &str) -> Result<Self, AnimaError> {
+        let invalid = |reason: &str| {
+            AnimaError::InvalidInput(format!("Invalid server URL '{url}': {reason}"))
+        };
+
+        let rest = url
+            .trim()
+            .strip_prefix("http://")
+            .ok_or_else(|| invalid("only http:// is supported"))?;
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, ""),
+        };
+
+        let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+            let (host, after) = bracketed
+                .split_once(']')
+                .ok_or_else(|| invalid("unclosed IPv6 bracket"))?;
+            (host, after.strip_prefix(':'))
+        } else {
+            match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            }
+        };
+        let port = match port {
+            Some(port) => port.parse::<u16>().map_err(|_| invalid("bad port"))?,
+            None => 80,
+        };
+
+        let is_loopback = host.eq_ignore_ascii_case("localhost")
+            || host
+                .parse::<IpAddr>()
+                .is_ok_and(|address| address.is_loopback());
+        if !is_loopback {
+            return Err(invalid("the server must run on this machine (localhost)"));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            base_path: path.trim_end_matches('/').to_string(),
+        })
+    }
+
+    fn host_header(&self) -> String {
+        if self.host.contains(':') {
+            format!("[{}]:{}", self.host, self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// Response with the body still unread, so streams can be consumed as they arrive.
+pub struct HttpResponse {
+    pub status: u16,
+    body: Box<dyn BufRead + Send>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl HttpResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn read_string(mut self) -> Result<String, AnimaError> {
+        let mut body = Vec::new();
+        retry_until_cancelled(self.cancellation.as_ref(), "reading response", || {
+            self.body.read_to_end(&mut body)
+        })?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    pub fn read_json(self) -> Result<Value, AnimaError> {
+        let body = self.read_string()?;
+        serde_json::from_str(&body).map_err(|error| {
+            AnimaError::BackendUnavailable(format!("Server sent invalid JSON: {error}"))
+        })
+    }
+
+    /// Next body line without the line ending; `None` at the end of the body.
+    /// Fails with `Cancelled` if the request's token is cancelled while
+    /// waiting for it.
+    pub fn next_line(&mut self) -> Result<Option<String>, AnimaError> {
+        read_line(&mut self.body, self.cancellation.as_ref(), "reading stream")
+    }
+}
+
+pub fn post_json(url: &LocalUrl, path: &str, body: &Value) -> Result<HttpResponse, AnimaError> {
+    send_json(url, path, body, None)
+}
+
+/// Like `post_json`, but every wait on the server, including the prompt
+/// processing before the first byte, ends within `CANCEL_POLL_INTERVAL` of
+/// `cancellation` with `AnimaError::Cancelled`.
+pub fn post_json_cancellable(
+    url: &LocalUrl,
+    path: &str,
+    body: &Value,
+    cancellation: &CancellationToken,
+) -> Result<HttpResponse, AnimaError> {
+    send_json(url, path, body, Some(cancellation.clone()))
+}
+
+fn send_json(
+    url: &LocalUrl,
+    path: &str,
+    body: &Value,
+    cancellation: Option<CancellationToken>,
+) -> Result<HttpResponse, AnimaError> {
+    let address = (url.host.as_str(), url.port)
+        .to_socket_addrs()
+        .map_err(|error| unavailable("resolving host", &error))?
+        .next()
+        .ok_or_else(|| AnimaError::BackendUnavailable(format!("No address for {}", url.host)))?;
+    let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)
+        .map_err(|error| unavailable(&format!("connecting to {address}"), &error))?;
+    let read_timeout = if cancellation.is_some() {
+        CANCEL_POLL_INTERVAL
+    } else {
+        READ_TIMEOUT
+    };
+    stream
+        .set_read_timeout(Some(read_timeout))
+        .map_err(|error| unavailable("configuring socket", &error))?;
+
+    let payload = body.to_string();
+    let request = format!(
+        "POST {}{path} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nAccept: application/json, text/event-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{payload}",
+        url.base_path,
+        url.host_header(),
+        payload.len()
+    );
+    (&stream)
+        .write_all(request.as_bytes())
+        .map_err(|error| unavailable("sending request", &error))?;
+
+    read_response(BufReader::new(stream), cancellation)
+}
+
+fn read_response<R: BufRead + Send + 'static>(
+    mut reader: R,
+    cancellation: Option<CancellationToken>,
+) -> Result<HttpResponse, AnimaError> {
+    let status_line =
+        read_line(&mut reader, cancellation.as_ref(), "reading status")?.unwrap_or_default();
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| {
+            AnimaError::BackendUnavailable(format!("Malformed HTTP status line: {status_line:?}"))
+        })?;
+
+    let mut content_length = None;
+    let mut chunked = false;
+    for _ in 0..MAX_HEADER_LINES {
+        let line =
+            read_line(&mut reader, cancellation.as_ref(), "reading headers")?.unwrap_or_default();
+        let line = line.trim_end();
+        if line.is_empty() {
+            let body: Box<dyn BufRead + Send> = if chunked {
+                Box::new(BufReader::new(ChunkedReader::new(reader)))
+            } else if let Some(length) = content_length {
+                Box::new(reader.take(length))
+            } else {
+                Box::new(reader)
+            };
+            return Ok(HttpResponse {
+                status,
+                body,
+                cancellation,
+            });
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse::<u64>().ok();
+            } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.to_ascii_lowercase().contains("chunked");
+            }
+        }
+    }
+
+    Err(AnimaError::BackendUnavailable(
+        "Too many HTTP response headers".to_string(),
+    ))
+}
+
+/// Reads one line, waiting out the poll timeouts of a cancellable request.
+/// `None` at the end of the input.
+fn read_line(
+    reader: &mut impl BufRead,
+    cancellation: Option<&CancellationToken>,
+    step: &str,
+) -> Result<Option<String>, AnimaError> {
+    let mut line = Vec::new();
+    retry_until_cancelled(cancellation, step, || reader.read_until(b'\n', &mut line))?;
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let line = String::from_utf8_lossy(&line);
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+/// Repeats `read` across the short socket timeouts of a cancellable request
+/// until it succeeds, the token is cancelled or the server has been silent
+/// for `READ_TIMEOUT`. `read` must keep partial progress between attempts,
+/// as `BufRead::read_until` and `Read::read_to_end` do.
+fn retry_until_cancelled<T>(
+    cancellation: Option<&CancellationToken>,
+    step: &str,
+    mut read: impl FnMut() -> std::io::Result<T>,
+) -> Result<T, AnimaError> {
+    let started = Instant::now();
+    loop {
+        match read() {
+            Err(error)
+                if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+                    && cancellation.is_some()
+                    && started.elapsed() < READ_TIMEOUT =>
+            {
+                if cancellation.is_some_and(CancellationToken::is_cancelled) {
+                    return Err(AnimaError::Cancelled(format!(
+                        "Request cancelled while {step}"
+                    )));
+                }
+            }
+            result => return result.map_err(|error| unavailable(step, &error)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkState {
+    Size,
+    Data {
+        remaining: usize,
+    },
+    /// The line ending after a chunk's data.
+    DataEnd,
+    Trailers,
+    Finished,
+}
+
+/// Decodes `Transfer-Encoding: chunked`, which servers use for streamed replies.
+/// A read that times out can be retried: partial lines are kept.
+struct ChunkedReader<R> {
+    inner: R,
+    state: ChunkState,
+    line: Vec<u8>,
+}
+
+impl<R: BufRead> ChunkedReader<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            state: ChunkState::Size,
+            line: Vec::new(),
+        }
+    }
+
+    fn read_line(&mut self) -> std::io::Result<String> {
+        self.inner.read_until(b'\n', &mut self.line)?;
+        let line = String::from_utf8_lossy(&self.line).trim_end().to_string();
+        self.line.clear();
+        Ok(line)
+    }
+}
+
+impl<R: BufRead> Read for ChunkedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            match self.state {
+                ChunkState::Size => {
+                    let size_line = self.read_line()?;
+                    let size = size_line.split(';').next().unwrap_or("").trim();
+                    let remaining = usize::from_str_radix(size, 16).map_err(|_| {
+                        std::io::Error::new(
+                            ErrorKind::InvalidData,
+                            format!("bad chunk size {size_line:?}"),
+                        )
+                    })?;
+                    self.state = if remaining == 0 {
+                        ChunkState::Trailers
+                    } else {
+                        ChunkState::Data { remaining }
+                    };
+                }
+                ChunkState::Data { remaining } => {
+                    let wanted = buf.len().min(remaining);
+                    let read = self.inner.read(&mut buf[..wanted])?;
+                    if read == 0 {
+                        return Err(ErrorKind::UnexpectedEof.into());
+                    }
+                    self.state = match remaining - read {
+                        0 => ChunkState::DataEnd,
+                        remaining => ChunkState::Data { remaining },
+                    };
+                    return Ok(read);
+                }
+                ChunkState::DataEnd => {
+                    self.read_line()?;
+                    self.state = ChunkState::Size;
+                }
+                // Skip trailers up to the final empty line.
+                ChunkState::Trailers => {
+                    if self.read_line()?.is_empty() {
+                        self.state = ChunkState::Finished;
+                    }
+                }
+                ChunkState::Finished => return Ok(0),
+            }
+        }
+    }
+}
+
+fn unavailable(step: &str, error: &std::io::Error) -> AnimaError {
+    AnimaError::BackendUnavailable(format!("Local server error while {step}: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_parse_accepts_only_loopback_http() {
+        let url = LocalUrl::parse("http://127.0.0.1:8080/v1/").unwrap();
+        assert_eq!((url.port, url.base_path.as_str()), (8080, "/v1"));
+        assert_eq!(
+            LocalUrl::parse("http://[::1]:11434").unwrap().host_header(),
+            "[::1]:11434"
+        );
+        assert_eq!(LocalUrl::parse("http://localhost").unwrap().port, 80);
+
+        assert!(LocalUrl::parse("https://127.0.0.1:8080").is_err());
+        assert!(LocalUrl::parse("http://192.168.1.20:8080").is_err());
+        assert!(LocalUrl::parse("http://example.com").is_err());
+    }
+
+    #[test]
+    fn test_reads_chunked_body() {
+        let raw = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n7\r\ndata: a\r\n9\r\n\ndata: b\n\r\n0\r\n\r\n";
+        let mut response = read_response(Cursor::new(raw.as_bytes().to_vec()), None).unwrap();
+        assert_eq!(response.status, 200);
+        // A line may span chunks.
+        assert_eq!(response.next_line().unwrap().as_deref(), Some("data: a"));
+        assert_eq!(response.next_line().unwrap().as_deref(), Some("data: b"));
+        assert_eq!(response.next_line().unwrap(), None);
+    }
+
+    #[test]
+    fn test_cancellation_ends_wait_on_silent_server() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = LocalUrl::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        // Accepts, then stays silent like a server still processing the prompt.
+        std::thread::spawn(move || {
+            let (_stream, _) = listener.accept().unwrap();
+            std::thread::sleep(Duration::from_secs(5));
+        });
+
+        let cancellation = CancellationToken::new();
+        let canceller = cancellation.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            canceller.cancel();
+        });
+
+        let started = Instant::now();
+        let result =
+            post_json_cancellable(&url, "/v1/chat/completions", &Value::Null, &cancellation);
+        assert!(matches!(result, Err(AnimaError::Cancelled(_))));
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,880 @@
+use crate::error::AnimaError;
+use rusqlite::{params, Connection};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+const INDEX_MAGIC: &[u8; 8] = b"ANMHNSW1";
+/// Neighbours per node on the upper layers; layer 0 keeps twice as many.
+const MAX_NEIGHBORS: usize = 16;
+const EF_CONSTRUCTION: usize = 100;
+const MIN_EF_SEARCH: usize = 64;
+/// Unsaved changes before the index is written back to disk. A crash in
+/// between only costs a rebuild on the next search.
+const SAVE_EVERY_CHANGES: usize = 64;
+/// Deleted nodes still route searches; past this share the graph is rebuilt.
+const MAX_DELETED_RATIO: f32 = 0.25;
+
+static INDEX: Mutex<Option<MemoryIndex>> = Mutex::new(None);
+
+/// Which memory rows an index covers, as the SQL aggregate over those rows
+/// sees them. A mismatch means the table changed behind the index's back
+/// (factory reset, cascade delete, a crash before saving), so it is rebuilt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct Fingerprint {
+    count: i64,
+    id_sum: i64,
+    max_id: i64,
+}
+
+/// HNSW graph over the memory vectors of one embedding model, persisted next
+/// to the database as `<db>.hnsw`.
+struct MemoryIndex {
+    model: String,
+    graph: Hnsw,
+    nodes_by_message: HashMap<i64, u32>,
+    /// Covered rows whose vector has no direction; kept so they still count
+    /// towards the fingerprint.
+    unindexable: HashSet<i64>,
+    unsaved_changes: usize,
+    path: PathBuf,
+}
+
+impl MemoryIndex {
+    fn fingerprint(&self) -> Fingerprint {
+        let mut fingerprint = Fingerprint::default();
+        for message_id in self.nodes_by_message.keys().chain(&self.unindexable) {
+            fingerprint.count += 1;
+            fingerprint.id_sum = fingerprint.id_sum.wrapping_add(*message_id);
+            fingerprint.max_id = fingerprint.max_id.max(*message_id);
+        }
+        fingerprint
+    }
+
+    fn covers(&self, model: &str, dim: usize) -> bool {
+        self.model == model && self.graph.dim == dim
+    }
+
+    fn upsert(&mut self, message_id: i64, vector: &[f32]) {
+        self.remove(message_id);
+        if let Some(node) = self.graph.insert(message_id, vector) {
+            self.nodes_by_message.insert(message_id, node);
+        } else {
+            self.unindexable.insert(message_id);
+        }
+        self.unsaved_changes += 1;
+    }
+
+    fn remove(&mut self, message_id: i64) {
+        if let Some(node) = self.nodes_by_message.remove(&message_id) {
+            self.graph.mark_deleted(node);
+            self.unsaved_changes += 1;
+        }
+        if self.unindexable.remove(&message_id) {
+            self.unsaved_changes += 1;
+        }
+    }
+
+    /// Compacts away deleted nodes and saves when enough changed.
+    fn maintain(&mut self) {
+        if self.graph.deleted_ratio() > MAX_DELETED_RATIO {
+            self.graph = self.graph.compacted();
+            self.nodes_by_message = self.graph.live_nodes().collect();
+            self.unsaved_changes = SAVE_EVERY_CHANGES;
+        }
+        if self.unsaved_changes >= SAVE_EVERY_CHANGES {
+            self.save();
+        }
+    }
+
+    fn save(&mut self) {
+        match write_index_file(&self.path, self) {
+            Ok(()) => self.unsaved_changes = 0,
+            Err(error) => eprintln!("[memory_index] save failed: {error}"),
+        }
+    }
+}
+
+/// Ids and cosine similarities of roughly the `limit` nearest memories of
+/// `embedding_model`, best first. Loads, or rebuilds from `conn`, the index
+/// for that model when needed.
+pub(crate) fn search(
+    conn: &Connection,
+    db_path: &Path,
+    query: &[f32],
+    embedding_model: &str,
+    limit: usize,
+) -> Result<Vec<(i64, f32)>, AnimaError> {
+    let mut slot = lock()?;
+    let expected = sql_fingerprint(conn, embedding_model, query.len())?;
+    let path = index_path(db_path);
+
+    let reusable = slot
+        .as_ref()
+        .is_some_and(|index| index.path == path && index.covers(embedding_model, query.len()));
+    if !reusable {
+        *slot = load_index_file(&path)
+            .inspect_err(|error| eprintln!("[memory_index] {error}"))
+            .ok()
+            .flatten()
+            .filter(|index| index.covers(embedding_model, query.len()));
+    }
+    let index = match slot.as_mut() {
+        Some(index) if index.fingerprint() == expected => index,
+        _ => {
+            let index = rebuild(conn, &path, embedding_model, query.len())?;
+            slot.insert(index)
+        }
+    };
+
+    let ef = MIN_EF_SEARCH.max(limit * 4);
+    Ok(index.graph.search(query, limit, ef))
+}
+
+/// Mirrors a stored vector into the loaded index. Rows of another model or
+/// dimension leave it, since the index no longer covers them; so do rows
+/// without a model id, which stay stale until `reembed_memories` tags them.
+pub(crate) fn record_upsert(message_id: i64, embedding_model: &str, vector: &[f32]) {
+    if let Ok(mut slot) = lock() {
+        if let Some(index) = slot.as_mut() {
+            if index.covers(embedding_model, vector.len()) {
+                index.upsert(message_id, vector);
+            } else {
+                index.remove(message_id);
+            }
+            index.maintain();
+        }
+    }
+}
+
+pub(crate) fn record_delete(message_id: i64) {
+    if let Ok(mut slot) = lock() {
+        if let Some(index) = slot.as_mut() {
+            index.remove(message_id);
+            index.maintain();
+        }
+    }
+}
+
+/// Writes pending changes to disk, e.g. before the app closes.
+pub fn flush() {
+    if let Ok(mut slot) = lock() {
+        if let Some(index) = slot.as_mut().filter(|index| index.unsaved_changes > 0) {
+            index.save();
+        }
+    }
+}
+
+/// Drops the loaded index, e.g. after the database file changed.
+pub(crate) fn invalidate() {
+    if let Ok(mut slot) = lock() {
+        *slot = None;
+    }
+}
+
+fn rebuild(
+    conn: &Connection,
+    path: &Path,
+    embedding_model: &str,
+    dim: usize,
+) -> Result<MemoryIndex, AnimaError> {
+    let mut statement = conn
+        .prepare(
+            "SELECT message_id, embedding FROM memories
+             WHERE embedding_dim = ?1 AND embedding_model = ?2
+             ORDER BY message_id",
+        )
+        .map_err(AnimaError::from)?;
+    let rows = statement
+        .query_map(params![dim as i64, embedding_model], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })
+        .map_err(AnimaError::from)?;
+
+    let mut graph = Hnsw::new(dim);
+    let mut nodes_by_message = HashMap::new();
+    let mut unindexable = HashSet::new();
+    for row in rows {
+        let (message_id, blob) = row.map_err(AnimaError::from)?;
+        let vector = blob
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect::<Vec<_>>();
+        if let Some(node) = graph.insert(message_id, &vector) {
+            nodes_by_message.insert(message_id, node);
+        } else {
+            unindexable.insert(message_id);
+        }
+    }
+    eprintln!(
+        "[memory_index] rebuilt for {embedding_model} ({} memories, dim {dim})",
+        nodes_by_message.len()
+    );
+
+    let mut index = MemoryIndex {
+        model: embedding_model.to_string(),
+        graph,
+        nodes_by_message,
+        unindexable,
+        unsaved_changes: 0,
+        path: path.to_path_buf(),
+    };
+    index.save();
+    Ok(index)
+}
+
+fn sql_fingerprint(
+    conn: &Connection,
+    embedding_model: &str,
+    dim: usize,
+) -> Result<Fingerprint, AnimaError> {
+    // Answered from `idx_memories_embedding_model`, which holds the row ids,
+    // without touching the vector blobs.
+    conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(message_id), 0), COALESCE(MAX(message_id), 0)
+         FROM memories
+         WHERE embedding_dim = ?1 AND embedding_model = ?2",
+        params![dim as i64, embedding_model],
+        |row| {
+            Ok(Fingerprint {
+                count: row.get(0)?,
+                id_sum: row.get(1)?,
+                max_id: row.get(2)?,
+            })
+        },
+    )
+    .map_err(AnimaError::from)
+}
+
+fn index_path(db_path: &Path) -> PathBuf {
+    let mut path = db_path.as_os_str().to_owned();
+    path.push(".hnsw");
+    PathBuf::from(path)
+}
+
+fn lock() -> Result<MutexGuard<'static, Option<MemoryIndex>>, AnimaError> {
+    INDEX
+        .lock()
+        .map_err(|error| AnimaError::Internal(format!("Memory index mutex is poisoned: {error}")))
+}
+
+fn write_index_file(path: &Path, index: &MemoryIndex) -> std::io::Result<()> {
+    // Written beside the target and renamed, so a crash never leaves half a file.
+    let temp_path = path.with_extension("hnsw.tmp");
+    {
+        let mut writer = BufWriter::new(File::create(&temp_path)?);
+        writer.write_all(INDEX_MAGIC)?;
+        write_bytes(&mut writer, index.model.as_bytes())?;
+        write_u64(&mut writer, index.unindexable.len() as u64)?;
+        for message_id in &index.unindexable {
+            writer.write_all(&message_id.to_le_bytes())?;
+        }
+        index.graph.write_to(&mut writer)?;
+        writer.flush()?;
+    }
+    fs::rename(&temp_path, path)
+}
+
+/// `None` when there is no index file yet.
+fn load_index_file(path: &Path) -> Result<Option<MemoryIndex>, AnimaError> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(AnimaError::Io(format!("Memory index open failed: {error}"))),
+    };
+    let corrupt = |error: std::io::Error| {
+        AnimaError::Io(format!(
+            "Memory index {} is unreadable: {error}",
+            path.display()
+        ))
+    };
+
+    let mut reader = BufReader::new(file);
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic).map_err(corrupt)?;
+    if &magic != INDEX_MAGIC {
+        return Err(corrupt(std::io::ErrorKind::InvalidData.into()));
+    }
+    let model = String::from_utf8(read_bytes(&mut reader).map_err(corrupt)?)
+        .map_err(|_| corrupt(std::io::ErrorKind::InvalidData.into()))?;
+    let unindexable_count = read_len(&mut reader, u32::MAX as usize).map_err(corrupt)?;
+    let mut unindexable = HashSet::new();
+    for _ in 0..unindexable_count {
+        unindexable.insert(read_u64(&mut reader).map_err(corrupt)? as i64);
+    }
+    let graph = Hnsw::read_from(&mut reader).map_err(corrupt)?;
+
+    Ok(Some(MemoryIndex {
+        model,
+        nodes_by_message: graph.live_nodes().collect(),
+        unindexable,
+        graph,
+        unsaved_changes: 0,
+        path: path.to_path_buf(),
+    }))
+}
+
+struct Node {
+    message_id: i64,
+    /// Unit length, so cosine similarity is a dot product.
+    vector: Vec<f32>,
+    /// `links[layer]` for every layer up to the node's level.
+    links: Vec<Vec<u32>>,
+    deleted: bool,
+}
+
+/// Hierarchical navigable small world graph (Malkov & Yashunin) with cosine
+/// distance. Deletion only marks nodes, which keep routing searches.
+pub(crate) struct Hnsw {
+    dim: usize,
+    nodes: Vec<Node>,
+    entry_point: Option<u32>,
+    deleted: usize,
+    rng_state: u64,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f32,
+    node: u32,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.node.cmp(&other.node))
+    }
+}
+
+impl Hnsw {
+    pub fn new(dim: usize) -> Self {
+        Self {
+            dim,
+            nodes: Vec::new(),
+            entry_point: None,
+            deleted: 0,
+            rng_state: 0x9e37_79b9_7f4a_7c15,
+        }
+    }
+
+    /// Adds a vector; `None` when it has the wrong dimension or no direction.
+    pub fn insert(&mut self, message_id: i64, vector: &[f32]) -> Option<u32> {
+        let vector = normalized(vector, self.dim)?;
+        let node = u32::try_from(self.nodes.len()).ok()?;
+        let level = self.random_level();
+        self.nodes.push(Node {
+            message_id,
+            vector,
+            links: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+
+        let Some(entry_point) = self.entry_point else {
+            self.entry_point = Some(node);
+            return Some(node);
+        };
+        let top_level = self.level(entry_point);
+        let query = self.nodes[node as usize].vector.clone();
+
+        let mut entry = self.candidate(&query, entry_point);
+        for layer in (level + 1..=top_level).rev() {
+            entry = self.greedy_closest(&query, entry, layer);
+        }
+        let mut entries = vec![entry];
+        for layer in (0..=level.min(top_level)).rev() {
+            let found = self.search_layer(&query, &entries, EF_CONSTRUCTION, layer);
+            let neighbors = self.select_neighbors(&found, MAX_NEIGHBORS);
+            self.nodes[node as usize].links[layer] = neighbors.clone();
+            for neighbor in neighbors {
+                self.connect(neighbor, node, layer);
+            }
+            entries = found;
+        }
+
+        if level > top_level {
+            self.entry_point = Some(node);
+        }
+        Some(node)
+    }
+
+    pub fn mark_deleted(&mut self, node: u32) {
+        if let Some(node) = self.nodes.get_mut(node as usize) {
+            if !node.deleted {
+                node.deleted = true;
+                self.deleted += 1;
+            }
+        }
+    }
+
+    /// Up to `limit` live (message id, cosine similarity) pairs, best first.
+    pub fn search(&self, query: &[f32], limit: usize, ef: usize) -> Vec<(i64, f32)> {
+        let (Some(query), Some(entry_point)) = (normalized(query, self.dim), self.entry_point)
+        else {
+            return Vec::new();
+        };
+        let mut entry = self.candidate(&query, entry_point);
+        for layer in (1..=self.level(entry_point)).rev() {
+            entry = self.greedy_closest(&query, entry, layer);
+        }
+        // Deleted nodes fill part of the beam, so it widens with their share.
+        let ef = ef.max(limit) + self.deleted.min(ef);
+        self.search_layer(&query, &[entry], ef, 0)
+            .into_iter()
+            .filter(|candidate| !self.nodes[candidate.node as usize].deleted)
+            .take(limit)
+            .map(|candidate| {
+                (
+                    self.nodes[candidate.node as usize].message_id,
+                    1.0 - candidate.distance,
+                )
+            })
+            .collect()
+    }
+
+    fn deleted_ratio(&self) -> f32 {
+        if self.nodes.is_empty() {
+            return 0.0;
+        }
+        self.deleted as f32 / self.nodes.len() as f32
+    }
+
+    fn live_nodes(&self) -> impl Iterator<Item = (i64, u32)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| !node.deleted)
+            .map(|(index, node)| (node.message_id, index as u32))
+    }
+
+    /// Same live vectors in a fresh graph without deleted nodes.
+    fn compacted(&self) -> Self {
+        let mut graph = Hnsw::new(self.dim);
+        for node in self.nodes.iter().filter(|node| !node.deleted) {
+            graph.insert(node.message_id, &node.vector);
+        }
+        graph
+    }
+
+    fn level(&self, node: u32) -> usize {
+        self.nodes[node as usize].links.len() - 1
+    }
+
+    fn candidate(&self, query: &[f32], node: u32) -> Candidate {
+        Candidate {
+            distance: 1.0 - dot(query, &self.nodes[node as usize].vector),
+            node,
+        }
+    }
+
+    fn greedy_closest(&self, query: &[f32], mut best: Candidate, layer: usize) -> Candidate {
+        loop {
+            let mut improved = false;
+            for &neighbor in &self.nodes[best.node as usize].links[layer] {
+                let candidate = self.candidate(query, neighbor);
+                if candidate < best {
+                    best = candidate;
+                    improved = true;
+                }
+            }
+            if !improved {
+                return best;
+            }
+        }
+    }
+
+    /// Beam search on one layer; the `ef` closest nodes found, nearest first.
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entries: &[Candidate],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited = entries
+            .iter()
+            .map(|entry| entry.node)
+            .collect::<HashSet<_>>();
+        let mut to_visit = entries
+            .iter()
+            .map(|entry| Reverse(*entry))
+            .collect::<BinaryHeap<_>>();
+        let mut found = entries.iter().copied().collect::<BinaryHeap<_>>();
+        while found.len() > ef {
+            found.pop();
+        }
+
+        while let Some(Reverse(current)) = to_visit.pop() {
+            if found.len() >= ef && found.peek().is_some_and(|worst| current > *worst) {
+                break;
+            }
+            for &neighbor in &self.nodes[current.node as usize].links[layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let candidate = self.candidate(query, neighbor);
+                if found.len() < ef || found.peek().is_some_and(|worst| candidate < *worst) {
+                    to_visit.push(Reverse(candidate));
+                    found.push(candidate);
+                    if found.len() > ef {
+                        found.pop();
+                    }
+                }
+            }
+        }
+        found.into_sorted_vec()
+    }
+
+    /// The paper's heuristic: a candidate is kept unless an already kept
+    /// neighbour is closer to it than the base is, which keeps links spread
+    /// across clusters. Pruned candidates fill any remaining slots.
+    fn select_neighbors(&self, candidates: &[Candidate], max: usize) -> Vec<u32> {
+        let mut selected = Vec::<Candidate>::with_capacity(max);
+        let mut pruned = Vec::new();
+        for &candidate in candidates {
+            if selected.len() >= max {
+                break;
+            }
+            let vector = &self.nodes[candidate.node as usize].vector;
+            let dominated = selected.iter().any(|kept| {
+                1.0 - dot(vector, &self.nodes[kept.node as usize].vector) < candidate.distance
+            });
+            if dominated {
+                pruned.push(candidate);
+            } else {
+                selected.push(candidate);
+            }
+        }
+        selected
+            .into_iter()
+            .chain(pruned)
+            .take(max)
+            .map(|candidate| candidate.node)
+            .collect()
+    }
+
+    fn connect(&mut self, from: u32, to: u32, layer: usize) {
+        let max = if layer == 0 {
+            MAX_NEIGHBORS * 2
+        } else {
+            MAX_NEIGHBORS
+        };
+        let links = &mut self.nodes[from as usize].links[layer];
+        links.push(to);
+        if links.len() <= max {
+            return;
+        }
+
+        let base = self.nodes[from as usize].vector.clone();
+        let mut candidates = self.nodes[from as usize].links[layer]
+            .iter()
+            .map(|&node| self.candidate(&base, node))
+            .collect::<Vec<_>>();
+        candidates.sort();
+        self.nodes[from as usize].links[layer] = self.select_neighbors(&candidates, max);
+    }
+
+    /// Exponentially distributed level with the usual `1 / ln(M)` scale,
+    /// from a fixed-seed generator so builds are reproducible.
+    fn random_level(&mut self) -> usize {
+        self.rng_state ^= self.rng_state << 13;
+        self.rng_state ^= self.rng_state >> 7;
+        self.rng_state ^= self.rng_state << 17;
+        let uniform = ((self.rng_state >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let level = -uniform.ln() / (MAX_NEIGHBORS as f64).ln();
+        (level as usize).min(16)
+    }
+
+    fn write_to(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        write_u64(writer, self.dim as u64)?;
+        write_u64(writer, self.rng_state)?;
+        write_u64(writer, self.entry_point.map_or(u64::MAX, u64::from))?;
+        write_u64(writer, self.nodes.len() as u64)?;
+        for node in &self.nodes {
+            writer.write_all(&node.message_id.to_le_bytes())?;
+            writer.write_all(&[u8::from(node.deleted)])?;
+            for value in &node.vector {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+            write_u64(writer, node.links.len() as u64)?;
+            for links in &node.links {
+                write_u64(writer, links.len() as u64)?;
+                for link in links {
+                    writer.write_all(&link.to_le_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_from(reader: &mut impl Read) -> std::io::Result<Self> {
+        let invalid = || std::io::Error::from(std::io::ErrorKind::InvalidData);
+        let dim = read_len(reader, 1 << 16)?;
+        let rng_state = read_u64(reader)?;
+        let entry_point = match read_u64(reader)? {
+            u64::MAX => None,
+            node => Some(u32::try_from(node).map_err(|_| invalid())?),
+        };
+        let node_count = read_len(reader, u32::MAX as usize)?;
+
+        let mut nodes = Vec::with_capacity(node_count.min(1 << 20));
+        let mut deleted = 0;
+        for _ in 0..node_count {
+            let mut id_bytes = [0u8; 8];
+            reader.read_exact(&mut id_bytes)?;
+            let mut flag = [0u8; 1];
+            reader.read_exact(&mut flag)?;
+            let mut vector = Vec::with_capacity(dim);
+            for _ in 0..dim {
+                let mut value = [0u8; 4];
+                reader.read_exact(&mut value)?;
+                vector.push(f32::from_le_bytes(value));
+            }
+            let layers = read_len(reader, 64)?;
+            let mut links = Vec::with_capacity(layers);
+            for _ in 0..layers {
+                let count = read_len(reader, MAX_NEIGHBORS * 2 + 1)?;
+                let mut layer = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let mut link = [0u8; 4];
+                    reader.read_exact(&mut link)?;
+                    let link = u32::from_le_bytes(link);
+                    if link as usize >= node_count {
+                        return Err(invalid());
+                    }
+                    layer.push(link);
+                }
+                links.push(layer);
+            }
+            if links.is_empty() {
+                return Err(invalid());
+            }
+            deleted += usize::from(flag[0] != 0);
+            nodes.push(Node {
+                message_id: i64::from_le_bytes(id_bytes),
+                vector,
+                links,
+                deleted: flag[0] != 0,
+            });
+        }
+        if entry_point.is_some_and(|node| node as usize >= nodes.len()) {
+            return Err(invalid());
+        }
+        // Links into a layer the target does not have would panic later.
+        for node in &nodes {
+            for (layer, links) in node.links.iter().enumerate() {
+                if links
+                    .iter()
+                    .any(|&link| nodes[link as usize].links.len() <= layer)
+                {
+                    return Err(invalid());
+                }
+            }
+        }
+
+        Ok(Self {
+            dim,
+            nodes,
+            entry_point,
+            deleted,
+            rng_state,
+        })
+    }
+}
+
+fn normalized(vector: &[f32], dim: usize) -> Option<Vec<f32>> {
+    if vector.len() != dim || dim == 0 {
+        return None;
+    }
+    let norm = dot(vector, vector).sqrt();
+    if !norm.is_finite() || norm <= f32::EPSILON {
+        return None;
+    }
+    Some(vector.iter().map(|value| value / norm).collect())
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn write_u64(writer: &mut impl Write, value: u64) -> std::io::Result<()> {
+    writer.write_all(&value.to_le_bytes())
+}
+
+fn write_bytes(writer: &mut impl Write, bytes: &[u8]) -> std::io::Result<()> {
+    write_u64(writer, bytes.len() as u64)?;
+    writer.write_all(bytes)
+}
+
+fn read_u64(reader: &mut impl Read) -> std::io::Result<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// A length field, rejected above `max` so a corrupt file cannot trigger a
+/// huge allocation.
+fn read_len(reader: &mut impl Read, max: usize) -> std::io::Result<usize> {
+    usize::try_from(read_u64(reader)?)
+        .ok()
+        .filter(|len| *len <= max)
+        .ok_or_else(|| std::io::ErrorKind::InvalidData.into())
+}
+
+fn read_bytes(reader: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let len = read_len(reader, 4096)?;
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn random_vectors(count: usize, dim: usize, seed: u64) -> Vec<Vec<f32>> {
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 40) as f32 / (1u64 << 24) as f32 - 0.5
+        };
+        (0..count)
+            .map(|_| (0..dim).map(|_| next()).collect())
+            .collect()
+    }
+
+    /// Noisy points around a few dozen centres. Real embeddings cluster by
+    /// topic; uniform noise in hundreds of dimensions has no near neighbours
+    /// worth finding.
+    fn clustered_vectors(count: usize, dim: usize, seed: u64) -> Vec<Vec<f32>> {
+        let centres = random_vectors(48, dim, 1);
+        random_vectors(count, dim, seed)
+            .into_iter()
+            .enumerate()
+            .map(|(index, noise)| {
+                let centre = &centres[index * 7919 % centres.len()];
+                centre.iter().zip(noise).map(|(c, n)| c + 0.6 * n).collect()
+            })
+            .collect()
+    }
+
+    fn brute_force(vectors: &[Vec<f32>], query: &[f32], limit: usize) -> Vec<i64> {
+        let query = normalized(query, query.len()).unwrap();
+        let mut scored = vectors
+            .iter()
+            .enumerate()
+            .map(|(id, vector)| {
+                (
+                    id as i64,
+                    dot(&query, &normalized(vector, vector.len()).unwrap()),
+                )
+            })
+            .collect::<Vec<_>>();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().take(limit).map(|(id, _)| id).collect()
+    }
+
+    /// Recall@k of the graph against the exact scan it replaces, with the
+    /// mean query time of both, for each beam width in `efs`.
+    fn measure_recall(
+        count: usize,
+        dim: usize,
+        limit: usize,
+        efs: &[usize],
+    ) -> Vec<(usize, f32, f64, f64)> {
+        let vectors = clustered_vectors(count, dim, 7);
+        let mut graph = Hnsw::new(dim);
+        for (id, vector) in vectors.iter().enumerate() {
+            graph.insert(id as i64, vector);
+        }
+
+        let queries = clustered_vectors(100, dim, 99);
+        let mut exact_time = 0.0;
+        let expected = queries
+            .iter()
+            .map(|query| {
+                let started = Instant::now();
+                let ids = brute_force(&vectors, query, limit);
+                exact_time += started.elapsed().as_secs_f64();
+                ids
+            })
+            .collect::<Vec<_>>();
+        let exact_time = exact_time / queries.len() as f64;
+
+        efs.iter()
+            .map(|&ef| {
+                let (mut hits, mut graph_time) = (0, 0.0);
+                for (query, expected) in queries.iter().zip(&expected) {
+                    let started = Instant::now();
+                    let found = graph.search(query, limit, ef);
+                    graph_time += started.elapsed().as_secs_f64();
+                    hits += found.iter().filter(|(id, _)| expected.contains(id)).count();
+                }
+                let recall = hits as f32 / (queries.len() * limit) as f32;
+                (ef, recall, exact_time, graph_time / queries.len() as f64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recall_against_brute_force() {
+        let (_, recall, _, _) = measure_recall(1500, 32, 10, &[MIN_EF_SEARCH])[0];
+        assert!(recall >= 0.9, "recall@10 = {recall}");
+    }
+
+    /// `cargo test --release memory_index -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_against_brute_force() {
+        for count in [10_000, 30_000] {
+            for (ef, recall, exact, graph) in measure_recall(count, 384, 10, &[64, 128, 256]) {
+                println!(
+                    "n={count} dim=384 ef={ef} recall@10={recall:.3} exact={:.3}ms hnsw={:.3}ms",
+                    exact * 1e3,
+                    graph * 1e3
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_deleted_nodes_are_skipped_and_survive_round_trip() {
+        let vectors = random_vectors(200, 8, 3);
+        let mut graph = Hnsw::new(8);
+        for (id, vector) in vectors.iter().enumerate() {
+            graph.insert(id as i64, vector);
+        }
+        graph.mark_deleted(0);
+        assert!(graph
+            .search(&vectors[0], 5, 32)
+            .iter()
+            .all(|(id, _)| *id != 0));
+
+        let mut bytes = Vec::new();
+        graph.write_to(&mut bytes).unwrap();
+        let restored = Hnsw::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(
+            restored.search(&vectors[5], 3, 32),
+            graph.search(&vectors[5], 3, 32)
+        );
+        assert_eq!(restored.live_nodes().count(), 199);
+
+        let compacted = restored.compacted();
+        assert_eq!((compacted.nodes.len(), compacted.deleted), (199, 0));
+        assert_eq!(compacted.search(&vectors[5], 1, 32)[0].0, 5);
+    }
+}
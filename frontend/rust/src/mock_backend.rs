@@ -0,0 +1,300 @@
+use crate::ai::{Embedding, Generation, StopReason};
+use crate::backend::{GenerationRequest, InferenceBackend};
+use crate::cancellation::CancellationToken;
+use crate::chat_template::ChatTemplate;
+use crate::error::AnimaError;
+use crate::job_queue::JobPriority;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Instant;
+
+pub const MOCK_EMBEDDING_DIM: usize = 64;
+const MOCK_CONTEXT_WINDOW: usize = 8192;
+const DEFAULT_MOCK_RESPONSE: &str = "Mock response.";
+
+/// Scripted backend for running the pipeline without model files. Replies
+/// come from a queue of canned responses, streamed word by word and left for
+/// `ai` to filter like any backend's; embeddings are hashed bags of words,
+/// so texts sharing words are similar and every run gives the same vectors.
+pub struct MockBackend {
+    template: ChatTemplate,
+    responses: Mutex<VecDeque<String>>,
+    requests: Mutex<Vec<GenerationRequest>>,
+    embedding_model_id: Mutex<String>,
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockBackend {
+    pub fn new() -> Self {
+        Self {
+            template: ChatTemplate::ChatMl,
+            responses: Mutex::new(VecDeque::new()),
+            requests: Mutex::new(Vec::new()),
+            embedding_model_id: Mutex::new(format!("mock:hash-{MOCK_EMBEDDING_DIM}")),
+        }
+    }
+
+    /// Queues the reply for the next generation. With the queue empty the
+    /// backend answers `DEFAULT_MOCK_RESPONSE`.
+    pub fn push_response(&self, response: impl Into<String>) {
+        if let Ok(mut responses) = self.responses.lock() {
+            responses.push_back(response.into());
+        }
+    }
+
+    /// Every request generated so far, oldest first.
+    pub fn requests(&self) -> Vec<GenerationRequest> {
+        self.requests
+            .lock()
+            .map(|requests| requests.clone())
+            .unwrap_or_default()
+    }
+
+    /// Reports a different embedding model from now on, as if the user
+    /// switched models; the vectors themselves do not change.
+    pub fn set_embedding_model_id(&self, model_id: impl Into<String>) {
+        if let Ok(mut current) = self.embedding_model_id.lock() {
+            *current = model_id.into();
+        }
+    }
+
+    fn next_response(&self) -> String {
+        self.responses
+            .lock()
+            .ok()
+            .and_then(|mut responses| responses.pop_front())
+            .unwrap_or_else(|| DEFAULT_MOCK_RESPONSE.to_string())
+    }
+}
+
+impl InferenceBackend for MockBackend {
+    fn name(&self) -> String {
+        "mock".to_string()
+    }
+
+    fn chat_template(&self) -> Result<ChatTemplate, AnimaError> {
+        Ok(self.template)
+    }
+
+    fn context_window(&self) -> Result<usize, AnimaError> {
+        Ok(MOCK_CONTEXT_WINDOW)
+    }
+
+    fn generate_stream(
+        &self,
+        request: &GenerationRequest,
+        cancellation: &CancellationToken,
+        on_chunk: &mut dyn FnMut(&str) -> Result<(), AnimaError>,
+    ) -> Result<Generation, AnimaError> {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.push(request.clone());
+        }
+        let response = self.next_response();
+        let prompt = self.template.render(&request.system_prompt, &request.turns);
+        let started = Instant::now();
+
+        let mut text = String::new();
+        let mut stop_reason = StopReason::EndOfTurn;
+        let mut completion_tokens = 0u32;
+        for piece in response.split_inclusive(' ') {
+            if cancellation.is_cancelled() {
+                stop_reason = StopReason::Cancelled;
+                break;
+            }
+            if completion_tokens >= request.max_tokens {
+                stop_reason = StopReason::MaxTokens;
+                break;
+            }
+            completion_tokens += 1;
+            on_chunk(piece)?;
+            text.push_str(piece);
+        }
+
+        Ok(Generation {
+            text,
+            stop_reason,
+            prompt_tokens: u32::try_from(self.count_tokens(&prompt)?).unwrap_or(u32::MAX),
+            completion_tokens,
+            decode_duration: started.elapsed(),
+        })
+    }
+
+    fn embedding_model_id(&self) -> Result<String, AnimaError> {
+        self.embedding_model_id
+            .lock()
+            .map(|model_id| model_id.clone())
+            .map_err(|_| AnimaError::Internal("Mock embedding model lock poisoned".to_string()))
+    }
+
+    fn embed(&self, texts: &[&str], _priority: JobPriority) -> Result<Vec<Embedding>, AnimaError> {
+        let model_id = self.embedding_model_id()?;
+        Ok(texts
+            .iter()
+            .map(|text| Embedding {
+                model_id: model_id.clone(),
+                vector: hashed_embedding(text),
+            })
+            .collect())
+    }
+
+    fn tokenize(&self, text: &str) -> Result<Vec<i32>, AnimaError> {
+        Ok(text
+            .split_whitespace()
+            .map(|word| (fnv1a(word) & 0x7fff_ffff) as i32)
+            .collect())
+    }
+}
+
+/// L2-normalized bag of lowercase words; empty for text without words, like
+/// the llama.cpp embedder for text without tokens.
+fn hashed_embedding(text: &str) -> Vec<f32> {
+    let mut vector = vec![0.0f32; MOCK_EMBEDDING_DIM];
+    let mut words = 0;
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let bucket = (fnv1a(&word.to_lowercase()) % MOCK_EMBEDDING_DIM as u64) as usize;
+        vector[bucket] += 1.0;
+        words += 1;
+    }
+    if words == 0 {
+        return Vec::new();
+    }
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    vector.iter_mut().for_each(|value| *value /= norm);
+    vector
+}
+
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Isolated app state for tests that run the real pipeline on the mock.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::MockBackend;
+    use crate::{backend, db};
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex, MutexGuard};
+
+    static SESSION_LOCK: Mutex<()> = Mutex::new(());
+    static NEXT_DB: AtomicUsize = AtomicUsize::new(0);
+
+    /// Holds the process-wide database and backend for one test; they are
+    /// shared globals, so sessions run one at a time.
+    pub(crate) struct MockSession {
+        pub backend: Arc<MockBackend>,
+        db_path: PathBuf,
+        _lock: MutexGuard<'static, ()>,
+    }
+
+    impl MockSession {
+        pub fn start() -> Self {
+            let lock = SESSION_LOCK
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let db_path = std::env::temp_dir().join(format!(
+                "anima_test_{}_{}.db",
+                std::process::id(),
+                NEXT_DB.fetch_add(1, Ordering::SeqCst)
+            ));
+            let _ = std::fs::remove_file(&db_path);
+            db::set_db_path(&db_path).expect("create test database");
+
+            let backend = Arc::new(MockBackend::new());
+            backend::set_override(Some(backend.clone()));
+            Self {
+                backend,
+                db_path,
+                _lock: lock,
+            }
+        }
+    }
+
+    impl Drop for MockSession {
+        fn drop(&mut self) {
+            backend::set_override(None);
+            let _ = std::fs::remove_file(&self.db_path);
+            let mut index_path = self.db_path.clone().into_os_string();
+            index_path.push(".hnsw");
+            let _ = std::fs::remove_file(index_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat_template::ChatTurn;
+    use crate::db::SamplingParams;
+
+    fn request(max_tokens: u32) -> GenerationRequest {
+        GenerationRequest {
+            system_prompt: "You are a test.".to_string(),
+            turns: vec![ChatTurn::user("hola")],
+            sampling: SamplingParams::default(),
+            max_tokens,
+            priority: JobPriority::Interactive,
+            response_schema: None,
+        }
+    }
+
+    #[test]
+    fn test_embeddings_are_deterministic_and_word_based() {
+        let backend = MockBackend::new();
+        let texts = [
+            "I love hiking in the mountains",
+            "hiking in the mountains",
+            "tax forms",
+            "",
+        ];
+        let first = backend.embed(&texts, JobPriority::Interactive).unwrap();
+        let second = backend.embed(&texts, JobPriority::Interactive).unwrap();
+        assert_eq!(first[0].vector, second[0].vector);
+        assert_eq!(first[0].vector.len(), MOCK_EMBEDDING_DIM);
+        assert!(first[3].vector.is_empty());
+
+        let dot = |a: &[f32], b: &[f32]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>();
+        let related = dot(&first[0].vector, &first[1].vector);
+        let unrelated = dot(&first[0].vector, &first[2].vector);
+        assert!(
+            related > 0.7 && related > unrelated,
+            "{related} vs {unrelated}"
+        );
+    }
+
+    #[test]
+    fn test_stream_relays_words_until_max_tokens() {
+        let backend = MockBackend::new();
+        backend.push_response("Hola amigo<|im_end|> leaked");
+        backend.push_response("one two three four");
+
+        let mut chunks = Vec::new();
+        let mut on_chunk = |chunk: &str| {
+            chunks.push(chunk.to_string());
+            Ok(())
+        };
+        let full = backend
+            .generate_stream(&request(100), &CancellationToken::new(), &mut on_chunk)
+            .unwrap();
+        assert_eq!(full.stop_reason, StopReason::EndOfTurn);
+        assert_eq!(full.text, "Hola amigo<|im_end|> leaked");
+        assert_eq!(chunks, ["Hola ", "amigo<|im_end|> ", "leaked"]);
+
+        let truncated = backend
+            .generate_stream(&request(2), &CancellationToken::new(), &mut |_| Ok(()))
+            .unwrap();
+        assert_eq!(truncated.stop_reason, StopReason::MaxTokens);
+        assert_eq!(truncated.text, "one two ");
+        assert_eq!(backend.requests().len(), 2);
+    }
+}
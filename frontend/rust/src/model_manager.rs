@@ -0,0 +1,636 @@
+use crate::ai::{self, ChatRuntime, EmbeddingRuntime, RerankTokens, RerankerRuntime};
+use crate::cancellation::CancellationToken;
+use crate::chat_template::ChatTemplate;
+use crate::error::AnimaError;
+use crate::gguf::GgufHeader;
+use crate::job_queue::{JobPriority, JobQueue, JobTicket, QueuedJob};
+use crate::load_strategy::{LoadPlan, LoadStrategy};
+use crate::model_validation;
+use crate::runtime_config::{self, ContextConfig, ModelLimits};
+use llama_cpp_2::model::LlamaModel;
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::thread::{self, JoinHandle};
+
+static MANAGER: OnceLock<Mutex<ModelManager>> = OnceLock::new();
+/// Serializes load/unload so two swaps never interleave.
+static SWAP_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelKind {
+    Chat,
+    Embedding,
+    /// Optional cross-encoder that reorders retrieved memories.
+    Reranker,
+}
+
+impl ModelKind {
+    fn label(self) -> &'static str {
+        match self {
+            ModelKind::Chat => "Chat",
+            ModelKind::Embedding => "Embedding",
+            ModelKind::Reranker => "Reranker",
+        }
+    }
+}
+
+/// Lifecycle of one model slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelState {
+    NotLoaded,
+    Loading {
+        path: String,
+    },
+    /// `load_strategy` is how the weights were actually loaded, never `Auto`.
+    Ready {
+        path: String,
+        load_strategy: LoadStrategy,
+    },
+    /// `code` is `AnimaError::code()` of the load failure.
+    Failed {
+        path: String,
+        code: String,
+        detail: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ModelStatus {
+    pub chat: ModelState,
+    pub embedding: ModelState,
+    pub reranker: ModelState,
+}
+
+/// Jobs waiting on or running in each worker, in pickup order.
+#[derive(Debug, Clone, Default)]
+pub struct InferenceQueueStatus {
+    pub chat: Vec<QueuedJob>,
+    pub embedding: Vec<QueuedJob>,
+    pub reranker: Vec<QueuedJob>,
+}
+
+pub(crate) type ChatJob = Box<dyn FnOnce(&mut ChatRuntime<'_>) + Send>;
+
+/// Keeps the chat model alive while held; jobs run on the chat worker thread,
+/// which owns the context, in priority order.
+#[derive(Clone)]
+pub(crate) struct ChatHandle {
+    pub model: Arc<LlamaModel>,
+    pub template: ChatTemplate,
+    pub context: ContextConfig,
+    jobs: Arc<JobQueue<ChatJob>>,
+}
+
+impl ChatHandle {
+    /// `cancellation` is cancelled if a background job is preempted.
+    pub fn submit(
+        &self,
+        priority: JobPriority,
+        cancellation: CancellationToken,
+        job: ChatJob,
+    ) -> Result<JobTicket, AnimaError> {
+        self.jobs
+            .push(priority, Some(cancellation), job)
+            .ok_or_else(|| AnimaError::ModelNotLoaded("Chat worker has shut down".to_string()))
+    }
+}
+
+pub(crate) type EmbeddingJob = Box<dyn FnOnce(&mut EmbeddingRuntime<'_>) + Send>;
+
+/// Jobs run on the embedding worker thread, which owns the model and a
+/// context reused across calls.
+#[derive(Clone)]
+pub(crate) struct EmbeddingHandle {
+    /// File name of the model; stored next to every vector it produces.
+    pub model_id: String,
+    jobs: Arc<JobQueue<EmbeddingJob>>,
+}
+
+impl EmbeddingHandle {
+    /// Embedding jobs are short, so they are ordered but never preempted.
+    pub fn submit(&self, priority: JobPriority, job: EmbeddingJob) -> Result<(), AnimaError> {
+        self.jobs
+            .push(priority, None, job)
+            .map(|_| ())
+            .ok_or_else(|| AnimaError::ModelNotLoaded("Embedding worker has shut down".to_string()))
+    }
+}
+
+pub(crate) type RerankJob = Box<dyn FnOnce(&mut RerankerRuntime<'_>) + Send>;
+
+/// Jobs run on the reranker worker thread, which owns the cross-encoder and
+/// its context.
+#[derive(Clone)]
+pub(crate) struct RerankerHandle {
+    jobs: Arc<JobQueue<RerankJob>>,
+}
+
+impl RerankerHandle {
+    pub fn submit(&self, priority: JobPriority, job: RerankJob) -> Result<(), AnimaError> {
+        self.jobs
+            .push(priority, None, job)
+            .map(|_| ())
+            .ok_or_else(|| AnimaError::ModelNotLoaded("Reranker worker has shut down".to_string()))
+    }
+}
+
+/// `released_bytes` is the RAM unloading the slot's model frees, counted as
+/// available when its replacement is planned.
+struct ChatSlot {
+    handle: ChatHandle,
+    worker: JoinHandle<()>,
+    released_bytes: u64,
+}
+
+struct EmbeddingSlot {
+    handle: EmbeddingHandle,
+    worker: JoinHandle<()>,
+    released_bytes: u64,
+}
+
+struct RerankerSlot {
+    handle: RerankerHandle,
+    worker: JoinHandle<()>,
+    released_bytes: u64,
+}
+
+struct ModelManager {
+    chat_state: ModelState,
+    chat: Option<ChatSlot>,
+    embedding_state: ModelState,
+    embedding: Option<EmbeddingSlot>,
+    reranker_state: ModelState,
+    reranker: Option<RerankerSlot>,
+}
+
+impl ModelManager {
+    fn state(&self, kind: ModelKind) -> &ModelState {
+        match kind {
+            ModelKind::Chat => &self.chat_state,
+            ModelKind::Embedding => &self.embedding_state,
+            ModelKind::Reranker => &self.reranker_state,
+        }
+    }
+
+    fn state_mut(&mut self, kind: ModelKind) -> &mut ModelState {
+        match kind {
+            ModelKind::Chat => &mut self.chat_state,
+            ModelKind::Embedding => &mut self.embedding_state,
+            ModelKind::Reranker => &mut self.reranker_state,
+        }
+    }
+
+    fn released_bytes(&self, kind: ModelKind) -> u64 {
+        let released = match kind {
+            ModelKind::Chat => self.chat.as_ref().map(|slot| slot.released_bytes),
+            ModelKind::Embedding => self.embedding.as_ref().map(|slot| slot.released_bytes),
+            ModelKind::Reranker => self.reranker.as_ref().map(|slot| slot.released_bytes),
+        };
+        released.unwrap_or(0)
+    }
+}
+
+pub fn status() -> ModelStatus {
+    match manager() {
+        Ok(manager) => ModelStatus {
+            chat: manager.chat_state.clone(),
+            embedding: manager.embedding_state.clone(),
+            reranker: manager.reranker_state.clone(),
+        },
+        Err(error) => {
+            let failed = ModelState::Failed {
+                path: String::new(),
+                code: error.code().to_string(),
+                detail: error.detail().to_string(),
+            };
+            ModelStatus {
+                chat: failed.clone(),
+                embedding: failed.clone(),
+                reranker: failed,
+            }
+        }
+    }
+}
+
+/// Loads `path` into the slot, unloading whatever was there first so two
+/// models never sit in RAM at once. Loading the path that is already ready is
+/// a no-op; use `reload` to force it.
+pub fn load(kind: ModelKind, path: &str) -> Result<(), AnimaError> {
+    let _swap = lock_swap()?;
+    let already_ready = matches!(
+        manager()?.state(kind),
+        ModelState::Ready { path: ready_path, .. } if ready_path == path
+    );
+    if already_ready {
+        return Ok(());
+    }
+    swap_in(kind, path)
+}
+
+/// Unloads and loads again from the same path, e.g. after the file changed.
+pub fn reload(kind: ModelKind) -> Result<(), AnimaError> {
+    let _swap = lock_swap()?;
+    let path = match manager()?.state(kind) {
+        ModelState::Ready { path, .. }
+        | ModelState::Loading { path }
+        | ModelState::Failed { path, .. } => path.clone(),
+        ModelState::NotLoaded => String::new(),
+    };
+    if path.is_empty() {
+        return Err(AnimaError::ModelNotLoaded(format!(
+            "{} model has never been loaded",
+            kind.label()
+        )));
+    }
+    swap_in(kind, &path)
+}
+
+/// Cancels running generations and frees the model. Returns once the chat
+/// worker has exited.
+pub fn unload(kind: ModelKind) -> Result<(), AnimaError> {
+    let _swap = lock_swap()?;
+    release(kind)
+}
+
+/// Records a failure that happened before the model could be handed to
+/// `load`, such as an unresolvable path. A loaded model keeps its state.
+pub fn record_failure(kind: ModelKind, path: &str, error: &AnimaError) {
+    if let Ok(mut manager) = manager() {
+        let state = manager.state_mut(kind);
+        if matches!(state, ModelState::NotLoaded | ModelState::Failed { .. }) {
+            *state = failed_state(path, error);
+        }
+    }
+}
+
+pub(crate) fn chat_handle() -> Result<ChatHandle, AnimaError> {
+    let manager = manager()?;
+    match &manager.chat {
+        Some(slot) => Ok(slot.handle.clone()),
+        None => Err(not_ready_error(ModelKind::Chat, &manager.chat_state)),
+    }
+}
+
+pub(crate) fn embedding_handle() -> Result<EmbeddingHandle, AnimaError> {
+    let manager = manager()?;
+    match &manager.embedding {
+        Some(slot) => Ok(slot.handle.clone()),
+        None => Err(not_ready_error(
+            ModelKind::Embedding,
+            &manager.embedding_state,
+        )),
+    }
+}
+
+/// `None` when no reranker is loaded; retrieval then keeps its own order.
+pub(crate) fn reranker_handle() -> Option<RerankerHandle> {
+    let manager = manager().ok()?;
+    manager.reranker.as_ref().map(|slot| slot.handle.clone())
+}
+
+pub fn queue_status() -> InferenceQueueStatus {
+    let Ok(manager) = manager() else {
+        return InferenceQueueStatus::default();
+    };
+    InferenceQueueStatus {
+        chat: manager
+            .chat
+            .as_ref()
+            .map(|slot| slot.handle.jobs.snapshot())
+            .unwrap_or_default(),
+        embedding: manager
+            .embedding
+            .as_ref()
+            .map(|slot| slot.handle.jobs.snapshot())
+            .unwrap_or_default(),
+        reranker: manager
+            .reranker
+            .as_ref()
+            .map(|slot| slot.handle.jobs.snapshot())
+            .unwrap_or_default(),
+    }
+}
+
+/// Preempts a running background chat turn, e.g. the sleep cycle, so queued
+/// work starts now. Returns `false` when none was running.
+pub fn preempt_background_jobs() -> bool {
+    manager().is_ok_and(|manager| {
+        manager
+            .chat
+            .as_ref()
+            .is_some_and(|slot| slot.handle.jobs.preempt_background())
+    })
+}
+
+pub fn chat_context_config() -> Result<ContextConfig, AnimaError> {
+    chat_handle().map(|chat| chat.context)
+}
+
+enum LoadedModel {
+    Chat(ChatSlot),
+    Embedding(EmbeddingSlot),
+    Reranker(RerankerSlot),
+}
+
+fn swap_in(kind: ModelKind, path: &str) -> Result<(), AnimaError> {
+    // Checked before unloading, so a bad file leaves the current model in place.
+    let header = match model_validation::validate_model_file(Path::new(path), kind) {
+        Ok(header) => header,
+        Err(error) => {
+            let error = error.context(&format!("{} model rejected", kind.label()));
+            eprintln!("[model_manager] {error}");
+            record_failure(kind, path, &error);
+            return Err(error);
+        }
+    };
+
+    let model_bytes = fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+    let limits = ModelLimits::from_header(&header, model_bytes);
+
+    // Planned before unloading, counting the current model's memory as free,
+    // so a model that does not fit leaves the current one in place.
+    let reclaimable_bytes = manager()?.released_bytes(kind);
+    let context = match kind {
+        ModelKind::Chat => runtime_config::context_config(&limits, reclaimable_bytes),
+        ModelKind::Embedding | ModelKind::Reranker => {
+            runtime_config::embedding_context_config(limits.n_ctx_train)
+        }
+    };
+    let plan = match ai::plan_model_load(path, &header, context.n_ctx, reclaimable_bytes) {
+        Ok(plan) => plan,
+        Err(error) => {
+            let error = error.context(&format!("{} model not loaded", kind.label()));
+            eprintln!("[model_manager] {error}");
+            record_failure(kind, path, &error);
+            return Err(error);
+        }
+    };
+    let released_bytes = plan.released_bytes(model_bytes);
+
+    release(kind)?;
+    *manager()?.state_mut(kind) = ModelState::Loading {
+        path: path.to_string(),
+    };
+
+    // The manager lock is not held while loading so status stays queryable.
+    let loaded = match kind {
+        ModelKind::Chat => {
+            spawn_chat_worker(path, &header, context, &plan, released_bytes).map(LoadedModel::Chat)
+        }
+        ModelKind::Embedding => {
+            spawn_embedding_worker(path, context, &plan, released_bytes).map(LoadedModel::Embedding)
+        }
+        ModelKind::Reranker => spawn_reranker_worker(path, &header, context, &plan, released_bytes)
+            .map(LoadedModel::Reranker),
+    };
+
+    let mut manager = manager()?;
+    match loaded {
+        Ok(LoadedModel::Chat(slot)) => manager.chat = Some(slot),
+        Ok(LoadedModel::Embedding(slot)) => manager.embedding = Some(slot),
+        Ok(LoadedModel::Reranker(slot)) => manager.reranker = Some(slot),
+        Err(error) => {
+            let error = error.context(&format!("{} model load failed", kind.label()));
+            eprintln!("[model_manager] {error}");
+            *manager.state_mut(kind) = failed_state(path, &error);
+            return Err(error);
+        }
+    };
+    *manager.state_mut(kind) = ModelState::Ready {
+        path: path.to_string(),
+        load_strategy: plan.strategy,
+    };
+    eprintln!(
+        "[model_manager] {} model ready: {path} ({}, {})",
+        kind.label(),
+        plan.summary(),
+        context.summary()
+    );
+    Ok(())
+}
+
+fn release(kind: ModelKind) -> Result<(), AnimaError> {
+    match kind {
+        ModelKind::Chat => {
+            let slot = {
+                let mut manager = manager()?;
+                manager.chat_state = ModelState::NotLoaded;
+                manager.chat.take()
+            };
+            if let Some(ChatSlot { handle, worker, .. }) = slot {
+                // The running turn stops at its next token; queued turns fail.
+                handle.jobs.shut_down();
+                drop(handle);
+                if worker.join().is_err() {
+                    eprintln!("[model_manager] chat worker panicked while shutting down");
+                }
+            }
+        }
+        ModelKind::Embedding => {
+            let slot = {
+                let mut manager = manager()?;
+                manager.embedding_state = ModelState::NotLoaded;
+                manager.embedding.take()
+            };
+            if let Some(EmbeddingSlot { handle, worker, .. }) = slot {
+                handle.jobs.shut_down();
+                drop(handle);
+                if worker.join().is_err() {
+                    eprintln!("[model_manager] embedding worker panicked while shutting down");
+                }
+            }
+        }
+        ModelKind::Reranker => {
+            let slot = {
+                let mut manager = manager()?;
+                manager.reranker_state = ModelState::NotLoaded;
+                manager.reranker.take()
+            };
+            if let Some(RerankerSlot { handle, worker, .. }) = slot {
+                handle.jobs.shut_down();
+                drop(handle);
+                if worker.join().is_err() {
+                    eprintln!("[model_manager] reranker worker panicked while shutting down");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn spawn_chat_worker(
+    path: &str,
+    header: &GgufHeader,
+    context: ContextConfig,
+    plan: &LoadPlan,
+    released_bytes: u64,
+) -> Result<ChatSlot, AnimaError> {
+    let model = Arc::new(ai::load_model_file(path, plan)?);
+    let template = ai::detect_chat_template(header);
+
+    let worker_model = Arc::clone(&model);
+    let (jobs, worker) = start_worker(ModelKind::Chat, move |queue, ready_tx| {
+        ai::run_chat_worker(&worker_model, template, context, queue, ready_tx)
+    })?;
+    Ok(ChatSlot {
+        handle: ChatHandle {
+            model,
+            template,
+            context,
+            jobs,
+        },
+        worker,
+        released_bytes,
+    })
+}
+
+fn spawn_embedding_worker(
+    path: &str,
+    context: ContextConfig,
+    plan: &LoadPlan,
+    released_bytes: u64,
+) -> Result<EmbeddingSlot, AnimaError> {
+    let model = ai::load_model_file(path, plan)?;
+    let model_id = embedding_model_id(path);
+
+    let (jobs, worker) = start_worker(ModelKind::Embedding, move |queue, ready_tx| {
+        ai::run_embedding_worker(&model, context, queue, ready_tx)
+    })?;
+    Ok(EmbeddingSlot {
+        handle: EmbeddingHandle { model_id, jobs },
+        worker,
+        released_bytes,
+    })
+}
+
+fn spawn_reranker_worker(
+    path: &str,
+    header: &GgufHeader,
+    context: ContextConfig,
+    plan: &LoadPlan,
+    released_bytes: u64,
+) -> Result<RerankerSlot, AnimaError> {
+    let model = ai::load_model_file(path, plan)?;
+    let tokens = RerankTokens::from_header(header);
+
+    let (jobs, worker) = start_worker(ModelKind::Reranker, move |queue, ready_tx| {
+        ai::run_reranker_worker(&model, context, tokens, queue, ready_tx)
+    })?;
+    Ok(RerankerSlot {
+        handle: RerankerHandle { jobs },
+        worker,
+        released_bytes,
+    })
+}
+
+fn embedding_model_id(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Spawns the worker thread for a slot and waits until it reports that its
+/// context was created.
+fn start_worker<J: Send + 'static>(
+    kind: ModelKind,
+    body: impl FnOnce(Arc<JobQueue<J>>, mpsc::Sender<Result<(), AnimaError>>) + Send + 'static,
+) -> Result<(Arc<JobQueue<J>>, JoinHandle<()>), AnimaError> {
+    let jobs = Arc::new(JobQueue::new());
+    let worker_jobs = Arc::clone(&jobs);
+    let (ready_tx, ready_rx) = mpsc::channel::<Result<(), AnimaError>>();
+    let worker = thread::Builder::new()
+        .name(format!("anima-{}", kind.label().to_lowercase()))
+        .spawn(move || body(worker_jobs, ready_tx))
+        .map_err(|error| {
+            AnimaError::Internal(format!("{} worker spawn failed: {error}", kind.label()))
+        })?;
+
+    match ready_rx.recv() {
+        Ok(Ok(())) => Ok((jobs, worker)),
+        Ok(Err(error)) => {
+            let _ = worker.join();
+            Err(error)
+        }
+        Err(_) => {
+            let _ = worker.join();
+            Err(AnimaError::Internal(format!(
+                "{} worker exited during startup",
+                kind.label()
+            )))
+        }
+    }
+}
+
+fn not_ready_error(kind: ModelKind, state: &ModelState) -> AnimaError {
+    let label = kind.label();
+    AnimaError::ModelNotLoaded(match state {
+        ModelState::NotLoaded => format!("{label} model is not loaded"),
+        ModelState::Loading { path } => format!("{label} model is still loading: {path}"),
+        ModelState::Ready { path, .. } => format!("{label} model is being swapped: {path}"),
+        ModelState::Failed { path, detail, .. } => {
+            format!("{label} model failed to load ({path}): {detail}")
+        }
+    })
+}
+
+fn failed_state(path: &str, error: &AnimaError) -> ModelState {
+    ModelState::Failed {
+        path: path.to_string(),
+        code: error.code().to_string(),
+        detail: error.detail().to_string(),
+    }
+}
+
+fn manager() -> Result<MutexGuard<'static, ModelManager>, AnimaError> {
+    MANAGER
+        .get_or_init(|| {
+            Mutex::new(ModelManager {
+                chat_state: ModelState::NotLoaded,
+                chat: None,
+                embedding_state: ModelState::NotLoaded,
+                embedding: None,
+                reranker_state: ModelState::NotLoaded,
+                reranker: None,
+            })
+        })
+        .lock()
+        .map_err(|error| AnimaError::Internal(format!("Model manager mutex is poisoned: {error}")))
+}
+
+fn lock_swap() -> Result<MutexGuard<'static, ()>, AnimaError> {
+    SWAP_LOCK
+        .lock()
+        .map_err(|error| AnimaError::Internal(format!("Model swap mutex is poisoned: {error}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unloaded_chat_reports_model_not_loaded() {
+        let error = not_ready_error(ModelKind::Chat, &ModelState::NotLoaded);
+        assert_eq!(error.code(), "model_not_loaded");
+    }
+
+    #[test]
+    fn test_failed_state_keeps_error_code() {
+        let error = AnimaError::ModelLoadFailed("bad magic".to_string());
+        let state = failed_state("models/x.gguf", &error);
+        assert_eq!(
+            state,
+            ModelState::Failed {
+                path: "models/x.gguf".to_string(),
+                code: "model_load_failed".to_string(),
+                detail: "bad magic".to_string(),
+            }
+        );
+        let error = not_ready_error(ModelKind::Embedding, &state);
+        assert!(error.detail().contains("bad magic"));
+    }
+}
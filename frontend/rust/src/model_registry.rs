@@ -0,0 +1,278 @@
+use crate::chat_template::ChatTemplate;
+use crate::db;
+use crate::error::AnimaError;
+use crate::gguf::{self, GgufHeader};
+use crate::model_manager::{self, ModelKind};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const DEFAULT_MODELS_DIR: &str = "models";
+/// `models/<family>/<file>.gguf` is as deep as the scan goes.
+const MAX_SCAN_DEPTH: usize = 2;
+
+/// A GGUF file found in the models folder, described from its header alone.
+#[derive(Debug, Clone)]
+pub struct ModelInfo {
+    /// Path relative to the models folder, with `/` separators.
+    pub id: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub architecture: String,
+    pub parameter_count: u64,
+    pub quantization: String,
+    pub context_length: Option<u32>,
+    pub embedding_length: Option<u32>,
+    /// Prompt format the chat runtime would use for this model.
+    pub chat_template: String,
+    pub has_embedded_template: bool,
+    /// Best guess from the architecture and pooling metadata.
+    pub suggested_kind: ModelKind,
+}
+
+/// The configured models folder, or the first `models/` found next to the
+/// working directory or up to two levels above it.
+pub fn models_dir() -> Result<PathBuf, AnimaError> {
+    if let Some(configured) = db::get_models_dir()
+        .map_err(|error| AnimaError::from(error).context("Failed to read models folder"))?
+    {
+        return Ok(PathBuf::from(configured));
+    }
+
+    let cwd = std::env::current_dir()
+        .map_err(|error| AnimaError::Io(format!("Cannot read current directory: {error}")))?;
+    let candidates = [
+        cwd.join(DEFAULT_MODELS_DIR),
+        cwd.join("..").join(DEFAULT_MODELS_DIR),
+        cwd.join("..").join("..").join(DEFAULT_MODELS_DIR),
+    ];
+    Ok(candidates
+        .iter()
+        .find(|candidate| candidate.is_dir())
+        .cloned()
+        .unwrap_or_else(|| cwd.join(DEFAULT_MODELS_DIR)))
+}
+
+pub fn set_models_dir(path: &str) -> Result<(), AnimaError> {
+    if !Path::new(path.trim()).is_dir() {
+        return Err(AnimaError::InvalidInput(format!(
+            "Models folder does not exist: {path}"
+        )));
+    }
+    db::set_models_dir(path)
+        .map_err(|error| AnimaError::from(error).context("Failed to save models folder"))
+}
+
+pub fn list_models() -> Result<Vec<ModelInfo>, AnimaError> {
+    scan_dir(&models_dir()?)
+}
+
+/// Loads the model into the slot and remembers the choice for the next start.
+/// The previous selection is kept if the load fails.
+pub fn select_model(kind: ModelKind, id: &str) -> Result<ModelInfo, AnimaError> {
+    let info = find_model(&models_dir()?, id)?;
+    model_manager::load(kind, &info.path)?;
+
+    let saved = match kind {
+        ModelKind::Chat => db::set_chat_model_id(&info.id),
+        ModelKind::Embedding => db::set_embedding_model_id(&info.id),
+        ModelKind::Reranker => db::set_reranker_model_id(&info.id),
+    };
+    saved.map_err(|error| AnimaError::from(error).context("Failed to save model selection"))?;
+    Ok(info)
+}
+
+/// Path of the persisted selection for the slot, if it is still on disk.
+pub fn selected_model_path(kind: ModelKind) -> Option<String> {
+    let id = match kind {
+        ModelKind::Chat => db::get_chat_model_id(),
+        ModelKind::Embedding => db::get_embedding_model_id(),
+        ModelKind::Reranker => db::get_reranker_model_id(),
+    };
+    let id = match id {
+        Ok(id) => id?,
+        Err(error) => {
+            eprintln!("[model_registry] failed to read selected model: {error}");
+            return None;
+        }
+    };
+
+    let path = models_dir().ok()?.join(checked_relative_path(&id).ok()?);
+    if path.is_file() {
+        Some(path.to_string_lossy().to_string())
+    } else {
+        eprintln!(
+            "[model_registry] selected model {id} is missing from {}",
+            path.display()
+        );
+        None
+    }
+}
+
+fn scan_dir(dir: &Path) -> Result<Vec<ModelInfo>, AnimaError> {
+    if !dir.is_dir() {
+        return Err(AnimaError::Io(format!(
+            "Models folder not found: {}",
+            dir.display()
+        )));
+    }
+
+    let mut files = Vec::new();
+    collect_gguf_files(dir, 0, &mut files);
+
+    let mut models = files
+        .iter()
+        .filter_map(|path| {
+            describe_model(dir, path)
+                .inspect_err(|error| eprintln!("[model_registry] skipping model: {error}"))
+                .ok()
+        })
+        .collect::<Vec<_>>();
+    models.sort_by(|left, right| left.id.cmp(&right.id));
+    Ok(models)
+}
+
+fn collect_gguf_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) => {
+            eprintln!("[model_registry] cannot read {}: {error}", dir.display());
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if depth < MAX_SCAN_DEPTH {
+                collect_gguf_files(&path, depth + 1, files);
+            }
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("gguf"))
+        {
+            files.push(path);
+        }
+    }
+}
+
+fn find_model(dir: &Path, id: &str) -> Result<ModelInfo, AnimaError> {
+    let path = dir.join(checked_relative_path(id)?);
+    if !path.is_file() {
+        return Err(AnimaError::InvalidInput(format!(
+            "No model with id '{id}' in {}",
+            dir.display()
+        )));
+    }
+    describe_model(dir, &path)
+}
+
+/// Ids come from the UI; only plain relative paths inside the folder are accepted.
+fn checked_relative_path(id: &str) -> Result<PathBuf, AnimaError> {
+    let path = PathBuf::from(id);
+    let is_plain = !id.is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+    if is_plain {
+        Ok(path)
+    } else {
+        Err(AnimaError::InvalidInput(format!("Invalid model id '{id}'")))
+    }
+}
+
+fn describe_model(dir: &Path, path: &Path) -> Result<ModelInfo, AnimaError> {
+    let header = gguf::read_header(path)?;
+    let size_bytes = fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .map_err(|error| AnimaError::Io(format!("{}: {error}", path.display())))?;
+    let id = path
+        .strip_prefix(dir)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    Ok(model_info(id, path, size_bytes, &header))
+}
+
+fn model_info(id: String, path: &Path, size_bytes: u64, header: &GgufHeader) -> ModelInfo {
+    let architecture = header.architecture().unwrap_or("unknown").to_string();
+    let embedded_template = header.chat_template();
+    let to_u32 = |value: u64| u32::try_from(value).unwrap_or(u32::MAX);
+
+    ModelInfo {
+        id,
+        path: path.to_string_lossy().to_string(),
+        size_bytes,
+        parameter_count: header.parameter_count,
+        quantization: header.quantization(),
+        context_length: header.context_length().map(to_u32),
+        embedding_length: header.embedding_length().map(to_u32),
+        chat_template: ChatTemplate::detect(
+            embedded_template,
+            &header.chat_marker_tokens,
+            Some(&architecture),
+        )
+        .name()
+        .to_string(),
+        has_embedded_template: embedded_template.is_some(),
+        suggested_kind: if header.is_reranker_model() {
+            ModelKind::Reranker
+        } else if header.is_embedding_model() {
+            ModelKind::Embedding
+        } else {
+            ModelKind::Chat
+        },
+        architecture,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gguf::tests::synthetic_gguf;
+    use crate::gguf::GgufValue;
+
+    #[test]
+    fn test_scan_describes_nested_models() {
+        let dir = std::env::temp_dir().join(format!("anima_registry_{}", std::process::id()));
+        fs::create_dir_all(dir.join("embed")).unwrap();
+        fs::write(
+            dir.join("chat.gguf"),
+            synthetic_gguf(&[
+                ("general.architecture", GgufValue::String("gemma2".into())),
+                ("gemma2.context_length", GgufValue::UInt(8192)),
+            ]),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("embed").join("nomic.GGUF"),
+            synthetic_gguf(&[(
+                "general.architecture",
+                GgufValue::String("nomic-bert".into()),
+            )]),
+        )
+        .unwrap();
+        fs::write(dir.join("broken.gguf"), b"not a model").unwrap();
+
+        let models = scan_dir(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].id, "chat.gguf");
+        assert_eq!(models[0].chat_template, "gemma");
+        assert_eq!(models[0].context_length, Some(8192));
+        assert_eq!(models[0].suggested_kind, ModelKind::Chat);
+        assert_eq!(models[1].id, "embed/nomic.GGUF");
+        assert_eq!(models[1].suggested_kind, ModelKind::Embedding);
+    }
+
+    #[test]
+    fn test_rejects_ids_outside_models_dir() {
+        assert!(checked_relative_path("../secret.gguf").is_err());
+        assert!(checked_relative_path("/etc/passwd").is_err());
+        assert!(checked_relative_path("").is_err());
+        assert!(checked_relative_path("family/model.gguf").is_ok());
+    }
+}
@@ -0,0 +1,241 @@
+use crate::error::AnimaError;
+use crate::gguf::{self, GgufHeader};
+use crate::model_manager::ModelKind;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+/// Sidecar next to the model, e.g. `anima_v1.gguf.sha256`, in `sha256sum` format.
+const CHECKSUM_SUFFIX: &str = ".sha256";
+const HASH_BUFFER_LEN: usize = 1024 * 1024;
+
+/// Size and modification time; a file with the same stamp is not hashed again.
+type FileStamp = (u64, Option<SystemTime>);
+
+/// Files whose checksum already matched, so a reload does not hash several
+/// gigabytes again.
+static VERIFIED_CHECKSUMS: OnceLock<Mutex<HashMap<PathBuf, FileStamp>>> = OnceLock::new();
+
+/// Checks a model file before it is handed to llama.cpp, which only reports a
+/// null model on failure. Returns the parsed header.
+pub fn validate_model_file(path: &Path, kind: ModelKind) -> Result<GgufHeader, AnimaError> {
+    let metadata = fs::metadata(path).map_err(|error| {
+        AnimaError::ModelLoadFailed(format!(
+            "Model file not found at {}: {error}",
+            path.display()
+        ))
+    })?;
+
+    let header = gguf::read_header(path)?;
+    check_complete(&header, metadata.len())
+        .map_err(|error| error.context(&path.display().to_string()))?;
+    check_kind(&header, kind).map_err(|error| error.context(&path.display().to_string()))?;
+    verify_checksum(path, metadata.len(), metadata.modified().ok())?;
+    Ok(header)
+}
+
+fn check_complete(header: &GgufHeader, file_len: u64) -> Result<(), AnimaError> {
+    let required = header.required_file_len();
+    if file_len < required {
+        return Err(AnimaError::ModelTruncated(format!(
+            "File has {file_len} bytes but its tensors need {required}; the download is probably incomplete"
+        )));
+    }
+    Ok(())
+}
+
+fn check_kind(header: &GgufHeader, kind: ModelKind) -> Result<(), AnimaError> {
+    let architecture = header.architecture().unwrap_or("unknown");
+    match (
+        kind,
+        header.is_embedding_model(),
+        header.is_reranker_model(),
+    ) {
+        (ModelKind::Chat, true, _) => Err(AnimaError::WrongModelKind(format!(
+            "'{architecture}' is an embedding model and cannot be used for chat"
+        ))),
+        (ModelKind::Embedding, false, _) => Err(AnimaError::WrongModelKind(format!(
+            "'{architecture}' is a chat model, not an embedding model"
+        ))),
+        (ModelKind::Embedding, _, true) => Err(AnimaError::WrongModelKind(format!(
+            "'{architecture}' is a reranker model; it scores text pairs instead of embedding text"
+        ))),
+        (ModelKind::Reranker, _, false) => Err(AnimaError::WrongModelKind(format!(
+            "'{architecture}' does not use rank pooling, so it is not a reranker model"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Compares against the sidecar checksum when there is one; models without a
+/// sidecar are accepted.
+fn verify_checksum(
+    path: &Path,
+    file_len: u64,
+    modified: Option<SystemTime>,
+) -> Result<(), AnimaError> {
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(CHECKSUM_SUFFIX);
+    let sidecar = PathBuf::from(sidecar);
+    if !sidecar.is_file() {
+        return Ok(());
+    }
+
+    let stamp = (file_len, modified);
+    let verified = VERIFIED_CHECKSUMS.get_or_init(|| Mutex::new(HashMap::new()));
+    if verified
+        .lock()
+        .is_ok_and(|verified| verified.get(path) == Some(&stamp))
+    {
+        return Ok(());
+    }
+
+    let manifest = fs::read_to_string(&sidecar).map_err(|error| {
+        AnimaError::Io(format!(
+            "Cannot read checksum {}: {error}",
+            sidecar.display()
+        ))
+    })?;
+    let expected = parse_checksum(&manifest).ok_or_else(|| {
+        AnimaError::InvalidModelFile(format!(
+            "{} does not contain a SHA-256 digest",
+            sidecar.display()
+        ))
+    })?;
+
+    eprintln!("[model_validation] hashing {}", path.display());
+    let actual = sha256_file(path)?;
+    if actual != expected {
+        return Err(AnimaError::ModelChecksumMismatch(format!(
+            "{}: expected SHA-256 {expected}, got {actual}",
+            path.display()
+        )));
+    }
+
+    if let Ok(mut verified) = verified.lock() {
+        verified.insert(path.to_path_buf(), stamp);
+    }
+    Ok(())
+}
+
+/// Accepts a bare digest or a `sha256sum` line (`<digest>  <file name>`).
+fn parse_checksum(manifest: &str) -> Option<String> {
+    let digest = manifest.split_whitespace().next()?.to_ascii_lowercase();
+    (digest.len() == 64
+        && digest
+            .chars()
+            .all(|character| character.is_ascii_hexdigit()))
+    .then_some(digest)
+}
+
+fn sha256_file(path: &Path) -> Result<String, AnimaError> {
+    let mut file = File::open(path)
+        .map_err(|error| AnimaError::Io(format!("Cannot open {}: {error}", path.display())))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0_u8; HASH_BUFFER_LEN];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|error| AnimaError::Io(format!("Cannot read {}: {error}", path.display())))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gguf::tests::synthetic_gguf;
+    use crate::gguf::GgufValue;
+
+    fn write_model(name: &str, architecture: &str, complete: bool) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("anima_{}_{name}", std::process::id()));
+        let mut bytes = synthetic_gguf(&[(
+            "general.architecture",
+            GgufValue::String(architecture.to_string()),
+        )]);
+        if complete {
+            let header = gguf::parse_header(&mut bytes.as_slice()).unwrap();
+            bytes.resize(header.required_file_len() as usize, 0);
+        }
+        fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_detects_truncated_file() {
+        let path = write_model("truncated.gguf", "llama", false);
+        let error = validate_model_file(&path, ModelKind::Chat).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(error.code(), "model_truncated");
+    }
+
+    #[test]
+    fn test_rejects_model_in_wrong_slot() {
+        let path = write_model("bert.gguf", "bert", true);
+        let chat = validate_model_file(&path, ModelKind::Chat).unwrap_err();
+        let embedding = validate_model_file(&path, ModelKind::Embedding);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(chat.code(), "wrong_model_kind");
+        assert!(embedding.is_ok());
+    }
+
+    #[test]
+    fn test_reranker_needs_rank_pooling() {
+        let header = |pooling_type: u64| {
+            let bytes = synthetic_gguf(&[
+                (
+                    "general.architecture",
+                    GgufValue::String("bert".to_string()),
+                ),
+                ("bert.pooling_type", GgufValue::UInt(pooling_type)),
+            ]);
+            gguf::parse_header(&mut bytes.as_slice()).unwrap()
+        };
+        let mean = header(1);
+        let rank = header(4);
+
+        assert!(check_kind(&rank, ModelKind::Reranker).is_ok());
+        assert!(check_kind(&mean, ModelKind::Embedding).is_ok());
+        assert_eq!(
+            check_kind(&mean, ModelKind::Reranker).unwrap_err().code(),
+            "wrong_model_kind"
+        );
+        assert_eq!(
+            check_kind(&rank, ModelKind::Embedding).unwrap_err().code(),
+            "wrong_model_kind"
+        );
+    }
+
+    #[test]
+    fn test_checks_sidecar_checksum() {
+        let path = write_model("checked.gguf", "llama", true);
+        let sidecar = PathBuf::from(format!("{}{CHECKSUM_SUFFIX}", path.display()));
+        let digest = sha256_file(&path).unwrap();
+
+        fs::write(&sidecar, format!("{}  checked.gguf\n", "0".repeat(64))).unwrap();
+        let mismatch = validate_model_file(&path, ModelKind::Chat).unwrap_err();
+        fs::write(
+            &sidecar,
+            format!("{}  checked.gguf\n", digest.to_uppercase()),
+        )
+        .unwrap();
+        let matched = validate_model_file(&path, ModelKind::Chat);
+
+        fs::remove_file(&sidecar).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(mismatch.code(), "model_checksum_mismatch");
+        assert!(matched.is_ok());
+    }
+}
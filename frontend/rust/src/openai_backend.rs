@@ -0,0 +1,487 @@
+use crate::ai::{Embedding, Generation, StopReason};
+use crate::backend::{BackendSettings, GenerationRequest, InferenceBackend};
+use crate::cancellation::CancellationToken;
+use crate::chat_template::{ChatTemplate, TurnRole};
+use crate::error::AnimaError;
+use crate::job_queue::JobPriority;
+use crate::local_http::{self, HttpResponse, LocalUrl};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+const MAX_ERROR_BODY_CHARS: usize = 300;
+/// Servers apply the model's own template; this one only measures the prompt
+/// budget and names the stop sequences sent along, at most the 4 OpenAI allows.
+const SERVER_TEMPLATE: ChatTemplate = ChatTemplate::ChatMl;
+
+/// Talks to an OpenAI-compatible server the user runs on this machine
+/// (llama-server, Ollama, ...) through `/v1/chat/completions` and
+/// `/v1/embeddings`. Token counts use llama-server's `/tokenize` when the
+/// server has it.
+pub struct OpenAiBackend {
+    server_url: String,
+    url: LocalUrl,
+    chat_model: String,
+    embedding_model: String,
+    context_window: u32,
+    /// Cleared after the server answers `/tokenize` with 404, as Ollama does.
+    tokenize_supported: AtomicBool,
+}
+
+impl OpenAiBackend {
+    pub fn new(settings: &BackendSettings) -> Result<Self, AnimaError> {
+        Ok(Self {
+            server_url: settings.server_url.clone(),
+            url: LocalUrl::parse(&settings.server_url)?,
+            chat_model: settings.server_chat_model.clone(),
+            embedding_model: settings.server_embedding_model.clone(),
+            context_window: settings.server_context_window,
+            tokenize_supported: AtomicBool::new(true),
+        })
+    }
+
+    fn chat_request_body(&self, request: &GenerationRequest) -> Value {
+        let mut messages = vec![json!({ "role": "system", "content": request.system_prompt })];
+        messages.extend(request.turns.iter().map(|turn| {
+            let role = match turn.role {
+                TurnRole::User => "user",
+                TurnRole::Assistant => "assistant",
+            };
+            json!({ "role": role, "content": turn.content })
+        }));
+
+        let sampling = request.sampling.sanitized();
+        let mut body = json!({
+            "model": self.chat_model,
+            "messages": messages,
+            "stream": true,
+            "stream_options": { "include_usage": true },
+            "max_tokens": request.max_tokens,
+            "stop": SERVER_TEMPLATE.stop_sequences(),
+            "temperature": sampling.temperature,
+            "top_p": sampling.top_p,
+            // Not in the OpenAI spec; llama-server and Ollama read them, others ignore them.
+            "top_k": sampling.top_k,
+            "min_p": sampling.min_p,
+            "repeat_penalty": sampling.repeat_penalty,
+            "repeat_last_n": sampling.repeat_last_n,
+        });
+        if let Some(seed) = sampling.seed {
+            body["seed"] = json!(seed);
+        }
+        if let Some(schema) = &request.response_schema {
+            body["response_format"] = json!({
+                "type": "json_schema",
+                "json_schema": { "name": "response", "strict": true, "schema": schema },
+            });
+        }
+        if sampling.mirostat > 0 {
+            body["mirostat"] = json!(sampling.mirostat);
+            body["mirostat_tau"] = json!(sampling.mirostat_tau);
+            body["mirostat_eta"] = json!(sampling.mirostat_eta);
+        }
+        body
+    }
+}
+
+impl InferenceBackend for OpenAiBackend {
+    fn name(&self) -> String {
+        format!("openai_server({})", self.server_url)
+    }
+
+    fn chat_template(&self) -> Result<ChatTemplate, AnimaError> {
+        Ok(SERVER_TEMPLATE)
+    }
+
+    fn context_window(&self) -> Result<usize, AnimaError> {
+        Ok(self.context_window as usize)
+    }
+
+    fn generate_stream(
+        &self,
+        request: &GenerationRequest,
+        cancellation: &CancellationToken,
+        on_chunk: &mut dyn FnMut(&str) -> Result<(), AnimaError>,
+    ) -> Result<Generation, AnimaError> {
+        let body = self.chat_request_body(request);
+        let started = Instant::now();
+        let mut generation = Generation {
+            text: String::new(),
+            stop_reason: StopReason::EndOfTurn,
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            decode_duration: Default::default(),
+        };
+
+        let response = local_http::post_json_cancellable(
+            &self.url,
+            "/v1/chat/completions",
+            &body,
+            cancellation,
+        );
+        let mut response = match response {
+            Err(AnimaError::Cancelled(_)) => {
+                generation.stop_reason = StopReason::Cancelled;
+                return Ok(generation);
+            }
+            response => response?,
+        };
+        if !response.is_success() {
+            return Err(AnimaError::GenerationFailed(error_detail(
+                "Chat completion",
+                response,
+            )));
+        }
+
+        let mut usage_reported = false;
+
+        // Returning early drops the connection, which makes the server stop.
+        // The response wakes up between lines to notice cancellation.
+        loop {
+            let line = match response.next_line() {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(AnimaError::Cancelled(_)) => {
+                    generation.stop_reason = StopReason::Cancelled;
+                    break;
+                }
+                Err(error) => return Err(error),
+            };
+            if cancellation.is_cancelled() {
+                generation.stop_reason = StopReason::Cancelled;
+                break;
+            }
+            let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+                continue;
+            };
+            if data == "[DONE]" {
+                break;
+            }
+
+            let event: Value = serde_json::from_str(data).map_err(|error| {
+                AnimaError::GenerationFailed(format!(
+                    "Server sent an invalid stream event: {error}"
+                ))
+            })?;
+            if let Some(message) = event["error"]["message"].as_str() {
+                return Err(AnimaError::GenerationFailed(format!(
+                    "Server stream error: {message}"
+                )));
+            }
+
+            let choice = &event["choices"][0];
+            if let Some(content) = choice["delta"]["content"].as_str() {
+                if !content.is_empty() {
+                    generation.text.push_str(content);
+                    if !usage_reported {
+                        generation.completion_tokens += 1;
+                    }
+                    on_chunk(content)?;
+                }
+            }
+            match choice["finish_reason"].as_str() {
+                Some("length") => generation.stop_reason = StopReason::MaxTokens,
+                Some(_) => generation.stop_reason = StopReason::EndOfTurn,
+                None => {}
+            }
+            if let Some(usage) = event.get("usage").filter(|usage| usage.is_object()) {
+                usage_reported = true;
+                generation.prompt_tokens = json_u32(&usage["prompt_tokens"]);
+                generation.completion_tokens = json_u32(&usage["completion_tokens"]);
+            }
+        }
+
+        generation.text = generation.text.trim().to_string();
+        generation.decode_duration = started.elapsed();
+        Ok(generation)
+    }
+
+    /// Without a model name there is no way to tell whether the server's
+    /// embedder changed, so memories could silently mix vector spaces.
+    fn embedding_model_id(&self) -> Result<String, AnimaError> {
+        if self.embedding_model.is_empty() {
+            return Err(AnimaError::InvalidInput(
+                "Set the server's embedding model name in the backend settings".to_string(),
+            ));
+        }
+        Ok(format!("server:{}", self.embedding_model))
+    }
+
+    fn embed(&self, texts: &[&str], _priority: JobPriority) -> Result<Vec<Embedding>, AnimaError> {
+        let model_id = self.embedding_model_id()?;
+        let mut vectors = vec![Vec::new(); texts.len()];
+
+        // Servers reject empty input; locally those texts get an empty vector too.
+        let (indexes, inputs): (Vec<usize>, Vec<&str>) = texts
+            .iter()
+            .enumerate()
+            .filter(|(_, text)| !text.trim().is_empty())
+            .map(|(index, text)| (index, *text))
+            .unzip();
+        if !inputs.is_empty() {
+            let body = json!({ "model": self.embedding_model, "input": inputs });
+            let response = local_http::post_json(&self.url, "/v1/embeddings", &body)?;
+            if !response.is_success() {
+                return Err(AnimaError::EmbeddingFailed(error_detail(
+                    "Embedding",
+                    response,
+                )));
+            }
+            let parsed = response.read_json()?;
+            let data = parsed["data"].as_array().ok_or_else(|| {
+                AnimaError::EmbeddingFailed("Embedding response has no data array".to_string())
+            })?;
+            if data.len() != inputs.len() {
+                return Err(AnimaError::EmbeddingFailed(format!(
+                    "Server returned {} embeddings for {} inputs",
+                    data.len(),
+                    inputs.len()
+                )));
+            }
+
+            for (position, item) in data.iter().enumerate() {
+                let input_index = item["index"]
+                    .as_u64()
+                    .map(|index| index as usize)
+                    .unwrap_or(position);
+                let text_index = *indexes.get(input_index).ok_or_else(|| {
+                    AnimaError::EmbeddingFailed(format!(
+                        "Embedding index {input_index} out of range"
+                    ))
+                })?;
+                vectors[text_index] = item["embedding"]
+                    .as_array()
+                    .map(|values| {
+                        values
+                            .iter()
+                            .filter_map(Value::as_f64)
+                            .map(|value| value as f32)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+            }
+        }
+
+        Ok(vectors
+            .into_iter()
+            .map(|vector| Embedding {
+                model_id: model_id.clone(),
+                vector,
+            })
+            .collect())
+    }
+
+    fn tokenize(&self, text: &str) -> Result<Vec<i32>, AnimaError> {
+        if !self.tokenize_supported.load(Ordering::Relaxed) {
+            return Err(AnimaError::BackendUnavailable(
+                "Server has no tokenize endpoint".to_string(),
+            ));
+        }
+
+        let response = local_http::post_json(&self.url, "/tokenize", &json!({ "content": text }))?;
+        if matches!(response.status, 404 | 405) {
+            self.tokenize_supported.store(false, Ordering::Relaxed);
+        }
+        if !response.is_success() {
+            return Err(AnimaError::BackendUnavailable(error_detail(
+                "Tokenize", response,
+            )));
+        }
+        let parsed = response.read_json()?;
+        Ok(parsed["tokens"]
+            .as_array()
+            .map(|tokens| {
+                tokens
+                    .iter()
+                    .filter_map(Value::as_i64)
+                    .map(|token| token as i32)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+fn json_u32(value: &Value) -> u32 {
+    value
+        .as_u64()
+        .map(|value| u32::try_from(value).unwrap_or(u32::MAX))
+        .unwrap_or(0)
+}
+
+fn error_detail(what: &str, response: HttpResponse) -> String {
+    let status = response.status;
+    let body = response.read_string().unwrap_or_default();
+    let body = body
+        .trim()
+        .chars()
+        .take(MAX_ERROR_BODY_CHARS)
+        .collect::<String>();
+    format!("{what} request failed with HTTP {status}: {body}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat_template::ChatTurn;
+    use crate::db::SamplingParams;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves one canned response per connection and hands back each request body.
+    fn stub_server(responses: Vec<String>) -> (String, thread::JoinHandle<Vec<Value>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for response in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                bodies.push(serde_json::from_slice(&body).unwrap());
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            bodies
+        });
+        (url, server)
+    }
+
+    fn backend(url: &str) -> OpenAiBackend {
+        OpenAiBackend::new(&BackendSettings {
+            server_url: url.to_string(),
+            server_chat_model: "qwen".to_string(),
+            server_embedding_model: "minilm".to_string(),
+            ..BackendSettings::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_generate_stream_relays_sse_chunks() {
+        let events = [
+            r#"{"choices":[{"delta":{"role":"assistant"}}]}"#,
+            r#"{"choices":[{"delta":{"content":"Hola"}}]}"#,
+            r#"{"choices":[{"delta":{"content":" Ana"},"finish_reason":"length"}]}"#,
+            r#"{"choices":[],"usage":{"prompt_tokens":12,"completion_tokens":2}}"#,
+        ];
+        let sse = events
+            .iter()
+            .map(|event| format!("data: {event}\n\n"))
+            .collect::<String>()
+            + "data: [DONE]\n\n";
+        let response = format!("HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\r\n{sse}");
+        let (url, server) = stub_server(vec![response]);
+
+        let request = GenerationRequest {
+            system_prompt: "Be brief.".to_string(),
+            turns: vec![ChatTurn::user("Hi")],
+            sampling: SamplingParams::default(),
+            max_tokens: 64,
+            priority: JobPriority::Interactive,
+            response_schema: None,
+        };
+        let mut chunks = Vec::new();
+        let generation = backend(&url)
+            .generate_stream(&request, &CancellationToken::new(), &mut |chunk| {
+                chunks.push(chunk.to_string());
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(chunks, vec!["Hola", " Ana"]);
+        assert_eq!(generation.text, "Hola Ana");
+        assert_eq!(generation.stop_reason, StopReason::MaxTokens);
+        assert_eq!(
+            (generation.prompt_tokens, generation.completion_tokens),
+            (12, 2)
+        );
+
+        let body = &server.join().unwrap()[0];
+        assert_eq!(body["model"], "qwen");
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], "Hi");
+        assert_eq!(body["stop"], json!(SERVER_TEMPLATE.stop_sequences()));
+    }
+
+    #[test]
+    fn test_cancel_stops_stream_while_server_is_silent() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        // Sends one token, then stalls without closing the connection.
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let event = r#"data: {"choices":[{"delta":{"content":"Hola"}}]}"#;
+            let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\r\n";
+            stream
+                .write_all(format!("{head}{event}\n\n").as_bytes())
+                .unwrap();
+            thread::sleep(std::time::Duration::from_secs(5));
+        });
+
+        let request = GenerationRequest {
+            system_prompt: String::new(),
+            turns: vec![ChatTurn::user("Hi")],
+            sampling: SamplingParams::default(),
+            max_tokens: 64,
+            priority: JobPriority::Interactive,
+            response_schema: None,
+        };
+        let cancellation = CancellationToken::new();
+        let started = Instant::now();
+        let generation = backend(&url)
+            .generate_stream(&request, &cancellation, &mut |_| {
+                cancellation.cancel();
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(generation.stop_reason, StopReason::Cancelled);
+        assert_eq!(generation.text, "Hola");
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_server_embeddings_require_a_model_name() {
+        let backend = OpenAiBackend::new(&BackendSettings::default()).unwrap();
+        assert!(matches!(
+            backend.embedding_model_id(),
+            Err(AnimaError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_embed_orders_by_index_and_skips_blank_texts() {
+        let json =
+            r#"{"data":[{"index":1,"embedding":[0.0,1.0]},{"index":0,"embedding":[1.0,0.0]}]}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{json}",
+            json.len()
+        );
+        let (url, server) = stub_server(vec![response]);
+
+        let embeddings = backend(&url)
+            .embed(&["first", "  ", "second"], JobPriority::Interactive)
+            .unwrap();
+        let vectors = embeddings
+            .iter()
+            .map(|e| e.vector.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(vectors, vec![vec![1.0, 0.0], vec![], vec![0.0, 1.0]]);
+        assert_eq!(embeddings[0].model_id, "server:minilm");
+
+        let body = &server.join().unwrap()[0];
+        assert_eq!(body["input"], json!(["first", "second"]));
+    }
+}
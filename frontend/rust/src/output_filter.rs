@@ -0,0 +1,244 @@
+use crate::error::AnimaError;
+use regex::Regex;
+use std::sync::OnceLock;
+
+static PROMPT_LEAK_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+
+/// Streams generated text to the listener while holding back any tail that
+/// could still become a stop sequence, and cuts the reply at stop sequences
+/// and at echoed system instructions.
+pub(crate) struct OutputFilter {
+    stop_sequences: Vec<&'static str>,
+    max_stop_len: usize,
+    generated: String,
+    emitted_len: usize,
+    /// Off for structured output, where a bracket or `\n` is JSON syntax.
+    sanitize: bool,
+}
+
+impl OutputFilter {
+    pub fn new(stop_sequences: Vec<&'static str>) -> Self {
+        let max_stop_len = stop_sequences
+            .iter()
+            .map(|sequence| sequence.len())
+            .max()
+            .unwrap_or(0);
+        Self {
+            stop_sequences,
+            max_stop_len,
+            generated: String::new(),
+            emitted_len: 0,
+            sanitize: true,
+        }
+    }
+
+    /// Only cuts at stop sequences; the text is passed through untouched.
+    pub fn verbatim(stop_sequences: Vec<&'static str>) -> Self {
+        Self {
+            sanitize: false,
+            ..Self::new(stop_sequences)
+        }
+    }
+
+    /// Appends a decoded piece and emits what is safe. Returns `true` when a
+    /// stop sequence or prompt leak ended the reply; the text is cut there.
+    pub fn push<F>(&mut self, piece: &str, on_chunk: &mut F) -> Result<bool, AnimaError>
+    where
+        F: FnMut(&str) -> Result<(), AnimaError> + ?Sized,
+    {
+        self.generated.push_str(piece);
+
+        let leak = if self.sanitize {
+            find_prompt_leak_index(&self.generated)
+        } else {
+            None
+        };
+        let cut = leak.or_else(|| find_stop_index(&self.generated, &self.stop_sequences));
+        if let Some(cut) = cut {
+            if cut > self.emitted_len {
+                self.emit_until(cut, on_chunk)?;
+            }
+            self.generated.truncate(cut);
+            return Ok(true);
+        }
+
+        let mut safe_end = if self.max_stop_len > 0 {
+            self.generated
+                .len()
+                .saturating_sub(self.max_stop_len.saturating_sub(1))
+        } else {
+            self.generated.len()
+        };
+        // An unclosed bracket may still turn into an echoed system note.
+        if let Some(open) = unclosed_bracket_index(&self.generated).filter(|_| self.sanitize) {
+            safe_end = safe_end.min(open);
+        }
+        self.emit_until(safe_end, on_chunk)?;
+        Ok(false)
+    }
+
+    /// Appends `tail` unchecked, such as bytes that never formed valid UTF-8,
+    /// flushes whatever was held back and returns the final reply text.
+    pub fn finish<F>(mut self, tail: &str, on_chunk: &mut F) -> Result<String, AnimaError>
+    where
+        F: FnMut(&str) -> Result<(), AnimaError> + ?Sized,
+    {
+        self.generated.push_str(tail);
+        if let Some(stop_index) = find_stop_index(&self.generated, &self.stop_sequences) {
+            self.generated.truncate(stop_index);
+        }
+        if !self.sanitize {
+            self.emit_until(self.generated.len(), on_chunk)?;
+            return Ok(self.generated.trim().to_string());
+        }
+        if let Some(leak_index) = find_prompt_leak_index(&self.generated) {
+            self.generated.truncate(leak_index);
+        }
+        self.emit_until(self.generated.len(), on_chunk)?;
+        Ok(finalize_model_output(&self.generated))
+    }
+
+    fn emit_until<F>(&mut self, end: usize, on_chunk: &mut F) -> Result<(), AnimaError>
+    where
+        F: FnMut(&str) -> Result<(), AnimaError> + ?Sized,
+    {
+        let end = floor_char_boundary(&self.generated, end);
+        if end <= self.emitted_len {
+            return Ok(());
+        }
+        let chunk = &self.generated[self.emitted_len..end];
+        let safe_chunk = if self.sanitize {
+            sanitize_model_output(chunk)
+        } else {
+            chunk.to_string()
+        };
+        if !safe_chunk.is_empty() {
+            on_chunk(&safe_chunk)?;
+        }
+        self.emitted_len = end;
+        Ok(())
+    }
+}
+
+pub(crate) fn find_stop_index(text: &str, stop_sequences: &[&str]) -> Option<usize> {
+    stop_sequences
+        .iter()
+        .filter_map(|stop| text.find(stop))
+        .min()
+}
+
+pub(crate) fn find_prompt_leak_index(text: &str) -> Option<usize> {
+    let regex = prompt_leak_regex()?;
+    regex.find(text).map(|matched| matched.start())
+}
+
+fn unclosed_bracket_index(text: &str) -> Option<usize> {
+    let open = text.rfind('[')?;
+    (!text[open..].contains(']')).then_some(open)
+}
+
+fn prompt_leak_regex() -> Option<&'static Regex> {
+    PROMPT_LEAK_REGEX
+        .get_or_init(|| {
+            Regex::new(
+                r"(?is)\[[^\]]*(?:System\s+note:|Remember\s+your\s+CRITICAL\s+INSTRUCTION|CRITICAL\s+INSTRUCTION)\s*[^\]]*\]",
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+pub(crate) fn sanitize_model_output(text: &str) -> String {
+    let removed = if let Some(regex) = prompt_leak_regex() {
+        regex.replace_all(text, "").into_owned()
+    } else {
+        text.to_string()
+    };
+    normalize_escaped_newlines(&removed)
+}
+
+pub(crate) fn finalize_model_output(text: &str) -> String {
+    sanitize_model_output(text).trim().to_string()
+}
+
+fn normalize_escaped_newlines(text: &str) -> String {
+    text.replace("\\r\\n", "\n")
+        .replace("\\n", "\n")
+        .replace("\\t", "\t")
+}
+
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while index > 0 && !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(stop_sequences: Vec<&'static str>, pieces: &[&str]) -> (Vec<String>, String, bool) {
+        let mut filter = OutputFilter::new(stop_sequences);
+        let mut chunks = Vec::new();
+        let mut on_chunk = |chunk: &str| {
+            chunks.push(chunk.to_string());
+            Ok(())
+        };
+        let mut stopped = false;
+        for piece in pieces {
+            if filter.push(piece, &mut on_chunk).unwrap() {
+                stopped = true;
+                break;
+            }
+        }
+        let text = filter.finish("", &mut on_chunk).unwrap();
+        (chunks, text, stopped)
+    }
+
+    #[test]
+    fn test_stop_sequence_split_across_pieces_is_never_emitted() {
+        let (chunks, text, stopped) =
+            run(vec!["<|im_end|>"], &["Hola", " mundo<|im", "_end|>tail"]);
+        assert!(stopped);
+        assert_eq!(text, "Hola mundo");
+        assert_eq!(chunks.concat(), "Hola mundo");
+    }
+
+    #[test]
+    fn test_prompt_leak_is_cut() {
+        let (chunks, text, stopped) = run(
+            vec![],
+            &["Claro. ", "[System note: stay ", "in character]", " more"],
+        );
+        assert!(stopped);
+        assert_eq!(text, "Claro.");
+        assert!(chunks.iter().all(|chunk| !chunk.contains("System note")));
+    }
+
+    #[test]
+    fn test_verbatim_keeps_json_escapes_and_brackets() {
+        let mut filter = OutputFilter::verbatim(vec!["<|im_end|>"]);
+        let mut chunks = String::new();
+        let mut on_chunk = |chunk: &str| {
+            chunks.push_str(chunk);
+            Ok(())
+        };
+        assert!(!filter.push(r#"{"a": ["x\ny", "#, &mut on_chunk).unwrap());
+        assert!(filter
+            .push(r#""[System note: z]"]}<|im_end|>"#, &mut on_chunk)
+            .unwrap());
+        let text = filter.finish("", &mut on_chunk).unwrap();
+        assert_eq!(text, r#"{"a": ["x\ny", "[System note: z]"]}"#);
+        assert_eq!(chunks, text);
+    }
+
+    #[test]
+    fn test_escaped_newlines_are_normalized() {
+        let (chunks, text, stopped) = run(vec![], &["a\\nb"]);
+        assert!(!stopped);
+        assert_eq!(text, "a\nb");
+        assert_eq!(chunks, vec!["a\nb"]);
+    }
+}
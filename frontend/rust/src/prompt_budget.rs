@@ -0,0 +1,526 @@
+use crate::chat_template::{self, ChatTurn};
+
+/// Keeps a few tokens free for the template tokens the estimate can miss.
+const SAFETY_MARGIN_TOKENS: usize = 8;
+const TRUNCATION_MARKER: &str = "…";
+
+/// Prompt sections in the order they are granted tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptSection {
+    UserMessage,
+    CorePrompt,
+    Memories,
+    History,
+    Profile,
+}
+
+impl PromptSection {
+    pub const PRIORITY: [PromptSection; 5] = [
+        PromptSection::UserMessage,
+        PromptSection::CorePrompt,
+        PromptSection::Memories,
+        PromptSection::History,
+        PromptSection::Profile,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PromptSection::UserMessage => "user_message",
+            PromptSection::CorePrompt => "core_prompt",
+            PromptSection::Memories => "memories",
+            PromptSection::History => "history",
+            PromptSection::Profile => "profile",
+        }
+    }
+}
+
+/// What one section asked for and what it was given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionAllocation {
+    pub section: PromptSection,
+    pub requested_tokens: usize,
+    pub granted_tokens: usize,
+    pub items_total: usize,
+    pub items_kept: usize,
+    pub truncated: bool,
+}
+
+impl SectionAllocation {
+    pub fn was_trimmed(&self) -> bool {
+        self.truncated || self.items_kept < self.items_total
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptBudgetReport {
+    pub context_window: usize,
+    pub reserved_for_generation: usize,
+    pub template_overhead: usize,
+    pub sections: Vec<SectionAllocation>,
+}
+
+impl PromptBudgetReport {
+    pub fn used_tokens(&self) -> usize {
+        self.template_overhead
+            + self
+                .sections
+                .iter()
+                .map(|section| section.granted_tokens)
+                .sum::<usize>()
+    }
+
+    pub fn was_trimmed(&self) -> bool {
+        self.sections.iter().any(SectionAllocation::was_trimmed)
+    }
+
+    /// One line per section, meant for the debug log.
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!(
+            "ctx={} reserved={} overhead={} used={}",
+            self.context_window,
+            self.reserved_for_generation,
+            self.template_overhead,
+            self.used_tokens()
+        )];
+        for section in &self.sections {
+            lines.push(format!(
+                "{}: {}/{} tokens, {}/{} items{}",
+                section.section.name(),
+                section.granted_tokens,
+                section.requested_tokens,
+                section.items_kept,
+                section.items_total,
+                if section.truncated { ", truncated" } else { "" }
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Raw material for one chat prompt before it is fitted to the context window.
+#[derive(Debug, Clone, Default)]
+pub struct PromptParts {
+    pub core_prompt: String,
+    /// Oldest first; the newest traits survive trimming.
+    pub profile_lines: Vec<String>,
+    /// Strongest match first; the weakest are dropped first.
+    pub memory_lines: Vec<String>,
+    /// Oldest first; the most recent turns survive trimming.
+    pub history: Vec<ChatTurn>,
+    pub user_message: String,
+    /// Goes after the profile, at the very end of the system prompt, so text
+    /// that changes every turn does not break the cached prompt prefix.
+    /// Budgeted with the core prompt and never trimmed.
+    pub system_suffix: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PromptBudget {
+    pub context_window: usize,
+    pub reserved_for_generation: usize,
+    /// Template tokens around the system prompt and the final user turn.
+    pub base_overhead: usize,
+    /// Template tokens added by each history turn.
+    pub turn_overhead: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct AssembledPrompt {
+    pub core_prompt: String,
+    /// Empty when there are no traits or none fit.
+    pub profile_block: String,
+    /// Empty when there are no memories or none fit.
+    pub memory_block: String,
+    pub history: Vec<ChatTurn>,
+    pub user_message: String,
+    pub system_suffix: String,
+    pub report: PromptBudgetReport,
+}
+
+impl AssembledPrompt {
+    pub fn system_prompt(&self) -> String {
+        format!(
+            "{}{}{}",
+            self.core_prompt, self.profile_block, self.system_suffix
+        )
+    }
+
+    /// The final user turn: the message with the memory snippets appended.
+    pub fn user_turn(&self) -> String {
+        format!("{}{}", self.user_message, self.memory_block)
+    }
+
+    /// History plus the final user turn, ready for `ChatTemplate::render`.
+    pub fn turns(&self) -> Vec<ChatTurn> {
+        let mut turns = self.history.clone();
+        turns.push(ChatTurn::user(self.user_turn()));
+        chat_template::normalize_turns(turns)
+    }
+}
+
+const PROFILE_HEADER: &str = "\n\nPERFIL CONSOLIDADO DEL USUARIO:\n";
+const MEMORY_HEADER: &str = "\n\nMEMORY SNIPPETS (REFERENCE ONLY, NOT DIALOGUE TURNS):\n";
+const MEMORY_FOOTER: &str = "\nUse this context only if relevant to the current user message. Never generate roleplay turns like 'User:' or simulate both sides.";
+
+/// Fits `parts` into `budget`, granting tokens by `PromptSection::PRIORITY`.
+///
+/// Text sections are cut at the end, list sections lose their lowest-ranked
+/// items whole. The user message is only cut when it cannot fit on its own.
+pub fn assemble<F>(parts: &PromptParts, budget: &PromptBudget, count_tokens: F) -> AssembledPrompt
+where
+    F: Fn(&str) -> usize,
+{
+    let mut remaining = budget
+        .context_window
+        .saturating_sub(budget.reserved_for_generation)
+        .saturating_sub(budget.base_overhead)
+        .saturating_sub(SAFETY_MARGIN_TOKENS);
+
+    let mut user_message = String::new();
+    let mut core_prompt = String::new();
+    let mut memory_block = String::new();
+    let mut history = Vec::<ChatTurn>::new();
+    let mut profile_block = String::new();
+    let mut sections = Vec::<SectionAllocation>::with_capacity(PromptSection::PRIORITY.len());
+
+    for section in PromptSection::PRIORITY {
+        let allocation = match section {
+            PromptSection::UserMessage => {
+                let (text, allocation) =
+                    fit_text(section, parts.user_message.trim(), remaining, &count_tokens);
+                user_message = text;
+                allocation
+            }
+            PromptSection::CorePrompt => {
+                let suffix_tokens = if parts.system_suffix.is_empty() {
+                    0
+                } else {
+                    count_tokens(&parts.system_suffix)
+                };
+                let (text, mut allocation) = fit_text(
+                    section,
+                    &parts.core_prompt,
+                    remaining.saturating_sub(suffix_tokens),
+                    &count_tokens,
+                );
+                allocation.requested_tokens += suffix_tokens;
+                allocation.granted_tokens += suffix_tokens;
+                core_prompt = text;
+                allocation
+            }
+            PromptSection::Memories => {
+                let lines = parts
+                    .memory_lines
+                    .iter()
+                    .map(|line| line.trim())
+                    .filter(|line| !line.is_empty())
+                    .enumerate()
+                    .map(|(index, line)| format!("{}. {}", index + 1, line))
+                    .collect::<Vec<String>>();
+                let fixed = count_tokens(MEMORY_HEADER) + count_tokens(MEMORY_FOOTER);
+                let (kept, allocation) =
+                    fit_lines(section, &lines, fixed, remaining, &count_tokens);
+                if !kept.is_empty() {
+                    memory_block = format!("{MEMORY_HEADER}{}{MEMORY_FOOTER}", kept.join("\n"));
+                }
+                allocation
+            }
+            PromptSection::History => {
+                let (kept, allocation) = fit_history(
+                    &parts.history,
+                    budget.turn_overhead,
+                    remaining,
+                    &count_tokens,
+                );
+                history = kept;
+                allocation
+            }
+            PromptSection::Profile => {
+                // Walk newest first so the oldest traits are the ones dropped.
+                let newest_first = parts
+                    .profile_lines
+                    .iter()
+                    .rev()
+                    .cloned()
+                    .collect::<Vec<String>>();
+                let (mut kept, allocation) = fit_lines(
+                    section,
+                    &newest_first,
+                    count_tokens(PROFILE_HEADER),
+                    remaining,
+                    &count_tokens,
+                );
+                if !kept.is_empty() {
+                    kept.reverse();
+                    profile_block = format!("{PROFILE_HEADER}{}", kept.join("\n"));
+                }
+                allocation
+            }
+        };
+
+        remaining = remaining.saturating_sub(allocation.granted_tokens);
+        sections.push(allocation);
+    }
+
+    AssembledPrompt {
+        core_prompt,
+        profile_block,
+        memory_block,
+        history,
+        user_message,
+        system_suffix: parts.system_suffix.clone(),
+        report: PromptBudgetReport {
+            context_window: budget.context_window,
+            reserved_for_generation: budget.reserved_for_generation,
+            template_overhead: budget.base_overhead,
+            sections,
+        },
+    }
+}
+
+fn fit_text<F>(
+    section: PromptSection,
+    text: &str,
+    available: usize,
+    count_tokens: &F,
+) -> (String, SectionAllocation)
+where
+    F: Fn(&str) -> usize,
+{
+    let requested = if text.is_empty() {
+        0
+    } else {
+        count_tokens(text)
+    };
+    let (kept, granted, truncated) = if requested <= available {
+        (text.to_string(), requested, false)
+    } else {
+        let kept = truncate_to_tokens(text, available, count_tokens);
+        let granted = if kept.is_empty() {
+            0
+        } else {
+            count_tokens(&kept)
+        };
+        (kept, granted, true)
+    };
+
+    let items_total = usize::from(!text.is_empty());
+    (
+        kept.clone(),
+        SectionAllocation {
+            section,
+            requested_tokens: requested,
+            granted_tokens: granted,
+            items_total,
+            items_kept: usize::from(!kept.is_empty()),
+            truncated,
+        },
+    )
+}
+
+/// Keeps `lines` in order until the next one no longer fits. `fixed` is the
+/// header cost, paid only if at least one line is kept.
+fn fit_lines<F>(
+    section: PromptSection,
+    lines: &[String],
+    fixed: usize,
+    available: usize,
+    count_tokens: &F,
+) -> (Vec<String>, SectionAllocation)
+where
+    F: Fn(&str) -> usize,
+{
+    let costs = lines
+        .iter()
+        .map(|line| count_tokens(line) + 1)
+        .collect::<Vec<usize>>();
+    let requested = if lines.is_empty() {
+        0
+    } else {
+        fixed + costs.iter().sum::<usize>()
+    };
+
+    let mut kept = Vec::<String>::new();
+    let mut granted = 0_usize;
+    for (line, cost) in lines.iter().zip(&costs) {
+        let header = if kept.is_empty() { fixed } else { 0 };
+        if granted + header + cost > available {
+            break;
+        }
+        granted += header + cost;
+        kept.push(line.clone());
+    }
+
+    let allocation = SectionAllocation {
+        section,
+        requested_tokens: requested,
+        granted_tokens: granted,
+        items_total: lines.len(),
+        items_kept: kept.len(),
+        truncated: false,
+    };
+    (kept, allocation)
+}
+
+fn fit_history<F>(
+    history: &[ChatTurn],
+    turn_overhead: usize,
+    available: usize,
+    count_tokens: &F,
+) -> (Vec<ChatTurn>, SectionAllocation)
+where
+    F: Fn(&str) -> usize,
+{
+    let costs = history
+        .iter()
+        .map(|turn| count_tokens(&turn.content) + turn_overhead)
+        .collect::<Vec<usize>>();
+
+    let mut kept_from = history.len();
+    let mut granted = 0_usize;
+    for (index, cost) in costs.iter().enumerate().rev() {
+        if granted + cost > available {
+            break;
+        }
+        granted += cost;
+        kept_from = index;
+    }
+
+    let allocation = SectionAllocation {
+        section: PromptSection::History,
+        requested_tokens: costs.iter().sum(),
+        granted_tokens: granted,
+        items_total: history.len(),
+        items_kept: history.len() - kept_from,
+        truncated: false,
+    };
+    (history[kept_from..].to_vec(), allocation)
+}
+
+/// Longest prefix of `text` (plus a marker) that fits in `max_tokens`.
+fn truncate_to_tokens<F>(text: &str, max_tokens: usize, count_tokens: &F) -> String
+where
+    F: Fn(&str) -> usize,
+{
+    let boundaries = text
+        .char_indices()
+        .map(|(index, _)| index)
+        .skip(1)
+        .chain(std::iter::once(text.len()))
+        .collect::<Vec<usize>>();
+
+    let fits =
+        |end: usize| count_tokens(&format!("{}{TRUNCATION_MARKER}", &text[..end])) <= max_tokens;
+
+    // Binary search for the last char boundary whose prefix still fits.
+    let (mut low, mut high) = (0_usize, boundaries.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if fits(boundaries[mid]) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    match low.checked_sub(1) {
+        Some(index) => format!(
+            "{}{TRUNCATION_MARKER}",
+            text[..boundaries[index]].trim_end()
+        ),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_count(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    fn budget(context_window: usize) -> PromptBudget {
+        PromptBudget {
+            context_window,
+            reserved_for_generation: 10,
+            base_overhead: 2,
+            turn_overhead: 1,
+        }
+    }
+
+    fn parts() -> PromptParts {
+        PromptParts {
+            core_prompt: "you are anima".to_string(),
+            profile_lines: vec![
+                "- oldest trait here".to_string(),
+                "- newest trait here".to_string(),
+            ],
+            memory_lines: vec!["strong memory".to_string(), "weak memory".to_string()],
+            history: vec![
+                ChatTurn::user("old question"),
+                ChatTurn::assistant("old answer"),
+                ChatTurn::user("recent question"),
+            ],
+            user_message: "hello there".to_string(),
+            system_suffix: "\n\nnow: today".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_everything_fits_untouched() {
+        let assembled = assemble(&parts(), &budget(200), word_count);
+        assert!(!assembled.report.was_trimmed());
+        assert_eq!(assembled.history.len(), 3);
+        assert!(assembled.system_prompt().contains("oldest trait"));
+        assert!(assembled.system_prompt().ends_with("now: today"));
+        assert_eq!(assembled.user_message, "hello there");
+        assert!(assembled.user_turn().contains("2. weak memory"));
+        // The trailing history question merges with the new user turn.
+        assert_eq!(assembled.turns().len(), 3);
+    }
+
+    #[test]
+    fn test_low_priority_sections_trimmed_first() {
+        // 81 - 10 reserved - 2 overhead - 8 margin = 61 tokens, 5 short of everything.
+        let assembled = assemble(&parts(), &budget(81), word_count);
+        let report = &assembled.report;
+        assert!(report.used_tokens() <= 81 - 10 - 8);
+
+        let by_name = |section: PromptSection| {
+            report
+                .sections
+                .iter()
+                .find(|allocation| allocation.section == section)
+                .cloned()
+                .unwrap()
+        };
+        assert!(!by_name(PromptSection::UserMessage).was_trimmed());
+        assert!(!by_name(PromptSection::CorePrompt).was_trimmed());
+        assert!(!by_name(PromptSection::Memories).was_trimmed());
+        assert!(by_name(PromptSection::Profile).was_trimmed());
+        assert!(!assembled.system_prompt().contains("oldest trait"));
+        assert!(assembled.system_prompt().contains("newest trait"));
+    }
+
+    #[test]
+    fn test_history_keeps_most_recent_turns() {
+        let mut parts = parts();
+        parts.memory_lines.clear();
+        parts.profile_lines.clear();
+        let assembled = assemble(&parts, &budget(30), word_count);
+        assert_eq!(assembled.history.len(), 1);
+        assert_eq!(assembled.history[0].content, "recent question");
+    }
+
+    #[test]
+    fn test_oversized_user_message_is_truncated() {
+        let mut parts = parts();
+        parts.user_message = "word ".repeat(100);
+        let assembled = assemble(&parts, &budget(40), word_count);
+        assert!(word_count(&assembled.user_message) <= 20);
+        assert!(assembled.user_message.ends_with(TRUNCATION_MARKER));
+        assert!(assembled.report.sections[0].truncated);
+        assert!(assembled.history.is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Bumped whenever an input of the system prompt (core prompt, profile,
+/// language, user name) is written, so a cached prefix is dropped even before
+/// its tokens are compared.
+static PROMPT_INPUTS_EPOCH: AtomicU64 = AtomicU64::new(0);
+
+pub fn invalidate() {
+    PROMPT_INPUTS_EPOCH.fetch_add(1, Ordering::SeqCst);
+}
+
+pub fn current_epoch() -> u64 {
+    PROMPT_INPUTS_EPOCH.load(Ordering::SeqCst)
+}
+
+/// Tokens whose KV entries were left resident in sequence 0 after a turn.
+///
+/// Only the system-prompt prefix is ever kept: dialogue turns and generated
+/// tokens are trimmed from the KV cache before the turn returns, so each turn
+/// still starts from a known state.
+#[derive(Debug, Clone)]
+pub struct PromptPrefixCache<T> {
+    tokens: Vec<T>,
+    epoch: u64,
+}
+
+impl<T: PartialEq + Clone> PromptPrefixCache<T> {
+    pub fn new(tokens: &[T], epoch: u64) -> Self {
+        Self {
+            tokens: tokens.to_vec(),
+            epoch,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// How many leading tokens of `prompt` can be served from the cache.
+    /// At least one prompt token is always left to decode, since sampling
+    /// needs fresh logits.
+    pub fn reusable_len(&self, prompt: &[T], epoch: u64) -> usize {
+        if epoch != self.epoch {
+            return 0;
+        }
+        common_prefix_len(&self.tokens, prompt).min(prompt.len().saturating_sub(1))
+    }
+}
+
+pub fn common_prefix_len<T: PartialEq>(left: &[T], right: &[T]) -> usize {
+    left.iter()
+        .zip(right)
+        .take_while(|(left, right)| left == right)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reuses_shared_prefix_only() {
+        let cache = PromptPrefixCache::new(&[1, 2, 3, 4], 7);
+        assert_eq!(cache.reusable_len(&[1, 2, 3, 9, 10], 7), 3);
+        assert_eq!(cache.reusable_len(&[5, 2, 3], 7), 0);
+    }
+
+    #[test]
+    fn test_leaves_a_token_to_decode() {
+        let cache = PromptPrefixCache::new(&[1, 2, 3], 0);
+        assert_eq!(cache.reusable_len(&[1, 2, 3], 0), 2);
+    }
+
+    #[test]
+    fn test_epoch_change_discards_cache() {
+        let cache = PromptPrefixCache::new(&[1, 2, 3], current_epoch());
+        invalidate();
+        assert_eq!(cache.reusable_len(&[1, 2, 3, 4], current_epoch()), 0);
+    }
+}
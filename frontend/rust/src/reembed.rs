@@ -0,0 +1,201 @@
+use crate::ai;
+use crate::backend;
+use crate::cancellation::CancellationToken;
+use crate::db;
+use crate::error::AnimaError;
+use crate::job_queue::JobPriority;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Memories embedded and committed per step; an interrupted job loses at most
+/// one step.
+const REEMBED_BATCH_SIZE: usize = 32;
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+static ACTIVE_TOKEN: Mutex<Option<CancellationToken>> = Mutex::new(None);
+
+/// How many memories were embedded by the loaded embedding model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddingStatus {
+    pub model_id: String,
+    pub total_memories: u32,
+    /// Memories from another model, or from before models were recorded.
+    pub stale_memories: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReembedProgress {
+    pub model_id: String,
+    pub done: u32,
+    pub total: u32,
+}
+
+pub fn embedding_status() -> Result<EmbeddingStatus, AnimaError> {
+    let model_id = backend::embedding()?.embedding_model_id()?;
+    let total = db::count_memories()
+        .map_err(|error| AnimaError::from(error).context("Failed to count memories"))?;
+    let stale = db::count_stale_memories(&model_id)
+        .map_err(|error| AnimaError::from(error).context("Failed to count stale memories"))?;
+    Ok(EmbeddingStatus {
+        model_id,
+        total_memories: saturating_u32(total),
+        stale_memories: saturating_u32(stale),
+    })
+}
+
+/// Re-embeds every stale memory with the loaded embedding model, calling
+/// `on_progress` after each committed step. Progress lives in the rows
+/// themselves, so a cancelled or failed job resumes where it stopped.
+pub fn run(mut on_progress: impl FnMut(&ReembedProgress)) -> Result<ReembedProgress, AnimaError> {
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return Err(AnimaError::InvalidInput(
+            "Re-embedding is already running".to_string(),
+        ));
+    }
+    let _running = RunningGuard;
+    let token = CancellationToken::new();
+    if let Ok(mut active) = ACTIVE_TOKEN.lock() {
+        *active = Some(token.clone());
+    }
+
+    let model_id = backend::embedding()?.embedding_model_id()?;
+    let stale = db::count_stale_memories(&model_id)
+        .map_err(|error| AnimaError::from(error).context("Failed to count stale memories"))?;
+    let mut progress = ReembedProgress {
+        model_id,
+        done: 0,
+        total: saturating_u32(stale),
+    };
+    on_progress(&progress);
+
+    loop {
+        if token.is_cancelled() {
+            return Err(AnimaError::Cancelled(
+                "Re-embedding was cancelled".to_string(),
+            ));
+        }
+
+        let rows = db::get_stale_memories(&progress.model_id, REEMBED_BATCH_SIZE)
+            .map_err(|error| AnimaError::from(error).context("Failed to read stale memories"))?;
+        if rows.is_empty() {
+            return Ok(progress);
+        }
+
+        let texts = rows
+            .iter()
+            .map(|(_, content)| content.as_str())
+            .collect::<Vec<_>>();
+        let embeddings = ai::generate_embeddings(&texts, JobPriority::EmbeddingBackfill)?;
+        if embeddings
+            .iter()
+            .any(|embedding| embedding.model_id != progress.model_id)
+        {
+            return Err(AnimaError::Cancelled(
+                "Embedding model changed during re-embedding".to_string(),
+            ));
+        }
+
+        // Texts without tokens get an empty vector; storing it still marks
+        // the row as done so the job cannot loop on it.
+        let updates = rows
+            .iter()
+            .zip(&embeddings)
+            .map(|((message_id, _), embedding)| (*message_id, embedding.vector.as_slice()))
+            .collect::<Vec<_>>();
+        db::update_memory_embeddings(&progress.model_id, &updates).map_err(|error| {
+            AnimaError::from(error).context("Failed to store re-embedded memories")
+        })?;
+
+        progress.done = progress.done.saturating_add(saturating_u32(rows.len()));
+        progress.total = progress.total.max(progress.done);
+        on_progress(&progress);
+    }
+}
+
+/// Stops the running job after its current step. Returns `false` when no job
+/// is running.
+pub fn cancel() -> bool {
+    match ACTIVE_TOKEN.lock() {
+        Ok(active) => match active.as_ref() {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        },
+        Err(_) => false,
+    }
+}
+
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        if let Ok(mut active) = ACTIVE_TOKEN.lock() {
+            *active = None;
+        }
+        RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+fn saturating_u32(value: usize) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_backend::test_support::MockSession;
+
+    fn insert_stale_memories(count: usize) {
+        for index in 0..count {
+            let message_id = db::insert_message("user", &format!("memory number {index}")).unwrap();
+            db::insert_memory(message_id, &[1.0, 0.0], "", "episodic", 0.5, 10).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_progress_counts_every_stale_memory() {
+        let _session = MockSession::start();
+        insert_stale_memories(REEMBED_BATCH_SIZE + 8);
+
+        let mut reports = Vec::new();
+        let finished = run(|progress| reports.push((progress.done, progress.total))).unwrap();
+        assert_eq!(reports, vec![(0, 40), (32, 40), (40, 40)]);
+        assert_eq!(finished.done, 40);
+        assert_eq!(embedding_status().unwrap().stale_memories, 0);
+    }
+
+    #[test]
+    fn test_cancelled_run_resumes_where_it_stopped() {
+        let _session = MockSession::start();
+        insert_stale_memories(REEMBED_BATCH_SIZE + 8);
+
+        let cancelled = run(|progress| {
+            if progress.done > 0 {
+                assert!(cancel());
+            }
+        });
+        assert!(matches!(cancelled, Err(AnimaError::Cancelled(_))));
+        assert!(!cancel());
+        assert_eq!(embedding_status().unwrap().stale_memories, 8);
+
+        let resumed = run(|_| {}).unwrap();
+        assert_eq!((resumed.done, resumed.total), (8, 8));
+        assert_eq!(embedding_status().unwrap().stale_memories, 0);
+    }
+
+    #[test]
+    fn test_model_change_mid_run_cancels_without_tagging_rows() {
+        let session = MockSession::start();
+        insert_stale_memories(3);
+
+        let changed = run(|progress| {
+            if progress.done == 0 {
+                session.backend.set_embedding_model_id("mock:other");
+            }
+        });
+        assert!(matches!(changed, Err(AnimaError::Cancelled(_))));
+        assert_eq!(db::count_stale_memories("mock:other").unwrap(), 3);
+    }
+}
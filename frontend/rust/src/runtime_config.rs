@@ -0,0 +1,241 @@
+use crate::db;
+use crate::gguf::GgufHeader;
+use crate::load_strategy;
+
+const DEFAULT_N_CTX: u32 = 2048;
+const DEFAULT_N_BATCH: u32 = 512;
+const DEFAULT_N_THREADS: u32 = 4;
+const MIN_N_CTX: u32 = 512;
+const MAX_N_CTX: u32 = 131_072;
+const MIN_N_BATCH: u32 = 32;
+const MAX_N_BATCH: u32 = 4096;
+const MAX_N_THREADS: u32 = 64;
+/// Auto-tune tries these, largest first; bigger windows cost RAM and prompt time.
+const AUTO_N_CTX_STEPS: [u32; 4] = [16_384, 8192, 4096, 2048];
+/// Used when the platform does not report free memory.
+const AUTO_N_CTX_UNKNOWN_RAM: u32 = 4096;
+/// Generation is memory-bound, so more threads than this rarely help.
+const AUTO_MAX_GENERATION_THREADS: u32 = 8;
+const AUTO_MAX_BATCH_THREADS: u32 = 16;
+const RAM_HEADROOM_BYTES: u64 = 1024 * 1024 * 1024;
+/// Embedding inputs are single messages; encoders are usually trained on 512.
+const EMBEDDING_MAX_N_CTX: u32 = 2048;
+
+/// Persisted context and thread settings. With `auto_tune` on, the other
+/// fields are ignored and picked from the model and the hardware instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeSettings {
+    pub auto_tune: bool,
+    pub n_ctx: u32,
+    pub n_batch: u32,
+    pub n_threads: u32,
+    pub n_threads_batch: u32,
+}
+
+impl Default for RuntimeSettings {
+    fn default() -> Self {
+        Self {
+            auto_tune: true,
+            n_ctx: DEFAULT_N_CTX,
+            n_batch: DEFAULT_N_BATCH,
+            n_threads: DEFAULT_N_THREADS,
+            n_threads_batch: DEFAULT_N_THREADS,
+        }
+    }
+}
+
+impl RuntimeSettings {
+    pub(crate) fn sanitized(&self) -> Self {
+        Self {
+            auto_tune: self.auto_tune,
+            n_ctx: self.n_ctx.clamp(MIN_N_CTX, MAX_N_CTX),
+            n_batch: self.n_batch.clamp(MIN_N_BATCH, MAX_N_BATCH),
+            n_threads: self.n_threads.clamp(1, MAX_N_THREADS),
+            n_threads_batch: self.n_threads_batch.clamp(1, MAX_N_THREADS),
+        }
+    }
+}
+
+/// Values a context is actually created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextConfig {
+    pub n_ctx: u32,
+    pub n_batch: u32,
+    pub n_threads: u32,
+    pub n_threads_batch: u32,
+}
+
+impl ContextConfig {
+    pub fn summary(&self) -> String {
+        format!(
+            "n_ctx={} n_batch={} n_threads={} n_threads_batch={}",
+            self.n_ctx, self.n_batch, self.n_threads, self.n_threads_batch
+        )
+    }
+}
+
+/// What auto-tune needs to know about the model, read from its GGUF header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelLimits {
+    pub n_ctx_train: Option<u32>,
+    pub kv_bytes_per_token: u64,
+    pub model_bytes: u64,
+}
+
+impl ModelLimits {
+    pub fn from_header(header: &GgufHeader, model_bytes: u64) -> Self {
+        Self {
+            n_ctx_train: header
+                .context_length()
+                .map(|length| u32::try_from(length).unwrap_or(u32::MAX)),
+            kv_bytes_per_token: load_strategy::estimate_kv_cache_bytes(header, 1),
+            model_bytes,
+        }
+    }
+}
+
+/// Settings from `config` applied to this model and machine. Meant to run
+/// before the model is loaded, since its size is subtracted from free RAM;
+/// `reclaimable_bytes` held by the model it replaces count as free.
+pub fn context_config(limits: &ModelLimits, reclaimable_bytes: u64) -> ContextConfig {
+    let settings = db::get_runtime_settings().unwrap_or_else(|error| {
+        eprintln!("Failed to load runtime settings, using defaults: {error}");
+        RuntimeSettings::default()
+    });
+    let cores = std::thread::available_parallelism()
+        .map(|cores| u32::try_from(cores.get()).unwrap_or(MAX_N_THREADS))
+        .unwrap_or(DEFAULT_N_THREADS);
+    resolve(
+        &settings,
+        limits,
+        load_strategy::available_after_release(reclaimable_bytes),
+        cores,
+    )
+}
+
+/// Thread settings as for chat; the window is the model's trained length up to
+/// `EMBEDDING_MAX_N_CTX`, decoded as a single batch.
+pub fn embedding_context_config(n_ctx_train: Option<u32>) -> ContextConfig {
+    let n_ctx = n_ctx_train
+        .unwrap_or(EMBEDDING_MAX_N_CTX)
+        .clamp(1, EMBEDDING_MAX_N_CTX);
+    let limits = ModelLimits {
+        n_ctx_train: Some(n_ctx),
+        kv_bytes_per_token: 0,
+        model_bytes: 0,
+    };
+    let threads = context_config(&limits, 0);
+    ContextConfig {
+        n_ctx,
+        n_batch: n_ctx,
+        ..threads
+    }
+}
+
+fn resolve(
+    settings: &RuntimeSettings,
+    limits: &ModelLimits,
+    available_bytes: Option<u64>,
+    cores: u32,
+) -> ContextConfig {
+    let settings = settings.sanitized();
+    let n_ctx_train = limits.n_ctx_train.unwrap_or(MAX_N_CTX).max(MIN_N_CTX);
+
+    if !settings.auto_tune {
+        let n_ctx = settings.n_ctx.min(n_ctx_train);
+        return ContextConfig {
+            n_ctx,
+            n_batch: settings.n_batch.min(n_ctx),
+            n_threads: settings.n_threads,
+            n_threads_batch: settings.n_threads_batch,
+        };
+    }
+
+    let n_ctx = match available_bytes {
+        Some(available) => {
+            let kv_budget = available
+                .saturating_sub(limits.model_bytes)
+                .saturating_sub(RAM_HEADROOM_BYTES);
+            AUTO_N_CTX_STEPS
+                .into_iter()
+                .filter(|&n_ctx| n_ctx <= n_ctx_train)
+                .find(|&n_ctx| {
+                    limits.kv_bytes_per_token.saturating_mul(u64::from(n_ctx)) <= kv_budget
+                })
+                .unwrap_or(DEFAULT_N_CTX)
+        }
+        None => AUTO_N_CTX_UNKNOWN_RAM,
+    }
+    .min(n_ctx_train);
+
+    // Logical cores; half of them approximates the physical count.
+    ContextConfig {
+        n_ctx,
+        n_batch: DEFAULT_N_BATCH.min(n_ctx),
+        n_threads: (cores / 2).clamp(1, AUTO_MAX_GENERATION_THREADS),
+        n_threads_batch: cores.clamp(1, AUTO_MAX_BATCH_THREADS),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn limits(n_ctx_train: u32) -> ModelLimits {
+        ModelLimits {
+            n_ctx_train: Some(n_ctx_train),
+            // 128 KiB per token, like a 7B model with grouped-query attention.
+            kv_bytes_per_token: 128 * 1024,
+            model_bytes: 4 * GIB,
+        }
+    }
+
+    #[test]
+    fn test_auto_tune_scales_with_free_ram_and_trained_context() {
+        let settings = RuntimeSettings::default();
+        assert_eq!(
+            resolve(&settings, &limits(32_768), Some(16 * GIB), 8).n_ctx,
+            16_384
+        );
+        assert_eq!(
+            resolve(&settings, &limits(32_768), Some(5 * GIB + GIB / 2), 8).n_ctx,
+            4096
+        );
+        assert_eq!(
+            resolve(&settings, &limits(4096), Some(64 * GIB), 8).n_ctx,
+            4096
+        );
+        // Too little RAM still gets the old default; the load check refuses it if needed.
+        assert_eq!(
+            resolve(&settings, &limits(32_768), Some(4 * GIB), 8).n_ctx,
+            2048
+        );
+
+        let config = resolve(&settings, &limits(8192), None, 12);
+        assert_eq!(config.n_ctx, AUTO_N_CTX_UNKNOWN_RAM);
+        assert_eq!((config.n_threads, config.n_threads_batch), (6, 12));
+    }
+
+    #[test]
+    fn test_manual_settings_are_clamped_to_model() {
+        let settings = RuntimeSettings {
+            auto_tune: false,
+            n_ctx: 65_536,
+            n_batch: 1024,
+            n_threads: 0,
+            n_threads_batch: 3,
+        };
+        let config = resolve(&settings, &limits(8192), Some(GIB), 8);
+        assert_eq!(
+            config,
+            ContextConfig {
+                n_ctx: 8192,
+                n_batch: 1024,
+                n_threads: 1,
+                n_threads_batch: 3,
+            }
+        );
+    }
+}